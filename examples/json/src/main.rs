@@ -24,9 +24,13 @@ use std::{collections::HashMap, fmt};
 //
 // This is because those bounds are required by HashMap and Vec, but we removed the default
 //   generated bounds to prevent a recursive impl.
+// The same goes for deserializing, where HashMap and Vec require `__D: AllocationBudget`.
 // We can fix this by manually specifying the bounds required by HashMap and Vec in an attribute,
 //   and then everything will compile:
-#[archive(bound(serialize = "__S: rkyv::ser::ScratchSpace + rkyv::ser::Serializer"))]
+#[archive(bound(
+    serialize = "__S: rkyv::ser::ScratchSpace + rkyv::ser::Serializer",
+    deserialize = "__D: rkyv::de::AllocationBudget"
+))]
 pub enum JsonValue {
     Null,
     Bool(bool),
@@ -0,0 +1,157 @@
+//! Archived versions of `num-bigint` types.
+
+use crate::{vec::ArchivedVec, Archived};
+use core::fmt;
+use num_bigint::{BigInt, BigUint, Sign};
+
+/// An archived [`Sign`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[repr(u8)]
+pub enum ArchivedSign {
+    /// A negative value
+    Minus,
+    /// Zero
+    NoSign,
+    /// A positive value
+    Plus,
+}
+
+impl ArchivedSign {
+    /// Returns the sign as a [`Sign`].
+    #[inline]
+    pub const fn as_sign(&self) -> Sign {
+        match self {
+            ArchivedSign::Minus => Sign::Minus,
+            ArchivedSign::NoSign => Sign::NoSign,
+            ArchivedSign::Plus => Sign::Plus,
+        }
+    }
+}
+
+impl From<Sign> for ArchivedSign {
+    #[inline]
+    fn from(sign: Sign) -> Self {
+        match sign {
+            Sign::Minus => ArchivedSign::Minus,
+            Sign::NoSign => ArchivedSign::NoSign,
+            Sign::Plus => ArchivedSign::Plus,
+        }
+    }
+}
+
+impl PartialEq<Sign> for ArchivedSign {
+    #[inline]
+    fn eq(&self, other: &Sign) -> bool {
+        self.as_sign() == *other
+    }
+}
+
+impl PartialEq<ArchivedSign> for Sign {
+    #[inline]
+    fn eq(&self, other: &ArchivedSign) -> bool {
+        other.eq(self)
+    }
+}
+
+/// An archived [`BigUint`].
+///
+/// The value is stored as an archived slice of its base 2<sup>32</sup> digits, least significant
+/// digit first. Zero has no digits.
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[repr(transparent)]
+pub struct ArchivedBigUint {
+    pub(crate) digits: ArchivedVec<Archived<u32>>,
+}
+
+impl ArchivedBigUint {
+    /// Returns the base 2<sup>32</sup> digits of the value, least significant digit first.
+    #[inline]
+    pub fn digits(&self) -> &[Archived<u32>] {
+        self.digits.as_slice()
+    }
+
+    /// Returns an iterator over the base 2<sup>32</sup> digits of the value, least significant
+    /// digit first.
+    // Archived digits are only a plain copy when the archive has native endianness
+    #[allow(clippy::map_clone)]
+    #[inline]
+    pub fn iter_u32_digits(&self) -> impl ExactSizeIterator<Item = u32> + '_ {
+        self.digits().iter().map(|digit| from_archived!(*digit))
+    }
+
+    /// Returns `true` if the value is zero.
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+}
+
+impl fmt::Debug for ArchivedBigUint {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter_u32_digits()).finish()
+    }
+}
+
+impl Eq for ArchivedBigUint {}
+
+impl PartialEq for ArchivedBigUint {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.digits() == other.digits()
+    }
+}
+
+impl PartialEq<BigUint> for ArchivedBigUint {
+    #[inline]
+    fn eq(&self, other: &BigUint) -> bool {
+        self.iter_u32_digits().eq(other.iter_u32_digits())
+    }
+}
+
+impl PartialEq<ArchivedBigUint> for BigUint {
+    #[inline]
+    fn eq(&self, other: &ArchivedBigUint) -> bool {
+        other.eq(self)
+    }
+}
+
+/// An archived [`BigInt`].
+///
+/// The value is stored as its sign and its magnitude.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedBigInt {
+    pub(crate) sign: ArchivedSign,
+    pub(crate) magnitude: ArchivedBigUint,
+}
+
+impl ArchivedBigInt {
+    /// Returns the sign of the value.
+    #[inline]
+    pub const fn sign(&self) -> Sign {
+        self.sign.as_sign()
+    }
+
+    /// Returns the magnitude of the value.
+    #[inline]
+    pub const fn magnitude(&self) -> &ArchivedBigUint {
+        &self.magnitude
+    }
+}
+
+impl PartialEq<BigInt> for ArchivedBigInt {
+    #[inline]
+    fn eq(&self, other: &BigInt) -> bool {
+        self.sign == other.sign() && self.magnitude == *other.magnitude()
+    }
+}
+
+impl PartialEq<ArchivedBigInt> for BigInt {
+    #[inline]
+    fn eq(&self, other: &ArchivedBigInt) -> bool {
+        other.eq(self)
+    }
+}
@@ -9,6 +9,10 @@ use core::{borrow::Borrow, cmp, fmt, hash, ops::Deref, pin::Pin};
 /// An archived [`Box`].
 ///
 /// This is a thin wrapper around a [`RelPtr`] to the archived type.
+///
+/// `Pin<Box<T>>` is also archived as an `ArchivedBox`. Pinning does not carry over into the archive
+/// because archived values are immutable except through a pinned mutable reference, and
+/// deserializing an `ArchivedBox` into a `Pin<Box<T>>` pins the new box again.
 #[repr(transparent)]
 pub struct ArchivedBox<T: ArchivePointee + ?Sized>(RelPtr<T>);
 
@@ -0,0 +1,237 @@
+//! Archived raw byte buffers.
+//!
+//! Fields marked with `#[archive(bytes)]` (or the equivalent [`AsBytes`](crate::with::AsBytes)
+//! wrapper) are archived as one of these types. They are guaranteed to be stored as a single
+//! contiguous region of bytes, with no per-element overhead and no endianness conversion.
+
+use crate::{
+    vec::{ArchivedVec, VecResolver},
+    Archived,
+};
+use core::{borrow::Borrow, fmt, ops::Deref};
+
+/// An archived `Vec<u8>` that is guaranteed to be stored as raw bytes.
+///
+/// The bytes are stored out-of-line as a single contiguous region, exactly as they were in the
+/// vector, and are borrowed directly from the archive by [`as_slice`](ArchivedBytes::as_slice).
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[derive(Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct ArchivedBytes(ArchivedVec<u8>);
+
+impl ArchivedBytes {
+    /// Gets the bytes as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    /// Returns the number of bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Resolves archived bytes from a given length and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of bytes that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing the bytes
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: BytesResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.0);
+        ArchivedVec::resolve_from_len(len, pos + fp, resolver.0, fo);
+    }
+
+    /// Serializes a byte slice as archived bytes.
+    #[inline]
+    pub fn serialize_from_slice<S: crate::ser::Serializer + ?Sized>(
+        bytes: &[u8],
+        serializer: &mut S,
+    ) -> Result<BytesResolver, S::Error> {
+        // Safety: `u8` archives as itself and has no padding
+        unsafe {
+            Ok(BytesResolver(ArchivedVec::serialize_copy_from_slice(
+                bytes, serializer,
+            )?))
+        }
+    }
+}
+
+impl AsRef<[u8]> for ArchivedBytes {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl Borrow<[u8]> for ArchivedBytes {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl Deref for ArchivedBytes {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl fmt::Debug for ArchivedBytes {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl PartialEq<[u8]> for ArchivedBytes {
+    #[inline]
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl PartialEq<ArchivedBytes> for [u8] {
+    #[inline]
+    fn eq(&self, other: &ArchivedBytes) -> bool {
+        self == other.as_slice()
+    }
+}
+
+/// The resolver for [`ArchivedBytes`].
+pub struct BytesResolver(VecResolver);
+
+/// An archived `[u8; N]` that is guaranteed to be stored as raw bytes.
+///
+/// The bytes are stored inline, exactly as they were in the array.
+#[derive(Clone, Copy, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct ArchivedByteArray<const N: usize>([Archived<u8>; N]);
+
+impl<const N: usize> ArchivedByteArray<N> {
+    /// Creates archived bytes from an array of bytes.
+    #[inline]
+    pub const fn new(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+
+    /// Gets the bytes as an array.
+    #[inline]
+    pub const fn as_array(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    /// Gets the bytes as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the number of bytes.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if there are no bytes.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for ArchivedByteArray<N> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> Borrow<[u8]> for ArchivedByteArray<N> {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> Deref for ArchivedByteArray<N> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArchivedByteArray<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<const N: usize> PartialEq<[u8]> for ArchivedByteArray<N> {
+    #[inline]
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for ArchivedByteArray<N> {
+    #[inline]
+    fn eq(&self, other: &[u8; N]) -> bool {
+        self.as_array() == other
+    }
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    #[cfg(not(feature = "std"))]
+    use ::alloc::vec::Vec;
+
+    impl PartialEq<Vec<u8>> for ArchivedBytes {
+        #[inline]
+        fn eq(&self, other: &Vec<u8>) -> bool {
+            self.as_slice() == other.as_slice()
+        }
+    }
+
+    impl PartialEq<ArchivedBytes> for Vec<u8> {
+        #[inline]
+        fn eq(&self, other: &ArchivedBytes) -> bool {
+            self.as_slice() == other.as_slice()
+        }
+    }
+};
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use bytecheck::CheckBytes;
+    use core::convert::Infallible;
+
+    impl<C: ?Sized, const N: usize> CheckBytes<C> for ArchivedByteArray<N> {
+        type Error = Infallible;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(value: *const Self, _: &mut C) -> Result<&'a Self, Self::Error> {
+            Ok(&*value)
+        }
+    }
+};
@@ -0,0 +1,55 @@
+//! Archived versions of `cmp` types.
+
+use core::cmp::Ordering;
+
+/// An archived [`Ordering`].
+///
+/// The discriminants of the variants are the same as those of `Ordering`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[repr(i8)]
+pub enum ArchivedOrdering {
+    /// An ordering where a compared value is less than another
+    Less = -1,
+    /// An ordering where a compared value is equal to another
+    Equal = 0,
+    /// An ordering where a compared value is greater than another
+    Greater = 1,
+}
+
+impl ArchivedOrdering {
+    /// Returns an [`Ordering`] with the same value.
+    #[inline]
+    pub const fn as_ordering(&self) -> Ordering {
+        match self {
+            ArchivedOrdering::Less => Ordering::Less,
+            ArchivedOrdering::Equal => Ordering::Equal,
+            ArchivedOrdering::Greater => Ordering::Greater,
+        }
+    }
+}
+
+impl From<Ordering> for ArchivedOrdering {
+    #[inline]
+    fn from(value: Ordering) -> Self {
+        match value {
+            Ordering::Less => ArchivedOrdering::Less,
+            Ordering::Equal => ArchivedOrdering::Equal,
+            Ordering::Greater => ArchivedOrdering::Greater,
+        }
+    }
+}
+
+impl PartialEq<Ordering> for ArchivedOrdering {
+    #[inline]
+    fn eq(&self, other: &Ordering) -> bool {
+        self.as_ordering() == *other
+    }
+}
+
+impl PartialEq<ArchivedOrdering> for Ordering {
+    #[inline]
+    fn eq(&self, other: &ArchivedOrdering) -> bool {
+        other.eq(self)
+    }
+}
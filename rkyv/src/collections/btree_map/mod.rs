@@ -11,7 +11,7 @@ use core::{
     hash::{Hash, Hasher},
     iter::FusedIterator,
     marker::PhantomData,
-    ops::Index,
+    ops::{Bound, Index, RangeBounds},
     ptr::NonNull,
 };
 use ptr_meta::Pointee;
@@ -206,6 +206,17 @@ pub const MIN_ENTRIES_PER_LEAF_NODE: usize = 1;
 /// This value must be greater than 1
 pub const MIN_ENTRIES_PER_INNER_NODE: usize = 2;
 
+/// The minimum branching factor that a B-tree map can be archived with.
+///
+/// Inner nodes hold at least [`MIN_ENTRIES_PER_INNER_NODE`] keys, so they always point to at least
+/// one more node than that.
+pub const MIN_BRANCHING_FACTOR: usize = MIN_ENTRIES_PER_INNER_NODE + 1;
+
+/// The maximum branching factor that a B-tree map can be archived with.
+///
+/// The number of entries in a node is stored in 15 bits.
+pub const MAX_BRANCHING_FACTOR: usize = 0x7F_FF;
+
 impl<K, V> ArchivedBTreeMap<K, V> {
     #[inline]
     fn root(&self) -> Option<ClassifiedNode<K, V>> {
@@ -236,6 +247,48 @@ impl<K, V> ArchivedBTreeMap<K, V> {
         }
     }
 
+    // Finds the position of the first entry whose key is not before some bound. Positions are
+    // pairs of a leaf node and an index into it, and the position past the end of a leaf node is
+    // equivalent to the start of the next leaf node.
+    fn position<F: FnMut(&K) -> bool>(&self, mut is_before: F) -> (NonNull<NodeHeader>, usize) {
+        // Keys are sorted, so the entries before the bound always come first
+        let mut partition = |key: &K| {
+            if is_before(key) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        };
+
+        if let Some(mut current) = self.root() {
+            loop {
+                match current {
+                    ClassifiedNode::Inner(node) => {
+                        let i = node
+                            .tail
+                            .binary_search_by(|probe| partition(&probe.key))
+                            .unwrap_err();
+                        let next = if i == 0 {
+                            unsafe { &*node.header.ptr.as_ptr() }
+                        } else {
+                            unsafe { &*node.tail[i - 1].ptr.as_ptr() }
+                        };
+                        current = next.classify();
+                    }
+                    ClassifiedNode::Leaf(node) => {
+                        let i = node
+                            .tail
+                            .binary_search_by(|probe| partition(&probe.key))
+                            .unwrap_err();
+                        break (NonNull::from(&node.header), i);
+                    }
+                }
+            }
+        } else {
+            (NonNull::dangling(), 0)
+        }
+    }
+
     /// Returns `true` if the map contains a value for the specified key.
     ///
     /// The key may be any borrowed form of the map's key type, but the ordering on the borrowed
@@ -329,6 +382,89 @@ impl<K, V> ArchivedBTreeMap<K, V> {
         }
     }
 
+    /// Gets an iterator over a sub-range of the entries in the map, sorted by key.
+    ///
+    /// The range may be any [`RangeBounds`] over a borrowed form of the map's key type, including
+    /// ranges like `start..=end` and pairs of [`Bound`]s. The ordering on the borrowed form _must_
+    /// match the ordering on the key type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end of the range, or if the start and
+    /// end of the range are equal and both excluded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ops::Bound;
+    /// use rkyv::{archived_root, ser::{serializers::AllocSerializer, Serializer}};
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut value = BTreeMap::new();
+    /// for (i, name) in ["apple", "banana", "cherry", "date"].iter().enumerate() {
+    ///     value.insert(name.to_string(), i as u32);
+    /// }
+    ///
+    /// let mut serializer = AllocSerializer::<256>::default();
+    /// serializer.serialize_value(&value).unwrap();
+    /// let bytes = serializer.into_serializer().into_inner();
+    /// let archived = unsafe { archived_root::<BTreeMap<String, u32>>(&bytes) };
+    ///
+    /// let keys = archived
+    ///     .range::<str, _>((Bound::Included("b"), Bound::Excluded("d")))
+    ///     .map(|(k, _)| k.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(keys, ["banana", "cherry"]);
+    ///
+    /// let keys = archived
+    ///     .range::<str, _>((Bound::Excluded("banana"), Bound::Unbounded))
+    ///     .map(|(k, _)| k.as_str())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(keys, ["cherry", "date"]);
+    /// ```
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+        R: RangeBounds<Q>,
+    {
+        let start = range.start_bound();
+        let end = range.end_bound();
+        match (start, end) {
+            (Bound::Excluded(s), Bound::Excluded(e)) if s == e => {
+                panic!("range start and end are equal and excluded in ArchivedBTreeMap")
+            }
+            (Bound::Included(s), Bound::Included(e))
+            | (Bound::Included(s), Bound::Excluded(e))
+            | (Bound::Excluded(s), Bound::Included(e))
+            | (Bound::Excluded(s), Bound::Excluded(e))
+                if s > e =>
+            {
+                panic!("range start is greater than range end in ArchivedBTreeMap")
+            }
+            _ => (),
+        }
+
+        let (front, front_index) = self.position(|key| match start {
+            Bound::Included(s) => key.borrow() < s,
+            Bound::Excluded(s) => key.borrow() <= s,
+            Bound::Unbounded => false,
+        });
+        let (back, back_index) = self.position(|key| match end {
+            Bound::Included(e) => key.borrow() <= e,
+            Bound::Excluded(e) => key.borrow() < e,
+            Bound::Unbounded => true,
+        });
+
+        Range {
+            front,
+            front_index,
+            back,
+            back_index,
+            _phantom: PhantomData,
+        }
+    }
+
     /// Returns the number of items in the archived B-tree map.
     #[inline]
     pub fn len(&self) -> usize {
@@ -375,27 +511,91 @@ const _: () = {
     impl<K, V> ArchivedBTreeMap<K, V> {
         /// Serializes an ordered iterator of key-value pairs as a B-tree map.
         ///
+        /// Nodes are filled until they span about 4KiB, including the out-of-line data of their
+        /// entries, which keeps each node within the distance of a single IO page.
+        ///
         /// # Safety
         ///
         /// - Keys returned by the iterator must be unique
         /// - Keys must be in reverse sorted order from last to first
+        #[inline]
         pub unsafe fn serialize_from_reverse_iter<'a, UK, UV, S, I>(
+            iter: I,
+            serializer: &mut S,
+        ) -> Result<BTreeMapResolver, S::Error>
+        where
+            UK: 'a + Serialize<S, Archived = K>,
+            UV: 'a + Serialize<S, Archived = V>,
+            S: Serializer + ?Sized,
+            I: ExactSizeIterator<Item = (&'a UK, &'a UV)>,
+        {
+            // The memory span of a single node should not exceed 4kb to keep everything within
+            // the distance of a single IO page
+            const MAX_NODE_SIZE: usize = 4096;
+
+            Self::serialize_nodes(iter, serializer, |node_size, _| node_size >= MAX_NODE_SIZE)
+        }
+
+        /// Serializes an ordered iterator of key-value pairs as a B-tree map with a fixed
+        /// branching factor.
+        ///
+        /// Each leaf node holds up to `branching_factor` entries and each inner node points to up
+        /// to `branching_factor` nodes in the next layer, regardless of how large the entries are.
+        /// To avoid leaving a node with a single child, one inner node in each layer may point to
+        /// one more node. Smaller branching factors make nodes cheaper to search while larger
+        /// ones make the tree shallower, so the best choice depends on the size of the entries and
+        /// the cache behavior of the target.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `branching_factor` is less than [`MIN_BRANCHING_FACTOR`] or greater than
+        /// [`MAX_BRANCHING_FACTOR`].
+        ///
+        /// # Safety
+        ///
+        /// - Keys returned by the iterator must be unique
+        /// - Keys must be in reverse sorted order from last to first
+        #[inline]
+        pub unsafe fn serialize_from_reverse_iter_with_branching_factor<'a, UK, UV, S, I>(
+            iter: I,
+            branching_factor: usize,
+            serializer: &mut S,
+        ) -> Result<BTreeMapResolver, S::Error>
+        where
+            UK: 'a + Serialize<S, Archived = K>,
+            UV: 'a + Serialize<S, Archived = V>,
+            S: Serializer + ?Sized,
+            I: ExactSizeIterator<Item = (&'a UK, &'a UV)>,
+        {
+            assert!(
+                (MIN_BRANCHING_FACTOR..=MAX_BRANCHING_FACTOR).contains(&branching_factor),
+                "B-tree branching factor must be between {} and {}, but was {}",
+                MIN_BRANCHING_FACTOR,
+                MAX_BRANCHING_FACTOR,
+                branching_factor,
+            );
+
+            Self::serialize_nodes(iter, serializer, |_, branches| branches >= branching_factor)
+        }
+
+        // Bulk-loads the B-tree from the back, one layer at a time. `is_full` is passed the
+        // estimated size of the node in bytes and the number of entries (for leaf nodes) or child
+        // nodes (for inner nodes) in it.
+        unsafe fn serialize_nodes<'a, UK, UV, S, I, F>(
             mut iter: I,
             serializer: &mut S,
+            is_full: F,
         ) -> Result<BTreeMapResolver, S::Error>
         where
             UK: 'a + Serialize<S, Archived = K>,
             UV: 'a + Serialize<S, Archived = V>,
             S: Serializer + ?Sized,
             I: ExactSizeIterator<Item = (&'a UK, &'a UV)>,
+            F: Fn(usize, usize) -> bool,
         {
             if iter.len() == 0 {
                 Ok(BTreeMapResolver { root_pos: 0 })
             } else {
-                // The memory span of a single node should not exceed 4kb to keep everything within
-                // the distance of a single IO page
-                const MAX_NODE_SIZE: usize = 4096;
-
                 // The nodes that must go in the next level in reverse order (key, node_pos)
                 let mut next_level = Vec::new();
                 let mut resolvers = Vec::new();
@@ -420,9 +620,8 @@ const _: () = {
                             + mem::size_of::<NodeHeader>()
                             + resolvers.len() * mem::size_of::<LeafNodeEntry<K, V>>();
 
-                        // If we've reached or exceeded the maximum node size and have put enough
-                        // entries in this node, then break
-                        if estimated_block_size >= MAX_NODE_SIZE
+                        // If the node is full and we've put enough entries in it, then break
+                        if is_full(estimated_block_size, resolvers.len())
                             && resolvers.len() >= MIN_ENTRIES_PER_LEAF_NODE
                         {
                             break;
@@ -494,9 +693,10 @@ const _: () = {
                                 + mem::size_of::<NodeHeader>()
                                 + resolvers.len() * mem::size_of::<InnerNodeEntry<K>>();
 
-                            // If we've reached or exceeded the maximum node size and have put enough
-                            // keys in this node, then break
-                            if estimated_block_size >= MAX_NODE_SIZE
+                            // If the node is full and we've put enough keys in it, then break. The
+                            // node also points to the first node, so it has one more child than it
+                            // has keys.
+                            if is_full(estimated_block_size, resolvers.len() + 1)
                                 && resolvers.len() >= MIN_ENTRIES_PER_INNER_NODE
                             {
                                 break;
@@ -567,8 +767,13 @@ where
 {
     type Output = V;
 
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
     fn index(&self, key: &Q) -> &V {
-        self.get(key).unwrap()
+        self.get(key).expect("no entry found for key")
     }
 }
 
@@ -693,6 +898,48 @@ impl<'a, K, V> Iterator for Iter<'a, K, V> {
 impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
 impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
 
+/// An iterator over a sub-range of the key-value pairs of an archived B-tree map.
+///
+/// This is returned by [`ArchivedBTreeMap::range`].
+pub struct Range<'a, K, V> {
+    front: NonNull<NodeHeader>,
+    front_index: usize,
+    back: NonNull<NodeHeader>,
+    back_index: usize,
+    _phantom: PhantomData<(&'a K, &'a V)>,
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.front == self.back && self.front_index == self.back_index {
+                return None;
+            }
+
+            unsafe {
+                // SAFETY: self.front always points to a leaf node header when it is not at the
+                // back of the range
+                let leaf = self.front.as_ref().classify_leaf::<K, V>();
+                if self.front_index == leaf.tail.len() {
+                    // SAFETY: the back of the range is in a later leaf node, so this points to the
+                    // next leaf node
+                    self.front = NonNull::new_unchecked(leaf.header.ptr.as_ptr() as *mut _);
+                    self.front_index = 0;
+                } else {
+                    let result = &leaf.tail[self.front_index];
+                    self.front_index += 1;
+                    return Some((&result.key, &result.value));
+                }
+            }
+        }
+    }
+}
+
+impl<'a, K, V> FusedIterator for Range<'a, K, V> {}
+
 /// An iterator over the keys of an archived B-tree map.
 pub struct Keys<'a, K, V> {
     inner: RawIter<'a, K, V>,
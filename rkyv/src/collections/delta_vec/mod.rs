@@ -0,0 +1,372 @@
+//! An archived vector of integers that stores the differences between consecutive values.
+
+#[cfg(feature = "validation")]
+pub mod validation;
+
+use crate::{
+    ser::{ScratchSpace, Serializer},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Archived, Serialize,
+};
+use core::{fmt, iter::FusedIterator};
+
+/// The number of elements in each block of a delta-encoded vector.
+const BLOCK_LEN: usize = 64;
+
+/// The maximum number of bytes that a varint-encoded `u64` can take.
+pub(crate) const MAX_VARINT_LEN: usize = 10;
+
+/// An integer type that can be delta-encoded in an [`ArchivedDeltaVec`].
+pub trait DeltaEncode: Archive + Copy {
+    /// Returns the difference from `prev` to `self`, zigzag-encoded so that small negative
+    /// differences are also small.
+    fn delta_from(self, prev: Self) -> u64;
+
+    /// Returns the value that is `delta` after `self`, where `delta` was returned from
+    /// [`delta_from`](DeltaEncode::delta_from).
+    fn apply_delta(self, delta: u64) -> Self;
+
+    /// Returns the native value of an archived value.
+    fn from_archived(archived: &Self::Archived) -> Self;
+}
+
+macro_rules! impl_delta_encode {
+    ($($ty:ty: $signed:ty),* $(,)?) => {
+        $(
+            impl DeltaEncode for $ty {
+                #[inline]
+                fn delta_from(self, prev: Self) -> u64 {
+                    let delta = self.wrapping_sub(prev) as $signed as i64;
+                    ((delta << 1) ^ (delta >> 63)) as u64
+                }
+
+                #[inline]
+                fn apply_delta(self, delta: u64) -> Self {
+                    let delta = (delta >> 1) as i64 ^ -((delta & 1) as i64);
+                    self.wrapping_add(delta as $ty)
+                }
+
+                #[inline]
+                fn from_archived(archived: &Self::Archived) -> Self {
+                    <$ty>::from(*archived)
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_encode! {
+    u8: i8,
+    u16: i16,
+    u32: i32,
+    u64: i64,
+    i8: i8,
+    i16: i16,
+    i32: i32,
+    i64: i64,
+}
+
+#[inline]
+pub(crate) fn varint_len(value: u64) -> usize {
+    (64 - (value | 1).leading_zeros() as usize + 6) / 7
+}
+
+#[inline]
+pub(crate) fn write_varint(mut value: u64, buf: &mut [u8; MAX_VARINT_LEN]) -> usize {
+    let mut len = 0;
+    while value >= 0x80 {
+        buf[len] = value as u8 | 0x80;
+        value >>= 7;
+        len += 1;
+    }
+    buf[len] = value as u8;
+    len + 1
+}
+
+/// Reads a varint from `bytes` starting at `pos` and advances `pos` past it.
+///
+/// Returns `None` if the varint is truncated or doesn't fit in a `u64`.
+#[inline]
+pub(crate) fn read_varint(bytes: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    for i in 0..MAX_VARINT_LEN {
+        let byte = *bytes.get(*pos + i)?;
+        let bits = (byte & 0x7f) as u64;
+        if i == MAX_VARINT_LEN - 1 && bits > 1 {
+            return None;
+        }
+        value |= bits << (7 * i);
+        if byte & 0x80 == 0 {
+            *pos += i + 1;
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// An archived vector of integers, laid out as the differences between consecutive values.
+///
+/// Sorted or slowly-changing sequences (like timestamps or increasing IDs) have differences that
+/// are much smaller than the values themselves. A delta vector stores each difference as a varint,
+/// which takes one byte for differences up to 63 in either direction and one more byte for every
+/// seven more bits. A column of millisecond timestamps taken about a second apart takes two bytes
+/// per value instead of eight.
+///
+/// The [`AsDeltaVec`](crate::with::AsDeltaVec) wrapper can be used to archive a `Vec<T>` as a
+/// delta vector. Integers of up to 64 bits can be delta-encoded; see [`DeltaEncode`].
+///
+/// # Layout
+///
+/// The values are split into blocks of 64. The first value of each block is stored as an
+/// `Archived<T>`, followed by the byte offset of the block's differences in a shared byte array.
+/// The differences are zigzag-encoded (so small negative differences are also small) and written
+/// as LEB128 varints.
+///
+/// # Access complexity
+///
+/// Values aren't stored directly, so they have to be reconstructed from the differences:
+///
+/// - [`get`] decodes from the start of the value's block, so it reads up to 63 varints and takes
+///   constant time that's much slower than indexing a slice.
+/// - [`iter`] decodes each varint once, so iterating over the whole vector takes linear time and
+///   is the fastest way to read many values.
+///
+/// [`get`]: ArchivedDeltaVec::get
+/// [`iter`]: ArchivedDeltaVec::iter
+///
+/// # Example
+///
+/// ```
+/// use core::mem::size_of;
+/// use rkyv::{
+///     archived_root,
+///     ser::{serializers::AllocSerializer, Serializer},
+///     with::AsDeltaVec,
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Samples {
+///     #[with(AsDeltaVec)]
+///     timestamps: Vec<u64>,
+/// }
+///
+/// // Timestamps about a second apart
+/// let timestamps = (0..10_000u64)
+///     .map(|i| 1_650_000_000_000 + i * 1_000 + i % 7)
+///     .collect::<Vec<_>>();
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&Samples { timestamps: timestamps.clone() }).unwrap();
+/// let buf = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<Samples>(&buf) };
+///
+/// assert_eq!(archived.timestamps.len(), 10_000);
+/// assert_eq!(archived.timestamps.get(1234), Some(timestamps[1234]));
+/// assert!(archived.timestamps.iter().eq(timestamps.iter().copied()));
+///
+/// // Each difference takes two bytes instead of the eight bytes of each value
+/// assert!(buf.len() < 10_000 * size_of::<u64>() / 3);
+/// ```
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedDeltaVec<T: Archive> {
+    len: Archived<usize>,
+    firsts: ArchivedVec<T::Archived>,
+    offsets: ArchivedVec<Archived<usize>>,
+    deltas: ArchivedVec<u8>,
+}
+
+impl<T: DeltaEncode> ArchivedDeltaVec<T> {
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        from_archived!(self.len) as usize
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the encoded differences between the values of the vector.
+    ///
+    /// This is the bulk of the archived vector, and its length is a good measure of how well the
+    /// values compressed.
+    #[inline]
+    pub fn delta_bytes(&self) -> &[u8] {
+        self.deltas.as_slice()
+    }
+
+    /// Gets the element at the given index.
+    ///
+    /// This decodes the differences from the start of the element's block, so it reads at most 63
+    /// varints. Use [`iter`](ArchivedDeltaVec::iter) to read many consecutive elements.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the differences are not validly encoded, which can only happen if the archive was
+    /// not validated.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let block = index / BLOCK_LEN;
+        let mut value = T::from_archived(&self.firsts[block]);
+        let mut pos = from_archived!(self.offsets[block]) as usize;
+        for _ in 0..index % BLOCK_LEN {
+            let delta = read_varint(self.deltas.as_slice(), &mut pos).expect("invalid delta");
+            value = value.apply_delta(delta);
+        }
+        Some(value)
+    }
+
+    /// Gets an iterator over the elements of the vector.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            vec: self,
+            index: 0,
+            value: None,
+            pos: 0,
+        }
+    }
+
+    /// Resolves an archived delta vector from a given length and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of elements that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a delta vector
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: DeltaVecResolver,
+        out: *mut Self,
+    ) {
+        let blocks_len = len / BLOCK_LEN + (len % BLOCK_LEN != 0) as usize;
+        let (fp, fo) = out_field!(out.len);
+        usize::resolve(&len, pos + fp, (), fo);
+        let (fp, fo) = out_field!(out.firsts);
+        ArchivedVec::resolve_from_len(blocks_len, pos + fp, resolver.firsts, fo);
+        let (fp, fo) = out_field!(out.offsets);
+        ArchivedVec::resolve_from_len(blocks_len, pos + fp, resolver.offsets, fo);
+        let (fp, fo) = out_field!(out.deltas);
+        ArchivedVec::resolve_from_len(resolver.deltas_len, pos + fp, resolver.deltas, fo);
+    }
+
+    /// Serializes a slice of integers as a delta vector.
+    pub fn serialize_from_slice<S>(
+        items: &[T],
+        serializer: &mut S,
+    ) -> Result<DeltaVecResolver, S::Error>
+    where
+        T: Serialize<S>,
+        S: ScratchSpace + Serializer + ?Sized,
+    {
+        let firsts = ArchivedVec::serialize_from_iter::<T, _, _, _>(
+            items.chunks(BLOCK_LEN).map(|block| &block[0]),
+            serializer,
+        )?;
+
+        let mut deltas_len = 0;
+        let offsets = ArchivedVec::serialize_from_iter::<usize, _, _, _>(
+            items.chunks(BLOCK_LEN).map(|block| {
+                let offset = deltas_len;
+                deltas_len += block
+                    .windows(2)
+                    .map(|pair| varint_len(pair[1].delta_from(pair[0])))
+                    .sum::<usize>();
+                offset
+            }),
+            serializer,
+        )?;
+
+        let deltas_pos = serializer.align_for::<u8>()?;
+        let mut buf = [0; MAX_VARINT_LEN];
+        for block in items.chunks(BLOCK_LEN) {
+            for pair in block.windows(2) {
+                let len = write_varint(pair[1].delta_from(pair[0]), &mut buf);
+                serializer.write(&buf[..len])?;
+            }
+        }
+
+        Ok(DeltaVecResolver {
+            firsts,
+            offsets,
+            deltas: VecResolver { pos: deltas_pos },
+            deltas_len,
+        })
+    }
+}
+
+impl<T: DeltaEncode + fmt::Debug> fmt::Debug for ArchivedDeltaVec<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: DeltaEncode + PartialEq> PartialEq<[T]> for ArchivedDeltaVec<T> {
+    #[inline]
+    fn eq(&self, other: &[T]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter().copied())
+    }
+}
+
+/// The resolver for archived delta vectors.
+pub struct DeltaVecResolver {
+    firsts: VecResolver,
+    offsets: VecResolver,
+    deltas: VecResolver,
+    deltas_len: usize,
+}
+
+/// An iterator over the elements of an archived delta vector.
+pub struct Iter<'a, T: Archive> {
+    vec: &'a ArchivedDeltaVec<T>,
+    index: usize,
+    value: Option<T>,
+    pos: usize,
+}
+
+impl<'a, T: DeltaEncode> Iterator for Iter<'a, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.vec.len() {
+            return None;
+        }
+
+        let value = match self.value {
+            Some(value) if self.index % BLOCK_LEN != 0 => {
+                let deltas = self.vec.deltas.as_slice();
+                value.apply_delta(read_varint(deltas, &mut self.pos).expect("invalid delta"))
+            }
+            _ => {
+                let block = self.index / BLOCK_LEN;
+                self.pos = from_archived!(self.vec.offsets[block]) as usize;
+                T::from_archived(&self.vec.firsts[block])
+            }
+        };
+        self.value = Some(value);
+        self.index += 1;
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.vec.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T: DeltaEncode> ExactSizeIterator for Iter<'a, T> {}
+impl<'a, T: DeltaEncode> FusedIterator for Iter<'a, T> {}
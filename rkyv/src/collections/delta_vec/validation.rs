@@ -0,0 +1,146 @@
+//! Validation implementation for ArchivedDeltaVec.
+
+use super::{read_varint, ArchivedDeltaVec, DeltaEncode, BLOCK_LEN};
+use crate::{
+    validation::{owned::CheckOwnedPointerError, ArchiveContext},
+    vec::ArchivedVec,
+    Archived,
+};
+use bytecheck::{CheckBytes, Error};
+use core::{convert::Infallible, fmt, ptr};
+
+/// Errors that can occur while checking an archived delta vector.
+#[derive(Debug)]
+pub enum DeltaVecError<F, O, D> {
+    /// An error occurred while checking the first values of the blocks
+    FirstsError(F),
+    /// An error occurred while checking the offsets of the blocks
+    OffsetsError(O),
+    /// An error occurred while checking the encoded differences
+    DeltasError(D),
+    /// The number of blocks did not match the length of the vector
+    InvalidBlockCount {
+        /// The length of the vector
+        len: usize,
+        /// The number of blocks that were archived
+        blocks: usize,
+    },
+    /// The encoded differences of a block did not decode to the number of elements in the block
+    InvalidBlock {
+        /// The index of the block
+        index: usize,
+    },
+}
+
+impl<F, O, D> From<Infallible> for DeltaVecError<F, O, D> {
+    #[inline]
+    fn from(_: Infallible) -> Self {
+        unsafe { core::hint::unreachable_unchecked() }
+    }
+}
+
+impl<F: fmt::Display, O: fmt::Display, D: fmt::Display> fmt::Display for DeltaVecError<F, O, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeltaVecError::FirstsError(e) => {
+                write!(f, "error while checking block first values: {}", e)
+            }
+            DeltaVecError::OffsetsError(e) => {
+                write!(f, "error while checking block offsets: {}", e)
+            }
+            DeltaVecError::DeltasError(e) => write!(f, "error while checking deltas: {}", e),
+            DeltaVecError::InvalidBlockCount { len, blocks } => write!(
+                f,
+                "invalid block count: {} blocks can't describe {} elements",
+                blocks, len
+            ),
+            DeltaVecError::InvalidBlock { index } => {
+                write!(f, "invalid deltas in block {}", index)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<F: Error + 'static, O: Error + 'static, D: Error + 'static> Error for DeltaVecError<F, O, D> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                DeltaVecError::FirstsError(e) => Some(e as &dyn Error),
+                DeltaVecError::OffsetsError(e) => Some(e as &dyn Error),
+                DeltaVecError::DeltasError(e) => Some(e as &dyn Error),
+                DeltaVecError::InvalidBlockCount { .. } | DeltaVecError::InvalidBlock { .. } => {
+                    None
+                }
+            }
+        }
+    }
+};
+
+impl<T, C> CheckBytes<C> for ArchivedDeltaVec<T>
+where
+    T: DeltaEncode,
+    T::Archived: CheckBytes<C>,
+    C: ArchiveContext + ?Sized,
+    C::Error: Error,
+{
+    type Error = DeltaVecError<
+        CheckOwnedPointerError<[T::Archived], C>,
+        CheckOwnedPointerError<[Archived<usize>], C>,
+        CheckOwnedPointerError<[u8], C>,
+    >;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        let len = from_archived!(*Archived::<usize>::check_bytes(
+            ptr::addr_of!((*value).len),
+            context,
+        )?) as usize;
+        let firsts =
+            ArchivedVec::<T::Archived>::check_bytes(ptr::addr_of!((*value).firsts), context)
+                .map_err(DeltaVecError::FirstsError)?;
+        let offsets =
+            ArchivedVec::<Archived<usize>>::check_bytes(ptr::addr_of!((*value).offsets), context)
+                .map_err(DeltaVecError::OffsetsError)?;
+        let deltas = ArchivedVec::<u8>::check_bytes(ptr::addr_of!((*value).deltas), context)
+            .map_err(DeltaVecError::DeltasError)?;
+
+        let blocks = len / BLOCK_LEN + (len % BLOCK_LEN != 0) as usize;
+        if firsts.len() != blocks || offsets.len() != blocks {
+            return Err(DeltaVecError::InvalidBlockCount {
+                len,
+                blocks: firsts.len().min(offsets.len()),
+            });
+        }
+
+        // Each block must decode exactly one difference for each element after its first, and end
+        // where the next block starts
+        let deltas = deltas.as_slice();
+        for index in 0..blocks {
+            let mut pos = from_archived!(offsets[index]) as usize;
+            let end = if index + 1 < blocks {
+                from_archived!(offsets[index + 1]) as usize
+            } else {
+                deltas.len()
+            };
+            if end > deltas.len() {
+                return Err(DeltaVecError::InvalidBlock { index });
+            }
+            let count = (len - index * BLOCK_LEN).min(BLOCK_LEN) - 1;
+            for _ in 0..count {
+                if pos >= end || read_varint(&deltas[..end], &mut pos).is_none() {
+                    return Err(DeltaVecError::InvalidBlock { index });
+                }
+            }
+            if pos != end {
+                return Err(DeltaVecError::InvalidBlock { index });
+            }
+        }
+
+        Ok(&*value)
+    }
+}
@@ -0,0 +1,766 @@
+//! Archived string sets and maps represented as finite state transducers.
+
+#[cfg(feature = "validation")]
+pub mod validation;
+
+use crate::{
+    de::ScratchPool,
+    ser::{ScratchSpace, Serializer},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Archived, Serialize,
+};
+#[cfg(not(feature = "std"))]
+use ::alloc::{string::String, vec, vec::Vec};
+use core::{fmt, iter::FusedIterator};
+use hashbrown::{hash_map::Entry, HashMap};
+
+/// The bit of a node's second word that is set if the node is final.
+const FINAL_BIT: u32 = 1 << 31;
+
+/// An archived set of strings, represented as a minimal finite state transducer.
+///
+/// The keys are stored as an automaton with one transition per byte. Keys that share a prefix share
+/// the transitions for that prefix, and keys that share a suffix share the transitions for that
+/// suffix, so large dictionaries of related strings (like words, paths, or URLs) are often many
+/// times smaller than the total length of their keys. Each transition also carries an output, and
+/// the outputs along the path of a key sum to its position in the sorted set. This makes it a
+/// transducer from keys to their indices rather than just an automaton.
+///
+/// The [`AsFst`](crate::with::AsFst) wrapper can be used to archive a `HashSet<String>` or
+/// `BTreeSet<String>` as an FST set.
+///
+/// # Compared to hash sets
+///
+/// Building an FST is more expensive than building a hash set. The keys are sorted, and then
+/// inserted one at a time while the serializer keeps a table of every distinct node that has been
+/// built to merge identical suffixes. This takes `O(n log n)` time for sorting and memory
+/// proportional to the size of the automaton. Sets of at most `u32::MAX` keys can be archived.
+///
+/// Looking up a key takes time proportional to the length of the key and does a binary search over
+/// the outgoing transitions of each node along the way, so it is usually slower than hashing the
+/// key for a hash set. In exchange, an FST set:
+///
+/// - is often much smaller than a hash set, which stores every key in full,
+/// - iterates over its keys in sorted order,
+/// - can iterate over all of the keys that start with a prefix with
+///   [`prefix_iter`](ArchivedFstSet::prefix_iter), and
+/// - can find the sorted position of a key with [`index_of`](ArchivedFstSet::index_of).
+///
+/// Because keys are not stored contiguously, iterating reconstructs each key into a new `String`.
+///
+/// # Layout
+///
+/// Nodes are written in post-order so that every transition points to a node that was written
+/// before the node it leaves. Each node is an `[Archived<u32>; 2]` that holds the index of its first
+/// outgoing transition followed by the number of transitions, with the highest bit set if the
+/// node ends a key. The outgoing transitions of a node are contiguous and sorted by byte. Each
+/// transition is an `[Archived<u32>; 2]` that holds the index of its target node followed by its
+/// output, and the byte of each transition is stored in a separate array so that the binary search
+/// for a byte only touches the bytes.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{archived_root, ser::{serializers::AllocSerializer, Serializer}, with::AsFst};
+/// use rkyv::{Archive, Serialize};
+/// use std::collections::HashSet;
+///
+/// #[derive(Archive, Serialize)]
+/// struct Dictionary {
+///     #[with(AsFst)]
+///     words: HashSet<String>,
+/// }
+///
+/// let words = ["car", "card", "care", "cart", "dart", "part"];
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer
+///     .serialize_value(&Dictionary {
+///         words: words.iter().map(|w| w.to_string()).collect(),
+///     })
+///     .unwrap();
+/// let buf = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<Dictionary>(&buf) };
+///
+/// assert_eq!(archived.words.len(), 6);
+/// assert!(archived.words.contains("card"));
+/// assert!(!archived.words.contains("ca"));
+/// assert_eq!(archived.words.index_of("dart"), Some(4));
+/// assert_eq!(
+///     archived.words.prefix_iter("car").collect::<Vec<_>>(),
+///     ["car", "card", "care", "cart"],
+/// );
+/// ```
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedFstSet {
+    len: Archived<u32>,
+    root: Archived<u32>,
+    nodes: ArchivedVec<[Archived<u32>; 2]>,
+    transitions: ArchivedVec<[Archived<u32>; 2]>,
+    bytes: ArchivedVec<u8>,
+}
+
+impl ArchivedFstSet {
+    /// Returns the number of keys in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        from_archived!(self.len) as usize
+    }
+
+    /// Returns `true` if the set contains no keys.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of nodes in the automaton.
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the number of transitions in the automaton.
+    #[inline]
+    pub fn transition_count(&self) -> usize {
+        self.transitions.len()
+    }
+
+    #[inline]
+    fn root(&self) -> u32 {
+        from_archived!(self.root)
+    }
+
+    #[inline]
+    fn is_final(&self, node: u32) -> bool {
+        from_archived!(self.nodes[node as usize][1]) & FINAL_BIT != 0
+    }
+
+    #[inline]
+    fn transition_range(&self, node: u32) -> (usize, usize) {
+        let node = &self.nodes[node as usize];
+        let start = from_archived!(node[0]) as usize;
+        let len = (from_archived!(node[1]) & !FINAL_BIT) as usize;
+        (start, start + len)
+    }
+
+    // Returns the target and output of the transition from a node on a byte.
+    #[inline]
+    fn transition(&self, node: u32, byte: u8) -> Option<(u32, u32)> {
+        let (start, end) = self.transition_range(node);
+        let index = self.bytes[start..end].binary_search(&byte).ok()?;
+        let transition = &self.transitions[start + index];
+        Some((from_archived!(transition[0]), from_archived!(transition[1])))
+    }
+
+    // Follows the path of a key, returning the node it ends at and the sum of the outputs along
+    // the path.
+    #[inline]
+    fn walk(&self, key: &[u8]) -> Option<(u32, usize)> {
+        let mut node = self.root();
+        let mut output = 0;
+        for &byte in key {
+            let (target, out) = self.transition(node, byte)?;
+            node = target;
+            output += out as usize;
+        }
+        Some((node, output))
+    }
+
+    /// Returns `true` if the set contains the given key.
+    #[inline]
+    pub fn contains(&self, key: &str) -> bool {
+        self.index_of(key).is_some()
+    }
+
+    /// Returns the index of the given key in the sorted order of the keys, or `None` if the set
+    /// doesn't contain the key.
+    #[inline]
+    pub fn index_of(&self, key: &str) -> Option<usize> {
+        match self.walk(key.as_bytes()) {
+            Some((node, output)) if self.is_final(node) => Some(output),
+            _ => None,
+        }
+    }
+
+    /// Gets an iterator over the keys of the set in sorted order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        self.prefix_iter("")
+    }
+
+    /// Gets an iterator over the keys of the set that start with the given prefix, in sorted
+    /// order.
+    ///
+    /// Finding the first key takes time proportional to the length of the prefix, after which only
+    /// the part of the automaton below the prefix is visited.
+    #[inline]
+    pub fn prefix_iter(&self, prefix: &str) -> Iter<'_> {
+        self.prefix_iter_in(prefix, Vec::new(), Vec::new())
+    }
+
+    /// Gets an iterator over all of the keys that uses scratch buffers from the given pool.
+    ///
+    /// The buffers can be given back with [`Iter::return_scratch`].
+    #[inline]
+    pub(crate) fn iter_with_scratch<P: ScratchPool + ?Sized>(&self, pool: &mut P) -> Iter<'_> {
+        self.prefix_iter_in("", pool.take_scratch(), pool.take_scratch())
+    }
+
+    fn prefix_iter_in(
+        &self,
+        prefix: &str,
+        mut key: Vec<u8>,
+        mut stack: Vec<(u32, Option<usize>)>,
+    ) -> Iter<'_> {
+        let index = match self.walk(prefix.as_bytes()) {
+            Some((node, index)) => {
+                key.extend_from_slice(prefix.as_bytes());
+                stack.push((node, None));
+                index
+            }
+            None => 0,
+        };
+        Iter {
+            set: self,
+            key,
+            stack,
+            index,
+        }
+    }
+
+    /// Resolves an archived FST set from a given length and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of keys that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing an FST set
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: FstSetResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.len);
+        (len as u32).resolve(pos + fp, (), fo);
+        let (fp, fo) = out_field!(out.root);
+        resolver.root.resolve(pos + fp, (), fo);
+        let (fp, fo) = out_field!(out.nodes);
+        ArchivedVec::resolve_from_len(resolver.nodes_len, pos + fp, resolver.nodes, fo);
+        let (fp, fo) = out_field!(out.transitions);
+        ArchivedVec::resolve_from_len(resolver.transitions_len, pos + fp, resolver.transitions, fo);
+        let (fp, fo) = out_field!(out.bytes);
+        ArchivedVec::resolve_from_len(resolver.transitions_len, pos + fp, resolver.bytes, fo);
+    }
+
+    /// Serializes an iterator of keys as an FST set.
+    ///
+    /// The keys are sorted before the automaton is built, so the iterator may return them in any
+    /// order. Duplicate keys are only stored once, and the length of the set is the number of
+    /// distinct keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than `u32::MAX` distinct keys.
+    pub fn serialize_from_iter<'a, I, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<FstSetResolver, S::Error>
+    where
+        I: Iterator<Item = &'a str>,
+        S: ScratchSpace + Serializer + ?Sized,
+    {
+        let mut keys = iter.collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys.dedup();
+        Self::serialize_sorted_keys(&keys, serializer)
+    }
+
+    // Builds and writes the automaton for a sorted slice of distinct keys.
+    fn serialize_sorted_keys<S>(
+        keys: &[&str],
+        serializer: &mut S,
+    ) -> Result<FstSetResolver, S::Error>
+    where
+        S: ScratchSpace + Serializer + ?Sized,
+    {
+        assert!(
+            keys.len() <= u32::MAX as usize,
+            "too many keys to archive as an FST"
+        );
+
+        let mut builder = Builder::new();
+        for key in keys.iter() {
+            builder.insert(key.as_bytes());
+        }
+        let root = builder.finish();
+
+        let nodes_len = builder.nodes.len();
+        let nodes = ArchivedVec::serialize_from_iter::<[u32; 2], _, _, _>(
+            builder.nodes.iter(),
+            serializer,
+        )?;
+        let transitions_len = builder.transitions.len();
+        let transitions = ArchivedVec::serialize_from_iter::<[u32; 2], _, _, _>(
+            builder.transitions.iter(),
+            serializer,
+        )?;
+        // Safety: `u8` archives as itself and has no padding
+        let bytes = unsafe { ArchivedVec::serialize_copy_from_slice(&builder.bytes, serializer)? };
+
+        Ok(FstSetResolver {
+            root,
+            nodes,
+            nodes_len,
+            transitions,
+            transitions_len,
+            bytes,
+        })
+    }
+}
+
+impl fmt::Debug for ArchivedFstSet {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for ArchivedFstSet {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for ArchivedFstSet {}
+
+impl<'a> IntoIterator for &'a ArchivedFstSet {
+    type Item = String;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The resolver for archived FST sets.
+pub struct FstSetResolver {
+    root: u32,
+    nodes: VecResolver,
+    nodes_len: usize,
+    transitions: VecResolver,
+    transitions_len: usize,
+    bytes: VecResolver,
+}
+
+/// An iterator over the keys of an archived FST set, in sorted order.
+///
+/// Keys are reconstructed from the automaton into new strings as they are visited. Keys are always
+/// valid UTF-8 when they are serialized; if the archive was corrupted so that they aren't, invalid
+/// sequences are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+pub struct Iter<'a> {
+    set: &'a ArchivedFstSet,
+    key: Vec<u8>,
+    // The nodes along the path to the current key, with the index of the next transition to visit
+    // from each. `None` means that the node has not yet been checked for whether it's final.
+    stack: Vec<(u32, Option<usize>)>,
+    index: usize,
+}
+
+impl<'a> Iter<'a> {
+    // Gives the key and stack buffers back to the pool they were taken from.
+    #[inline]
+    pub(crate) fn return_scratch<P: ScratchPool + ?Sized>(self, pool: &mut P) {
+        pool.return_scratch(self.key);
+        pool.return_scratch(self.stack);
+    }
+
+    // Returns the next key as bytes, along with its index in the set.
+    fn next_bytes(&mut self) -> Option<(usize, &[u8])> {
+        loop {
+            let (node, next) = self.stack.last_mut()?;
+            let node = *node;
+            match next {
+                None => {
+                    *next = Some(0);
+                    if self.set.is_final(node) {
+                        let index = self.index;
+                        self.index += 1;
+                        return Some((index, &self.key));
+                    }
+                }
+                Some(next) => {
+                    let (start, end) = self.set.transition_range(node);
+                    let transition = start + *next;
+                    if transition < end {
+                        *next += 1;
+                        self.key.push(self.set.bytes[transition]);
+                        let target = from_archived!(self.set.transitions[transition][0]);
+                        self.stack.push((target, None));
+                    } else {
+                        self.stack.pop();
+                        if !self.stack.is_empty() {
+                            self.key.pop();
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = String;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_bytes()
+            .map(|(_, key)| String::from_utf8_lossy(key).into_owned())
+    }
+}
+
+impl<'a> FusedIterator for Iter<'a> {}
+
+/// An archived map from strings to values, represented as a minimal finite state transducer.
+///
+/// The keys are stored as an [`ArchivedFstSet`], and the values are stored in an array in the
+/// sorted order of their keys. Looking up a key finds its index in the sorted order with the
+/// transducer and then reads the value at that index. See [`ArchivedFstSet`] for details on the
+/// build cost and query capabilities compared to a hash map.
+///
+/// The [`AsFst`](crate::with::AsFst) wrapper can be used to archive a `HashMap<String, V>` or
+/// `BTreeMap<String, V>` as an FST map.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{archived_root, ser::{serializers::AllocSerializer, Serializer}, with::AsFst};
+/// use rkyv::{Archive, Serialize};
+/// use std::collections::HashMap;
+///
+/// #[derive(Archive, Serialize)]
+/// struct Index {
+///     #[with(AsFst)]
+///     counts: HashMap<String, u32>,
+/// }
+///
+/// let mut counts = HashMap::new();
+/// counts.insert("apple".to_string(), 3);
+/// counts.insert("apricot".to_string(), 5);
+/// counts.insert("banana".to_string(), 7);
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&Index { counts }).unwrap();
+/// let buf = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<Index>(&buf) };
+///
+/// assert_eq!(*archived.counts.get("apricot").unwrap(), 5);
+/// assert!(archived.counts.get("cherry").is_none());
+/// assert_eq!(
+///     archived.counts.prefix_iter("ap").map(|(k, _)| k).collect::<Vec<_>>(),
+///     ["apple", "apricot"],
+/// );
+/// ```
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedFstMap<V> {
+    keys: ArchivedFstSet,
+    values: ArchivedVec<V>,
+}
+
+impl<V> ArchivedFstMap<V> {
+    /// Returns the number of entries in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the map contains no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the set of keys in the map.
+    #[inline]
+    pub fn keys(&self) -> &ArchivedFstSet {
+        &self.keys
+    }
+
+    /// Gets the values of the map as a slice, in the sorted order of their keys.
+    #[inline]
+    pub fn values(&self) -> &[V] {
+        self.values.as_slice()
+    }
+
+    /// Returns `true` if the map contains a value for the given key.
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.keys.contains(key)
+    }
+
+    /// Returns a reference to the value corresponding to the given key.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.keys
+            .index_of(key)
+            .and_then(|index| self.values.get(index))
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    #[inline]
+    pub fn iter(&self) -> MapIter<'_, V> {
+        self.prefix_iter("")
+    }
+
+    /// Gets an iterator over the entries of the map with keys that start with the given prefix,
+    /// sorted by key.
+    #[inline]
+    pub fn prefix_iter(&self, prefix: &str) -> MapIter<'_, V> {
+        MapIter {
+            keys: self.keys.prefix_iter(prefix),
+            values: self.values.as_slice(),
+        }
+    }
+
+    /// Gets an iterator over all of the entries that uses scratch buffers from the given pool.
+    ///
+    /// The buffers can be given back with [`MapIter::return_scratch`].
+    #[inline]
+    pub(crate) fn iter_with_scratch<P: ScratchPool + ?Sized>(
+        &self,
+        pool: &mut P,
+    ) -> MapIter<'_, V> {
+        MapIter {
+            keys: self.keys.iter_with_scratch(pool),
+            values: self.values.as_slice(),
+        }
+    }
+
+    /// Resolves an archived FST map from a given length and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of entries that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing an FST map
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: FstMapResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.keys);
+        ArchivedFstSet::resolve_from_len(len, pos + fp, resolver.keys, fo);
+        let (fp, fo) = out_field!(out.values);
+        ArchivedVec::resolve_from_len(len, pos + fp, resolver.values, fo);
+    }
+
+    /// Serializes an iterator of key-value pairs as an FST map.
+    ///
+    /// The pairs are sorted by key before the automaton is built, so the iterator may return them in
+    /// any order. If the iterator returns the same key multiple times, only one of its values is
+    /// stored. The length of the map is the number of distinct keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than `u32::MAX` distinct keys.
+    pub fn serialize_from_iter<'a, UV, I, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<FstMapResolver, S::Error>
+    where
+        UV: 'a + Serialize<S, Archived = V>,
+        I: Iterator<Item = (&'a str, &'a UV)>,
+        S: ScratchSpace + Serializer + ?Sized,
+    {
+        let mut entries = iter.collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        entries.dedup_by(|a, b| a.0 == b.0);
+
+        let keys = entries.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        let keys = ArchivedFstSet::serialize_sorted_keys(&keys, serializer)?;
+        let values = ArchivedVec::serialize_from_iter::<UV, _, _, _>(
+            entries.iter().map(|(_, value)| *value),
+            serializer,
+        )?;
+
+        Ok(FstMapResolver { keys, values })
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for ArchivedFstMap<V> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V: PartialEq> PartialEq for ArchivedFstMap<V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.keys == other.keys && self.values() == other.values()
+    }
+}
+
+impl<V: Eq> Eq for ArchivedFstMap<V> {}
+
+impl<'a, V> IntoIterator for &'a ArchivedFstMap<V> {
+    type Item = (String, &'a V);
+    type IntoIter = MapIter<'a, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The resolver for archived FST maps.
+pub struct FstMapResolver {
+    keys: FstSetResolver,
+    values: VecResolver,
+}
+
+/// An iterator over the entries of an archived FST map, sorted by key.
+///
+/// See [`Iter`] for details on how keys are reconstructed.
+pub struct MapIter<'a, V> {
+    keys: Iter<'a>,
+    values: &'a [V],
+}
+
+impl<'a, V> MapIter<'a, V> {
+    // Gives the key and stack buffers back to the pool they were taken from.
+    #[inline]
+    pub(crate) fn return_scratch<P: ScratchPool + ?Sized>(self, pool: &mut P) {
+        self.keys.return_scratch(pool);
+    }
+}
+
+impl<'a, V> Iterator for MapIter<'a, V> {
+    type Item = (String, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let values = self.values;
+        self.keys.next_bytes().and_then(|(index, key)| {
+            values
+                .get(index)
+                .map(|value| (String::from_utf8_lossy(key).into_owned(), value))
+        })
+    }
+}
+
+impl<'a, V> FusedIterator for MapIter<'a, V> {}
+
+// A node that is still being built. Its transitions to finished nodes are sorted by byte, and it
+// may have one more transition on `next` to the next node on the stack of unfinished nodes.
+#[derive(Default)]
+struct Unfinished {
+    is_final: bool,
+    transitions: Vec<(u8, u32)>,
+    next: Option<u8>,
+}
+
+// Builds a minimal acyclic transducer from sorted keys, merging identical nodes as soon as they
+// are finished.
+//
+// See "Incremental Construction of Minimal Acyclic Finite-State Automata" by Daciuk et al.
+struct Builder {
+    nodes: Vec<[u32; 2]>,
+    transitions: Vec<[u32; 2]>,
+    bytes: Vec<u8>,
+    // The number of keys accepted starting at each finished node
+    counts: Vec<u32>,
+    registry: HashMap<(bool, Vec<(u8, u32)>), u32>,
+    unfinished: Vec<Unfinished>,
+    previous: Vec<u8>,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            transitions: Vec::new(),
+            bytes: Vec::new(),
+            counts: Vec::new(),
+            registry: HashMap::new(),
+            unfinished: vec![Unfinished::default()],
+            previous: Vec::new(),
+        }
+    }
+
+    // Inserts a key, which must be greater than all of the previously-inserted keys.
+    fn insert(&mut self, key: &[u8]) {
+        debug_assert!(self.previous.is_empty() || self.previous.as_slice() < key);
+
+        let prefix = self
+            .previous
+            .iter()
+            .zip(key.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        self.finish_to(prefix);
+
+        for &byte in &key[prefix..] {
+            self.unfinished.last_mut().unwrap().next = Some(byte);
+            self.unfinished.push(Unfinished::default());
+        }
+        self.unfinished.last_mut().unwrap().is_final = true;
+
+        self.previous.clear();
+        self.previous.extend_from_slice(key);
+    }
+
+    // Finishes the unfinished nodes deeper than `depth`.
+    fn finish_to(&mut self, depth: usize) {
+        while self.unfinished.len() > depth + 1 {
+            let node = self.unfinished.pop().unwrap();
+            let id = self.register(node);
+            let parent = self.unfinished.last_mut().unwrap();
+            let byte = parent.next.take().unwrap();
+            parent.transitions.push((byte, id));
+        }
+    }
+
+    // Finishes all of the nodes and returns the root.
+    fn finish(&mut self) -> u32 {
+        self.finish_to(0);
+        let root = self.unfinished.pop().unwrap();
+        self.register(root)
+    }
+
+    // Returns the index of a finished node that is identical to the given node, adding it if there
+    // isn't one.
+    fn register(&mut self, node: Unfinished) -> u32 {
+        match self.registry.entry((node.is_final, node.transitions)) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                let (is_final, transitions) = entry.key();
+                let id = self.nodes.len() as u32;
+                let start = self.transitions.len() as u32;
+
+                // The output of each transition is the number of keys that come before the keys
+                // reached through it: one if this node is final, plus the keys reached through
+                // all of the transitions on smaller bytes.
+                let mut count = *is_final as u32;
+                for &(byte, target) in transitions.iter() {
+                    self.transitions.push([target, count]);
+                    self.bytes.push(byte);
+                    count += self.counts[target as usize];
+                }
+
+                let mut info = transitions.len() as u32;
+                if *is_final {
+                    info |= FINAL_BIT;
+                }
+                self.nodes.push([start, info]);
+                self.counts.push(count);
+                entry.insert(id);
+                id
+            }
+        }
+    }
+}
@@ -0,0 +1,313 @@
+//! Validation implementations for ArchivedFstSet and ArchivedFstMap.
+
+use super::{ArchivedFstMap, ArchivedFstSet, FINAL_BIT};
+use crate::{
+    validation::{owned::CheckOwnedPointerError, ArchiveContext},
+    vec::ArchivedVec,
+    Archived,
+};
+#[cfg(not(feature = "std"))]
+use ::alloc::vec::Vec;
+use bytecheck::{CheckBytes, Error};
+use core::{convert::Infallible, fmt, ptr};
+
+/// Errors that can occur while checking an archived FST set.
+#[derive(Debug)]
+pub enum FstSetError<N, B> {
+    /// An error occurred while checking the nodes
+    NodesError(N),
+    /// An error occurred while checking the transitions
+    TransitionsError(N),
+    /// An error occurred while checking the bytes of the transitions
+    BytesError(B),
+    /// The number of transition bytes did not match the number of transitions
+    InvalidByteCount {
+        /// The number of transitions
+        transitions: usize,
+        /// The number of transition bytes
+        bytes: usize,
+    },
+    /// The root was not a valid node
+    InvalidRoot {
+        /// The index of the root node
+        root: usize,
+        /// The number of nodes
+        nodes: usize,
+    },
+    /// A node's transitions were out of bounds
+    InvalidTransitions {
+        /// The index of the node
+        node: usize,
+    },
+    /// A node's transitions were not sorted by byte, or had duplicate bytes
+    UnsortedTransitions {
+        /// The index of the node
+        node: usize,
+    },
+    /// A transition pointed to a node that was not written before the node it leaves
+    InvalidTarget {
+        /// The index of the node the transition leaves
+        node: usize,
+        /// The index of the target node
+        target: usize,
+    },
+    /// A transition had an incorrect output
+    InvalidOutput {
+        /// The index of the transition
+        transition: usize,
+        /// The number of keys before the keys reached through the transition
+        expected: usize,
+        /// The output that was archived
+        actual: usize,
+    },
+    /// The length of the set did not match the number of keys in the automaton
+    InvalidLength {
+        /// The number of keys in the automaton
+        expected: usize,
+        /// The length that was archived
+        actual: usize,
+    },
+}
+
+impl<N, B> From<Infallible> for FstSetError<N, B> {
+    #[inline]
+    fn from(_: Infallible) -> Self {
+        unsafe { core::hint::unreachable_unchecked() }
+    }
+}
+
+impl<N: fmt::Display, B: fmt::Display> fmt::Display for FstSetError<N, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FstSetError::NodesError(e) => write!(f, "error while checking nodes: {}", e),
+            FstSetError::TransitionsError(e) => {
+                write!(f, "error while checking transitions: {}", e)
+            }
+            FstSetError::BytesError(e) => write!(f, "error while checking transition bytes: {}", e),
+            FstSetError::InvalidByteCount { transitions, bytes } => write!(
+                f,
+                "invalid transition byte count: {} bytes for {} transitions",
+                bytes, transitions
+            ),
+            FstSetError::InvalidRoot { root, nodes } => {
+                write!(f, "invalid root node {} with {} nodes", root, nodes)
+            }
+            FstSetError::InvalidTransitions { node } => {
+                write!(f, "transitions of node {} are out of bounds", node)
+            }
+            FstSetError::UnsortedTransitions { node } => {
+                write!(f, "transitions of node {} are not sorted by byte", node)
+            }
+            FstSetError::InvalidTarget { node, target } => write!(
+                f,
+                "invalid transition from node {} to node {}: targets must come before the node",
+                node, target
+            ),
+            FstSetError::InvalidOutput {
+                transition,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "invalid output for transition {}: expected {}, found {}",
+                transition, expected, actual
+            ),
+            FstSetError::InvalidLength { expected, actual } => write!(
+                f,
+                "invalid length: expected {} keys, found {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<N: Error + 'static, B: Error + 'static> Error for FstSetError<N, B> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                FstSetError::NodesError(e) | FstSetError::TransitionsError(e) => {
+                    Some(e as &dyn Error)
+                }
+                FstSetError::BytesError(e) => Some(e as &dyn Error),
+                FstSetError::InvalidByteCount { .. }
+                | FstSetError::InvalidRoot { .. }
+                | FstSetError::InvalidTransitions { .. }
+                | FstSetError::UnsortedTransitions { .. }
+                | FstSetError::InvalidTarget { .. }
+                | FstSetError::InvalidOutput { .. }
+                | FstSetError::InvalidLength { .. } => None,
+            }
+        }
+    }
+};
+
+impl<C> CheckBytes<C> for ArchivedFstSet
+where
+    C: ArchiveContext + ?Sized,
+    C::Error: Error,
+{
+    type Error = FstSetError<
+        CheckOwnedPointerError<[[Archived<u32>; 2]], C>,
+        CheckOwnedPointerError<[u8], C>,
+    >;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        let len = from_archived!(*Archived::<u32>::check_bytes(
+            ptr::addr_of!((*value).len),
+            context,
+        )?) as usize;
+        let root = from_archived!(*Archived::<u32>::check_bytes(
+            ptr::addr_of!((*value).root),
+            context,
+        )?) as usize;
+        let nodes =
+            ArchivedVec::<[Archived<u32>; 2]>::check_bytes(ptr::addr_of!((*value).nodes), context)
+                .map_err(FstSetError::NodesError)?;
+        let transitions = ArchivedVec::<[Archived<u32>; 2]>::check_bytes(
+            ptr::addr_of!((*value).transitions),
+            context,
+        )
+        .map_err(FstSetError::TransitionsError)?;
+        let bytes = ArchivedVec::<u8>::check_bytes(ptr::addr_of!((*value).bytes), context)
+            .map_err(FstSetError::BytesError)?;
+
+        if bytes.len() != transitions.len() {
+            return Err(FstSetError::InvalidByteCount {
+                transitions: transitions.len(),
+                bytes: bytes.len(),
+            });
+        }
+        if root >= nodes.len() {
+            return Err(FstSetError::InvalidRoot {
+                root,
+                nodes: nodes.len(),
+            });
+        }
+
+        // Every transition must point to an earlier node, which guarantees that the automaton is
+        // acyclic. The number of keys accepted from each node can then be computed in order.
+        let mut counts = Vec::with_capacity(nodes.len());
+        for (index, node) in nodes.iter().enumerate() {
+            let info = from_archived!(node[1]);
+            let start = from_archived!(node[0]) as usize;
+            let end = start + (info & !FINAL_BIT) as usize;
+            if end > transitions.len() {
+                return Err(FstSetError::InvalidTransitions { node: index });
+            }
+
+            if bytes[start..end].windows(2).any(|w| w[0] >= w[1]) {
+                return Err(FstSetError::UnsortedTransitions { node: index });
+            }
+
+            let mut count = (info & FINAL_BIT != 0) as usize;
+            for (i, transition) in transitions[start..end].iter().enumerate() {
+                let target = from_archived!(transition[0]) as usize;
+                if target >= index {
+                    return Err(FstSetError::InvalidTarget {
+                        node: index,
+                        target,
+                    });
+                }
+                let output = from_archived!(transition[1]) as usize;
+                if output != count {
+                    return Err(FstSetError::InvalidOutput {
+                        transition: start + i,
+                        expected: count,
+                        actual: output,
+                    });
+                }
+                count = count.saturating_add(counts[target]);
+            }
+            counts.push(count);
+        }
+
+        if counts[root] != len {
+            return Err(FstSetError::InvalidLength {
+                expected: counts[root],
+                actual: len,
+            });
+        }
+
+        Ok(&*value)
+    }
+}
+
+/// Errors that can occur while checking an archived FST map.
+#[derive(Debug)]
+pub enum FstMapError<K, V> {
+    /// An error occurred while checking the keys
+    KeysError(K),
+    /// An error occurred while checking the values
+    ValuesError(V),
+    /// The number of values did not match the number of keys
+    InvalidValueCount {
+        /// The number of keys
+        expected: usize,
+        /// The number of values that were archived
+        actual: usize,
+    },
+}
+
+impl<K: fmt::Display, V: fmt::Display> fmt::Display for FstMapError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FstMapError::KeysError(e) => write!(f, "error while checking keys: {}", e),
+            FstMapError::ValuesError(e) => write!(f, "error while checking values: {}", e),
+            FstMapError::InvalidValueCount { expected, actual } => write!(
+                f,
+                "invalid value count: expected {} values, found {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<K: Error + 'static, V: Error + 'static> Error for FstMapError<K, V> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                FstMapError::KeysError(e) => Some(e as &dyn Error),
+                FstMapError::ValuesError(e) => Some(e as &dyn Error),
+                FstMapError::InvalidValueCount { .. } => None,
+            }
+        }
+    }
+};
+
+impl<V, C> CheckBytes<C> for ArchivedFstMap<V>
+where
+    V: CheckBytes<C>,
+    C: ArchiveContext + ?Sized,
+    C::Error: Error,
+{
+    type Error =
+        FstMapError<<ArchivedFstSet as CheckBytes<C>>::Error, CheckOwnedPointerError<[V], C>>;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        let keys = ArchivedFstSet::check_bytes(ptr::addr_of!((*value).keys), context)
+            .map_err(FstMapError::KeysError)?;
+        let values = ArchivedVec::<V>::check_bytes(ptr::addr_of!((*value).values), context)
+            .map_err(FstMapError::ValuesError)?;
+
+        if values.len() != keys.len() {
+            return Err(FstMapError::InvalidValueCount {
+                expected: keys.len(),
+                actual: values.len(),
+            });
+        }
+
+        Ok(&*value)
+    }
+}
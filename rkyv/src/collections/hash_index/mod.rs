@@ -45,6 +45,17 @@ impl ArchivedHashIndex {
         Self::make_hasher()
     }
 
+    /// Hashes a key with the hasher used by all archived hash indexes.
+    ///
+    /// This can be used to precompute the hashes passed to
+    /// [`build_and_serialize_prehashed`](ArchivedHashIndex::build_and_serialize_prehashed).
+    #[inline]
+    pub fn hash_key<K: Hash + ?Sized>(k: &K) -> u64 {
+        let mut hasher = Self::make_hasher();
+        k.hash(&mut hasher);
+        hasher.finish()
+    }
+
     #[inline]
     fn displace_slice(&self) -> &[Archived<u32>] {
         unsafe { slice::from_raw_parts(self.displace.as_ptr(), self.len()) }
@@ -138,6 +149,37 @@ const _: () = {
             V: 'a,
             S: Serializer + ScratchSpace + ?Sized,
             I: ExactSizeIterator<Item = (&'a K, &'a V)>,
+        {
+            Self::build_and_serialize_prehashed(
+                iter.map(|(key, value)| (Self::hash_key(key), key, value)),
+                serializer,
+                entries,
+            )
+        }
+
+        /// Builds and serializes a hash index from an iterator of precomputed key hashes and
+        /// key-value pairs.
+        ///
+        /// This skips hashing each key up front, which is useful when the hashes of large
+        /// collections were already computed while building them.
+        ///
+        /// # Safety
+        ///
+        /// - The keys returned by the iterator must be unique.
+        /// - Each hash must be the result of calling
+        ///   [`hash_key`](ArchivedHashIndex::hash_key) on its key.
+        /// - `entries` must have a capacity of `iter.len()` entries.
+        #[allow(clippy::type_complexity)]
+        pub unsafe fn build_and_serialize_prehashed<'a, K, V, S, I>(
+            iter: I,
+            serializer: &mut S,
+            entries: &mut ScratchVec<MaybeUninit<(&'a K, &'a V)>>,
+        ) -> Result<HashIndexResolver, S::Error>
+        where
+            K: 'a + Hash,
+            V: 'a,
+            S: Serializer + ScratchSpace + ?Sized,
+            I: ExactSizeIterator<Item = (u64, &'a K, &'a V)>,
         {
             let len = iter.len();
 
@@ -148,10 +190,8 @@ const _: () = {
 
             let mut displaces = ScratchVec::new(serializer, len)?;
 
-            for (key, value) in iter {
-                let mut hasher = Self::make_hasher();
-                key.hash(&mut hasher);
-                let displace = (hasher.finish() % len as u64) as u32;
+            for (hash, key, value) in iter {
+                let displace = (hash % len as u64) as u32;
                 displaces.push((displace, (key, value)));
                 bucket_size[displace as usize] += 1;
             }
@@ -19,10 +19,39 @@ use crate::{
     Serialize,
 };
 use core::{
-    borrow::Borrow, fmt, hash::Hash, iter::FusedIterator, marker::PhantomData, ops::Index, pin::Pin,
+    borrow::Borrow,
+    fmt,
+    hash::Hash,
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    pin::Pin,
 };
 
 /// An archived `HashMap`.
+///
+/// Archived hash maps use a [perfect hash](crate::collections::hash_index) of their keys, so they
+/// always have a load factor of exactly 1: the entries are stored without any empty buckets and the
+/// map takes up no more space than its entries and one 32-bit displacement per entry. Lookups
+/// compute at most two hashes and compare against exactly one key, so making the map sparser would
+/// not reduce the number of probes. Because of this, the load factor of archived hash maps can't be
+/// configured. To archive a hash map as an ordinary hash table with a chosen load factor and probe
+/// sequence instead, use the [`OpenAddressed`](crate::with::OpenAddressed) wrapper.
+///
+/// Since archived hash maps are immutable, they don't need any room to grow or tombstones for
+/// removed entries either. The archived map is built from the entries alone, so its size depends
+/// only on the number of entries and not on the capacity of the map it was archived from.
+///
+/// Because every key type trivially borrows as itself, lookups can also be performed with an
+/// archived key (`&K`), for example one read from a different archive when joining two archives on
+/// a shared key type. Archived keys can be used this way as long as they hash and compare the same
+/// as the unarchived keys that the map was built from, which is the case for archived strings,
+/// primitives, and archived types that derive `Hash` and `Eq` for the same fields.
+///
+/// Each entry stores its key and value inline, so finding a key also brings its value into cache.
+/// For maps with large values that are rarely read, the [`BoxedValues`](crate::with::BoxedValues)
+/// wrapper stores each value behind a relative pointer instead so that reading keys touches no
+/// value bytes.
 #[cfg_attr(feature = "strict", repr(C))]
 pub struct ArchivedHashMap<K, V> {
     index: ArchivedHashIndex,
@@ -232,6 +261,77 @@ const _: () = {
             VU: 'a + Serialize<S, Archived = V>,
             S: Serializer + ScratchSpace + ?Sized,
             I: ExactSizeIterator<Item = (&'a KU, &'a VU)>,
+        {
+            Self::serialize_from_prehashed_iter(
+                iter.map(|(key, value)| (ArchivedHashIndex::hash_key(key), key, value)),
+                serializer,
+            )
+        }
+
+        /// Serializes an iterator of precomputed key hashes and key-value pairs as a hash map.
+        ///
+        /// The archived map is identical to one serialized with
+        /// [`serialize_from_iter`](ArchivedHashMap::serialize_from_iter), but the keys are not
+        /// hashed again while building the map. Hashes can be computed ahead of time with
+        /// [`ArchivedHashIndex::hash_key`].
+        ///
+        /// # Safety
+        ///
+        /// - The keys returned by the iterator must be unique.
+        /// - Each hash must be the result of calling [`ArchivedHashIndex::hash_key`] on its key.
+        pub unsafe fn serialize_from_prehashed_iter<'a, KU, VU, S, I>(
+            iter: I,
+            serializer: &mut S,
+        ) -> Result<HashMapResolver, S::Error>
+        where
+            KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
+            VU: 'a + Serialize<S, Archived = V>,
+            S: Serializer + ScratchSpace + ?Sized,
+            I: ExactSizeIterator<Item = (u64, &'a KU, &'a VU)>,
+        {
+            Self::serialize_entries(iter, serializer, false)
+        }
+
+        /// Serializes an iterator of key-value pairs as a hash map, writing the dependencies of
+        /// all the keys before those of any of the values.
+        ///
+        /// The archived map is identical to one serialized with
+        /// [`serialize_from_iter`](ArchivedHashMap::serialize_from_iter), but keys that store
+        /// their data out-of-line (like strings) end up in one contiguous pool. This keeps the
+        /// bytes compared during lookups close together instead of interleaving them with values.
+        ///
+        /// # Safety
+        ///
+        /// The keys returned by the iterator must be unique.
+        pub unsafe fn serialize_from_iter_pooled_keys<'a, KU, VU, S, I>(
+            iter: I,
+            serializer: &mut S,
+        ) -> Result<HashMapResolver, S::Error>
+        where
+            KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
+            VU: 'a + Serialize<S, Archived = V>,
+            S: Serializer + ScratchSpace + ?Sized,
+            I: ExactSizeIterator<Item = (&'a KU, &'a VU)>,
+        {
+            Self::serialize_entries(
+                iter.map(|(key, value)| (ArchivedHashIndex::hash_key(key), key, value)),
+                serializer,
+                true,
+            )
+        }
+
+        // Builds the index and serializes the entries. With `pool_keys`, the dependencies of all
+        // the keys are written before those of any of the values.
+        unsafe fn serialize_entries<'a, KU, VU, S, I>(
+            iter: I,
+            serializer: &mut S,
+            pool_keys: bool,
+        ) -> Result<HashMapResolver, S::Error>
+        where
+            KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
+            VU: 'a + Serialize<S, Archived = V>,
+            S: Serializer + ScratchSpace + ?Sized,
+            I: ExactSizeIterator<Item = (u64, &'a KU, &'a VU)>,
         {
             use crate::ScratchVec;
 
@@ -240,25 +340,38 @@ const _: () = {
             let mut entries = ScratchVec::new(serializer, len)?;
             entries.set_len(len);
             let index_resolver =
-                ArchivedHashIndex::build_and_serialize(iter, serializer, &mut entries)?;
+                ArchivedHashIndex::build_and_serialize_prehashed(iter, serializer, &mut entries)?;
             let mut entries = entries.assume_init();
 
             // Serialize entries
-            let mut resolvers = ScratchVec::new(serializer, len)?;
-            for (key, value) in entries.iter() {
-                resolvers.push((key.serialize(serializer)?, value.serialize(serializer)?));
+            let mut key_resolvers = ScratchVec::new(serializer, len)?;
+            let mut value_resolvers = ScratchVec::new(serializer, len)?;
+            if pool_keys {
+                for (key, _) in entries.iter() {
+                    key_resolvers.push(key.serialize(serializer)?);
+                }
+                for (_, value) in entries.iter() {
+                    value_resolvers.push(value.serialize(serializer)?);
+                }
+            } else {
+                for (key, value) in entries.iter() {
+                    key_resolvers.push(key.serialize(serializer)?);
+                    value_resolvers.push(value.serialize(serializer)?);
+                }
             }
 
             let entries_pos = serializer.align_for::<Entry<K, V>>()?;
-            for ((key, value), (key_resolver, value_resolver)) in
-                entries.drain(..).zip(resolvers.drain(..))
+            for ((key, value), (key_resolver, value_resolver)) in entries
+                .drain(..)
+                .zip(key_resolvers.drain(..).zip(value_resolvers.drain(..)))
             {
                 serializer
                     .resolve_aligned(&Entry { key, value }, (key_resolver, value_resolver))?;
             }
 
             // Free scratch vecs
-            resolvers.free(serializer)?;
+            value_resolvers.free(serializer)?;
+            key_resolvers.free(serializer)?;
             entries.free(serializer)?;
 
             Ok(HashMapResolver {
@@ -281,9 +394,39 @@ impl<K: Hash + Eq, V: Eq> Eq for ArchivedHashMap<K, V> {}
 impl<K: Eq + Hash + Borrow<Q>, Q: Eq + Hash + ?Sized, V> Index<&'_ Q> for ArchivedHashMap<K, V> {
     type Output = V;
 
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
     #[inline]
     fn index(&self, key: &Q) -> &V {
-        self.get(key).unwrap()
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+/// Archived hash maps are `!Unpin` because moving one would break its relative pointers, so a
+/// `&mut ArchivedHashMap` can only be obtained from a pinned reference with the unsafe
+/// [`Pin::get_unchecked_mut`]. When a mutable reference is available, values that are [`Unpin`]
+/// (like primitives) can be indexed mutably to update them in place.
+///
+/// Without unsafe code, use [`get_pin`](ArchivedHashMap::get_pin) instead. Its result can be
+/// unwrapped with [`Pin::get_mut`] when the values are [`Unpin`].
+impl<K, Q, V> IndexMut<&'_ Q> for ArchivedHashMap<K, V>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash + ?Sized,
+    V: Unpin,
+{
+    /// Returns a mutable reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    #[inline]
+    fn index_mut(&mut self, key: &Q) -> &mut V {
+        let index = self.find(key).expect("no entry found for key");
+        unsafe { &mut self.entry_mut(index).value }
     }
 }
 
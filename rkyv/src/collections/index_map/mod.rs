@@ -13,7 +13,7 @@ use crate::{
     },
     out_field, Archived, RelPtr,
 };
-use core::{borrow::Borrow, fmt, hash::Hash, iter::FusedIterator, marker::PhantomData};
+use core::{borrow::Borrow, fmt, hash::Hash, iter::FusedIterator, marker::PhantomData, ops::Index};
 
 /// An archived `IndexMap`.
 #[cfg_attr(feature = "strict", repr(C))]
@@ -285,6 +285,20 @@ impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for ArchivedIndexMap<K, V> {
     }
 }
 
+impl<K: Eq + Hash + Borrow<Q>, Q: Eq + Hash + ?Sized, V> Index<&'_ Q> for ArchivedIndexMap<K, V> {
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    #[inline]
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
 impl<K: PartialEq, V: PartialEq> PartialEq for ArchivedIndexMap<K, V> {
     fn eq(&self, other: &Self) -> bool {
         self.iter().eq(other.iter())
@@ -0,0 +1,169 @@
+//! An archived vector with a fixed capacity that stores its elements inline.
+
+#[cfg(feature = "validation")]
+pub mod validation;
+
+use crate::Archived;
+#[cfg(feature = "alloc")]
+use crate::{Archive, Serialize};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use ::alloc::vec::Vec;
+use core::{fmt, hash, mem::MaybeUninit, ops::Deref, slice};
+#[cfg(feature = "alloc")]
+use core::{mem::size_of, ptr};
+
+/// An archived vector with a fixed capacity of `N` elements that stores its elements inline.
+///
+/// An [`ArchivedVec`](crate::vec::ArchivedVec) stores its elements out-of-line and points to them
+/// with a relative pointer, so reading its elements has to follow the pointer to another part of
+/// the archive. An inline vector instead stores its length followed by space for `N` elements
+/// directly in the containing value. For small vectors with a known upper bound on their length,
+/// this keeps the elements next to the rest of the value and removes a pointer chase, at the cost
+/// of always reserving space for `N` elements. The unused space is zeroed.
+///
+/// The [`AsInlineVec`](crate::with::AsInlineVec) wrapper can be used to archive a `Vec<T>` as an
+/// inline vector.
+///
+/// # Layout
+///
+/// An inline vector holds an `Archived<usize>` length and an array of `N` archived elements, so it
+/// always takes the same amount of space regardless of its length. Validation checks that the
+/// length is at most `N`, and only checks the first `len` elements.
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedInlineVec<T, const N: usize> {
+    len: Archived<usize>,
+    items: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> ArchivedInlineVec<T, N> {
+    /// The maximum number of elements the vector can hold.
+    pub const CAPACITY: usize = N;
+
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        from_archived!(self.len) as usize
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of elements the vector can hold.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Gets the elements of the vector as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.items.as_ptr().cast(), self.len()) }
+    }
+
+    /// Resolves an archived inline vector from a given slice.
+    ///
+    /// # Safety
+    ///
+    /// - `slice` must have at most `N` elements
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing `slice`
+    #[cfg(feature = "alloc")]
+    pub unsafe fn resolve_from_slice<U: Archive<Archived = T>>(
+        slice: &[U],
+        pos: usize,
+        resolver: InlineVecResolver<U::Resolver>,
+        out: *mut Self,
+    ) {
+        debug_assert!(slice.len() <= N);
+
+        let (fp, fo) = out_field!(out.len);
+        usize::resolve(&slice.len(), pos + fp, (), fo);
+
+        let (fp, fo) = out_field!(out.items);
+        let items = fo.cast::<T>();
+        for (i, (value, resolver)) in slice.iter().zip(resolver.resolvers).enumerate() {
+            value.resolve(pos + fp + i * size_of::<T>(), resolver, items.add(i));
+        }
+        // Zero the unused capacity so that the archive doesn't depend on what was in memory
+        ptr::write_bytes(items.add(slice.len()), 0, N - slice.len());
+    }
+
+    /// Serializes the dependencies of the elements of an archived inline vector from a given
+    /// slice.
+    ///
+    /// The slice must have at most `N` elements for it to be resolved, and the
+    /// [`AsInlineVec`](crate::with::AsInlineVec) wrapper returns an error before serializing
+    /// anything if it has more.
+    #[cfg(feature = "alloc")]
+    pub fn serialize_from_slice<U, S>(
+        slice: &[U],
+        serializer: &mut S,
+    ) -> Result<InlineVecResolver<U::Resolver>, S::Error>
+    where
+        U: Serialize<S, Archived = T>,
+        S: crate::Fallible + ?Sized,
+    {
+        let mut resolvers = Vec::with_capacity(slice.len());
+        for value in slice.iter() {
+            resolvers.push(value.serialize(serializer)?);
+        }
+        Ok(InlineVecResolver { resolvers })
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for ArchivedInlineVec<T, N> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArchivedInlineVec<T, N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<T, const N: usize> Deref for ArchivedInlineVec<T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ArchivedInlineVec<T, N> {}
+
+impl<T: hash::Hash, const N: usize> hash::Hash for ArchivedInlineVec<T, N> {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize, const M: usize> PartialEq<ArchivedInlineVec<U, M>>
+    for ArchivedInlineVec<T, N>
+{
+    #[inline]
+    fn eq(&self, other: &ArchivedInlineVec<U, M>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U]> for ArchivedInlineVec<T, N> {
+    #[inline]
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice().eq(other)
+    }
+}
+
+/// The resolver for archived inline vectors.
+#[cfg(feature = "alloc")]
+pub struct InlineVecResolver<R> {
+    resolvers: Vec<R>,
+}
@@ -0,0 +1,80 @@
+//! Validation implementation for ArchivedInlineVec.
+
+use super::ArchivedInlineVec;
+use crate::Archived;
+use bytecheck::{CheckBytes, SliceCheckError};
+use core::{convert::Infallible, fmt, ptr};
+
+/// Errors that can occur while checking an archived inline vector.
+#[derive(Debug)]
+pub enum InlineVecError<E> {
+    /// The length of the vector was greater than its capacity
+    InvalidLength {
+        /// The length that was archived
+        len: usize,
+        /// The capacity of the vector
+        capacity: usize,
+    },
+    /// An error occurred while checking the elements of the vector
+    ElementsError(SliceCheckError<E>),
+}
+
+impl<E> From<Infallible> for InlineVecError<E> {
+    #[inline]
+    fn from(_: Infallible) -> Self {
+        unsafe { core::hint::unreachable_unchecked() }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for InlineVecError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InlineVecError::InvalidLength { len, capacity } => write!(
+                f,
+                "invalid length: {} elements exceeds the capacity of {}",
+                len, capacity
+            ),
+            InlineVecError::ElementsError(e) => write!(f, "error while checking elements: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<E: Error + 'static> Error for InlineVecError<E> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                InlineVecError::InvalidLength { .. } => None,
+                InlineVecError::ElementsError(e) => Some(e as &dyn Error),
+            }
+        }
+    }
+};
+
+impl<T, C, const N: usize> CheckBytes<C> for ArchivedInlineVec<T, N>
+where
+    T: CheckBytes<C>,
+    C: ?Sized,
+{
+    type Error = InlineVecError<T::Error>;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        let len = from_archived!(*Archived::<usize>::check_bytes(
+            ptr::addr_of!((*value).len),
+            context,
+        )?) as usize;
+        if len > N {
+            return Err(InlineVecError::InvalidLength { len, capacity: N });
+        }
+
+        let items = ptr_meta::from_raw_parts::<[T]>(ptr::addr_of!((*value).items).cast(), len);
+        <[T]>::check_bytes(items, context).map_err(InlineVecError::ElementsError)?;
+
+        Ok(&*value)
+    }
+}
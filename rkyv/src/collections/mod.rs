@@ -2,18 +2,38 @@
 
 pub mod btree_map;
 pub mod btree_set;
+pub mod delta_vec;
+#[cfg(feature = "fst")]
+pub mod fst;
 pub mod hash_index;
 pub mod hash_map;
 pub mod hash_set;
 // TODO: move these into a separate crate when indexmap adds rkyv support
 pub mod index_map;
 pub mod index_set;
+pub mod inline_vec;
+pub mod nullable_vec;
+pub mod open_hash_map;
+pub mod result_vec;
+pub mod rle_vec;
+pub mod sorted_index;
+pub mod sorted_vec;
 pub mod util;
 
 pub use self::btree_map::ArchivedBTreeMap;
+pub use self::delta_vec::ArchivedDeltaVec;
+#[cfg(feature = "fst")]
+pub use self::fst::{ArchivedFstMap, ArchivedFstSet};
 pub use self::hash_index::ArchivedHashIndex;
 pub use self::hash_map::ArchivedHashMap;
 pub use self::hash_set::ArchivedHashSet;
 // TODO: move these into a separate crate when indexmap adds rkyv support
 pub use self::index_map::ArchivedIndexMap;
 pub use self::index_set::ArchivedIndexSet;
+pub use self::inline_vec::ArchivedInlineVec;
+pub use self::nullable_vec::ArchivedNullableVec;
+pub use self::open_hash_map::ArchivedOpenHashMap;
+pub use self::result_vec::ArchivedResultVec;
+pub use self::rle_vec::ArchivedRleVec;
+pub use self::sorted_index::ArchivedSortedIndex;
+pub use self::sorted_vec::ArchivedSortedVec;
@@ -0,0 +1,317 @@
+//! An archived vector of optional values that stores which values are present in a bitmap.
+
+#[cfg(feature = "validation")]
+pub mod validation;
+
+use crate::{
+    ser::{ScratchSpace, Serializer},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Archived, Serialize,
+};
+use core::{fmt, iter::FusedIterator, slice};
+
+/// The number of elements described by each block of the validity bitmap.
+const BLOCK_BITS: usize = 32;
+
+/// An archived vector of optional values, laid out as a dense array of the present values and a
+/// validity bitmap.
+///
+/// An archived `Vec<Option<T>>` stores a tag next to every element and reserves space for a value
+/// even when the element is `None`. A nullable vector instead stores only the values that are
+/// present, packed together, and records which elements are present with one bit per element (like
+/// the validity bitmaps of Apache Arrow). For large columns of data with missing values, this is
+/// much more compact and keeps the values contiguous for scanning.
+///
+/// The [`AsNullable`](crate::with::AsNullable) wrapper can be used to archive a `Vec<Option<T>>` as
+/// a nullable vector.
+///
+/// # Layout
+///
+/// The bitmap is split into blocks of 32 elements. Each block is an `[Archived<u32>; 2]` that holds
+/// the number of present values before the block followed by the validity bits of the block, with
+/// the bit for element `i` at `1 << (i % 32)`. Looking up an element reads its block and counts the
+/// set bits below it to find the element's position in the dense array, so [`get`] takes constant
+/// time.
+///
+/// [`get`]: ArchivedNullableVec::get
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{archived_root, ser::{serializers::AllocSerializer, Serializer}, with::AsNullable};
+/// use rkyv::{Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Column {
+///     #[with(AsNullable)]
+///     values: Vec<Option<u32>>,
+/// }
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer
+///     .serialize_value(&Column {
+///         values: vec![Some(1), None, Some(3), None],
+///     })
+///     .unwrap();
+/// let buf = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<Column>(&buf) };
+///
+/// assert_eq!(archived.values.len(), 4);
+/// assert_eq!(*archived.values.get(2).unwrap(), 3);
+/// assert_eq!(archived.values.get(1), None);
+/// assert_eq!(archived.values.null_count(), 2);
+/// // Only the present values are stored
+/// assert_eq!(archived.values.values(), [1, 3]);
+/// ```
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedNullableVec<T> {
+    len: Archived<usize>,
+    blocks: ArchivedVec<[Archived<u32>; 2]>,
+    values: ArchivedVec<T>,
+}
+
+impl<T> ArchivedNullableVec<T> {
+    /// Returns the number of elements in the vector, including null elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        from_archived!(self.len) as usize
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of null elements in the vector.
+    #[inline]
+    pub fn null_count(&self) -> usize {
+        self.len() - self.values.len()
+    }
+
+    /// Returns the present values of the vector as a dense slice, in order.
+    #[inline]
+    pub fn values(&self) -> &[T] {
+        self.values.as_slice()
+    }
+
+    /// Returns `true` if the element at the given index is null.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    pub fn is_null(&self, index: usize) -> bool {
+        assert!(
+            index < self.len(),
+            "index out of bounds: the len is {} but the index is {}",
+            self.len(),
+            index
+        );
+        let bits = from_archived!(self.blocks[index / BLOCK_BITS][1]);
+        bits & (1 << (index % BLOCK_BITS)) == 0
+    }
+
+    /// Gets the value of the element at the given index.
+    ///
+    /// Returns `None` if the element is null or the index is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        if self.is_null(index) {
+            None
+        } else {
+            Some(&self.values[self.rank(index)])
+        }
+    }
+
+    /// Returns the number of present values before the element at the given index.
+    ///
+    /// `index` must be less than the length of the vector.
+    #[inline]
+    pub(crate) fn rank(&self, index: usize) -> usize {
+        let block = &self.blocks[index / BLOCK_BITS];
+        let bit = 1u32 << (index % BLOCK_BITS);
+        let bits = from_archived!(block[1]);
+        (from_archived!(block[0]) + (bits & (bit - 1)).count_ones()) as usize
+    }
+
+    /// Gets an iterator over the elements of the vector.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            vec: self,
+            index: 0,
+            values: self.values.iter(),
+        }
+    }
+
+    /// Resolves an archived nullable vector from a given length and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of elements that were serialized, including null elements
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a nullable vector
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: NullableVecResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.len);
+        usize::resolve(&len, pos + fp, (), fo);
+        let (fp, fo) = out_field!(out.blocks);
+        ArchivedVec::resolve_from_len(resolver.blocks_len, pos + fp, resolver.blocks, fo);
+        let (fp, fo) = out_field!(out.values);
+        ArchivedVec::resolve_from_len(resolver.values_len, pos + fp, resolver.values, fo);
+    }
+
+    /// Serializes a slice of optional values as a nullable vector.
+    #[inline]
+    pub fn serialize_from_slice<U, S>(
+        items: &[Option<U>],
+        serializer: &mut S,
+    ) -> Result<NullableVecResolver, S::Error>
+    where
+        U: Serialize<S, Archived = T>,
+        S: ScratchSpace + Serializer + ?Sized,
+    {
+        Self::serialize_from_iter(items.iter().map(Option::as_ref), serializer)
+    }
+
+    /// Serializes an iterator of optional values as a nullable vector.
+    ///
+    /// The iterator is cloned to make a second pass over the items, so it should be cheap to clone.
+    pub fn serialize_from_iter<'a, U, S, I>(
+        items: I,
+        serializer: &mut S,
+    ) -> Result<NullableVecResolver, S::Error>
+    where
+        U: 'a + Serialize<S, Archived = T>,
+        S: ScratchSpace + Serializer + ?Sized,
+        I: ExactSizeIterator<Item = Option<&'a U>> + Clone,
+    {
+        let blocks_len = (items.len() + BLOCK_BITS - 1) / BLOCK_BITS;
+        let mut rank = 0;
+        let mut present = items.clone().map(|item| item.is_some());
+        let blocks = ArchivedVec::serialize_from_iter::<[u32; 2], _, _, _>(
+            (0..blocks_len).map(|_| {
+                let bits = present
+                    .by_ref()
+                    .take(BLOCK_BITS)
+                    .enumerate()
+                    .filter(|&(_, is_present)| is_present)
+                    .fold(0u32, |bits, (i, _)| bits | (1 << i));
+                let block = [rank, bits];
+                rank += bits.count_ones();
+                block
+            }),
+            serializer,
+        )?;
+
+        let values_len = rank as usize;
+        let values = ArchivedVec::serialize_from_iter::<U, _, _, _>(
+            Present {
+                items,
+                remaining: values_len,
+            },
+            serializer,
+        )?;
+
+        Ok(NullableVecResolver {
+            blocks,
+            blocks_len,
+            values,
+            values_len,
+        })
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedNullableVec<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<[Option<U>]> for ArchivedNullableVec<T> {
+    #[inline]
+    fn eq(&self, other: &[Option<U>]) -> bool {
+        self.len() == other.len()
+            && self.iter().zip(other.iter()).all(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => a.eq(b),
+                (None, None) => true,
+                _ => false,
+            })
+    }
+}
+
+/// The resolver for archived nullable vectors.
+pub struct NullableVecResolver {
+    blocks: VecResolver,
+    blocks_len: usize,
+    values: VecResolver,
+    values_len: usize,
+}
+
+// Iterates over the present values of an iterator of options with a known count.
+struct Present<I> {
+    items: I,
+    remaining: usize,
+}
+
+impl<'a, U: 'a, I: Iterator<Item = Option<&'a U>>> Iterator for Present<I> {
+    type Item = &'a U;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.items.by_ref().flatten().next();
+        if next.is_some() {
+            self.remaining -= 1;
+        }
+        next
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, U: 'a, I: Iterator<Item = Option<&'a U>>> ExactSizeIterator for Present<I> {}
+
+/// An iterator over the elements of an archived nullable vector.
+pub struct Iter<'a, T> {
+    vec: &'a ArchivedNullableVec<T>,
+    index: usize,
+    values: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = Option<&'a T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.vec.len() {
+            let is_null = self.vec.is_null(self.index);
+            self.index += 1;
+            Some(if is_null { None } else { self.values.next() })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.vec.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+impl<'a, T> FusedIterator for Iter<'a, T> {}
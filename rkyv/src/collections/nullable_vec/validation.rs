@@ -0,0 +1,165 @@
+//! Validation implementation for ArchivedNullableVec.
+
+use super::{ArchivedNullableVec, BLOCK_BITS};
+use crate::{
+    validation::{owned::CheckOwnedPointerError, ArchiveContext},
+    vec::ArchivedVec,
+    Archived,
+};
+use bytecheck::{CheckBytes, Error};
+use core::{convert::Infallible, fmt, ptr};
+
+/// Errors that can occur while checking an archived nullable vector.
+#[derive(Debug)]
+pub enum NullableVecError<B, V> {
+    /// An error occurred while checking the blocks of the validity bitmap
+    BlocksError(B),
+    /// An error occurred while checking the dense array of values
+    ValuesError(V),
+    /// The number of bitmap blocks did not match the length of the vector
+    InvalidBlockCount {
+        /// The length of the vector
+        len: usize,
+        /// The number of blocks that were archived
+        blocks: usize,
+    },
+    /// A bitmap block had an incorrect count of the values before it
+    InvalidRank {
+        /// The index of the block
+        index: usize,
+        /// The number of values present before the block
+        expected: usize,
+        /// The count that was archived in the block
+        actual: usize,
+    },
+    /// The last bitmap block had validity bits set past the end of the vector
+    InvalidPadding,
+    /// The number of values did not match the number of set bits in the bitmap
+    InvalidValueCount {
+        /// The number of set bits in the bitmap
+        expected: usize,
+        /// The number of values that were archived
+        actual: usize,
+    },
+}
+
+impl<B, V> From<Infallible> for NullableVecError<B, V> {
+    #[inline]
+    fn from(_: Infallible) -> Self {
+        unsafe { core::hint::unreachable_unchecked() }
+    }
+}
+
+impl<B: fmt::Display, V: fmt::Display> fmt::Display for NullableVecError<B, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NullableVecError::BlocksError(e) => write!(f, "error while checking bitmap: {}", e),
+            NullableVecError::ValuesError(e) => write!(f, "error while checking values: {}", e),
+            NullableVecError::InvalidBlockCount { len, blocks } => write!(
+                f,
+                "invalid bitmap block count: {} blocks can't describe {} elements",
+                blocks, len
+            ),
+            NullableVecError::InvalidRank {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "invalid rank in bitmap block {}: expected {}, found {}",
+                index, expected, actual
+            ),
+            NullableVecError::InvalidPadding => {
+                write!(f, "bitmap has validity bits set past the end of the vector")
+            }
+            NullableVecError::InvalidValueCount { expected, actual } => write!(
+                f,
+                "invalid value count: expected {} values, found {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<B: Error + 'static, V: Error + 'static> Error for NullableVecError<B, V> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                NullableVecError::BlocksError(e) => Some(e as &dyn Error),
+                NullableVecError::ValuesError(e) => Some(e as &dyn Error),
+                NullableVecError::InvalidBlockCount { .. }
+                | NullableVecError::InvalidRank { .. }
+                | NullableVecError::InvalidPadding
+                | NullableVecError::InvalidValueCount { .. } => None,
+            }
+        }
+    }
+};
+
+impl<T, C> CheckBytes<C> for ArchivedNullableVec<T>
+where
+    T: CheckBytes<C>,
+    C: ArchiveContext + ?Sized,
+    C::Error: Error,
+{
+    type Error = NullableVecError<
+        CheckOwnedPointerError<[[Archived<u32>; 2]], C>,
+        CheckOwnedPointerError<[T], C>,
+    >;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        let len = from_archived!(*Archived::<usize>::check_bytes(
+            ptr::addr_of!((*value).len),
+            context,
+        )?) as usize;
+        let blocks =
+            ArchivedVec::<[Archived<u32>; 2]>::check_bytes(ptr::addr_of!((*value).blocks), context)
+                .map_err(NullableVecError::BlocksError)?;
+        let values = ArchivedVec::<T>::check_bytes(ptr::addr_of!((*value).values), context)
+            .map_err(NullableVecError::ValuesError)?;
+
+        // The last block must describe at least one element
+        if blocks.len() != len / BLOCK_BITS + (len % BLOCK_BITS != 0) as usize {
+            return Err(NullableVecError::InvalidBlockCount {
+                len,
+                blocks: blocks.len(),
+            });
+        }
+
+        let mut rank = 0;
+        for (index, block) in blocks.iter().enumerate() {
+            let actual = from_archived!(block[0]) as usize;
+            if actual != rank {
+                return Err(NullableVecError::InvalidRank {
+                    index,
+                    expected: rank,
+                    actual,
+                });
+            }
+            rank += from_archived!(block[1]).count_ones() as usize;
+        }
+
+        let tail = len % BLOCK_BITS;
+        if tail > 0 {
+            let bits = from_archived!(blocks[blocks.len() - 1][1]);
+            if bits >> tail != 0 {
+                return Err(NullableVecError::InvalidPadding);
+            }
+        }
+
+        if values.len() != rank {
+            return Err(NullableVecError::InvalidValueCount {
+                expected: rank,
+                actual: values.len(),
+            });
+        }
+
+        Ok(&*value)
+    }
+}
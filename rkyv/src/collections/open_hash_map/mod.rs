@@ -0,0 +1,583 @@
+//! An archived hash map that resolves collisions with open addressing.
+//!
+//! # Choosing a probe sequence
+//!
+//! When a key's home bucket is taken, the map keeps checking other buckets in an order given by its
+//! [`Probe`] sequence until it finds the key or an empty bucket:
+//!
+//! - [`Linear`] checks the buckets right after the home bucket, one after the other. Consecutive
+//!   probes usually land on the same cache line, so it has the fastest hits while the map is
+//!   sparse (a load factor of 50% or less). As the map fills up, occupied buckets merge into long
+//!   runs that every key hashing into them has to walk past. At a load factor of 90%, lookups for
+//!   missing keys take more than half again as long as with the other probe sequences.
+//! - [`Quadratic`] checks buckets that are further and further away from the home bucket, which
+//!   breaks up long runs of occupied buckets. It is a little slower than linear probing in sparse
+//!   maps because every probe after the first few touches a new cache line, but it keeps both hits
+//!   and misses fast at high load factors (75% and above).
+//! - [`RobinHood`] checks the same buckets as linear probing, but entries that are further from
+//!   their home bucket take the bucket from entries that are closer to theirs while the map is
+//!   built. This evens out the probe lengths of all of the entries, so the longest lookup stays
+//!   close to the average one, and a lookup for a missing key stops as soon as it passes the point
+//!   where the key would have been placed. It has the fastest misses up to a load factor of 75%
+//!   and hits about as fast as linear probing below a load factor of 90%. Because its worst case is
+//!   the most predictable, it is the default.
+//!
+//! The `rkyv open hash map` group of the benchmarks in `rkyv_bench` compares hits and misses for each
+//! probe sequence at load factors of 50%, 75%, and 90%.
+
+#[cfg(feature = "validation")]
+pub mod validation;
+
+use crate::{
+    collections::{hash_index::ArchivedHashIndex, util::Entry},
+    vec::{ArchivedVec, VecResolver},
+    Archived,
+};
+#[cfg(feature = "alloc")]
+use crate::{
+    ser::{ScratchSpace, Serializer},
+    Serialize,
+};
+use core::{
+    borrow::Borrow, fmt, hash::Hash, iter::FusedIterator, marker::PhantomData, ops::Index, slice,
+};
+
+/// A probe sequence for an [`ArchivedOpenHashMap`].
+///
+/// See the [module-level documentation](self) for how to choose between the provided probe
+/// sequences.
+pub trait Probe {
+    /// Whether entries are ordered by their distance from their home bucket.
+    ///
+    /// When this is `true`, lookups for missing keys stop as soon as they reach an entry that is
+    /// closer to its home bucket than the key would be.
+    const ROBIN_HOOD: bool;
+
+    /// Returns the distance from the home bucket of the bucket to check on the given attempt.
+    ///
+    /// The distance is wrapped around the number of buckets, which is always a power of two. The
+    /// offsets for attempts `0..capacity` must visit every bucket.
+    fn offset(attempt: usize) -> usize;
+}
+
+/// Linear probing.
+///
+/// See the [module-level documentation](self) for when to use it.
+#[derive(Debug)]
+pub struct Linear;
+
+impl Probe for Linear {
+    const ROBIN_HOOD: bool = false;
+
+    #[inline]
+    fn offset(attempt: usize) -> usize {
+        attempt
+    }
+}
+
+/// Quadratic probing.
+///
+/// The offsets are the triangular numbers, which visit every bucket of a table with a power of two
+/// buckets. See the [module-level documentation](self) for when to use it.
+#[derive(Debug)]
+pub struct Quadratic;
+
+impl Probe for Quadratic {
+    const ROBIN_HOOD: bool = false;
+
+    #[inline]
+    fn offset(attempt: usize) -> usize {
+        attempt.wrapping_mul(attempt.wrapping_add(1)) / 2
+    }
+}
+
+/// Robin Hood hashing over a linear probe sequence.
+///
+/// See the [module-level documentation](self) for when to use it.
+#[derive(Debug)]
+pub struct RobinHood;
+
+impl Probe for RobinHood {
+    const ROBIN_HOOD: bool = true;
+
+    #[inline]
+    fn offset(attempt: usize) -> usize {
+        attempt
+    }
+}
+
+const EMPTY: u32 = u32::MAX;
+
+/// A bucket of an [`ArchivedOpenHashMap`].
+///
+/// Each bucket stores the index of its entry and the low 32 bits of the hash of its key, so probing
+/// only compares keys when their hashes match and never has to rehash the stored keys.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[repr(C)]
+pub struct Bucket {
+    index: Archived<u32>,
+    hash: Archived<u32>,
+}
+
+impl Bucket {
+    #[inline]
+    fn index(&self) -> Option<usize> {
+        let index = from_archived!(self.index);
+        if index == EMPTY {
+            None
+        } else {
+            Some(index as usize)
+        }
+    }
+
+    #[inline]
+    fn hash(&self) -> u32 {
+        from_archived!(self.hash)
+    }
+}
+
+/// An archived `HashMap` that resolves collisions by probing for an empty bucket.
+///
+/// Unlike [`ArchivedHashMap`](crate::collections::ArchivedHashMap), which builds a perfect hash of
+/// its keys, this map is an ordinary hash table with a power of two buckets. Its load factor and
+/// the probe sequence `P` can be chosen with the [`OpenAddressed`](crate::with::OpenAddressed)
+/// wrapper.
+///
+/// # Layout
+///
+/// The entries are stored densely in the order they were serialized, followed by the buckets. Each
+/// bucket holds the index of its entry and 32 bits of the hash of its key, or is empty. There is
+/// always at least one empty bucket, so probing always terminates.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     archived_root,
+///     collections::open_hash_map::Quadratic,
+///     ser::{serializers::AllocSerializer, Serializer},
+///     with::OpenAddressed,
+///     Archive, Serialize,
+/// };
+/// use std::collections::HashMap;
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     // Robin Hood hashing with a maximum load factor of 80%
+///     #[with(OpenAddressed)]
+///     robin_hood: HashMap<String, u32>,
+///     // Quadratic probing with a maximum load factor of 90%
+///     #[with(OpenAddressed<Quadratic, 90>)]
+///     quadratic: HashMap<String, u32>,
+/// }
+///
+/// let map = (0..100).map(|i| (i.to_string(), i)).collect::<HashMap<_, _>>();
+/// let value = Example { robin_hood: map.clone(), quadratic: map };
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&value).unwrap();
+/// let buf = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<Example>(&buf) };
+///
+/// assert_eq!(archived.robin_hood.capacity(), 128);
+/// assert_eq!(archived.robin_hood["42"], 42);
+/// assert_eq!(archived.quadratic.capacity(), 128);
+/// assert_eq!(archived.quadratic.get("100"), None);
+/// ```
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedOpenHashMap<K, V, P = RobinHood> {
+    entries: ArchivedVec<Entry<K, V>>,
+    buckets: ArchivedVec<Bucket>,
+    _phantom: PhantomData<P>,
+}
+
+impl<K, V, P> ArchivedOpenHashMap<K, V, P> {
+    /// Gets the number of items in the hash map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether there are no items in the hash map.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Gets the number of buckets in the hash map.
+    ///
+    /// This is zero for empty maps and a power of two greater than the length otherwise.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Gets an iterator over the key-value entries in the hash map.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Gets an iterator over the keys in the hash map.
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Gets an iterator over the values in the hash map.
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Resolves an archived open hash map from a given length and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of elements that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a hash map
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: OpenHashMapResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.entries);
+        ArchivedVec::resolve_from_len(
+            len,
+            pos + fp,
+            VecResolver {
+                pos: resolver.entries_pos,
+            },
+            fo,
+        );
+
+        let (fp, fo) = out_field!(out.buckets);
+        ArchivedVec::resolve_from_len(
+            resolver.capacity,
+            pos + fp,
+            VecResolver {
+                pos: resolver.buckets_pos,
+            },
+            fo,
+        );
+    }
+}
+
+impl<K, V, P: Probe> ArchivedOpenHashMap<K, V, P> {
+    #[inline]
+    fn find<Q>(&self, k: &Q) -> Option<&Entry<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let buckets = self.buckets.as_slice();
+        if buckets.is_empty() {
+            return None;
+        }
+
+        let mask = buckets.len() - 1;
+        let hash = ArchivedHashIndex::hash_key(k) as u32;
+        let home = hash as usize & mask;
+        for attempt in 0..buckets.len() {
+            let position = home.wrapping_add(P::offset(attempt)) & mask;
+            let bucket = &buckets[position];
+            let index = bucket.index()?;
+            if P::ROBIN_HOOD && displacement(position, bucket.hash(), mask) < attempt {
+                return None;
+            }
+            if bucket.hash() == hash {
+                let entry = &self.entries[index];
+                if entry.key.borrow() == k {
+                    return Some(entry);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns whether a key is present in the hash map.
+    #[inline]
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(k).is_some()
+    }
+
+    /// Gets the value associated with the given key.
+    #[inline]
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(k).map(|entry| &entry.value)
+    }
+
+    /// Gets the key-value pair associated with the given key.
+    #[inline]
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(k).map(|entry| (&entry.key, &entry.value))
+    }
+}
+
+// Returns how far the bucket at `position` is from the home bucket of the given hash.
+#[inline]
+fn displacement(position: usize, hash: u32, mask: usize) -> usize {
+    position.wrapping_sub(hash as usize) & mask
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use crate::ScratchVec;
+    use core::mem::size_of;
+
+    // Returns the number of buckets needed to keep `len` entries at or below the maximum load
+    // factor while leaving at least one bucket empty.
+    fn capacity_for(len: usize, max_load: usize) -> usize {
+        assert!(
+            max_load > 0 && max_load <= 100,
+            "the maximum load factor of an open hash map must be between 1 and 100 percent",
+        );
+        if len == 0 {
+            return 0;
+        }
+        // `usize::div_ceil` requires a newer compiler than the minimum supported version
+        #[allow(clippy::manual_div_ceil)]
+        let capacity = ((len * 100 + max_load - 1) / max_load).next_power_of_two();
+        if capacity > len {
+            capacity
+        } else {
+            capacity * 2
+        }
+    }
+
+    // Places an entry in the buckets, moving entries that are closer to their home buckets out of
+    // the way when `P` is Robin Hood hashing.
+    fn insert<P: Probe>(buckets: &mut [Bucket], index: u32, hash: u32) {
+        let mask = buckets.len() - 1;
+        let mut current = Bucket {
+            index: to_archived!(index),
+            hash: to_archived!(hash),
+        };
+        let mut home = hash as usize & mask;
+        let mut attempt = 0;
+        loop {
+            assert!(
+                attempt < buckets.len(),
+                "probe sequence did not visit an empty bucket"
+            );
+            let position = home.wrapping_add(P::offset(attempt)) & mask;
+            let bucket = &mut buckets[position];
+            if bucket.index().is_none() {
+                *bucket = current;
+                return;
+            }
+            if P::ROBIN_HOOD {
+                let existing = displacement(position, bucket.hash(), mask);
+                if existing < attempt {
+                    // Take the bucket and keep placing the entry that was displaced
+                    core::mem::swap(bucket, &mut current);
+                    home = position.wrapping_sub(existing) & mask;
+                    attempt = existing;
+                }
+            }
+            attempt += 1;
+        }
+    }
+
+    impl<K, V, P: Probe> ArchivedOpenHashMap<K, V, P> {
+        /// Serializes an iterator of key-value pairs as an open hash map with the given maximum
+        /// load factor, in percent.
+        ///
+        /// The number of buckets is the smallest power of two that keeps the load factor at or
+        /// below `max_load` while leaving at least one bucket empty.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `max_load` is not between 1 and 100.
+        ///
+        /// # Safety
+        ///
+        /// The keys returned by the iterator must be unique.
+        pub unsafe fn serialize_from_iter<'a, KU, VU, S, I>(
+            iter: I,
+            max_load: usize,
+            serializer: &mut S,
+        ) -> Result<OpenHashMapResolver, S::Error>
+        where
+            KU: 'a + Serialize<S, Archived = K> + Hash + Eq,
+            VU: 'a + Serialize<S, Archived = V>,
+            S: Serializer + ScratchSpace + ?Sized,
+            I: ExactSizeIterator<Item = (&'a KU, &'a VU)>,
+        {
+            let len = iter.len();
+            assert!(
+                len < EMPTY as usize,
+                "too many entries to archive as an open hash map"
+            );
+            let capacity = capacity_for(len, max_load);
+
+            let mut entries = ScratchVec::new(serializer, len)?;
+            let mut buckets = ScratchVec::new(serializer, capacity)?;
+            for _ in 0..capacity {
+                buckets.push(Bucket {
+                    index: to_archived!(EMPTY),
+                    hash: to_archived!(0u32),
+                });
+            }
+            for (index, (key, value)) in iter.enumerate() {
+                let hash = ArchivedHashIndex::hash_key(key) as u32;
+                insert::<P>(&mut buckets, index as u32, hash);
+                entries.push((key, value));
+            }
+
+            // Serialize entries
+            let mut resolvers = ScratchVec::new(serializer, len)?;
+            for (key, value) in entries.iter() {
+                resolvers.push((key.serialize(serializer)?, value.serialize(serializer)?));
+            }
+
+            let entries_pos = serializer.align_for::<Entry<K, V>>()?;
+            for (&(key, value), (key_resolver, value_resolver)) in
+                entries.iter().zip(resolvers.drain(..))
+            {
+                serializer
+                    .resolve_aligned(&Entry { key, value }, (key_resolver, value_resolver))?;
+            }
+
+            // Write buckets
+            let buckets_pos = serializer.align_for::<Bucket>()?;
+            let buckets_slice = slice::from_raw_parts(
+                buckets.as_ptr().cast::<u8>(),
+                capacity * size_of::<Bucket>(),
+            );
+            serializer.write(buckets_slice)?;
+
+            // Free scratch vecs
+            resolvers.free(serializer)?;
+            buckets.free(serializer)?;
+            entries.free(serializer)?;
+
+            Ok(OpenHashMapResolver {
+                capacity,
+                entries_pos,
+                buckets_pos,
+            })
+        }
+    }
+};
+
+impl<K: fmt::Debug, V: fmt::Debug, P> fmt::Debug for ArchivedOpenHashMap<K, V, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, Q, V, P> Index<&'_ Q> for ArchivedOpenHashMap<K, V, P>
+where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+    P: Probe,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    #[inline]
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+/// The resolver for archived open hash maps.
+pub struct OpenHashMapResolver {
+    capacity: usize,
+    entries_pos: usize,
+    buckets_pos: usize,
+}
+
+/// An iterator over the key-value pairs of an archived open hash map.
+pub struct Iter<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| (&entry.key, &entry.value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+/// An iterator over the keys of an archived open hash map.
+pub struct Keys<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| &entry.key)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+
+/// An iterator over the values of an archived open hash map.
+pub struct Values<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| &entry.value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+impl<K, V> FusedIterator for Values<'_, K, V> {}
@@ -0,0 +1,160 @@
+//! Validation implementation for ArchivedOpenHashMap.
+
+use super::{ArchivedOpenHashMap, Bucket, Probe};
+use crate::{
+    collections::{hash_index::ArchivedHashIndex, util::Entry},
+    validation::{owned::CheckOwnedPointerError, ArchiveContext},
+    vec::ArchivedVec,
+};
+use bytecheck::{CheckBytes, Error};
+use core::{convert::Infallible, fmt, hash::Hash, ptr};
+
+/// Errors that can occur while checking an archived open hash map.
+#[derive(Debug)]
+pub enum OpenHashMapError<E, B> {
+    /// An error occurred while checking the entries
+    EntriesError(E),
+    /// An error occurred while checking the buckets
+    BucketsError(B),
+    /// The number of buckets was not a power of two greater than the number of entries
+    InvalidCapacity {
+        /// The number of entries
+        len: usize,
+        /// The number of buckets
+        capacity: usize,
+    },
+    /// A bucket pointed to an entry that doesn't exist or stored the wrong hash for its key
+    InvalidBucket {
+        /// The index of the bucket
+        index: usize,
+    },
+    /// The number of occupied buckets did not match the number of entries
+    InvalidOccupiedCount {
+        /// The number of entries
+        expected: usize,
+        /// The number of occupied buckets
+        actual: usize,
+    },
+    /// A key could not be found by probing for it
+    InvalidKeyPosition {
+        /// The index of the entry
+        index: usize,
+    },
+}
+
+impl<E, B> From<Infallible> for OpenHashMapError<E, B> {
+    #[inline]
+    fn from(_: Infallible) -> Self {
+        unsafe { core::hint::unreachable_unchecked() }
+    }
+}
+
+impl<E: fmt::Display, B: fmt::Display> fmt::Display for OpenHashMapError<E, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenHashMapError::EntriesError(e) => write!(f, "error while checking entries: {}", e),
+            OpenHashMapError::BucketsError(e) => write!(f, "error while checking buckets: {}", e),
+            OpenHashMapError::InvalidCapacity { len, capacity } => write!(
+                f,
+                "invalid capacity: {} buckets is not a power of two greater than {} entries",
+                capacity, len
+            ),
+            OpenHashMapError::InvalidBucket { index } => write!(f, "invalid bucket {}", index),
+            OpenHashMapError::InvalidOccupiedCount { expected, actual } => write!(
+                f,
+                "invalid occupied count: expected {} occupied buckets, found {}",
+                expected, actual
+            ),
+            OpenHashMapError::InvalidKeyPosition { index } => {
+                write!(f, "invalid key position: at index {}", index)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<E: Error + 'static, B: Error + 'static> Error for OpenHashMapError<E, B> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                OpenHashMapError::EntriesError(e) => Some(e as &dyn Error),
+                OpenHashMapError::BucketsError(e) => Some(e as &dyn Error),
+                OpenHashMapError::InvalidCapacity { .. }
+                | OpenHashMapError::InvalidBucket { .. }
+                | OpenHashMapError::InvalidOccupiedCount { .. }
+                | OpenHashMapError::InvalidKeyPosition { .. } => None,
+            }
+        }
+    }
+};
+
+impl<K, V, P, C> CheckBytes<C> for ArchivedOpenHashMap<K, V, P>
+where
+    K: CheckBytes<C> + Hash + Eq,
+    V: CheckBytes<C>,
+    P: Probe,
+    C: ArchiveContext + ?Sized,
+    C::Error: Error,
+{
+    type Error = OpenHashMapError<
+        CheckOwnedPointerError<[Entry<K, V>], C>,
+        CheckOwnedPointerError<[Bucket], C>,
+    >;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        let entries =
+            ArchivedVec::<Entry<K, V>>::check_bytes(ptr::addr_of!((*value).entries), context)
+                .map_err(OpenHashMapError::EntriesError)?;
+        let buckets = ArchivedVec::<Bucket>::check_bytes(ptr::addr_of!((*value).buckets), context)
+            .map_err(OpenHashMapError::BucketsError)?;
+
+        let len = entries.len();
+        let capacity = buckets.len();
+        if capacity != 0 && (!capacity.is_power_of_two() || capacity <= len)
+            || capacity == 0 && len != 0
+        {
+            return Err(OpenHashMapError::InvalidCapacity { len, capacity });
+        }
+
+        let mut occupied = 0;
+        for (index, bucket) in buckets.iter().enumerate() {
+            if let Some(entry_index) = bucket.index() {
+                // `Option::is_some_and` is newer than the minimum supported Rust version
+                #[allow(clippy::unnecessary_map_or)]
+                let valid = entries.get(entry_index).map_or(false, |entry| {
+                    ArchivedHashIndex::hash_key(&entry.key) as u32 == bucket.hash()
+                });
+                if !valid {
+                    return Err(OpenHashMapError::InvalidBucket { index });
+                }
+                occupied += 1;
+            }
+        }
+        if occupied != len {
+            return Err(OpenHashMapError::InvalidOccupiedCount {
+                expected: len,
+                actual: occupied,
+            });
+        }
+
+        // Every key must be found at its own entry, which also ensures that each entry has exactly
+        // one bucket
+        let map = &*value;
+        for (index, entry) in entries.iter().enumerate() {
+            #[allow(clippy::unnecessary_map_or)]
+            let found = map
+                .find(&entry.key)
+                .map_or(false, |found| ptr::eq(found, entry));
+            if !found {
+                return Err(OpenHashMapError::InvalidKeyPosition { index });
+            }
+        }
+
+        Ok(map)
+    }
+}
@@ -0,0 +1,289 @@
+//! An archived vector of results that stores whether each result is `Ok` in a bitmap.
+
+#[cfg(feature = "validation")]
+pub mod validation;
+
+use crate::{
+    collections::nullable_vec::{ArchivedNullableVec, NullableVecResolver},
+    ser::{ScratchSpace, Serializer},
+    vec::{ArchivedVec, VecResolver},
+    Serialize,
+};
+use core::{fmt, iter::FusedIterator};
+
+/// An archived vector of results, laid out as a dense array of the `Ok` values, a dense array of
+/// the `Err` values, and a bitmap that records which elements are `Ok`.
+///
+/// An archived `Vec<Result<T, E>>` stores a tag next to every element and reserves enough space for
+/// the larger of `T` and `E` in each one. For logs of operations that mostly succeed (like an
+/// audit trail of `Result<(), ErrorCode>`), that's mostly tags and padding. A result vector instead
+/// stores one bit per element and only stores the values that are actually present. When `T` is
+/// a zero-sized type like `()`, successes take up no space besides their bit.
+///
+/// The [`AsResultVec`](crate::with::AsResultVec) wrapper can be used to archive a
+/// `Vec<Result<T, E>>` as a result vector.
+///
+/// # Layout
+///
+/// The `Ok` values and the bitmap are stored as an
+/// [`ArchivedNullableVec`](crate::collections::ArchivedNullableVec) where the `Ok` elements are
+/// present, so looking up an element takes constant time. The `Err` values are stored in a
+/// separate array in order, and the position of an `Err` value in it is the index of the element
+/// minus the number of `Ok` elements before it.
+///
+/// # Example
+///
+/// ```
+/// use core::mem::size_of;
+/// use rkyv::{
+///     archived_root,
+///     ser::{serializers::AllocSerializer, Serializer},
+///     with::AsResultVec,
+///     Archive, Archived, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct AuditLog {
+///     #[with(AsResultVec)]
+///     entries: Vec<Result<(), u16>>,
+/// }
+///
+/// // One failure for every hundred successes
+/// let entries = (0..10_000)
+///     .map(|i| if i % 100 == 99 { Err(i as u16) } else { Ok(()) })
+///     .collect::<Vec<_>>();
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&AuditLog { entries: entries.clone() }).unwrap();
+/// let buf = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<AuditLog>(&buf) };
+///
+/// assert_eq!(archived.entries.len(), 10_000);
+/// assert_eq!(archived.entries.err_count(), 100);
+/// assert!(archived.entries.get(0).unwrap().is_ok());
+/// assert_eq!(*archived.entries.get(199).unwrap().unwrap_err(), 199);
+///
+/// // An archived `Vec<Result<(), u16>>` takes at least four bytes per entry, but a result vector
+/// // takes a quarter of a byte per entry plus the errors
+/// let naive_size = entries.len() * size_of::<Archived<Result<(), u16>>>();
+/// assert_eq!(naive_size, 40_000);
+/// assert!(buf.len() < 3_000);
+/// ```
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedResultVec<T, E> {
+    oks: ArchivedNullableVec<T>,
+    errs: ArchivedVec<E>,
+}
+
+impl<T, E> ArchivedResultVec<T, E> {
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.oks.len()
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.oks.is_empty()
+    }
+
+    /// Returns the number of `Ok` elements in the vector.
+    #[inline]
+    pub fn ok_count(&self) -> usize {
+        self.oks.values().len()
+    }
+
+    /// Returns the number of `Err` elements in the vector.
+    #[inline]
+    pub fn err_count(&self) -> usize {
+        self.errs.len()
+    }
+
+    /// Returns the `Ok` values of the vector as a dense slice, in order.
+    #[inline]
+    pub fn oks(&self) -> &[T] {
+        self.oks.values()
+    }
+
+    /// Returns the `Err` values of the vector as a dense slice, in order.
+    #[inline]
+    pub fn errs(&self) -> &[E] {
+        self.errs.as_slice()
+    }
+
+    /// Returns `true` if the element at the given index is `Ok`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    pub fn is_ok(&self, index: usize) -> bool {
+        !self.oks.is_null(index)
+    }
+
+    /// Returns `true` if the element at the given index is `Err`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    pub fn is_err(&self, index: usize) -> bool {
+        self.oks.is_null(index)
+    }
+
+    /// Gets the element at the given index.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<Result<&T, &E>> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let rank = self.oks.rank(index);
+        if self.oks.is_null(index) {
+            Some(Err(&self.errs[index - rank]))
+        } else {
+            Some(Ok(&self.oks.values()[rank]))
+        }
+    }
+
+    /// Gets an iterator over the elements of the vector.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T, E> {
+        Iter {
+            oks: self.oks.iter(),
+            errs: self.errs.iter(),
+        }
+    }
+
+    /// Resolves an archived result vector from a given length and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of elements that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a result vector
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: ResultVecResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.oks);
+        ArchivedNullableVec::resolve_from_len(len, pos + fp, resolver.oks, fo);
+        let (fp, fo) = out_field!(out.errs);
+        ArchivedVec::resolve_from_len(resolver.errs_len, pos + fp, resolver.errs, fo);
+    }
+
+    /// Serializes a slice of results as a result vector.
+    pub fn serialize_from_slice<U, F, S>(
+        items: &[Result<U, F>],
+        serializer: &mut S,
+    ) -> Result<ResultVecResolver, S::Error>
+    where
+        U: Serialize<S, Archived = T>,
+        F: Serialize<S, Archived = E>,
+        S: ScratchSpace + Serializer + ?Sized,
+    {
+        let oks = ArchivedNullableVec::serialize_from_iter(
+            items.iter().map(|r| r.as_ref().ok()),
+            serializer,
+        )?;
+
+        let errs_len = items.iter().filter(|item| item.is_err()).count();
+        let errs = ArchivedVec::serialize_from_iter::<F, _, _, _>(
+            Errs {
+                items: items.iter(),
+                remaining: errs_len,
+            },
+            serializer,
+        )?;
+
+        Ok(ResultVecResolver {
+            oks,
+            errs,
+            errs_len,
+        })
+    }
+}
+
+impl<T: fmt::Debug, E: fmt::Debug> fmt::Debug for ArchivedResultVec<T, E> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq<U>, E: PartialEq<F>, U, F> PartialEq<[Result<U, F>]> for ArchivedResultVec<T, E> {
+    #[inline]
+    fn eq(&self, other: &[Result<U, F>]) -> bool {
+        self.len() == other.len()
+            && self.iter().zip(other.iter()).all(|(a, b)| match (a, b) {
+                (Ok(a), Ok(b)) => a.eq(b),
+                (Err(a), Err(b)) => a.eq(b),
+                _ => false,
+            })
+    }
+}
+
+/// The resolver for archived result vectors.
+pub struct ResultVecResolver {
+    oks: NullableVecResolver,
+    errs: VecResolver,
+    errs_len: usize,
+}
+
+// Iterates over the `Err` values of a slice of results with a known count.
+struct Errs<'a, U, F> {
+    items: core::slice::Iter<'a, Result<U, F>>,
+    remaining: usize,
+}
+
+impl<'a, U, F> Iterator for Errs<'a, U, F> {
+    type Item = &'a F;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.items.by_ref().find_map(|item| item.as_ref().err());
+        if next.is_some() {
+            self.remaining -= 1;
+        }
+        next
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, U, F> ExactSizeIterator for Errs<'a, U, F> {}
+
+/// An iterator over the elements of an archived result vector.
+pub struct Iter<'a, T, E> {
+    oks: crate::collections::nullable_vec::Iter<'a, T>,
+    errs: core::slice::Iter<'a, E>,
+}
+
+impl<'a, T, E> Iterator for Iter<'a, T, E> {
+    type Item = Result<&'a T, &'a E>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.oks.next()? {
+            Some(value) => Some(Ok(value)),
+            None => self.errs.next().map(Err),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.oks.size_hint()
+    }
+}
+
+impl<'a, T, E> ExactSizeIterator for Iter<'a, T, E> {}
+impl<'a, T, E> FusedIterator for Iter<'a, T, E> {}
@@ -0,0 +1,94 @@
+//! Validation implementation for ArchivedResultVec.
+
+use super::ArchivedResultVec;
+use crate::{
+    collections::nullable_vec::ArchivedNullableVec,
+    validation::{owned::CheckOwnedPointerError, ArchiveContext},
+    vec::ArchivedVec,
+};
+use bytecheck::{CheckBytes, Error};
+use core::{convert::Infallible, fmt, ptr};
+
+/// Errors that can occur while checking an archived result vector.
+#[derive(Debug)]
+pub enum ResultVecError<O, E> {
+    /// An error occurred while checking the bitmap and `Ok` values
+    OksError(O),
+    /// An error occurred while checking the dense array of `Err` values
+    ErrsError(E),
+    /// The number of `Err` values did not match the number of unset bits in the bitmap
+    InvalidErrCount {
+        /// The number of unset bits in the bitmap
+        expected: usize,
+        /// The number of `Err` values that were archived
+        actual: usize,
+    },
+}
+
+impl<O, E> From<Infallible> for ResultVecError<O, E> {
+    #[inline]
+    fn from(_: Infallible) -> Self {
+        unsafe { core::hint::unreachable_unchecked() }
+    }
+}
+
+impl<O: fmt::Display, E: fmt::Display> fmt::Display for ResultVecError<O, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResultVecError::OksError(e) => write!(f, "error while checking ok values: {}", e),
+            ResultVecError::ErrsError(e) => write!(f, "error while checking err values: {}", e),
+            ResultVecError::InvalidErrCount { expected, actual } => write!(
+                f,
+                "invalid err count: expected {} err values, found {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<O: Error + 'static, E: Error + 'static> Error for ResultVecError<O, E> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                ResultVecError::OksError(e) => Some(e as &dyn Error),
+                ResultVecError::ErrsError(e) => Some(e as &dyn Error),
+                ResultVecError::InvalidErrCount { .. } => None,
+            }
+        }
+    }
+};
+
+impl<T, E, C> CheckBytes<C> for ArchivedResultVec<T, E>
+where
+    T: CheckBytes<C>,
+    E: CheckBytes<C>,
+    C: ArchiveContext + ?Sized,
+    C::Error: Error,
+{
+    type Error = ResultVecError<
+        <ArchivedNullableVec<T> as CheckBytes<C>>::Error,
+        CheckOwnedPointerError<[E], C>,
+    >;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        let oks = ArchivedNullableVec::<T>::check_bytes(ptr::addr_of!((*value).oks), context)
+            .map_err(ResultVecError::OksError)?;
+        let errs = ArchivedVec::<E>::check_bytes(ptr::addr_of!((*value).errs), context)
+            .map_err(ResultVecError::ErrsError)?;
+
+        if errs.len() != oks.null_count() {
+            return Err(ResultVecError::InvalidErrCount {
+                expected: oks.null_count(),
+                actual: errs.len(),
+            });
+        }
+
+        Ok(&*value)
+    }
+}
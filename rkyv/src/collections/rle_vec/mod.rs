@@ -0,0 +1,328 @@
+//! An archived vector that stores runs of equal values once.
+
+#[cfg(feature = "validation")]
+pub mod validation;
+
+use crate::{
+    ser::{ScratchSpace, Serializer},
+    vec::{ArchivedVec, VecResolver},
+    Archived, Serialize,
+};
+use core::{fmt, iter::FusedIterator, slice};
+
+/// An archived vector of values, laid out as the value of each run of equal elements and the
+/// index where each run ends.
+///
+/// Repetitive sequences (like a column of status codes or a per-sample sensor state) are mostly
+/// runs of the same value. A run-length-encoded vector stores each run's value once, along with the
+/// end of the run, so its size depends on the number of runs instead of the number of elements.
+///
+/// The [`AsRleVec`](crate::with::AsRleVec) wrapper can be used to archive a `Vec<T>` as a
+/// run-length-encoded vector.
+///
+/// # Layout
+///
+/// The runs are stored as two arrays: the values of the runs in order, and the exclusive end index
+/// of each run as an `Archived<usize>`. The ends are strictly increasing, and the last end is the
+/// length of the vector.
+///
+/// # Access complexity
+///
+/// - [`get`] binary searches the run ends, so it takes `O(log r)` time for `r` runs.
+/// - [`iter`] walks the runs in order, so iterating over the whole vector takes linear time.
+/// - [`runs`] iterates over the runs themselves, which takes `O(r)` time and is the fastest way to
+///   scan a vector with long runs.
+///
+/// [`get`]: ArchivedRleVec::get
+/// [`iter`]: ArchivedRleVec::iter
+/// [`runs`]: ArchivedRleVec::runs
+///
+/// # Example
+///
+/// ```
+/// use core::mem::size_of;
+/// use rkyv::{
+///     archived_root,
+///     ser::{serializers::AllocSerializer, Serializer},
+///     with::AsRleVec,
+///     Archive, Archived, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Readings {
+///     #[with(AsRleVec)]
+///     states: Vec<u32>,
+/// }
+///
+/// // A state that changes every thousand samples
+/// let states = (0..10_000u32).map(|i| i / 1_000).collect::<Vec<_>>();
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&Readings { states: states.clone() }).unwrap();
+/// let buf = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<Readings>(&buf) };
+///
+/// assert_eq!(archived.states.len(), 10_000);
+/// assert_eq!(archived.states.run_count(), 10);
+/// assert_eq!(*archived.states.get(4_321).unwrap(), 4);
+///
+/// // Ten runs take a tiny fraction of the space of ten thousand values
+/// assert!(buf.len() < 10 * (size_of::<Archived<u32>>() + size_of::<Archived<usize>>()) + 64);
+/// ```
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedRleVec<T> {
+    ends: ArchivedVec<Archived<usize>>,
+    values: ArchivedVec<T>,
+}
+
+impl<T> ArchivedRleVec<T> {
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ends
+            .as_slice()
+            .last()
+            .map_or(0, |end| from_archived!(*end) as usize)
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Returns the number of runs of equal elements in the vector.
+    #[inline]
+    pub fn run_count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Gets the element at the given index.
+    ///
+    /// This binary searches the runs, so it takes `O(log r)` time for `r` runs.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let run = match self
+            .ends
+            .as_slice()
+            .binary_search_by(|end| (from_archived!(*end) as usize).cmp(&index))
+        {
+            // A run that ends at the index is before the run that contains it
+            Ok(run) => run + 1,
+            Err(run) => run,
+        };
+        Some(&self.values[run])
+    }
+
+    /// Gets an iterator over the runs of the vector, as the value of each run and its length.
+    #[inline]
+    pub fn runs(&self) -> Runs<'_, T> {
+        Runs {
+            ends: self.ends.iter(),
+            values: self.values.iter(),
+            start: 0,
+        }
+    }
+
+    /// Gets an iterator over the elements of the vector.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            runs: self.runs(),
+            current: None,
+            remaining: self.len(),
+        }
+    }
+
+    /// Resolves an archived run-length-encoded vector from the given parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a run-length-encoded vector
+    #[inline]
+    pub unsafe fn resolve_from_resolver(pos: usize, resolver: RleVecResolver, out: *mut Self) {
+        let (fp, fo) = out_field!(out.ends);
+        ArchivedVec::resolve_from_len(resolver.runs_len, pos + fp, resolver.ends, fo);
+        let (fp, fo) = out_field!(out.values);
+        ArchivedVec::resolve_from_len(resolver.runs_len, pos + fp, resolver.values, fo);
+    }
+
+    /// Serializes a slice of values as a run-length-encoded vector.
+    pub fn serialize_from_slice<U, S>(
+        items: &[U],
+        serializer: &mut S,
+    ) -> Result<RleVecResolver, S::Error>
+    where
+        U: Serialize<S, Archived = T> + PartialEq,
+        S: ScratchSpace + Serializer + ?Sized,
+    {
+        let bounds = RunBounds::new(items);
+        let runs_len = bounds.len();
+        let ends = ArchivedVec::serialize_from_iter::<usize, _, _, _>(
+            bounds.clone().map(|(_, end)| end),
+            serializer,
+        )?;
+        let values = ArchivedVec::serialize_from_iter::<U, _, _, _>(
+            bounds.map(|(start, _)| &items[start]),
+            serializer,
+        )?;
+
+        Ok(RleVecResolver {
+            ends,
+            values,
+            runs_len,
+        })
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedRleVec<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for ArchivedRleVec<T> {
+    #[inline]
+    fn eq(&self, other: &[U]) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a.eq(b))
+    }
+}
+
+/// The resolver for archived run-length-encoded vectors.
+pub struct RleVecResolver {
+    ends: VecResolver,
+    values: VecResolver,
+    runs_len: usize,
+}
+
+// Iterates over the start and end indices of the runs of equal elements in a slice.
+struct RunBounds<'a, U> {
+    items: &'a [U],
+    start: usize,
+    remaining: usize,
+}
+
+impl<'a, U> Clone for RunBounds<'a, U> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items,
+            start: self.start,
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, U: PartialEq> RunBounds<'a, U> {
+    #[inline]
+    fn new(items: &'a [U]) -> Self {
+        let remaining = if items.is_empty() {
+            0
+        } else {
+            1 + items.windows(2).filter(|pair| pair[0] != pair[1]).count()
+        };
+        Self {
+            items,
+            start: 0,
+            remaining,
+        }
+    }
+}
+
+impl<'a, U: PartialEq> Iterator for RunBounds<'a, U> {
+    type Item = (usize, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let start = self.start;
+        let value = &self.items[start];
+        let len = self.items[start..]
+            .iter()
+            .position(|item| item != value)
+            .unwrap_or(self.items.len() - start);
+        self.start += len;
+        self.remaining -= 1;
+        Some((start, self.start))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, U: PartialEq> ExactSizeIterator for RunBounds<'a, U> {}
+
+/// An iterator over the runs of an archived run-length-encoded vector.
+///
+/// Each run is yielded as its value and the number of elements in it.
+pub struct Runs<'a, T> {
+    ends: slice::Iter<'a, Archived<usize>>,
+    values: slice::Iter<'a, T>,
+    start: usize,
+}
+
+impl<'a, T> Iterator for Runs<'a, T> {
+    type Item = (&'a T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = from_archived!(*self.ends.next()?) as usize;
+        let len = end - self.start;
+        self.start = end;
+        Some((self.values.next()?, len))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ends.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Runs<'a, T> {}
+impl<'a, T> FusedIterator for Runs<'a, T> {}
+
+/// An iterator over the elements of an archived run-length-encoded vector.
+pub struct Iter<'a, T> {
+    runs: Runs<'a, T>,
+    current: Option<(&'a T, usize)>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.current {
+                Some((value, ref mut len)) if *len > 0 => {
+                    *len -= 1;
+                    self.remaining -= 1;
+                    return Some(value);
+                }
+                _ => self.current = Some(self.runs.next()?),
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+impl<'a, T> FusedIterator for Iter<'a, T> {}
@@ -0,0 +1,109 @@
+//! Validation implementation for ArchivedRleVec.
+
+use super::ArchivedRleVec;
+use crate::{
+    validation::{owned::CheckOwnedPointerError, ArchiveContext},
+    vec::ArchivedVec,
+    Archived,
+};
+use bytecheck::{CheckBytes, Error};
+use core::{convert::Infallible, fmt, ptr};
+
+/// Errors that can occur while checking an archived run-length-encoded vector.
+#[derive(Debug)]
+pub enum RleVecError<N, V> {
+    /// An error occurred while checking the ends of the runs
+    EndsError(N),
+    /// An error occurred while checking the values of the runs
+    ValuesError(V),
+    /// The number of run ends did not match the number of run values
+    InvalidRunCount {
+        /// The number of run ends that were archived
+        ends: usize,
+        /// The number of run values that were archived
+        values: usize,
+    },
+    /// A run did not end after the previous run
+    InvalidRunEnd {
+        /// The index of the run
+        index: usize,
+    },
+}
+
+impl<N, V> From<Infallible> for RleVecError<N, V> {
+    #[inline]
+    fn from(_: Infallible) -> Self {
+        unsafe { core::hint::unreachable_unchecked() }
+    }
+}
+
+impl<N: fmt::Display, V: fmt::Display> fmt::Display for RleVecError<N, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RleVecError::EndsError(e) => write!(f, "error while checking run ends: {}", e),
+            RleVecError::ValuesError(e) => write!(f, "error while checking run values: {}", e),
+            RleVecError::InvalidRunCount { ends, values } => write!(
+                f,
+                "invalid run count: {} run ends don't match {} run values",
+                ends, values
+            ),
+            RleVecError::InvalidRunEnd { index } => {
+                write!(f, "run {} does not end after the previous run", index)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<N: Error + 'static, V: Error + 'static> Error for RleVecError<N, V> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                RleVecError::EndsError(e) => Some(e as &dyn Error),
+                RleVecError::ValuesError(e) => Some(e as &dyn Error),
+                RleVecError::InvalidRunCount { .. } | RleVecError::InvalidRunEnd { .. } => None,
+            }
+        }
+    }
+};
+
+impl<T, C> CheckBytes<C> for ArchivedRleVec<T>
+where
+    T: CheckBytes<C>,
+    C: ArchiveContext + ?Sized,
+    C::Error: Error,
+{
+    type Error =
+        RleVecError<CheckOwnedPointerError<[Archived<usize>], C>, CheckOwnedPointerError<[T], C>>;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        let ends =
+            ArchivedVec::<Archived<usize>>::check_bytes(ptr::addr_of!((*value).ends), context)
+                .map_err(RleVecError::EndsError)?;
+        let values = ArchivedVec::<T>::check_bytes(ptr::addr_of!((*value).values), context)
+            .map_err(RleVecError::ValuesError)?;
+
+        if ends.len() != values.len() {
+            return Err(RleVecError::InvalidRunCount {
+                ends: ends.len(),
+                values: values.len(),
+            });
+        }
+
+        let mut start = 0;
+        for (index, end) in ends.iter().enumerate() {
+            let end = from_archived!(*end) as usize;
+            if end <= start {
+                return Err(RleVecError::InvalidRunEnd { index });
+            }
+            start = end;
+        }
+
+        Ok(&*value)
+    }
+}
@@ -0,0 +1,264 @@
+//! An archived array of indices that orders the elements of a separate archived slice.
+
+use crate::{
+    ser::{ScratchSpace, Serializer},
+    vec::{ArchivedVec, VecResolver},
+    Archived,
+};
+use core::{cmp::Ordering, fmt, iter::FusedIterator, slice};
+
+/// An archived array of indices into a separate slice, sorted by some ordering of the elements
+/// they refer to.
+///
+/// A sorted index is a secondary index over data that is stored in a different order. The index
+/// does not hold the data itself, so every lookup takes the archived data slice as an argument.
+/// Together they support `O(log n)` lookups by any key that the index was sorted by, without
+/// reordering or duplicating the data. Multiple indices can be kept over the same data to look it
+/// up by different keys.
+///
+/// The indices are archived in the order they are provided, so they must already be sorted by the
+/// same ordering that is used to search them. The
+/// [`AsSortedIndex`](crate::with::AsSortedIndex) wrapper can be used to archive a `Vec<u32>` as a
+/// sorted index.
+///
+/// # Panics
+///
+/// Methods that look up elements of the data slice panic if the index refers to an element that is
+/// out of bounds. This can only happen if the index is paired with a different slice than the one
+/// it was built for.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{archived_root, ser::{serializers::AllocSerializer, Serializer}, with::AsSortedIndex};
+/// use rkyv::{Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// #[derive(Archive, Serialize)]
+/// struct People {
+///     people: Vec<Person>,
+///     #[with(AsSortedIndex)]
+///     by_name: Vec<u32>,
+/// }
+///
+/// let people = vec![
+///     Person { name: "Mallory".to_string(), age: 41 },
+///     Person { name: "Alice".to_string(), age: 32 },
+///     Person { name: "Bob".to_string(), age: 27 },
+/// ];
+///
+/// // Build the index with any comparator
+/// let mut by_name = (0..people.len() as u32).collect::<Vec<_>>();
+/// by_name.sort_by(|&a, &b| people[a as usize].name.cmp(&people[b as usize].name));
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&People { people, by_name }).unwrap();
+/// let buf = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<People>(&buf) };
+///
+/// // Search the index using a key extracted from the archived data
+/// let bob = archived
+///     .by_name
+///     .find_by_key(&archived.people, &"Bob", |person| person.name.as_str())
+///     .unwrap();
+/// assert_eq!(bob.age, 27);
+///
+/// // Iterating the index yields the data in sorted order
+/// let names = archived
+///     .by_name
+///     .iter(&archived.people)
+///     .map(|person| person.name.as_str())
+///     .collect::<Vec<_>>();
+/// assert_eq!(names, ["Alice", "Bob", "Mallory"]);
+/// ```
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[derive(Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct ArchivedSortedIndex(ArchivedVec<Archived<u32>>);
+
+impl ArchivedSortedIndex {
+    /// Gets the indices of the sorted index as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[Archived<u32>] {
+        self.0.as_slice()
+    }
+
+    /// Returns the number of indices in the sorted index.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the sorted index contains no indices.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the index into the data of the element at the given position in sorted order.
+    #[inline]
+    pub fn index_at(&self, position: usize) -> Option<usize> {
+        self.0
+            .get(position)
+            .map(|index| from_archived!(*index) as usize)
+    }
+
+    /// Gets the element of `data` at the given position in sorted order.
+    #[inline]
+    pub fn get<'a, T>(&self, data: &'a [T], position: usize) -> Option<&'a T> {
+        self.index_at(position).map(|index| &data[index])
+    }
+
+    /// Gets an iterator over the elements of `data` in sorted order.
+    #[inline]
+    pub fn iter<'a, T>(&'a self, data: &'a [T]) -> Iter<'a, T> {
+        Iter {
+            indices: self.0.iter(),
+            data,
+        }
+    }
+
+    /// Binary searches the elements of `data` in sorted order with a comparator function.
+    ///
+    /// The comparator should return the ordering of the element it is passed relative to the
+    /// target. If a matching element is found, then its position in sorted order is returned in
+    /// `Ok`. Otherwise, the position where a matching element could be inserted to maintain sorted
+    /// order is returned in `Err`. See [`slice::binary_search_by`] for more details.
+    #[inline]
+    pub fn binary_search_by<'a, T, F>(&self, data: &'a [T], mut f: F) -> Result<usize, usize>
+    where
+        F: FnMut(&'a T) -> Ordering,
+    {
+        self.0
+            .binary_search_by(|index| f(&data[from_archived!(*index) as usize]))
+    }
+
+    /// Binary searches the elements of `data` in sorted order for a key.
+    ///
+    /// The key extractor function must return keys in the same order that the index was sorted by.
+    /// The return value is the same as for
+    /// [`binary_search_by`](ArchivedSortedIndex::binary_search_by).
+    #[inline]
+    pub fn binary_search_by_key<'a, T, B, F>(
+        &self,
+        data: &'a [T],
+        key: &B,
+        mut f: F,
+    ) -> Result<usize, usize>
+    where
+        B: Ord,
+        F: FnMut(&'a T) -> B,
+    {
+        self.binary_search_by(data, |element| f(element).cmp(key))
+    }
+
+    /// Finds an element of `data` with the given key.
+    ///
+    /// The key extractor function must return keys in the same order that the index was sorted by.
+    /// If multiple elements have the same key, then any one of them may be returned.
+    #[inline]
+    pub fn find_by_key<'a, T, B, F>(&self, data: &'a [T], key: &B, f: F) -> Option<&'a T>
+    where
+        B: Ord,
+        F: FnMut(&'a T) -> B,
+    {
+        self.binary_search_by_key(data, key, f)
+            .ok()
+            .and_then(|position| self.get(data, position))
+    }
+
+    /// Resolves an archived sorted index from a given length and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of indices that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a sorted index
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: SortedIndexResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.0);
+        ArchivedVec::resolve_from_len(len, pos + fp, resolver.0, fo);
+    }
+
+    /// Serializes a slice of indices as a sorted index.
+    ///
+    /// The indices are written in the order they are provided, and should already be sorted by the
+    /// ordering that will be used to search them.
+    #[inline]
+    pub fn serialize_from_slice<S: ScratchSpace + Serializer + ?Sized>(
+        indices: &[u32],
+        serializer: &mut S,
+    ) -> Result<SortedIndexResolver, S::Error> {
+        Ok(SortedIndexResolver(ArchivedVec::serialize_from_slice(
+            indices, serializer,
+        )?))
+    }
+}
+
+impl fmt::Debug for ArchivedSortedIndex {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl PartialEq<[u32]> for ArchivedSortedIndex {
+    #[inline]
+    fn eq(&self, other: &[u32]) -> bool {
+        self.len() == other.len()
+            && self
+                .as_slice()
+                .iter()
+                .zip(other.iter())
+                .all(|(a, b)| from_archived!(*a) == *b)
+    }
+}
+
+/// The resolver for archived sorted indices.
+pub struct SortedIndexResolver(VecResolver);
+
+/// An iterator over the elements of a slice in the order of a sorted index.
+pub struct Iter<'a, T> {
+    indices: slice::Iter<'a, Archived<u32>>,
+    data: &'a [T],
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        self.indices
+            .next()
+            .map(|index| &data[from_archived!(*index) as usize])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        self.indices
+            .next_back()
+            .map(|index| &data[from_archived!(*index) as usize])
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+impl<'a, T> FusedIterator for Iter<'a, T> {}
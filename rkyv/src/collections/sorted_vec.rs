@@ -0,0 +1,327 @@
+//! An archived lookup table backed by a vector of key-value pairs sorted by key.
+
+use crate::{
+    collections::util::Entry,
+    vec::{ArchivedVec, VecResolver},
+};
+use core::{borrow::Borrow, cmp::Ordering, fmt, iter::FusedIterator, ops::Index, slice};
+
+/// An archived vector of key-value pairs that is sorted by key.
+///
+/// For small associative data, a sorted vector is often smaller and faster than a hash map. Lookups
+/// are performed with a binary search in `O(log n)` time and iteration yields the entries in key
+/// order.
+///
+/// The entries are sorted while they are serialized, so the source data does not need to be
+/// sorted beforehand. The [`AsSortedVec`](crate::with::AsSortedVec) wrapper can be used to archive
+/// a `Vec<(K, V)>` as a sorted vector.
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[derive(Eq, PartialEq)]
+#[repr(transparent)]
+pub struct ArchivedSortedVec<K, V>(ArchivedVec<Entry<K, V>>);
+
+impl<K, V> ArchivedSortedVec<K, V> {
+    #[inline]
+    fn find<Q: Ord + ?Sized>(&self, key: &Q) -> Option<&Entry<K, V>>
+    where
+        K: Borrow<Q>,
+    {
+        self.0
+            .binary_search_by(|entry| entry.key.borrow().cmp(key))
+            .ok()
+            .map(|index| &self.0[index])
+    }
+
+    /// Returns `true` if the vector contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the vector's key type, but the ordering on the borrowed
+    /// form _must_ match the ordering on the key type.
+    #[inline]
+    pub fn contains_key<Q: Ord + ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the vector's key type, but the ordering on the borrowed
+    /// form _must_ match the ordering on the key type.
+    #[inline]
+    pub fn get<Q: Ord + ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.find(key).map(|entry| &entry.value)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// The key may be any borrowed form of the vector's key type, but the ordering on the borrowed
+    /// form _must_ match the ordering on the key type.
+    #[inline]
+    pub fn get_key_value<Q: Ord + ?Sized>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+    {
+        self.find(key).map(|entry| (&entry.key, &entry.value))
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Gets an iterator over the entries of the vector, sorted by key.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.0.iter(),
+        }
+    }
+
+    /// Gets an iterator over the keys of the vector, in sorted order.
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys {
+            inner: self.0.iter(),
+        }
+    }
+
+    /// Returns the number of items in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Gets an iterator over the values of the vector, sorted by key.
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values {
+            inner: self.0.iter(),
+        }
+    }
+
+    /// Resolves an archived sorted vector from a given length and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of elements that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a sorted vector
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: SortedVecResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.0);
+        ArchivedVec::resolve_from_len(len, pos + fp, resolver.0, fo);
+    }
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use crate::{
+        ser::{ScratchSpace, Serializer},
+        Serialize,
+    };
+
+    impl<K, V> ArchivedSortedVec<K, V> {
+        /// Serializes an iterator of key-value pairs as a sorted vector.
+        ///
+        /// The pairs are sorted by key before they are written, so the iterator may return them in
+        /// any order. If the iterator returns the same key multiple times, lookups for that key may
+        /// return any one of its values.
+        pub fn serialize_from_iter<'a, UK, UV, S, I>(
+            iter: I,
+            serializer: &mut S,
+        ) -> Result<SortedVecResolver, S::Error>
+        where
+            UK: 'a + Serialize<S, Archived = K> + Ord,
+            UV: 'a + Serialize<S, Archived = V>,
+            S: Serializer + ScratchSpace + ?Sized,
+            I: ExactSizeIterator<Item = (&'a UK, &'a UV)>,
+        {
+            use crate::ScratchVec;
+
+            unsafe {
+                let mut entries = ScratchVec::new(serializer, iter.len())?;
+                for (key, value) in iter {
+                    entries.push(Entry { key, value });
+                }
+                entries.sort_unstable_by(|a, b| a.key.cmp(b.key));
+
+                let resolver = ArchivedVec::serialize_from_iter::<Entry<&UK, &UV>, _, _, _>(
+                    entries.iter(),
+                    serializer,
+                )?;
+
+                entries.free(serializer)?;
+
+                Ok(SortedVecResolver(resolver))
+            }
+        }
+
+        /// Serializes an iterator of key-value pairs that are already sorted by key as a sorted
+        /// vector.
+        ///
+        /// The archived vector is identical to one serialized with
+        /// [`serialize_from_iter`](ArchivedSortedVec::serialize_from_iter), but the pairs are
+        /// written in the order they are returned instead of being collected and sorted first.
+        ///
+        /// # Safety
+        ///
+        /// The keys returned by the iterator must be in ascending sorted order.
+        pub unsafe fn serialize_from_sorted_iter<'a, UK, UV, S, I>(
+            iter: I,
+            serializer: &mut S,
+        ) -> Result<SortedVecResolver, S::Error>
+        where
+            UK: 'a + Serialize<S, Archived = K>,
+            UV: 'a + Serialize<S, Archived = V>,
+            S: Serializer + ScratchSpace + ?Sized,
+            I: ExactSizeIterator<Item = (&'a UK, &'a UV)>,
+        {
+            let resolver = ArchivedVec::serialize_from_iter::<Entry<&UK, &UV>, _, _, _>(
+                iter.map(|(key, value)| Entry { key, value }),
+                serializer,
+            )?;
+
+            Ok(SortedVecResolver(resolver))
+        }
+    }
+};
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for ArchivedSortedVec<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Borrow<Q>, Q: Ord + ?Sized, V> Index<&'_ Q> for ArchivedSortedVec<K, V> {
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    #[inline]
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a ArchivedSortedVec<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: PartialOrd, V: PartialOrd> PartialOrd for ArchivedSortedVec<K, V> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+/// The resolver for archived sorted vectors.
+pub struct SortedVecResolver(VecResolver);
+
+/// An iterator over the key-value pairs of an archived sorted vector.
+pub struct Iter<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| (&entry.key, &entry.value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|entry| (&entry.key, &entry.value))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
+
+/// An iterator over the keys of an archived sorted vector.
+pub struct Keys<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| &entry.key)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|entry| &entry.key)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
+
+/// An iterator over the values of an archived sorted vector.
+pub struct Values<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| &entry.value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|entry| &entry.value)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
@@ -0,0 +1,149 @@
+//! Size-adaptive compression for archived byte buffers.
+//!
+//! The [`Compress`](crate::with::Compress) wrapper archives a byte buffer as an
+//! [`ArchivedCompressed`], compressing it only when it is larger than a threshold. Small buffers
+//! are stored as-is to avoid the overhead of compressing and decompressing them, and a one-byte
+//! flag records which representation was chosen.
+
+#[cfg(feature = "validation")]
+pub mod validation;
+
+use crate::{ser::Serializer, Archive, Archived, RelPtr};
+#[cfg(not(feature = "std"))]
+use ::alloc::{borrow::Cow, vec::Vec};
+#[cfg(feature = "std")]
+use ::std::borrow::Cow;
+use core::{fmt, marker::PhantomData, slice};
+
+/// A compression algorithm used to store archived byte buffers.
+///
+/// rkyv does not provide any compression algorithms itself. Implement this trait for a marker type
+/// that calls into the compression library of your choice, then use that type with the
+/// [`Compress`](crate::with::Compress) wrapper.
+pub trait Compression {
+    /// The error returned when compressed bytes cannot be decompressed.
+    type Error;
+
+    /// Compresses the given bytes.
+    fn compress(bytes: &[u8]) -> Vec<u8>;
+
+    /// Decompresses bytes that were returned from [`compress`](Compression::compress).
+    fn decompress(bytes: &[u8]) -> Result<Vec<u8>, Self::Error>;
+}
+
+/// An archived byte buffer that may be compressed.
+///
+/// # Layout
+///
+/// An archived compressed buffer is laid out as (`#[repr(C)]`):
+///
+/// - A one-byte flag at offset 0. It is [`RAW`](ArchivedCompressed::RAW) (`0`) if the stored bytes
+///   are the original bytes, and [`COMPRESSED`](ArchivedCompressed::COMPRESSED) (`1`) if they were
+///   compressed with `C`. Validation rejects any other value.
+/// - The length of the stored bytes as an `Archived<usize>`, aligned after the flag.
+/// - A relative pointer to the stored bytes.
+///
+/// The stored bytes are only decompressed when [`bytes`](ArchivedCompressed::bytes) is called on a
+/// compressed buffer. Raw buffers are borrowed directly from the archive.
+#[repr(C)]
+pub struct ArchivedCompressed<C> {
+    flag: u8,
+    len: Archived<usize>,
+    ptr: RelPtr<u8>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C> ArchivedCompressed<C> {
+    /// The flag value for bytes that are stored uncompressed.
+    pub const RAW: u8 = 0;
+    /// The flag value for bytes that are stored compressed.
+    pub const COMPRESSED: u8 = 1;
+
+    /// Returns whether the stored bytes are compressed.
+    #[inline]
+    pub fn is_compressed(&self) -> bool {
+        self.flag == Self::COMPRESSED
+    }
+
+    /// Returns the bytes stored in the archive, which are compressed if
+    /// [`is_compressed`](ArchivedCompressed::is_compressed) returns `true`.
+    #[inline]
+    pub fn stored_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), from_archived!(self.len) as usize) }
+    }
+
+    /// Resolves an archived compressed buffer from the resolver returned when serializing it.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a compressed buffer
+    #[inline]
+    pub unsafe fn resolve_from_slice(pos: usize, resolver: CompressedResolver, out: *mut Self) {
+        let (_, fo) = out_field!(out.flag);
+        fo.write(if resolver.compressed {
+            Self::COMPRESSED
+        } else {
+            Self::RAW
+        });
+        let (fp, fo) = out_field!(out.len);
+        resolver.len.resolve(pos + fp, (), fo);
+        let (fp, fo) = out_field!(out.ptr);
+        RelPtr::emplace(pos + fp, resolver.pos, fo);
+    }
+}
+
+impl<C: Compression> ArchivedCompressed<C> {
+    /// Returns the original bytes, decompressing them if they are compressed.
+    #[inline]
+    pub fn bytes(&self) -> Result<Cow<'_, [u8]>, C::Error> {
+        if self.is_compressed() {
+            C::decompress(self.stored_bytes()).map(Cow::Owned)
+        } else {
+            Ok(Cow::Borrowed(self.stored_bytes()))
+        }
+    }
+
+    /// Serializes a byte slice as a compressed buffer.
+    ///
+    /// The bytes are compressed only if there are more than `threshold` of them. If compressing
+    /// them does not make them smaller, they are stored uncompressed instead.
+    pub fn serialize_from_slice<S: Serializer + ?Sized>(
+        bytes: &[u8],
+        threshold: usize,
+        serializer: &mut S,
+    ) -> Result<CompressedResolver, S::Error> {
+        let compressed = if bytes.len() > threshold {
+            Some(C::compress(bytes)).filter(|compressed| compressed.len() < bytes.len())
+        } else {
+            None
+        };
+
+        let pos = serializer.pos();
+        let stored = compressed.as_deref().unwrap_or(bytes);
+        serializer.write(stored)?;
+
+        Ok(CompressedResolver {
+            compressed: compressed.is_some(),
+            len: stored.len(),
+            pos,
+        })
+    }
+}
+
+impl<C> fmt::Debug for ArchivedCompressed<C> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchivedCompressed")
+            .field("compressed", &self.is_compressed())
+            .field("stored_bytes", &self.stored_bytes())
+            .finish()
+    }
+}
+
+/// The resolver for [`ArchivedCompressed`].
+pub struct CompressedResolver {
+    compressed: bool,
+    len: usize,
+    pos: usize,
+}
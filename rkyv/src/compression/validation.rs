@@ -0,0 +1,82 @@
+//! Validation implementation for ArchivedCompressed.
+
+use crate::{compression::ArchivedCompressed, validation::ArchiveContext, Archived, RelPtr};
+use bytecheck::{CheckBytes, Error};
+use core::{convert::Infallible, fmt, ptr};
+
+/// Errors that can occur while checking an archived compressed buffer.
+#[derive(Debug)]
+pub enum CompressedError<C> {
+    /// The flag was not a valid value
+    InvalidFlag(u8),
+    /// A bounds error occurred
+    ContextError(C),
+}
+
+impl<C> From<Infallible> for CompressedError<C> {
+    #[inline]
+    fn from(_: Infallible) -> Self {
+        unsafe { core::hint::unreachable_unchecked() }
+    }
+}
+
+impl<C: fmt::Display> fmt::Display for CompressedError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompressedError::InvalidFlag(flag) => {
+                write!(f, "invalid compression flag: {}", flag)
+            }
+            CompressedError::ContextError(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<C: Error + 'static> Error for CompressedError<C> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                CompressedError::InvalidFlag(_) => None,
+                CompressedError::ContextError(e) => Some(e as &dyn Error),
+            }
+        }
+    }
+};
+
+impl<T, C: ArchiveContext + ?Sized> CheckBytes<C> for ArchivedCompressed<T>
+where
+    C::Error: Error,
+{
+    type Error = CompressedError<C::Error>;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        let flag = *u8::check_bytes(ptr::addr_of!((*value).flag), context)?;
+        if flag != Self::RAW && flag != Self::COMPRESSED {
+            return Err(CompressedError::InvalidFlag(flag));
+        }
+
+        let len = from_archived!(*Archived::<usize>::check_bytes(
+            ptr::addr_of!((*value).len),
+            context,
+        )?) as usize;
+        let rel_ptr = RelPtr::manual_check_bytes(ptr::addr_of!((*value).ptr), context)?;
+        let bytes_ptr = context
+            .check_subtree_ptr::<[u8]>(rel_ptr.base(), rel_ptr.offset(), len)
+            .map_err(CompressedError::ContextError)?;
+
+        // The stored bytes are only bytes, so claiming them is enough to validate them
+        let range = context
+            .push_prefix_subtree(bytes_ptr)
+            .map_err(CompressedError::ContextError)?;
+        context
+            .pop_prefix_range(range)
+            .map_err(CompressedError::ContextError)?;
+
+        Ok(&*value)
+    }
+}
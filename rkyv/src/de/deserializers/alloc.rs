@@ -1,16 +1,22 @@
 //! Adapters wrap deserializers and add support for deserializer traits.
 
 use crate::{
-    de::{SharedDeserializeRegistry, SharedPointer},
+    de::{AllocationBudget, ScratchPool, SharedDeserializeRegistry, SharedPointer},
     Fallible,
 };
 #[cfg(not(feature = "std"))]
-use alloc::boxed::Box;
-use core::fmt;
+use ::alloc::{alloc, boxed::Box, vec::Vec};
+use core::{
+    alloc::Layout,
+    convert::Infallible,
+    fmt,
+    mem::{align_of, size_of, ManuallyDrop},
+    ptr::NonNull,
+};
 #[cfg(not(feature = "std"))]
 use hashbrown::hash_map;
 #[cfg(feature = "std")]
-use std::collections::hash_map;
+use std::{alloc, collections::hash_map};
 
 /// An error that can occur while deserializing shared pointers.
 #[derive(Debug)]
@@ -34,9 +40,126 @@ const _: () = {
     impl Error for SharedDeserializeMapError {}
 };
 
+/// A deserializer that pools scratch buffers so they can be reused across calls.
+///
+/// Buffers given back to the pool keep their memory, and the next buffer taken with a compatible
+/// element type reuses it. Reusing one `VecPool` for many deserializations avoids allocating
+/// scratch memory once the pool has warmed up. See [`ScratchPool`] for the deserialize
+/// implementations that use scratch buffers.
+///
+/// # Examples
+/// ```
+/// use rkyv::de::{deserializers::VecPool, ScratchPool};
+///
+/// let mut pool = VecPool::new();
+/// let mut scratch = pool.take_scratch::<u32>();
+/// scratch.extend(0..100);
+/// pool.return_scratch(scratch);
+/// assert_eq!(pool.len(), 1);
+///
+/// // The memory of the returned buffer is reused
+/// let scratch = pool.take_scratch::<u32>();
+/// assert!(scratch.is_empty());
+/// assert!(scratch.capacity() >= 100);
+/// assert!(pool.is_empty());
+/// ```
+#[derive(Debug, Default)]
+pub struct VecPool {
+    buffers: Vec<(NonNull<u8>, Layout)>,
+}
+
+// SAFETY: `VecPool` only holds unused allocations that it owns.
+unsafe impl Send for VecPool {}
+// SAFETY: `VecPool` only holds unused allocations that it owns.
+unsafe impl Sync for VecPool {}
+
+impl VecPool {
+    /// Creates a new, empty pool.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            buffers: Vec::new(),
+        }
+    }
+
+    /// Returns the number of buffers in the pool.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Returns whether the pool holds no buffers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty()
+    }
+
+    /// Frees all of the buffers in the pool.
+    #[inline]
+    pub fn clear(&mut self) {
+        for (ptr, layout) in self.buffers.drain(..) {
+            unsafe {
+                alloc::dealloc(ptr.as_ptr(), layout);
+            }
+        }
+    }
+}
+
+impl Drop for VecPool {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl Fallible for VecPool {
+    type Error = Infallible;
+}
+
+impl AllocationBudget for VecPool {}
+
+impl ScratchPool for VecPool {
+    fn take_scratch<T>(&mut self) -> Vec<T> {
+        if size_of::<T>() == 0 {
+            return Vec::new();
+        }
+
+        // A buffer can only be reused if deallocating it as a `Vec<T>` uses the same layout that it
+        // was allocated with.
+        let compatible = self.buffers.iter().position(|(_, layout)| {
+            layout.align() == align_of::<T>() && layout.size() % size_of::<T>() == 0
+        });
+        match compatible {
+            Some(index) => {
+                let (ptr, layout) = self.buffers.swap_remove(index);
+                unsafe {
+                    Vec::from_raw_parts(ptr.as_ptr().cast(), 0, layout.size() / size_of::<T>())
+                }
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn return_scratch<T>(&mut self, mut scratch: Vec<T>) {
+        scratch.clear();
+        if size_of::<T>() == 0 || scratch.capacity() == 0 {
+            return;
+        }
+
+        let mut scratch = ManuallyDrop::new(scratch);
+        let layout = Layout::array::<T>(scratch.capacity()).unwrap();
+        let ptr = unsafe { NonNull::new_unchecked(scratch.as_mut_ptr().cast()) };
+        self.buffers.push((ptr, layout));
+    }
+}
+
 /// An adapter that adds shared deserialization support to a deserializer.
+///
+/// It also pools scratch buffers like a [`VecPool`]. To reuse one map for many deserializations,
+/// [`clear`](SharedDeserializeMap::clear) it between them.
 pub struct SharedDeserializeMap {
     shared_pointers: hash_map::HashMap<*const u8, Box<dyn SharedPointer>>,
+    pool: VecPool,
 }
 
 impl SharedDeserializeMap {
@@ -45,8 +168,19 @@ impl SharedDeserializeMap {
     pub fn new() -> Self {
         Self {
             shared_pointers: hash_map::HashMap::new(),
+            pool: VecPool::new(),
         }
     }
+
+    /// Forgets all of the shared pointers that have been deserialized so far.
+    ///
+    /// This must be called before deserializing from a different archive, since a shared pointer in
+    /// the new archive may be at the same address as one in the old archive. The memory used to
+    /// track shared pointers and the pooled scratch buffers are kept for reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.shared_pointers.clear();
+    }
 }
 
 impl fmt::Debug for SharedDeserializeMap {
@@ -72,6 +206,20 @@ impl Fallible for SharedDeserializeMap {
     type Error = SharedDeserializeMapError;
 }
 
+impl AllocationBudget for SharedDeserializeMap {}
+
+impl ScratchPool for SharedDeserializeMap {
+    #[inline]
+    fn take_scratch<T>(&mut self) -> Vec<T> {
+        self.pool.take_scratch()
+    }
+
+    #[inline]
+    fn return_scratch<T>(&mut self, scratch: Vec<T>) {
+        self.pool.return_scratch(scratch)
+    }
+}
+
 impl SharedDeserializeRegistry for SharedDeserializeMap {
     fn get_shared_ptr(&mut self, ptr: *const u8) -> Option<&dyn SharedPointer> {
         self.shared_pointers.get(&ptr).map(|p| p.as_ref())
@@ -0,0 +1,178 @@
+use crate::{de::AllocationBudget, Fallible};
+use core::{alloc::Layout, fmt};
+
+/// An error that can occur while deserializing with a [`BudgetDeserializer`].
+#[derive(Debug)]
+pub enum BudgetDeserializerError<E> {
+    /// Deserializing would have allocated more memory than the remaining budget
+    ExceededBudget {
+        /// The number of bytes that were requested
+        requested: usize,
+        /// The number of bytes that were left in the budget
+        remaining: usize,
+    },
+    /// An error occurred in the wrapped deserializer
+    Inner(E),
+}
+
+impl<E: fmt::Display> fmt::Display for BudgetDeserializerError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExceededBudget {
+                requested,
+                remaining,
+            } => write!(
+                f,
+                "exceeded allocation budget: requested {} bytes with {} bytes remaining",
+                requested, remaining
+            ),
+            Self::Inner(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<E: Error + 'static> Error for BudgetDeserializerError<E> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                Self::ExceededBudget { .. } => None,
+                Self::Inner(e) => Some(e as &dyn Error),
+            }
+        }
+    }
+};
+
+/// An adapter that limits the total amount of memory a deserializer can allocate.
+///
+/// Validation guarantees that an archive is well-formed, but a small archive can still describe a
+/// very large deserialized value. When deserializing untrusted archives, wrapping the deserializer
+/// in a `BudgetDeserializer` makes deserialization fail once the cumulative size of its allocations
+/// would exceed the budget.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     de::deserializers::{BudgetDeserializer, BudgetDeserializerError},
+///     to_archived_root, Deserialize, Infallible,
+/// };
+///
+/// let root = to_archived_root::<_, 256>(&vec![0u32; 64]).unwrap();
+/// let archived = &*root;
+///
+/// let mut deserializer = BudgetDeserializer::new(Infallible, 1024);
+/// let result: Vec<u32> = archived.deserialize(&mut deserializer).unwrap();
+/// assert_eq!(result.len(), 64);
+/// assert_eq!(deserializer.remaining(), 1024 - 256);
+///
+/// let mut deserializer = BudgetDeserializer::new(Infallible, 128);
+/// let result: Result<Vec<u32>, _> = archived.deserialize(&mut deserializer);
+/// assert!(matches!(result, Err(BudgetDeserializerError::ExceededBudget { .. })));
+/// ```
+#[derive(Debug)]
+pub struct BudgetDeserializer<D> {
+    inner: D,
+    remaining: usize,
+}
+
+impl<D> BudgetDeserializer<D> {
+    /// Wraps the given deserializer and allows it to allocate up to `budget` bytes.
+    #[inline]
+    pub fn new(inner: D, budget: usize) -> Self {
+        Self {
+            inner,
+            remaining: budget,
+        }
+    }
+
+    /// Returns the number of bytes that can still be allocated.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Consumes the adapter and returns the wrapped deserializer.
+    #[inline]
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+impl<D: Default> Default for BudgetDeserializer<D> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(D::default(), usize::MAX)
+    }
+}
+
+impl<D: Fallible> Fallible for BudgetDeserializer<D> {
+    type Error = BudgetDeserializerError<D::Error>;
+}
+
+impl<D: AllocationBudget> AllocationBudget for BudgetDeserializer<D> {
+    #[inline]
+    fn reserve_allocation(&mut self, layout: Layout) -> Result<(), Self::Error> {
+        if layout.size() > self.remaining {
+            return Err(BudgetDeserializerError::ExceededBudget {
+                requested: layout.size(),
+                remaining: self.remaining,
+            });
+        }
+        self.remaining -= layout.size();
+        self.inner
+            .reserve_allocation(layout)
+            .map_err(BudgetDeserializerError::Inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D: crate::de::InstantEpoch> crate::de::InstantEpoch for BudgetDeserializer<D> {
+    #[inline]
+    fn instant_from_offset(
+        &mut self,
+        offset: core::time::Duration,
+    ) -> Result<std::time::Instant, Self::Error> {
+        self.inner
+            .instant_from_offset(offset)
+            .map_err(BudgetDeserializerError::Inner)
+    }
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use crate::de::{ScratchPool, SharedDeserializeRegistry, SharedPointer};
+    #[cfg(not(feature = "std"))]
+    use alloc::{boxed::Box, vec::Vec};
+
+    impl<D: ScratchPool> ScratchPool for BudgetDeserializer<D> {
+        #[inline]
+        fn take_scratch<T>(&mut self) -> Vec<T> {
+            self.inner.take_scratch()
+        }
+
+        #[inline]
+        fn return_scratch<T>(&mut self, scratch: Vec<T>) {
+            self.inner.return_scratch(scratch)
+        }
+    }
+
+    impl<D: SharedDeserializeRegistry> SharedDeserializeRegistry for BudgetDeserializer<D> {
+        #[inline]
+        fn get_shared_ptr(&mut self, ptr: *const u8) -> Option<&dyn SharedPointer> {
+            self.inner.get_shared_ptr(ptr)
+        }
+
+        #[inline]
+        fn add_shared_ptr(
+            &mut self,
+            ptr: *const u8,
+            shared: Box<dyn SharedPointer>,
+        ) -> Result<(), Self::Error> {
+            self.inner
+                .add_shared_ptr(ptr, shared)
+                .map_err(BudgetDeserializerError::Inner)
+        }
+    }
+};
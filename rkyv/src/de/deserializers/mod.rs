@@ -2,7 +2,15 @@
 
 #[cfg(feature = "alloc")]
 mod alloc;
+mod core;
+#[cfg(feature = "std")]
+mod std;
 
 #[doc(inline)]
 #[cfg(feature = "alloc")]
 pub use self::alloc::*;
+#[doc(inline)]
+pub use self::core::*;
+#[doc(inline)]
+#[cfg(feature = "std")]
+pub use self::std::*;
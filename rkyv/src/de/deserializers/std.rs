@@ -0,0 +1,118 @@
+use crate::{
+    de::{AllocationBudget, InstantEpoch, ScratchPool, SharedDeserializeRegistry, SharedPointer},
+    Fallible,
+};
+use core::{alloc::Layout, fmt};
+use std::{
+    error::Error,
+    time::{Duration, Instant},
+};
+
+/// An error that can occur while deserializing with an [`InstantDeserializer`].
+#[derive(Debug)]
+pub enum InstantDeserializerError<E> {
+    /// An archived instant was too far from the base instant to be represented
+    Overflow,
+    /// An error occurred in the wrapped deserializer
+    Inner(E),
+}
+
+impl<E: fmt::Display> fmt::Display for InstantDeserializerError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow => write!(f, "instant overflowed when added to the base instant"),
+            Self::Inner(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for InstantDeserializerError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Overflow => None,
+            Self::Inner(e) => Some(e as &dyn Error),
+        }
+    }
+}
+
+/// An adapter that adds support for deserializing [`Instant`]s to a deserializer.
+///
+/// Archived instants are reconstructed by adding their offsets to the base instant. See
+/// [`InstantEpoch`] for more information.
+#[derive(Debug)]
+pub struct InstantDeserializer<D> {
+    inner: D,
+    base: Instant,
+}
+
+impl<D> InstantDeserializer<D> {
+    /// Wraps the given deserializer and reconstructs instants relative to `base`.
+    #[inline]
+    pub fn new(inner: D, base: Instant) -> Self {
+        Self { inner, base }
+    }
+
+    /// Returns the base instant that instants are reconstructed relative to.
+    #[inline]
+    pub fn base(&self) -> Instant {
+        self.base
+    }
+
+    /// Consumes the adapter and returns the wrapped deserializer.
+    #[inline]
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+impl<D: Fallible> Fallible for InstantDeserializer<D> {
+    type Error = InstantDeserializerError<D::Error>;
+}
+
+impl<D: AllocationBudget> AllocationBudget for InstantDeserializer<D> {
+    #[inline]
+    fn reserve_allocation(&mut self, layout: Layout) -> Result<(), Self::Error> {
+        self.inner
+            .reserve_allocation(layout)
+            .map_err(InstantDeserializerError::Inner)
+    }
+}
+
+impl<D: Fallible> InstantEpoch for InstantDeserializer<D> {
+    #[inline]
+    fn instant_from_offset(&mut self, offset: Duration) -> Result<Instant, Self::Error> {
+        self.base
+            .checked_add(offset)
+            .ok_or(InstantDeserializerError::Overflow)
+    }
+}
+
+impl<D: ScratchPool> ScratchPool for InstantDeserializer<D> {
+    #[inline]
+    fn take_scratch<T>(&mut self) -> Vec<T> {
+        self.inner.take_scratch()
+    }
+
+    #[inline]
+    fn return_scratch<T>(&mut self, scratch: Vec<T>) {
+        self.inner.return_scratch(scratch)
+    }
+}
+
+impl<D: SharedDeserializeRegistry> SharedDeserializeRegistry for InstantDeserializer<D> {
+    #[inline]
+    fn get_shared_ptr(&mut self, ptr: *const u8) -> Option<&dyn SharedPointer> {
+        self.inner.get_shared_ptr(ptr)
+    }
+
+    #[inline]
+    fn add_shared_ptr(
+        &mut self,
+        ptr: *const u8,
+        shared: Box<dyn SharedPointer>,
+    ) -> Result<(), Self::Error> {
+        self.inner
+            .add_shared_ptr(ptr, shared)
+            .map_err(InstantDeserializerError::Inner)
+    }
+}
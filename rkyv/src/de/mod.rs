@@ -2,12 +2,95 @@
 
 pub mod deserializers;
 
+use crate::Fallible;
 #[cfg(feature = "alloc")]
-use crate::{ArchiveUnsized, DeserializeUnsized, Fallible};
+use crate::{ArchiveUnsized, DeserializeUnsized};
 #[cfg(all(feature = "alloc", not(feature = "std")))]
-use ::alloc::boxed::Box;
-#[cfg(feature = "alloc")]
+use ::alloc::{boxed::Box, vec::Vec};
 use ::core::alloc::Layout;
+#[cfg(feature = "std")]
+use ::std::time::{Duration, Instant};
+
+/// A deserializer that can limit the memory allocated while deserializing.
+///
+/// Deserialize implementations that allocate memory for deserialized values (like those for
+/// `Vec`, `Box`, `String`, and the standard maps and sets) require this trait and call
+/// [`reserve_allocation`](AllocationBudget::reserve_allocation) before allocating. It does nothing
+/// by default, but deserializers can override it to limit the total amount of memory deserializing
+/// an archive can allocate (see [`BudgetDeserializer`](deserializers::BudgetDeserializer)).
+///
+/// Custom deserializers that don't limit their allocations only need an empty implementation of
+/// this trait, and adapters should forward it to the deserializer they wrap.
+pub trait AllocationBudget: Fallible {
+    /// Reserves memory for an allocation that is about to be made during deserialization.
+    #[inline]
+    fn reserve_allocation(&mut self, layout: Layout) -> Result<(), Self::Error> {
+        let _ = layout;
+        Ok(())
+    }
+}
+
+impl AllocationBudget for crate::Infallible {}
+
+/// A deserializer that reconstructs archived [`Instant`]s relative to a new base instant.
+///
+/// Archived instants are stored as offsets from the base instant of the serializer that created
+/// them (see [`ser::InstantEpoch`](crate::ser::InstantEpoch)). When deserializing, those offsets
+/// are added to a new base instant, which preserves the timings between the instants of one archive
+/// but not their relationship to instants from any other process.
+///
+/// This trait is required to deserialize `Instant`s. The
+/// [`InstantDeserializer`](deserializers::InstantDeserializer) adapter adds it to any deserializer.
+#[cfg(feature = "std")]
+pub trait InstantEpoch: Fallible {
+    /// Returns the instant at the given offset from the base instant.
+    fn instant_from_offset(&mut self, offset: Duration) -> Result<Instant, Self::Error>;
+}
+
+/// A deserializer that lends out reusable scratch buffers.
+///
+/// This is the deserializing counterpart of [`ScratchSpace`](crate::ser::ScratchSpace). Some
+/// deserialize implementations need temporary buffers that are discarded once the value has been
+/// deserialized. Instead of allocating those buffers on every call, they take them from the
+/// deserializer and give them back when they're done. A deserializer that pools the buffers it gets
+/// back can then be reused across many calls without allocating scratch memory again, which adds up
+/// when deserializing many small archives in a hot loop.
+///
+/// [`VecPool`](deserializers::VecPool) and [`SharedDeserializeMap`](deserializers::SharedDeserializeMap)
+/// pool returned buffers, and the adapters in [`deserializers`] forward to the deserializer they
+/// wrap. [`Infallible`](crate::Infallible) doesn't pool buffers: it hands out new ones and drops the
+/// ones it gets back.
+///
+/// The deserialize implementations in rkyv that use scratch buffers are:
+///
+/// - FST sets and maps archived with [`AsFst`](crate::with::AsFst), which use two buffers to track
+///   the current key and the path through the automaton while walking it.
+///
+/// Buffers that aren't given back (for example, because deserialization failed) are dropped
+/// normally.
+#[cfg(feature = "alloc")]
+pub trait ScratchPool: Fallible {
+    /// Takes an empty buffer from the pool.
+    ///
+    /// The buffer may have capacity left over from an earlier use.
+    fn take_scratch<T>(&mut self) -> Vec<T>;
+
+    /// Gives a buffer back to the pool so that its memory can be reused.
+    ///
+    /// The buffer is cleared before it's pooled.
+    fn return_scratch<T>(&mut self, scratch: Vec<T>);
+}
+
+#[cfg(feature = "alloc")]
+impl ScratchPool for crate::Infallible {
+    #[inline]
+    fn take_scratch<T>(&mut self) -> Vec<T> {
+        Vec::new()
+    }
+
+    #[inline]
+    fn return_scratch<T>(&mut self, _: Vec<T>) {}
+}
 
 /// A deserializable shared pointer type.
 #[cfg(feature = "alloc")]
@@ -0,0 +1,120 @@
+//! An archived version of `Either`.
+
+use core::{
+    cmp::{Ord, Ordering, PartialOrd},
+    hash,
+};
+use either::Either;
+
+/// An archived [`Either`] that holds a value of one of two types, either
+/// [`Left`](ArchivedEither::Left) or [`Right`](ArchivedEither::Right).
+#[derive(Debug)]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[repr(u8)]
+pub enum ArchivedEither<L, R> {
+    /// A value of type `L`
+    Left(L),
+    /// A value of type `R`
+    Right(R),
+}
+
+impl<L, R> ArchivedEither<L, R> {
+    /// Returns `true` if the value is [`Left`](ArchivedEither::Left).
+    #[inline]
+    pub const fn is_left(&self) -> bool {
+        matches!(self, ArchivedEither::Left(_))
+    }
+
+    /// Returns `true` if the value is [`Right`](ArchivedEither::Right).
+    #[inline]
+    pub const fn is_right(&self) -> bool {
+        matches!(self, ArchivedEither::Right(_))
+    }
+
+    /// Returns a reference to the left value, if any.
+    #[inline]
+    pub fn left(&self) -> Option<&L> {
+        match self {
+            ArchivedEither::Left(left) => Some(left),
+            ArchivedEither::Right(_) => None,
+        }
+    }
+
+    /// Returns a reference to the right value, if any.
+    #[inline]
+    pub fn right(&self) -> Option<&R> {
+        match self {
+            ArchivedEither::Left(_) => None,
+            ArchivedEither::Right(right) => Some(right),
+        }
+    }
+
+    /// Returns an `Either` containing references to the left and right values of this
+    /// `ArchivedEither`.
+    #[inline]
+    pub fn as_ref(&self) -> Either<&L, &R> {
+        match self {
+            ArchivedEither::Left(left) => Either::Left(left),
+            ArchivedEither::Right(right) => Either::Right(right),
+        }
+    }
+
+    /// Converts from `&mut ArchivedEither<L, R>` to `Either<&mut L, &mut R>`.
+    #[inline]
+    pub fn as_mut(&mut self) -> Either<&mut L, &mut R> {
+        match self {
+            ArchivedEither::Left(left) => Either::Left(left),
+            ArchivedEither::Right(right) => Either::Right(right),
+        }
+    }
+}
+
+impl<L: Eq, R: Eq> Eq for ArchivedEither<L, R> {}
+
+impl<L: hash::Hash, R: hash::Hash> hash::Hash for ArchivedEither<L, R> {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state)
+    }
+}
+
+impl<L: Ord, R: Ord> Ord for ArchivedEither<L, R> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ref().cmp(&other.as_ref())
+    }
+}
+
+impl<L: PartialEq, R: PartialEq> PartialEq for ArchivedEither<L, R> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref().eq(&other.as_ref())
+    }
+}
+
+impl<L: PartialOrd, R: PartialOrd> PartialOrd for ArchivedEither<L, R> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_ref().partial_cmp(&other.as_ref())
+    }
+}
+
+impl<L, M: PartialEq<L>, R, S: PartialEq<R>> PartialEq<Either<L, R>> for ArchivedEither<M, S> {
+    #[inline]
+    fn eq(&self, other: &Either<L, R>) -> bool {
+        match (self, other) {
+            (ArchivedEither::Left(self_left), Either::Left(other_left)) => self_left.eq(other_left),
+            (ArchivedEither::Right(self_right), Either::Right(other_right)) => {
+                self_right.eq(other_right)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<L: PartialEq<M>, M, R: PartialEq<S>, S> PartialEq<ArchivedEither<L, R>> for Either<M, S> {
+    #[inline]
+    fn eq(&self, other: &ArchivedEither<L, R>) -> bool {
+        other.eq(self)
+    }
+}
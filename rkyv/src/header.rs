@@ -0,0 +1,669 @@
+//! A fixed header for archives that are read by other versions of rkyv.
+//!
+//! Archived types have the same layout only when the archive and the reader agree on the format
+//! version, endianness, and pointer width. When an archive is stored in a file that may outlive
+//! the program that wrote it, it can start with an [`ArchiveHeader`] so that any reader can tell
+//! whether it is able to read the archive before touching any archived data.
+//!
+//! The header has a layout that never changes between versions:
+//!
+//! | Bytes    | Contents                                                                |
+//! |----------|-------------------------------------------------------------------------|
+//! | `0..4`   | The magic bytes `rkyv`                                                  |
+//! | `4..6`   | The format version, as a little-endian `u16`                            |
+//! | `6`      | Flags (see below)                                                       |
+//! | `7`      | The pointer width in bytes (2, 4, or 8)                                 |
+//! | `8..16`  | The position of the root object, as a little-endian `u64`               |
+//!
+//! The flags are:
+//!
+//! | Bit | Set when                                            |
+//! |-----|-----------------------------------------------------|
+//! | `0` | The archive is big-endian                           |
+//! | `1` | The archive uses `strict` layouts                   |
+//! | `2` | The archive is followed by a [checksum](#checksums) |
+//!
+//! The archive itself starts right after the header, and the root position is relative to the
+//! start of the archive (not the start of the header). Because the header is 16 bytes long, an
+//! archive that starts with a header keeps the alignment of the buffer it is loaded into.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{
+//!     header::{ArchiveHeader, HeaderError},
+//!     ser::{serializers::AllocSerializer, Serializer},
+//!     AlignedVec,
+//! };
+//!
+//! let mut serializer = AllocSerializer::<256>::default();
+//! let root_pos = serializer.serialize_value(&"hello world".to_string()).unwrap();
+//! let archive = serializer.into_serializer().into_inner();
+//!
+//! // Write the header followed by the archive, for example to a file
+//! let mut file = Vec::new();
+//! ArchiveHeader::new(root_pos).write(&mut file).unwrap();
+//! file.extend_from_slice(&archive);
+//!
+//! // Load the file into an aligned buffer and check the header before reading the archive
+//! let mut bytes = AlignedVec::new();
+//! bytes.extend_from_slice(&file);
+//! let (header, archive) = ArchiveHeader::split(&bytes).unwrap();
+//! let archived = unsafe { rkyv::archived_value::<String>(archive, header.root_pos()) };
+//! assert_eq!(archived, "hello world");
+//!
+//! // Readers reject archives that they can't read
+//! let mut bytes = file.clone();
+//! bytes[4] = 0xff;
+//! assert_eq!(
+//!     ArchiveHeader::split(&bytes).unwrap_err(),
+//!     HeaderError::UnsupportedVersion(0x00ff)
+//! );
+//! ```
+//!
+//! # Checksums
+//!
+//! A header created with [`with_checksum`](ArchiveHeader::with_checksum) sets bit 2 of its flags,
+//! and the archive that follows it must be followed by an 8-byte trailer:
+//!
+//! | Bytes          | Contents                               |
+//! |----------------|----------------------------------------|
+//! | `0..16`        | The header                             |
+//! | `16..len - 8`  | The archive                            |
+//! | `len - 8..len` | The checksum, as a little-endian `u64` |
+//!
+//! The checksum is the [SeaHash](https://docs.rs/seahash) (with its default seeds) of the 16
+//! header bytes followed by the bytes of the archive, as computed by
+//! [`checksum`](ArchiveHeader::checksum). Since the trailer comes last, it can be written after
+//! streaming the header and archive out.
+//!
+//! When the checksum flag is set, [`split`](ArchiveHeader::split) hashes the header and archive and
+//! returns [`HeaderError::ChecksumMismatch`] if the result doesn't match the trailer. This happens
+//! before the archive is returned, so [`check_archived_root_with_header`] catches corruption (like
+//! bit rot on disk) before it starts following any relative pointers in the archive. Hashing reads
+//! every byte of the archive, so accessing an archive with a checksum takes time proportional to
+//! its length.
+//!
+//! ```
+//! use rkyv::{
+//!     header::{archived_root_with_header, ArchiveHeader, HeaderError},
+//!     ser::{serializers::AllocSerializer, Serializer},
+//!     AlignedVec,
+//! };
+//!
+//! let mut serializer = AllocSerializer::<256>::default();
+//! let root_pos = serializer.serialize_value(&vec![1u32, 2, 3]).unwrap();
+//! let archive = serializer.into_serializer().into_inner();
+//!
+//! // Write the header, the archive, and then the checksum
+//! let header = ArchiveHeader::new(root_pos).with_checksum();
+//! let mut file = Vec::new();
+//! header.write(&mut file).unwrap();
+//! file.extend_from_slice(&archive);
+//! file.extend_from_slice(&header.checksum(&archive).to_le_bytes());
+//!
+//! let mut bytes = AlignedVec::new();
+//! bytes.extend_from_slice(&file);
+//! let archived = unsafe { archived_root_with_header::<Vec<u32>>(&bytes).unwrap() };
+//! assert_eq!(archived.len(), 3);
+//!
+//! // A corrupted archive is rejected before it is accessed
+//! bytes[ArchiveHeader::LEN] ^= 0x80;
+//! assert!(matches!(
+//!     unsafe { archived_root_with_header::<Vec<u32>>(&bytes) },
+//!     Err(HeaderError::ChecksumMismatch { .. })
+//! ));
+//! ```
+
+#[cfg(feature = "validation")]
+use crate::validation::{validators::DefaultValidator, CheckTypeError};
+#[cfg(feature = "alloc")]
+use crate::{
+    ser::{serializers::AllocSerializer, Serializer},
+    AlignedVec, Fallible, Serialize,
+};
+use crate::{Archive, FixedUsize};
+#[cfg(feature = "validation")]
+use bytecheck::CheckBytes;
+use core::{convert::TryFrom, fmt, mem};
+#[cfg(feature = "std")]
+use std::io;
+
+const FLAG_BIG_ENDIAN: u8 = 0b001;
+const FLAG_STRICT: u8 = 0b010;
+const FLAG_CHECKSUM: u8 = 0b100;
+
+/// The byte order of the primitives in an archive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// Primitives are stored least significant byte first
+    Little,
+    /// Primitives are stored most significant byte first
+    Big,
+}
+
+impl Endianness {
+    /// The endianness of archives written by this build of rkyv.
+    #[cfg(feature = "archive_le")]
+    pub const ARCHIVE: Self = Endianness::Little;
+    /// The endianness of archives written by this build of rkyv.
+    #[cfg(feature = "archive_be")]
+    pub const ARCHIVE: Self = Endianness::Big;
+    /// The endianness of archives written by this build of rkyv.
+    #[cfg(all(
+        not(feature = "archive_le"),
+        not(feature = "archive_be"),
+        target_endian = "little"
+    ))]
+    pub const ARCHIVE: Self = Endianness::Little;
+    /// The endianness of archives written by this build of rkyv.
+    #[cfg(all(
+        not(feature = "archive_le"),
+        not(feature = "archive_be"),
+        target_endian = "big"
+    ))]
+    pub const ARCHIVE: Self = Endianness::Big;
+}
+
+/// An error that occurred while reading an [`ArchiveHeader`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeaderError {
+    /// The bytes were too short to contain a header
+    TooShort(usize),
+    /// The bytes did not start with the magic bytes
+    InvalidMagic([u8; 4]),
+    /// The format version is not supported by this build of rkyv
+    UnsupportedVersion(u16),
+    /// The header has flags set that are not known to this build of rkyv
+    UnknownFlags(u8),
+    /// The endianness of the archive is not the endianness of this build of rkyv
+    EndiannessMismatch(Endianness),
+    /// The pointer width of the archive is not the pointer width of this build of rkyv
+    PointerWidthMismatch(u8),
+    /// The archive was written with a different `strict` setting than this build of rkyv
+    StrictMismatch(bool),
+    /// The header has the checksum flag set, but the bytes were too short to end with a checksum
+    MissingChecksum(usize),
+    /// The checksum of the header and archive did not match the checksum that followed them
+    ChecksumMismatch {
+        /// The checksum that followed the archive
+        expected: u64,
+        /// The checksum of the header and archive
+        actual: u64,
+    },
+    /// The root position is past the end of the archive
+    RootOutOfBounds {
+        /// The position of the root object
+        root_pos: u64,
+        /// The length of the archive
+        len: usize,
+    },
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderError::TooShort(len) => write!(
+                f,
+                "expected at least {} bytes for the archive header but found {}",
+                ArchiveHeader::LEN,
+                len
+            ),
+            HeaderError::InvalidMagic(magic) => {
+                write!(f, "invalid archive header magic bytes {:?}", magic)
+            }
+            HeaderError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported archive format version {} (expected {})",
+                version,
+                ArchiveHeader::FORMAT_VERSION
+            ),
+            HeaderError::UnknownFlags(flags) => {
+                write!(f, "unknown archive header flags {:#04x}", flags)
+            }
+            HeaderError::EndiannessMismatch(endianness) => write!(
+                f,
+                "archive is {:?} endian but expected {:?} endian",
+                endianness,
+                Endianness::ARCHIVE
+            ),
+            HeaderError::PointerWidthMismatch(width) => write!(
+                f,
+                "archive has {}-byte pointers but expected {}-byte pointers",
+                width,
+                mem::size_of::<FixedUsize>()
+            ),
+            HeaderError::StrictMismatch(strict) => write!(
+                f,
+                "archive was written {} strict layouts but this build {} them",
+                if *strict { "with" } else { "without" },
+                if *strict { "does not use" } else { "uses" }
+            ),
+            HeaderError::MissingChecksum(len) => write!(
+                f,
+                "expected at least {} bytes for the archive header and checksum but found {}",
+                ArchiveHeader::LEN + ArchiveHeader::CHECKSUM_LEN,
+                len
+            ),
+            HeaderError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "archive checksum mismatch: expected {:#018x} but found {:#018x}",
+                expected, actual
+            ),
+            HeaderError::RootOutOfBounds { root_pos, len } => write!(
+                f,
+                "root position {} is out of bounds for an archive of length {}",
+                root_pos, len
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HeaderError {}
+
+/// A fixed-size header that describes the format of an archive.
+///
+/// See the [module-level documentation](self) for the layout of the header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArchiveHeader {
+    format_version: u16,
+    endianness: Endianness,
+    strict: bool,
+    checksum: bool,
+    pointer_width: u8,
+    root_pos: u64,
+}
+
+impl ArchiveHeader {
+    /// The number of bytes in an encoded header.
+    pub const LEN: usize = 16;
+
+    /// The number of bytes in the checksum that follows archives with the checksum flag set.
+    pub const CHECKSUM_LEN: usize = 8;
+
+    /// The bytes that every header starts with.
+    pub const MAGIC: [u8; 4] = *b"rkyv";
+
+    /// The format version of archives written by this build of rkyv.
+    ///
+    /// This is incremented whenever the archived layout of a type changes incompatibly.
+    pub const FORMAT_VERSION: u16 = 1;
+
+    /// Creates a header for an archive written by this build of rkyv with the given root
+    /// position.
+    #[inline]
+    pub fn new(root_pos: usize) -> Self {
+        Self {
+            format_version: Self::FORMAT_VERSION,
+            endianness: Endianness::ARCHIVE,
+            strict: cfg!(feature = "strict"),
+            checksum: false,
+            pointer_width: mem::size_of::<FixedUsize>() as u8,
+            root_pos: root_pos as u64,
+        }
+    }
+
+    /// Sets the checksum flag of the header.
+    ///
+    /// The archive written after the header must then be followed by its
+    /// [`checksum`](ArchiveHeader::checksum). See the [module-level documentation](self#checksums)
+    /// for more details.
+    #[inline]
+    pub fn with_checksum(mut self) -> Self {
+        self.checksum = true;
+        self
+    }
+
+    /// Returns the format version of the archive.
+    #[inline]
+    pub fn format_version(&self) -> u16 {
+        self.format_version
+    }
+
+    /// Returns the endianness of the archive.
+    #[inline]
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// Returns whether the archive was written with `strict` layouts.
+    #[inline]
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns whether the archive is followed by a checksum.
+    #[inline]
+    pub fn has_checksum(&self) -> bool {
+        self.checksum
+    }
+
+    /// Returns the width of the archive's pointers and sizes in bytes.
+    #[inline]
+    pub fn pointer_width(&self) -> u8 {
+        self.pointer_width
+    }
+
+    /// Returns the position of the root object relative to the start of the archive.
+    ///
+    /// This may be truncated on targets where `usize` is narrower than the root position. Headers
+    /// returned from [`split`](ArchiveHeader::split) always have a root position that fits.
+    #[inline]
+    pub fn root_pos(&self) -> usize {
+        self.root_pos as usize
+    }
+
+    /// Encodes the header as bytes.
+    #[inline]
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut flags = 0;
+        if self.endianness == Endianness::Big {
+            flags |= FLAG_BIG_ENDIAN;
+        }
+        if self.strict {
+            flags |= FLAG_STRICT;
+        }
+        if self.checksum {
+            flags |= FLAG_CHECKSUM;
+        }
+
+        let mut bytes = [0; Self::LEN];
+        bytes[0..4].copy_from_slice(&Self::MAGIC);
+        bytes[4..6].copy_from_slice(&self.format_version.to_le_bytes());
+        bytes[6] = flags;
+        bytes[7] = self.pointer_width;
+        bytes[8..16].copy_from_slice(&self.root_pos.to_le_bytes());
+        bytes
+    }
+
+    /// Decodes a header from the start of the given bytes.
+    ///
+    /// This only checks that the bytes are a header. Any header written by any version of rkyv
+    /// can be decoded, even if the archive it describes can't be read by this build of rkyv. Use
+    /// [`check_compatible`](ArchiveHeader::check_compatible) to check whether it can be read.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HeaderError> {
+        if bytes.len() < Self::LEN {
+            return Err(HeaderError::TooShort(bytes.len()));
+        }
+
+        let mut magic = [0; 4];
+        magic.copy_from_slice(&bytes[0..4]);
+        if magic != Self::MAGIC {
+            return Err(HeaderError::InvalidMagic(magic));
+        }
+
+        let mut format_version = [0; 2];
+        format_version.copy_from_slice(&bytes[4..6]);
+        let mut root_pos = [0; 8];
+        root_pos.copy_from_slice(&bytes[8..16]);
+        let flags = bytes[6];
+
+        Ok(Self {
+            format_version: u16::from_le_bytes(format_version),
+            endianness: if flags & FLAG_BIG_ENDIAN != 0 {
+                Endianness::Big
+            } else {
+                Endianness::Little
+            },
+            strict: flags & FLAG_STRICT != 0,
+            checksum: flags & FLAG_CHECKSUM != 0,
+            pointer_width: bytes[7],
+            root_pos: u64::from_le_bytes(root_pos),
+        })
+    }
+
+    /// Checks whether the archive described by the header can be read by this build of rkyv.
+    ///
+    /// The format version, endianness, pointer width, and `strict` setting must all match the ones
+    /// that this build of rkyv writes.
+    pub fn check_compatible(&self) -> Result<(), HeaderError> {
+        let expected = Self::new(self.root_pos as usize);
+        if self.format_version != expected.format_version {
+            Err(HeaderError::UnsupportedVersion(self.format_version))
+        } else if self.endianness != expected.endianness {
+            Err(HeaderError::EndiannessMismatch(self.endianness))
+        } else if self.pointer_width != expected.pointer_width {
+            Err(HeaderError::PointerWidthMismatch(self.pointer_width))
+        } else if self.strict != expected.strict {
+            Err(HeaderError::StrictMismatch(self.strict))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Computes the checksum of this header followed by the given archive.
+    ///
+    /// The checksum is computed over the encoded header, so it changes if any of the fields of the
+    /// header do. See the [module-level documentation](self#checksums) for the algorithm.
+    pub fn checksum(&self, archive: &[u8]) -> u64 {
+        use core::hash::Hasher;
+
+        let mut hasher = seahash::SeaHasher::new();
+        hasher.write(&self.to_bytes());
+        hasher.write(archive);
+        hasher.finish()
+    }
+
+    /// Decodes the header at the start of the given bytes and returns it along with the archive
+    /// that follows it.
+    ///
+    /// If the header has the checksum flag set, the checksum at the end of the bytes is verified
+    /// first and is not part of the returned archive. The header must then be
+    /// [compatible](ArchiveHeader::check_compatible) with this build of rkyv and its root position
+    /// must be within the archive. The archive can then be accessed with
+    /// [`archived_value`](crate::archived_value) or checked with `check_archived_value` at the
+    /// header's root position.
+    pub fn split(bytes: &[u8]) -> Result<(Self, &[u8]), HeaderError> {
+        let header = Self::from_bytes(bytes)?;
+        // Flags that aren't known to this version may change the meaning of the archive
+        if bytes[6] & !(FLAG_BIG_ENDIAN | FLAG_STRICT | FLAG_CHECKSUM) != 0 {
+            return Err(HeaderError::UnknownFlags(bytes[6]));
+        }
+
+        let archive = if header.checksum {
+            if bytes.len() < Self::LEN + Self::CHECKSUM_LEN {
+                return Err(HeaderError::MissingChecksum(bytes.len()));
+            }
+            let (archive, trailer) =
+                bytes[Self::LEN..].split_at(bytes.len() - Self::LEN - Self::CHECKSUM_LEN);
+            let mut expected = [0; Self::CHECKSUM_LEN];
+            expected.copy_from_slice(trailer);
+            let expected = u64::from_le_bytes(expected);
+            let actual = header.checksum(archive);
+            if expected != actual {
+                return Err(HeaderError::ChecksumMismatch { expected, actual });
+            }
+            archive
+        } else {
+            &bytes[Self::LEN..]
+        };
+
+        header.check_compatible()?;
+
+        match usize::try_from(header.root_pos) {
+            Ok(root_pos) if root_pos <= archive.len() => Ok((header, archive)),
+            _ => Err(HeaderError::RootOutOfBounds {
+                root_pos: header.root_pos,
+                len: archive.len(),
+            }),
+        }
+    }
+
+    /// Writes the encoded header to the given writer.
+    ///
+    /// The archive should be written immediately after the header.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn write<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+/// Accesses the archived root of the given bytes, which start with an [`ArchiveHeader`].
+///
+/// Returns an error if the header can't be read or the archive isn't compatible with this build
+/// of rkyv. See [`ArchiveHeader::split`] for more details.
+///
+/// # Safety
+///
+/// The archive after the header must contain a valid archived `T` at the root position of the
+/// header, and must be aligned for it.
+#[inline]
+pub unsafe fn archived_root_with_header<T: Archive + ?Sized>(
+    bytes: &[u8],
+) -> Result<&T::Archived, HeaderError> {
+    let (header, archive) = ArchiveHeader::split(bytes)?;
+    Ok(crate::archived_value::<T>(archive, header.root_pos()))
+}
+
+/// An error that occurred while checking an archive that starts with an [`ArchiveHeader`].
+#[cfg(feature = "validation")]
+#[derive(Debug)]
+pub enum CheckHeaderError<E> {
+    /// The header couldn't be read, the archive isn't compatible, or its checksum didn't match
+    HeaderError(HeaderError),
+    /// The archive was read but failed validation
+    CheckError(E),
+}
+
+#[cfg(feature = "validation")]
+impl<E: fmt::Display> fmt::Display for CheckHeaderError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckHeaderError::HeaderError(e) => write!(f, "header error: {}", e),
+            CheckHeaderError::CheckError(e) => write!(f, "check error: {}", e),
+        }
+    }
+}
+
+#[cfg(all(feature = "validation", feature = "std"))]
+impl<E: std::error::Error + 'static> std::error::Error for CheckHeaderError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CheckHeaderError::HeaderError(e) => Some(e as &dyn std::error::Error),
+            CheckHeaderError::CheckError(e) => Some(e as &dyn std::error::Error),
+        }
+    }
+}
+
+/// Checks the archived root of the given bytes, which start with an [`ArchiveHeader`].
+///
+/// This is a safe alternative to [`archived_root_with_header`]. The header is read and checked
+/// with [`ArchiveHeader::split`], which also verifies the checksum of the archive if the header
+/// has one. Only then is the archive checked with
+/// [`check_archived_value`](crate::check_archived_value) at the header's root position, so
+/// corrupted archives are rejected before any of their relative pointers are followed.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::header::{
+///     check_archived_root_with_header, to_bytes_with_checksum, CheckHeaderError, HeaderError,
+/// };
+///
+/// let mut bytes = to_bytes_with_checksum::<_, 256>(&"hello world".to_string()).unwrap();
+/// let archived = check_archived_root_with_header::<String>(&bytes).unwrap();
+/// assert_eq!(archived, "hello world");
+///
+/// // Corrupt the length of the string so that it points past the end of the archive
+/// let len = bytes.len();
+/// bytes[len - 12] = 0xff;
+/// assert!(matches!(
+///     check_archived_root_with_header::<String>(&bytes),
+///     Err(CheckHeaderError::HeaderError(HeaderError::ChecksumMismatch { .. }))
+/// ));
+/// ```
+#[cfg(feature = "validation")]
+pub fn check_archived_root_with_header<'a, T: Archive>(
+    bytes: &'a [u8],
+) -> Result<&'a T::Archived, CheckHeaderError<CheckTypeError<T::Archived, DefaultValidator<'a>>>>
+where
+    T::Archived: CheckBytes<DefaultValidator<'a>>,
+{
+    let (header, archive) = ArchiveHeader::split(bytes).map_err(CheckHeaderError::HeaderError)?;
+    crate::check_archived_value::<T>(archive, header.root_pos())
+        .map_err(CheckHeaderError::CheckError)
+}
+
+/// Serializes the given value and returns the bytes of the archive, starting with an
+/// [`ArchiveHeader`].
+///
+/// This uses an [`AllocSerializer`] with `N` bytes of scratch space. The bytes can be accessed with
+/// [`archived_root_with_header`].
+///
+/// # Example
+///
+/// ```
+/// use rkyv::header::{archived_root_with_header, to_bytes_with_header, ArchiveHeader};
+///
+/// let bytes = to_bytes_with_header::<_, 256>(&vec![1, 2, 3]).unwrap();
+/// assert_eq!(&bytes[0..4], &ArchiveHeader::MAGIC);
+///
+/// let archived = unsafe { archived_root_with_header::<Vec<i32>>(&bytes).unwrap() };
+/// assert_eq!(archived.len(), 3);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn to_bytes_with_header<T, const N: usize>(
+    value: &T,
+) -> Result<AlignedVec, <AllocSerializer<N> as Fallible>::Error>
+where
+    T: Serialize<AllocSerializer<N>>,
+{
+    // Reserve space for the header so that the archive is written right after it
+    let mut bytes = AlignedVec::new();
+    bytes.extend_from_slice(&[0; ArchiveHeader::LEN]);
+
+    let mut serializer = AllocSerializer::<N>::new(
+        crate::ser::serializers::AlignedSerializer::new(bytes),
+        Default::default(),
+        Default::default(),
+    );
+    let pos = serializer.serialize_value(value)?;
+    let mut bytes = serializer.into_serializer().into_inner();
+
+    let header = ArchiveHeader::new(pos - ArchiveHeader::LEN);
+    bytes[..ArchiveHeader::LEN].copy_from_slice(&header.to_bytes());
+    Ok(bytes)
+}
+
+/// Serializes the given value and returns the bytes of the archive, starting with an
+/// [`ArchiveHeader`] and ending with a checksum.
+///
+/// This is like [`to_bytes_with_header`], but the header has the checksum flag set and the
+/// checksum is appended to the archive. See the [module-level documentation](self#checksums) for
+/// the layout.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::header::{archived_root_with_header, to_bytes_with_checksum, HeaderError};
+///
+/// let mut bytes = to_bytes_with_checksum::<_, 256>(&vec![1, 2, 3]).unwrap();
+/// let archived = unsafe { archived_root_with_header::<Vec<i32>>(&bytes).unwrap() };
+/// assert_eq!(archived.len(), 3);
+///
+/// // Flipping any bit of the header or archive is caught before the archive is accessed
+/// bytes[20] ^= 1;
+/// assert!(matches!(
+///     unsafe { archived_root_with_header::<Vec<i32>>(&bytes) },
+///     Err(HeaderError::ChecksumMismatch { .. })
+/// ));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn to_bytes_with_checksum<T, const N: usize>(
+    value: &T,
+) -> Result<AlignedVec, <AllocSerializer<N> as Fallible>::Error>
+where
+    T: Serialize<AllocSerializer<N>>,
+{
+    let mut bytes = to_bytes_with_header::<T, N>(value)?;
+
+    let header = ArchiveHeader::from_bytes(&bytes)
+        .expect("failed to read back archive header")
+        .with_checksum();
+    bytes[..ArchiveHeader::LEN].copy_from_slice(&header.to_bytes());
+    let checksum = header.checksum(&bytes[ArchiveHeader::LEN..]);
+    bytes.extend_from_slice(&checksum.to_le_bytes());
+    Ok(bytes)
+}
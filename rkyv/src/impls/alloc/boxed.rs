@@ -5,7 +5,7 @@ use crate::{
 };
 #[cfg(not(feature = "std"))]
 use ::alloc::{alloc, boxed::Box};
-use ::core::cmp;
+use ::core::{cmp, pin::Pin};
 #[cfg(feature = "std")]
 use ::std::alloc;
 
@@ -58,3 +58,56 @@ impl<T: ArchivePointee + PartialOrd<U> + ?Sized, U: ?Sized> PartialOrd<Box<U>> f
         self.get().partial_cmp(other.as_ref())
     }
 }
+
+// Pin<Box<T>>
+
+// Archives are immutable once written, so pinning has nothing to protect in the archived form.
+// `Pin<Box<T>>` archives exactly like `Box<T>` and is pinned again when it is deserialized.
+
+impl<T: ArchiveUnsized + ?Sized> Archive for Pin<Box<T>> {
+    type Archived = ArchivedBox<T::Archived>;
+    type Resolver = BoxResolver<T::MetadataResolver>;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedBox::resolve_from_ref(self.as_ref().get_ref(), pos, resolver, out);
+    }
+}
+
+impl<T: SerializeUnsized<S> + ?Sized, S: Fallible + ?Sized> Serialize<S> for Pin<Box<T>> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedBox::serialize_from_ref(self.as_ref().get_ref(), serializer)
+    }
+}
+
+impl<T, D> Deserialize<Pin<Box<T>>, D> for ArchivedBox<T::Archived>
+where
+    T: ArchiveUnsized + ?Sized,
+    T::Archived: DeserializeUnsized<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Pin<Box<T>>, D::Error> {
+        let boxed: Box<T> = self.deserialize(deserializer)?;
+        Ok(Box::into_pin(boxed))
+    }
+}
+
+impl<T: ArchivePointee + PartialEq<U> + ?Sized, U: ?Sized> PartialEq<Pin<Box<U>>>
+    for ArchivedBox<T>
+{
+    #[inline]
+    fn eq(&self, other: &Pin<Box<U>>) -> bool {
+        self.get().eq(other.as_ref().get_ref())
+    }
+}
+
+impl<T: ArchivePointee + PartialOrd<U> + ?Sized, U: ?Sized> PartialOrd<Pin<Box<U>>>
+    for ArchivedBox<T>
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Pin<Box<U>>) -> Option<cmp::Ordering> {
+        self.get().partial_cmp(other.as_ref().get_ref())
+    }
+}
@@ -1,10 +1,12 @@
 use crate::{
     collections::btree_map::{ArchivedBTreeMap, BTreeMapResolver},
+    de::AllocationBudget,
     ser::Serializer,
-    Archive, Deserialize, Fallible, Serialize,
+    Archive, Deserialize, Serialize,
 };
 #[cfg(not(feature = "std"))]
 use alloc::collections::BTreeMap;
+use core::alloc::Layout;
 #[cfg(feature = "std")]
 use std::collections::BTreeMap;
 
@@ -31,7 +33,7 @@ where
     }
 }
 
-impl<K: Archive + Ord, V: Archive, D: Fallible + ?Sized> Deserialize<BTreeMap<K, V>, D>
+impl<K: Archive + Ord, V: Archive, D: AllocationBudget + ?Sized> Deserialize<BTreeMap<K, V>, D>
     for ArchivedBTreeMap<K::Archived, V::Archived>
 where
     K::Archived: Deserialize<K, D> + Ord,
@@ -41,6 +43,7 @@ where
     fn deserialize(&self, deserializer: &mut D) -> Result<BTreeMap<K, V>, D::Error> {
         let mut result = BTreeMap::new();
         for (key, value) in self.iter() {
+            deserializer.reserve_allocation(Layout::new::<(K, V)>())?;
             result.insert(
                 key.deserialize(deserializer)?,
                 value.deserialize(deserializer)?,
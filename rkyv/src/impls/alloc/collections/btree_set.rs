@@ -1,10 +1,12 @@
 use crate::{
     collections::btree_set::{ArchivedBTreeSet, BTreeSetResolver},
+    de::AllocationBudget,
     ser::Serializer,
-    Archive, Deserialize, Fallible, Serialize,
+    Archive, Deserialize, Serialize,
 };
 #[cfg(not(feature = "std"))]
 use alloc::collections::BTreeSet;
+use core::alloc::Layout;
 #[cfg(feature = "std")]
 use std::collections::BTreeSet;
 
@@ -35,12 +37,13 @@ impl<K, D> Deserialize<BTreeSet<K>, D> for ArchivedBTreeSet<K::Archived>
 where
     K: Archive + Ord,
     K::Archived: Deserialize<K, D> + Ord,
-    D: Fallible + ?Sized,
+    D: AllocationBudget + ?Sized,
 {
     #[inline]
     fn deserialize(&self, deserializer: &mut D) -> Result<BTreeSet<K>, D::Error> {
         let mut result = BTreeSet::new();
         for k in self.iter() {
+            deserializer.reserve_allocation(Layout::new::<K>())?;
             result.insert(k.deserialize(deserializer)?);
         }
         Ok(result)
@@ -1,9 +1,11 @@
 use crate::{
+    de::AllocationBudget,
     string::{ArchivedString, StringResolver},
     Archive, Deserialize, DeserializeUnsized, Fallible, Serialize, SerializeUnsized,
 };
 #[cfg(not(feature = "std"))]
 use ::alloc::string::{String, ToString};
+use core::alloc::Layout;
 
 impl Archive for String {
     type Archived = ArchivedString;
@@ -25,12 +27,13 @@ where
     }
 }
 
-impl<D: Fallible + ?Sized> Deserialize<String, D> for ArchivedString
+impl<D: AllocationBudget + ?Sized> Deserialize<String, D> for ArchivedString
 where
     str: DeserializeUnsized<str, D>,
 {
     #[inline]
-    fn deserialize(&self, _: &mut D) -> Result<String, D::Error> {
+    fn deserialize(&self, deserializer: &mut D) -> Result<String, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<u8>(self.len()).unwrap())?;
         Ok(self.as_str().to_string())
     }
 }
@@ -0,0 +1,28 @@
+use crate::{cmp::ArchivedOrdering, Archive, ArchiveFixedSize, Deserialize, Fallible, Serialize};
+use core::cmp::Ordering;
+
+impl Archive for Ordering {
+    type Archived = ArchivedOrdering;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        out.write(ArchivedOrdering::from(*self));
+    }
+}
+
+unsafe impl ArchiveFixedSize for Ordering {}
+
+impl<S: Fallible + ?Sized> Serialize<S> for Ordering {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Ordering, D> for ArchivedOrdering {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Ordering, D::Error> {
+        Ok(self.as_ordering())
+    }
+}
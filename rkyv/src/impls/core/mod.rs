@@ -1,17 +1,21 @@
 #[cfg(feature = "copy")]
 use crate::copy::ArchiveCopyOptimize;
 use crate::{
+    de::AllocationBudget,
     ser::{ScratchSpace, Serializer},
-    Archive, ArchivePointee, ArchiveUnsized, Archived, ArchivedMetadata, Deserialize,
-    DeserializeUnsized, Fallible, FixedUsize, Serialize, SerializeUnsized,
+    Archive, ArchiveFixedSize, ArchivePointee, ArchiveUnsized, Archived, ArchivedMetadata,
+    Deserialize, DeserializeUnsized, Fallible, FixedUsize, Serialize, SerializeUnsized,
 };
 use core::{alloc::Layout, ptr, str};
 use ptr_meta::Pointee;
 
+pub mod cmp;
+pub mod num;
 pub mod ops;
 pub mod option;
 pub mod primitive;
 pub mod result;
+pub mod sync;
 pub mod time;
 
 impl<T> ArchivePointee for T {
@@ -48,7 +52,7 @@ impl<T: Serialize<S>, S: Serializer + ?Sized> SerializeUnsized<S> for T {
     }
 }
 
-impl<T: Archive, D: Fallible + ?Sized> DeserializeUnsized<T, D> for T::Archived
+impl<T: Archive, D: AllocationBudget + ?Sized> DeserializeUnsized<T, D> for T::Archived
 where
     T::Archived: Deserialize<T, D>,
 {
@@ -64,6 +68,7 @@ where
         if layout.size() == 0 {
             Ok(ptr::NonNull::<T>::dangling().as_ptr().cast())
         } else {
+            deserializer.reserve_allocation(layout)?;
             let ptr = alloc(layout).cast::<T>();
             ptr.write(deserialized);
             Ok(ptr.cast())
@@ -98,6 +103,8 @@ macro_rules! impl_tuple {
             }
         }
 
+        unsafe impl<$($type: ArchiveFixedSize),+> ArchiveFixedSize for ($($type,)+) {}
+
         impl<$($type: Serialize<S>),+, S: Fallible + ?Sized> Serialize<S> for ($($type,)+) {
             #[inline]
             fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
@@ -143,6 +150,8 @@ impl<T: Archive, const N: usize> Archive for [T; N] {
     }
 }
 
+unsafe impl<T: ArchiveFixedSize, const N: usize> ArchiveFixedSize for [T; N] {}
+
 impl<T: Serialize<S>, S: Fallible + ?Sized, const N: usize> Serialize<S> for [T; N] {
     #[inline]
     fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
@@ -256,13 +265,15 @@ where
     }
 }
 
-impl<T: Deserialize<U, D>, U, D: Fallible + ?Sized> DeserializeUnsized<[U], D> for [T] {
+impl<T: Deserialize<U, D>, U, D: AllocationBudget + ?Sized> DeserializeUnsized<[U], D> for [T] {
     default! {
         unsafe fn deserialize_unsized(&self, deserializer: &mut D, mut alloc: impl FnMut(Layout) -> *mut u8) -> Result<*mut (), D::Error> {
             if self.is_empty() || core::mem::size_of::<U>() == 0 {
                 Ok(ptr::NonNull::<U>::dangling().as_ptr().cast())
             } else {
-                let result = alloc(Layout::array::<U>(self.len()).unwrap()).cast::<U>();
+                let layout = Layout::array::<U>(self.len()).unwrap();
+                deserializer.reserve_allocation(layout)?;
+                let result = alloc(layout).cast::<U>();
                 for (i, item) in self.iter().enumerate() {
                     result.add(i).write(item.deserialize(deserializer)?);
                 }
@@ -284,17 +295,19 @@ impl<T, U, D> DeserializeUnsized<[U], D> for [T]
 where
     T: Deserialize<U, D>,
     U: ArchiveCopyOptimize,
-    D: Fallible + ?Sized,
+    D: AllocationBudget + ?Sized,
 {
     unsafe fn deserialize_unsized(
         &self,
-        _: &mut D,
+        deserializer: &mut D,
         mut alloc: impl FnMut(Layout) -> *mut u8,
     ) -> Result<*mut (), D::Error> {
         if self.is_empty() || core::mem::size_of::<T>() == 0 {
             Ok(ptr::NonNull::<U>::dangling().as_ptr().cast())
         } else {
-            let result = alloc(Layout::array::<T>(self.len()).unwrap()).cast::<T>();
+            let layout = Layout::array::<T>(self.len()).unwrap();
+            deserializer.reserve_allocation(layout)?;
+            let result = alloc(layout).cast::<T>();
             ptr::copy_nonoverlapping(self.as_ptr(), result, self.len());
             Ok(result.cast())
         }
@@ -337,7 +350,9 @@ impl<S: Serializer + ?Sized> SerializeUnsized<S> for str {
     #[inline]
     fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, S::Error> {
         let result = serializer.pos();
+        serializer.begin_type("str");
         serializer.write(self.as_bytes())?;
+        serializer.end_type();
         Ok(result)
     }
 
@@ -347,17 +362,21 @@ impl<S: Serializer + ?Sized> SerializeUnsized<S> for str {
     }
 }
 
-impl<D: Fallible + ?Sized> DeserializeUnsized<str, D> for <str as ArchiveUnsized>::Archived {
+impl<D: AllocationBudget + ?Sized> DeserializeUnsized<str, D>
+    for <str as ArchiveUnsized>::Archived
+{
     #[inline]
     unsafe fn deserialize_unsized(
         &self,
-        _: &mut D,
+        deserializer: &mut D,
         mut alloc: impl FnMut(Layout) -> *mut u8,
     ) -> Result<*mut (), D::Error> {
         if self.is_empty() {
             Ok(ptr::null_mut())
         } else {
-            let bytes = alloc(Layout::array::<u8>(self.len()).unwrap());
+            let layout = Layout::array::<u8>(self.len()).unwrap();
+            deserializer.reserve_allocation(layout)?;
+            let bytes = alloc(layout);
             ptr::copy_nonoverlapping(self.as_ptr(), bytes, self.len());
             Ok(bytes.cast())
         }
@@ -0,0 +1,28 @@
+use crate::{num::ArchivedFpCategory, Archive, ArchiveFixedSize, Deserialize, Fallible, Serialize};
+use core::num::FpCategory;
+
+impl Archive for FpCategory {
+    type Archived = ArchivedFpCategory;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        out.write(ArchivedFpCategory::from(*self));
+    }
+}
+
+unsafe impl ArchiveFixedSize for FpCategory {}
+
+impl<S: Fallible + ?Sized> Serialize<S> for FpCategory {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<FpCategory, D> for ArchivedFpCategory {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<FpCategory, D::Error> {
+        Ok(self.as_fp_category())
+    }
+}
@@ -1,11 +1,14 @@
 use crate::{
     ops::{
-        ArchivedRange, ArchivedRangeFrom, ArchivedRangeInclusive, ArchivedRangeTo,
+        ArchivedBound, ArchivedRange, ArchivedRangeFrom, ArchivedRangeInclusive, ArchivedRangeTo,
         ArchivedRangeToInclusive,
     },
-    Archive, Archived, Deserialize, Fallible, Serialize,
+    Archive, ArchiveFixedSize, Archived, Deserialize, Fallible, Serialize,
+};
+use core::{
+    ops::{Bound, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive},
+    ptr,
 };
-use core::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 
 // RangeFull
 
@@ -247,3 +250,133 @@ impl<T, U: PartialEq<T>> PartialEq<RangeToInclusive<T>> for ArchivedRangeToInclu
         self.end.eq(&other.end)
     }
 }
+
+// Bound
+
+#[allow(dead_code)]
+#[repr(u8)]
+enum ArchivedBoundTag {
+    Included,
+    Excluded,
+    Unbounded,
+}
+
+#[repr(C)]
+struct ArchivedBoundVariantValue<T>(ArchivedBoundTag, T);
+
+#[repr(C)]
+struct ArchivedBoundVariantUnbounded(ArchivedBoundTag);
+
+impl<T> ArchivedBound<T> {
+    /// Resolves an archived bound from a reference to a bound.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing `bound`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` and `resolver` are different kinds of bounds.
+    #[inline]
+    pub unsafe fn resolve_from_bound<U: Archive<Archived = T>>(
+        bound: Bound<&U>,
+        pos: usize,
+        resolver: Bound<U::Resolver>,
+        out: *mut Self,
+    ) {
+        let (tag, value, resolver) = match (bound, resolver) {
+            (Bound::Included(value), Bound::Included(resolver)) => {
+                (ArchivedBoundTag::Included, value, resolver)
+            }
+            (Bound::Excluded(value), Bound::Excluded(resolver)) => {
+                (ArchivedBoundTag::Excluded, value, resolver)
+            }
+            (Bound::Unbounded, Bound::Unbounded) => {
+                let out = out.cast::<ArchivedBoundVariantUnbounded>();
+                ptr::addr_of_mut!((*out).0).write(ArchivedBoundTag::Unbounded);
+                return;
+            }
+            _ => panic!("bound resolver does not match the bound"),
+        };
+
+        let out = out.cast::<ArchivedBoundVariantValue<T>>();
+        ptr::addr_of_mut!((*out).0).write(tag);
+        let (fp, fo) = out_field!(out.1);
+        value.resolve(pos + fp, resolver, fo);
+    }
+
+    /// Serializes the value of a reference to a bound and returns the resolver for the bound.
+    #[inline]
+    pub fn serialize_from_bound<U, S>(
+        bound: Bound<&U>,
+        serializer: &mut S,
+    ) -> Result<Bound<U::Resolver>, S::Error>
+    where
+        U: Serialize<S, Archived = T>,
+        S: Fallible + ?Sized,
+    {
+        Ok(match bound {
+            Bound::Included(value) => Bound::Included(value.serialize(serializer)?),
+            Bound::Excluded(value) => Bound::Excluded(value.serialize(serializer)?),
+            Bound::Unbounded => Bound::Unbounded,
+        })
+    }
+}
+
+impl<T: Archive> Archive for Bound<T> {
+    type Archived = ArchivedBound<T::Archived>;
+    type Resolver = Bound<T::Resolver>;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let bound = match self {
+            Bound::Included(value) => Bound::Included(value),
+            Bound::Excluded(value) => Bound::Excluded(value),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        ArchivedBound::resolve_from_bound(bound, pos, resolver, out);
+    }
+}
+
+unsafe impl<T: ArchiveFixedSize> ArchiveFixedSize for Bound<T> {}
+
+impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for Bound<T> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let bound = match self {
+            Bound::Included(value) => Bound::Included(value),
+            Bound::Excluded(value) => Bound::Excluded(value),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        ArchivedBound::serialize_from_bound(bound, serializer)
+    }
+}
+
+impl<T, D> Deserialize<Bound<T>, D> for ArchivedBound<T::Archived>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Bound<T>, D::Error> {
+        Ok(match self {
+            ArchivedBound::Included(value) => Bound::Included(value.deserialize(deserializer)?),
+            ArchivedBound::Excluded(value) => Bound::Excluded(value.deserialize(deserializer)?),
+            ArchivedBound::Unbounded => Bound::Unbounded,
+        })
+    }
+}
+
+impl<T, U: PartialEq<T>> PartialEq<Bound<T>> for ArchivedBound<U> {
+    #[inline]
+    fn eq(&self, other: &Bound<T>) -> bool {
+        match (self, other) {
+            (ArchivedBound::Included(a), Bound::Included(b))
+            | (ArchivedBound::Excluded(a), Bound::Excluded(b)) => a.eq(b),
+            (ArchivedBound::Unbounded, Bound::Unbounded) => true,
+            _ => false,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{option::ArchivedOption, Archive, Deserialize, Fallible, Serialize};
+use crate::{option::ArchivedOption, Archive, ArchiveFixedSize, Deserialize, Fallible, Serialize};
 use core::{hint::unreachable_unchecked, ptr};
 
 #[allow(dead_code)]
@@ -42,6 +42,8 @@ impl<T: Archive> Archive for Option<T> {
     }
 }
 
+unsafe impl<T: ArchiveFixedSize> ArchiveFixedSize for Option<T> {}
+
 impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for Option<T> {
     #[inline]
     fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
@@ -1,4 +1,7 @@
-use crate::{Archive, Archived, Deserialize, Fallible, FixedIsize, FixedUsize, Serialize};
+use crate::{
+    Archive, ArchiveFixedSize, ArchivePrimitive, Archived, Deserialize, Fallible, FixedIsize,
+    FixedUsize, Serialize,
+};
 #[cfg(has_atomics)]
 use core::sync::atomic::{
     AtomicBool, AtomicI16, AtomicI32, AtomicI8, AtomicIsize, AtomicU16, AtomicU32, AtomicU8,
@@ -36,6 +39,15 @@ macro_rules! impl_primitive {
 
         impl_primitive!(@serialize $type);
 
+        unsafe impl ArchiveFixedSize for $type {}
+
+        unsafe impl ArchivePrimitive for $type {
+            #[inline]
+            fn archive_primitive(&self) -> Self::Archived {
+                *self
+            }
+        }
+
         impl<D: Fallible + ?Sized> Deserialize<$type, D> for Archived<$type> {
             #[inline]
             fn deserialize(&self, _: &mut D) -> Result<$type, D::Error> {
@@ -64,6 +76,15 @@ macro_rules! impl_primitive {
 
             impl_primitive!(@serialize $type);
 
+            unsafe impl ArchiveFixedSize for $type {}
+
+            unsafe impl ArchivePrimitive for $type {
+                #[inline]
+                fn archive_primitive(&self) -> Self::Archived {
+                    to_archived!(*self as Self)
+                }
+            }
+
             impl<D: Fallible + ?Sized> Deserialize<$type, D> for Archived {
                 #[inline]
                 fn deserialize(&self, _: &mut D) -> Result<$type, D::Error> {
@@ -183,6 +204,8 @@ impl_atomic!(@multibyte AtomicU64, u64);
 
 // PhantomData
 
+unsafe impl<T: ?Sized> ArchiveFixedSize for PhantomData<T> {}
+
 impl<T: ?Sized> Archive for PhantomData<T> {
     type Archived = PhantomData<T>;
     type Resolver = ();
@@ -206,6 +229,8 @@ impl<T: ?Sized, D: Fallible + ?Sized> Deserialize<PhantomData<T>, D> for Phantom
 }
 
 // PhantomPinned
+unsafe impl ArchiveFixedSize for PhantomPinned {}
+
 impl Archive for PhantomPinned {
     type Archived = PhantomPinned;
     type Resolver = ();
@@ -230,6 +255,8 @@ impl<D: Fallible + ?Sized> Deserialize<PhantomPinned, D> for PhantomPinned {
 
 // usize
 
+unsafe impl ArchiveFixedSize for usize {}
+
 impl Archive for usize {
     type Archived = Archived<FixedUsize>;
     type Resolver = ();
@@ -240,6 +267,13 @@ impl Archive for usize {
     }
 }
 
+unsafe impl ArchivePrimitive for usize {
+    #[inline]
+    fn archive_primitive(&self) -> Self::Archived {
+        to_archived!(*self as FixedUsize)
+    }
+}
+
 impl<S: Fallible + ?Sized> Serialize<S> for usize {
     #[inline]
     fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
@@ -256,6 +290,8 @@ impl<D: Fallible + ?Sized> Deserialize<usize, D> for Archived<usize> {
 
 // isize
 
+unsafe impl ArchiveFixedSize for isize {}
+
 impl Archive for isize {
     type Archived = Archived<FixedIsize>;
     type Resolver = ();
@@ -266,6 +302,13 @@ impl Archive for isize {
     }
 }
 
+unsafe impl ArchivePrimitive for isize {
+    #[inline]
+    fn archive_primitive(&self) -> Self::Archived {
+        to_archived!(*self as FixedIsize)
+    }
+}
+
 impl<S: Fallible + ?Sized> Serialize<S> for isize {
     #[inline]
     fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
@@ -284,6 +327,8 @@ impl<D: Fallible + ?Sized> Deserialize<isize, D> for Archived<isize> {
 
 type FixedNonZeroUsize = pick_size_type!(NonZeroU16, NonZeroU32, NonZeroU64);
 
+unsafe impl ArchiveFixedSize for NonZeroUsize {}
+
 impl Archive for NonZeroUsize {
     type Archived = Archived<FixedNonZeroUsize>;
     type Resolver = ();
@@ -314,6 +359,8 @@ impl<D: Fallible + ?Sized> Deserialize<NonZeroUsize, D> for Archived<NonZeroUsiz
 
 type FixedNonZeroIsize = pick_size_type!(NonZeroI16, NonZeroI32, NonZeroI64);
 
+unsafe impl ArchiveFixedSize for NonZeroIsize {}
+
 impl Archive for NonZeroIsize {
     type Archived = Archived<FixedNonZeroIsize>;
     type Resolver = ();
@@ -0,0 +1,30 @@
+use crate::{
+    sync::ArchivedAtomicOrdering, Archive, ArchiveFixedSize, Deserialize, Fallible, Serialize,
+};
+use core::sync::atomic::Ordering;
+
+impl Archive for Ordering {
+    type Archived = ArchivedAtomicOrdering;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        out.write(ArchivedAtomicOrdering::from(*self));
+    }
+}
+
+unsafe impl ArchiveFixedSize for Ordering {}
+
+impl<S: Fallible + ?Sized> Serialize<S> for Ordering {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Ordering, D> for ArchivedAtomicOrdering {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Ordering, D::Error> {
+        Ok(self.as_ordering())
+    }
+}
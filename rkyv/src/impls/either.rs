@@ -0,0 +1,112 @@
+use crate::{either::ArchivedEither, Archive, Deserialize, Fallible, Serialize};
+use core::{hint::unreachable_unchecked, ptr};
+use either::Either;
+
+#[allow(dead_code)]
+#[repr(u8)]
+enum ArchivedEitherTag {
+    Left,
+    Right,
+}
+
+#[repr(C)]
+struct ArchivedEitherVariantLeft<L>(ArchivedEitherTag, L);
+
+#[repr(C)]
+struct ArchivedEitherVariantRight<R>(ArchivedEitherTag, R);
+
+impl<L: Archive, R: Archive> Archive for Either<L, R> {
+    type Archived = ArchivedEither<L::Archived, R::Archived>;
+    type Resolver = Either<L::Resolver, R::Resolver>;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        match resolver {
+            Either::Left(resolver) => {
+                let out = out.cast::<ArchivedEitherVariantLeft<L::Archived>>();
+                ptr::addr_of_mut!((*out).0).write(ArchivedEitherTag::Left);
+
+                let (fp, fo) = out_field!(out.1);
+                match self.as_ref() {
+                    Either::Left(left) => left.resolve(pos + fp, resolver, fo),
+                    Either::Right(_) => unreachable_unchecked(),
+                }
+            }
+            Either::Right(resolver) => {
+                let out = out.cast::<ArchivedEitherVariantRight<R::Archived>>();
+                ptr::addr_of_mut!((*out).0).write(ArchivedEitherTag::Right);
+
+                let (fp, fo) = out_field!(out.1);
+                match self.as_ref() {
+                    Either::Left(_) => unreachable_unchecked(),
+                    Either::Right(right) => right.resolve(pos + fp, resolver, fo),
+                }
+            }
+        }
+    }
+}
+
+impl<L: Serialize<S>, R: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for Either<L, R> {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(match self.as_ref() {
+            Either::Left(left) => Either::Left(left.serialize(serializer)?),
+            Either::Right(right) => Either::Right(right.serialize(serializer)?),
+        })
+    }
+}
+
+impl<L, R, D> Deserialize<Either<L, R>, D> for ArchivedEither<L::Archived, R::Archived>
+where
+    L: Archive,
+    R: Archive,
+    D: Fallible + ?Sized,
+    L::Archived: Deserialize<L, D>,
+    R::Archived: Deserialize<R, D>,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Either<L, R>, D::Error> {
+        match self {
+            ArchivedEither::Left(left) => Ok(Either::Left(left.deserialize(deserializer)?)),
+            ArchivedEither::Right(right) => Ok(Either::Right(right.deserialize(deserializer)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod rkyv_tests {
+    use crate::{
+        archived_root,
+        either::ArchivedEither,
+        ser::{serializers::AlignedSerializer, Serializer},
+        util::AlignedVec,
+        Deserialize, Infallible,
+    };
+    use either::Either;
+
+    #[test]
+    fn test_serialize_deserialize() {
+        let values: [Either<u8, bool>; 2] = [Either::Left(42), Either::Right(true)];
+
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        serializer
+            .serialize_value(&values)
+            .expect("failed to archive either");
+        let buf = serializer.into_inner();
+        let archived = unsafe { archived_root::<[Either<u8, bool>; 2]>(buf.as_ref()) };
+
+        assert_eq!(archived[0], values[0]);
+        assert_eq!(archived[1], values[1]);
+        match &archived[0] {
+            ArchivedEither::Left(left) => assert_eq!(*left, 42),
+            ArchivedEither::Right(_) => panic!("archived either was the wrong variant"),
+        }
+        assert_eq!(archived[1].right(), Some(&true));
+
+        let deserialized: [Either<u8, bool>; 2] = archived
+            .deserialize(&mut Infallible)
+            .expect("failed to deserialize either");
+
+        assert_eq!(values, deserialized);
+    }
+}
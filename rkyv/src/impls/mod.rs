@@ -13,8 +13,12 @@ mod std;
 // implementations should be moved into their respective crates over time. Before adding support for
 // another crate, please consider getting rkyv support in the crate instead.
 
+#[cfg(feature = "either")]
+mod either;
 #[cfg(feature = "indexmap")]
 mod indexmap;
+#[cfg(feature = "num-bigint")]
+mod num_bigint;
 #[cfg(feature = "smallvec")]
 mod smallvec;
 #[cfg(feature = "tinyvec")]
@@ -0,0 +1,141 @@
+use crate::{
+    bigint::{ArchivedBigInt, ArchivedBigUint, ArchivedSign},
+    ser::Serializer,
+    vec::{ArchivedVec, VecResolver},
+    Archive, Archived, Serialize,
+};
+#[cfg(feature = "alloc")]
+use crate::{de::AllocationBudget, Deserialize};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use ::alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::alloc::Layout;
+use core::ptr;
+use num_bigint::{BigInt, BigUint};
+
+impl Archive for BigUint {
+    type Archived = ArchivedBigUint;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        let (fp, fo) = out_field!(out.digits);
+        ArchivedVec::resolve_from_len(self.iter_u32_digits().len(), pos + fp, resolver, fo);
+    }
+}
+
+impl<S: Serializer + ?Sized> Serialize<S> for BigUint {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        let pos = serializer.align_for::<Archived<u32>>()?;
+        for digit in self.iter_u32_digits() {
+            unsafe {
+                serializer.resolve_aligned(&digit, ())?;
+            }
+        }
+        Ok(VecResolver { pos })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: AllocationBudget + ?Sized> Deserialize<BigUint, D> for ArchivedBigUint {
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<BigUint, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<u32>(self.digits().len()).unwrap())?;
+        Ok(BigUint::new(self.iter_u32_digits().collect::<Vec<_>>()))
+    }
+}
+
+impl Archive for BigInt {
+    type Archived = ArchivedBigInt;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ptr::addr_of_mut!((*out).sign).write(ArchivedSign::from(self.sign()));
+        let (fp, fo) = out_field!(out.magnitude);
+        self.magnitude().resolve(pos + fp, resolver, fo);
+    }
+}
+
+impl<S: Serializer + ?Sized> Serialize<S> for BigInt {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.magnitude().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: AllocationBudget + ?Sized> Deserialize<BigInt, D> for ArchivedBigInt {
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<BigInt, D::Error> {
+        Ok(BigInt::from_biguint(
+            self.sign(),
+            self.magnitude().deserialize(deserializer)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        archived_root,
+        ser::{serializers::CoreSerializer, Serializer},
+        Deserialize, Infallible,
+    };
+    use num_bigint::{BigInt, BigUint, Sign};
+
+    #[test]
+    fn big_int() {
+        // 7^500 has more than a thousand bits
+        let magnitude = BigUint::from(7u32).pow(500);
+        let digits = magnitude.iter_u32_digits().len();
+        assert!(digits > 40);
+
+        for value in [
+            BigInt::from_biguint(Sign::Plus, magnitude.clone()),
+            BigInt::from_biguint(Sign::Minus, magnitude.clone()),
+            BigInt::from(0),
+        ] {
+            let mut serializer = CoreSerializer::<1024, 0>::default();
+            serializer.serialize_value(&value).unwrap();
+            let end = serializer.pos();
+            let result = serializer.into_serializer().into_inner();
+            #[cfg(feature = "validation")]
+            crate::check_archived_root::<BigInt>(&result[0..end]).unwrap();
+            let archived = unsafe { archived_root::<BigInt>(&result[0..end]) };
+
+            assert_eq!(archived.sign(), value.sign());
+            assert_eq!(archived.magnitude().is_zero(), value.sign() == Sign::NoSign);
+            assert_eq!(archived, &value);
+
+            #[cfg(feature = "alloc")]
+            {
+                let deserialized: BigInt = archived.deserialize(&mut Infallible).unwrap();
+                assert_eq!(deserialized, value);
+            }
+        }
+
+        let mut serializer = CoreSerializer::<1024, 0>::default();
+        serializer.serialize_value(&magnitude).unwrap();
+        let end = serializer.pos();
+        let result = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<BigUint>(&result[0..end]) };
+        assert_eq!(archived.digits().len(), digits);
+        assert_eq!(archived, &magnitude);
+
+        // The digits count against the allocation budget
+        #[cfg(feature = "alloc")]
+        {
+            use crate::de::deserializers::BudgetDeserializer;
+
+            let mut deserializer = BudgetDeserializer::new(Infallible, digits * 4);
+            let deserialized: BigUint = archived.deserialize(&mut deserializer).unwrap();
+            assert_eq!(deserialized, magnitude);
+            assert_eq!(deserializer.remaining(), 0);
+
+            let mut deserializer = BudgetDeserializer::new(Infallible, digits * 4 - 1);
+            assert!(Deserialize::<BigUint, _>::deserialize(archived, &mut deserializer).is_err());
+        }
+    }
+}
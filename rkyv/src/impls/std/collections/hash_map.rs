@@ -1,9 +1,11 @@
 use crate::{
     collections::hash_map::{ArchivedHashMap, HashMapResolver},
+    de::AllocationBudget,
     ser::{ScratchSpace, Serializer},
-    Archive, Deserialize, Fallible, Serialize,
+    Archive, Deserialize, Serialize,
 };
 use core::{
+    alloc::Layout,
     borrow::Borrow,
     hash::{BuildHasher, Hash},
 };
@@ -35,14 +37,19 @@ where
     }
 }
 
-impl<K: Archive + Hash + Eq, V: Archive, D: Fallible + ?Sized, S: Default + BuildHasher>
-    Deserialize<HashMap<K, V, S>, D> for ArchivedHashMap<K::Archived, V::Archived>
+impl<
+        K: Archive + Hash + Eq,
+        V: Archive,
+        D: AllocationBudget + ?Sized,
+        S: Default + BuildHasher,
+    > Deserialize<HashMap<K, V, S>, D> for ArchivedHashMap<K::Archived, V::Archived>
 where
     K::Archived: Deserialize<K, D> + Hash + Eq,
     V::Archived: Deserialize<V, D>,
 {
     #[inline]
     fn deserialize(&self, deserializer: &mut D) -> Result<HashMap<K, V, S>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<(K, V)>(self.len()).unwrap())?;
         let mut result = HashMap::with_capacity_and_hasher(self.len(), S::default());
         for (k, v) in self.iter() {
             result.insert(k.deserialize(deserializer)?, v.deserialize(deserializer)?);
@@ -1,9 +1,11 @@
 use crate::{
     collections::hash_set::{ArchivedHashSet, HashSetResolver},
+    de::AllocationBudget,
     ser::{ScratchSpace, Serializer},
-    Archive, Deserialize, Fallible, Serialize,
+    Archive, Deserialize, Serialize,
 };
 use core::{
+    alloc::Layout,
     borrow::Borrow,
     hash::{BuildHasher, Hash},
 };
@@ -38,13 +40,14 @@ impl<K, D, S> Deserialize<HashSet<K, S>, D> for ArchivedHashSet<K::Archived>
 where
     K: Archive + Hash + Eq,
     K::Archived: Deserialize<K, D> + Hash + Eq,
-    D: Fallible + ?Sized,
+    D: AllocationBudget + ?Sized,
     S: Default + BuildHasher,
 {
     #[inline]
     fn deserialize(&self, deserializer: &mut D) -> Result<HashSet<K, S>, D::Error> {
         let mut result = HashSet::with_hasher(S::default());
         for k in self.iter() {
+            deserializer.reserve_allocation(Layout::new::<K>())?;
             result.insert(k.deserialize(deserializer)?);
         }
         Ok(result)
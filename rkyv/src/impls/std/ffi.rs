@@ -1,4 +1,5 @@
 use crate::{
+    de::AllocationBudget,
     ffi::{ArchivedCString, CStringResolver},
     ser::Serializer,
     Archive, ArchivePointee, ArchiveUnsized, Archived, ArchivedMetadata, Deserialize,
@@ -50,15 +51,19 @@ impl<S: Serializer + ?Sized> SerializeUnsized<S> for CStr {
     }
 }
 
-impl<D: Fallible + ?Sized> DeserializeUnsized<CStr, D> for <CStr as ArchiveUnsized>::Archived {
+impl<D: AllocationBudget + ?Sized> DeserializeUnsized<CStr, D>
+    for <CStr as ArchiveUnsized>::Archived
+{
     #[inline]
     unsafe fn deserialize_unsized(
         &self,
-        _: &mut D,
+        deserializer: &mut D,
         mut alloc: impl FnMut(Layout) -> *mut u8,
     ) -> Result<*mut (), D::Error> {
         let slice = self.to_bytes_with_nul();
-        let bytes = alloc(Layout::array::<u8>(slice.len()).unwrap());
+        let layout = Layout::array::<u8>(slice.len()).unwrap();
+        deserializer.reserve_allocation(layout)?;
+        let bytes = alloc(layout);
         ptr::copy_nonoverlapping(slice.as_ptr(), bytes, slice.len());
         Ok(bytes.cast())
     }
@@ -1,3 +1,4 @@
 mod collections;
 mod ffi;
 mod net;
+mod time;
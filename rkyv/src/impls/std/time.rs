@@ -0,0 +1,37 @@
+use crate::{
+    de::InstantEpoch as DeserializeInstantEpoch,
+    ser::InstantEpoch as SerializeInstantEpoch,
+    time::{ArchivedDuration, InstantResolver},
+    Archive, Deserialize, Serialize,
+};
+use std::time::{Duration, Instant};
+
+impl Archive for Instant {
+    type Archived = ArchivedDuration;
+    type Resolver = InstantResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, _: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedDuration::emplace(
+            resolver.offset.as_secs(),
+            resolver.offset.subsec_nanos(),
+            out,
+        );
+    }
+}
+
+impl<S: SerializeInstantEpoch + ?Sized> Serialize<S> for Instant {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(InstantResolver {
+            offset: serializer.instant_offset(*self)?,
+        })
+    }
+}
+
+impl<D: DeserializeInstantEpoch + ?Sized> Deserialize<Instant, D> for ArchivedDuration {
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Instant, D::Error> {
+        deserializer.instant_from_offset(Duration::new(self.as_secs(), self.subsec_nanos()))
+    }
+}
@@ -59,10 +59,16 @@
 //!   compatibility optimized for big-endian architectures.
 //! - `archive_le`: Forces archives into a little-endian format. This guarantees cross-endian
 //!   compatibility optimized for little-endian architectures.
+//! - `checked_align`: Checks alignment invariants while serializing in release builds as well as
+//!   debug builds. Misaligned writes panic instead of silently producing a corrupt archive. This
+//!   trades a small amount of serialization speed for correctness.
 //! - `copy`: Enables copy optimizations for packed copyable data types. Requires nightly.
 //! - `copy_unsafe`: Automatically opts all potentially copyable types into copy optimization. This
 //!   broadly improves performance but may cause uninitialized bytes to be copied to the output.
 //!   Requires nightly.
+//! - `fst`: Enables archiving string-keyed sets and maps as finite state transducers with the
+//!   `AsFst` wrapper. These are much more compact than hash maps for large
+//!   dictionaries and support prefix queries.
 //! - `size_16`: Archives integral `*size` types as 16-bit integers. This is intended to be used
 //!   only for small archives and may not handle large, more general data.
 //! - `size_32`: Archives integral `*size` types as 32-bit integers. Enabled by default.
@@ -87,7 +93,9 @@
 //!
 //! Crates supported by rkyv:
 //!
+//! - [`either`](https://docs.rs/either)
 //! - [`indexmap`](https://docs.rs/indexmap)
+//! - [`num-bigint`](https://docs.rs/num-bigint)
 //! - [`rend`](https://docs.rs/rend) *Enabled automatically when using endian-specific archive
 //!   features.*
 //! - [`tinyvec`](https://docs.rs/tinyvec)
@@ -141,25 +149,37 @@ extern crate std;
 #[macro_use]
 pub mod macros;
 
+#[cfg(feature = "num-bigint")]
+pub mod bigint;
 pub mod boxed;
+pub mod bytes;
+pub mod cmp;
 pub mod collections;
+#[cfg(feature = "alloc")]
+pub mod compression;
 #[cfg(feature = "copy")]
 pub mod copy;
 pub mod de;
+#[cfg(feature = "either")]
+pub mod either;
 // This is pretty unfortunate. CStr doesn't rely on the rest of std, but it's not in core.
 // If CStr ever gets moved into `core` then this module will no longer need cfg(feature = "std")
 #[cfg(feature = "std")]
 pub mod ffi;
+pub mod header;
 mod impls;
 pub mod net;
 pub mod niche;
+pub mod num;
 pub mod ops;
 pub mod option;
 pub mod rc;
 pub mod rel_ptr;
 pub mod result;
 pub mod ser;
+pub mod size_hint;
 pub mod string;
+pub mod sync;
 pub mod time;
 pub mod util;
 #[cfg(feature = "validation")]
@@ -167,6 +187,8 @@ pub mod validation;
 pub mod vec;
 pub mod with;
 
+#[cfg(feature = "validation")]
+pub use bytecheck;
 #[cfg(feature = "rend")]
 pub use rend;
 
@@ -178,7 +200,7 @@ pub use util::*;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "validation")))]
 pub use validation::{
     check_archived_root_with_context, check_archived_value_with_context,
-    validators::{check_archived_root, check_archived_value},
+    validators::{check_archived_root, check_archived_value, deserialize_checked},
 };
 
 /// A type that can produce an error.
@@ -424,7 +446,18 @@ pub trait Serialize<S: Fallible + ?Sized>: Archive {
 ///
 /// Some types may require specific deserializer capabilities, such as `Rc` and `Arc`. In these
 /// cases, the deserializer type `D` should be bound so that it implements traits that provide those
-/// capabilities (e.g. [`SharedDeserializeRegistry`](de::SharedDeserializeRegistry)).
+/// capabilities (e.g. [`SharedDeserializeRegistry`](de::SharedDeserializeRegistry)). The
+/// deserializer is passed along to every contained value, so it also serves as a context for the
+/// whole deserialization. The standard containers (`Vec`, `Box`, `String`, and so on) always
+/// allocate through the global allocator because they can't be given a different allocator on
+/// stable Rust. Types that allocate from an arena or a custom allocator can instead require a trait
+/// on the deserializer that provides their allocator.
+///
+/// Deserializing never checks the archive. It assumes that the archived value is valid, which is
+/// true for references returned by [`archived_root`], [`archived_value`], or a validated access like
+/// `check_archived_root`. A reference returned by validated access can be deserialized directly
+/// without being checked again. To check and deserialize untrusted bytes in a single call, use
+/// `deserialize_checked` (with the `validation` feature).
 ///
 /// This can be derived with [`Deserialize`](macro@Deserialize).
 pub trait Deserialize<T, D: Fallible + ?Sized> {
@@ -432,6 +465,71 @@ pub trait Deserialize<T, D: Fallible + ?Sized> {
     fn deserialize(&self, deserializer: &mut D) -> Result<T, D::Error>;
 }
 
+/// An [`Archive`] type that does not write any dependencies when it's serialized.
+///
+/// The archived form of these types is entirely contained in `Self::Archived`, so the size of the
+/// archive they produce is known at compile time. This can be used to allocate exactly enough
+/// space for an archive up front, for example with
+/// [`AlignedSerializer::for_value`](ser::serializers::AlignedSerializer::for_value).
+///
+/// This is only implemented for types with a bounded archived size. Types like `String` and `Vec`
+/// write a variable amount of data when they're serialized and so do not implement it.
+///
+/// # Safety
+///
+/// Serializing a value of the type as the root of an archive must write at most `ARCHIVED_SIZE`
+/// bytes, and none of the data it writes may require an alignment greater than `ARCHIVED_ALIGN`.
+/// Types that write any dependencies (like an out-of-line buffer) must not implement this trait.
+///
+/// # Examples
+/// ```
+/// use rkyv::{Archive, ArchiveFixedSize, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Point {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// // SAFETY: All of the fields of `Point` implement `ArchiveFixedSize`.
+/// unsafe impl ArchiveFixedSize for Point {}
+///
+/// assert_eq!(Point::ARCHIVED_SIZE, 8);
+/// assert_eq!(Point::ARCHIVED_ALIGN, 4);
+/// ```
+pub unsafe trait ArchiveFixedSize: Archive {
+    /// The number of bytes written when serializing the type as the root of an archive.
+    const ARCHIVED_SIZE: usize = core::mem::size_of::<Self::Archived>();
+    /// The maximum alignment of any archived data written for the type.
+    const ARCHIVED_ALIGN: usize = core::mem::align_of::<Self::Archived>();
+}
+
+/// A primitive type whose archived form only depends on its value.
+///
+/// Primitives can be archived without a serializer or a position, so slices of them can be
+/// archived in batches instead of resolving each element on its own. When the archive's
+/// endianness differs from the target's, converting a batch is a single pass of byte swaps that
+/// the compiler can vectorize. See [`SwapOptimize`](with::SwapOptimize) and
+/// [`ArchivedVec::serialize_from_primitive_slice`](vec::ArchivedVec::serialize_from_primitive_slice).
+///
+/// # Examples
+/// ```
+/// use rkyv::{ArchivePrimitive, Archived};
+///
+/// let archived: Archived<u32> = 0x0102_0304u32.archive_primitive();
+/// assert_eq!(archived, 0x0102_0304);
+/// ```
+///
+/// # Safety
+///
+/// The archived form of the type must not contain any padding or otherwise uninitialized bytes,
+/// and archiving a value with [`archive_primitive`](ArchivePrimitive::archive_primitive) must
+/// produce the same bytes as resolving it.
+pub unsafe trait ArchivePrimitive: Archive<Resolver = ()> {
+    /// Returns the archived form of the value.
+    fn archive_primitive(&self) -> Self::Archived;
+}
+
 /// A counterpart of [`Archive`] that's suitable for unsized types.
 ///
 /// Unlike `Archive`, types that implement `ArchiveUnsized` must be serialized separately from their
@@ -10,6 +10,18 @@ macro_rules! default {
     ($($fn:tt)*) => { $($fn)* };
 }
 
+// Alignment checks are only performed in debug builds unless `checked_align` is enabled, in which
+// case they are always performed.
+#[cfg(feature = "checked_align")]
+macro_rules! check_align {
+    ($($arg:tt)*) => { assert!($($arg)*) };
+}
+
+#[cfg(not(feature = "checked_align"))]
+macro_rules! check_align {
+    ($($arg:tt)*) => { debug_assert!($($arg)*) };
+}
+
 /// Returns a tuple of the field offset and a mutable pointer to the field of the given struct
 /// pointer.
 ///
@@ -136,3 +148,167 @@ macro_rules! pick_size_type {
         $s64
     };
 }
+
+/// Implements the serializer traits for a [`SerializerLayer`](crate::ser::SerializerLayer) by
+/// forwarding them to its inner serializer.
+///
+/// The layer must be a type with a single type parameter, like `Counting<S>`. The macro implements
+/// [`Serializer`](crate::ser::Serializer) by passing writes through
+/// [`write_layer`](crate::ser::SerializerLayer::write_layer) and forwarding everything else to the
+/// inner serializer, including [`begin_type`](crate::ser::Serializer::begin_type) and
+/// [`end_type`](crate::ser::Serializer::end_type). It also implements
+/// [`ScratchSpace`](crate::ser::ScratchSpace),
+/// [`SharedSerializeRegistry`](crate::ser::SharedSerializeRegistry),
+/// [`StringInterner`](crate::ser::StringInterner), and `InstantEpoch` (with the `std` feature)
+/// whenever the inner serializer implements them, so capabilities added by any layer in a stack are
+/// available from the outermost one.
+///
+/// A layer that provides one of the capability traits itself can opt out of forwarding it with
+/// `except`:
+///
+/// ```ignore
+/// impl_serializer_layer!(Interning, except(StringInterner));
+/// ```
+///
+/// See [`SerializerLayer`](crate::ser::SerializerLayer) for an example.
+#[macro_export]
+macro_rules! impl_serializer_layer {
+    ($ty:ident) => {
+        $crate::impl_serializer_layer!($ty, except());
+    };
+    ($ty:ident, except($($except:ident),* $(,)?)) => {
+        impl<S> $crate::ser::Serializer for $ty<S>
+        where
+            Self: $crate::ser::SerializerLayer,
+        {
+            #[inline]
+            fn pos(&self) -> usize {
+                $crate::ser::SerializerLayer::inner(self).pos()
+            }
+
+            #[inline]
+            fn write(&mut self, bytes: &[u8]) -> ::core::result::Result<(), Self::Error> {
+                $crate::ser::SerializerLayer::write_layer(self, bytes)
+            }
+
+            #[inline]
+            fn begin_type(&mut self, type_name: &'static str) {
+                $crate::ser::SerializerLayer::inner_mut(self).begin_type(type_name)
+            }
+
+            #[inline]
+            fn end_type(&mut self) {
+                $crate::ser::SerializerLayer::inner_mut(self).end_type()
+            }
+        }
+
+        impl<S> $crate::ser::ScratchSpace for $ty<S>
+        where
+            Self: $crate::ser::SerializerLayer,
+            <Self as $crate::ser::SerializerLayer>::Inner: $crate::ser::ScratchSpace,
+        {
+            #[inline]
+            unsafe fn push_scratch(
+                &mut self,
+                layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<::core::ptr::NonNull<[u8]>, Self::Error> {
+                $crate::ser::SerializerLayer::inner_mut(self)
+                    .push_scratch(layout)
+                    .map_err(<Self as $crate::ser::SerializerLayer>::map_inner_error)
+            }
+
+            #[inline]
+            unsafe fn pop_scratch(
+                &mut self,
+                ptr: ::core::ptr::NonNull<u8>,
+                layout: ::core::alloc::Layout,
+            ) -> ::core::result::Result<(), Self::Error> {
+                $crate::ser::SerializerLayer::inner_mut(self)
+                    .pop_scratch(ptr, layout)
+                    .map_err(<Self as $crate::ser::SerializerLayer>::map_inner_error)
+            }
+        }
+
+        impl<S> $crate::ser::SharedSerializeRegistry for $ty<S>
+        where
+            Self: $crate::ser::SerializerLayer,
+            <Self as $crate::ser::SerializerLayer>::Inner: $crate::ser::SharedSerializeRegistry,
+        {
+            #[inline]
+            fn get_shared_ptr(&mut self, value: *const u8) -> ::core::option::Option<usize> {
+                $crate::ser::SerializerLayer::inner_mut(self).get_shared_ptr(value)
+            }
+
+            #[inline]
+            fn add_shared_ptr(
+                &mut self,
+                value: *const u8,
+                pos: usize,
+            ) -> ::core::result::Result<(), Self::Error> {
+                $crate::ser::SerializerLayer::inner_mut(self)
+                    .add_shared_ptr(value, pos)
+                    .map_err(<Self as $crate::ser::SerializerLayer>::map_inner_error)
+            }
+        }
+
+        $crate::__forward_string_interner!($ty; $($except)*);
+        $crate::__forward_instant_epoch!($ty; $($except)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __forward_string_interner {
+    ($ty:ident;) => {
+        impl<S> $crate::ser::StringInterner for $ty<S>
+        where
+            Self: $crate::ser::SerializerLayer,
+            <Self as $crate::ser::SerializerLayer>::Inner: $crate::ser::StringInterner,
+        {
+            #[inline]
+            fn intern(&mut self, value: &str) -> ::core::result::Result<usize, Self::Error> {
+                $crate::ser::SerializerLayer::inner_mut(self)
+                    .intern(value)
+                    .map_err(<Self as $crate::ser::SerializerLayer>::map_inner_error)
+            }
+        }
+    };
+    ($ty:ident; StringInterner $($rest:ident)*) => {};
+    ($ty:ident; $other:ident $($rest:ident)*) => {
+        $crate::__forward_string_interner!($ty; $($rest)*);
+    };
+}
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __forward_instant_epoch {
+    ($ty:ident;) => {
+        impl<S> $crate::ser::InstantEpoch for $ty<S>
+        where
+            Self: $crate::ser::SerializerLayer,
+            <Self as $crate::ser::SerializerLayer>::Inner: $crate::ser::InstantEpoch,
+        {
+            #[inline]
+            fn instant_offset(
+                &mut self,
+                instant: ::std::time::Instant,
+            ) -> ::core::result::Result<::core::time::Duration, Self::Error> {
+                $crate::ser::SerializerLayer::inner_mut(self)
+                    .instant_offset(instant)
+                    .map_err(<Self as $crate::ser::SerializerLayer>::map_inner_error)
+            }
+        }
+    };
+    ($ty:ident; InstantEpoch $($rest:ident)*) => {};
+    ($ty:ident; $other:ident $($rest:ident)*) => {
+        $crate::__forward_instant_epoch!($ty; $($rest)*);
+    };
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __forward_instant_epoch {
+    ($($tt:tt)*) => {};
+}
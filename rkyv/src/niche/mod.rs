@@ -2,3 +2,4 @@
 
 #[cfg(feature = "alloc")]
 pub mod option_box;
+pub mod sentinel;
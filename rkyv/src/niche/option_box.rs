@@ -248,3 +248,31 @@ pub enum OptionBoxResolver<T> {
     /// The resolver for the `ArchivedBox`
     Some(BoxResolver<T>),
 }
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use crate::{Fallible, RelPtr};
+    use bytecheck::CheckBytes;
+
+    impl<T, C> CheckBytes<C> for ArchivedOptionBox<T>
+    where
+        T: ArchivePointee + ?Sized,
+        C: Fallible + ?Sized,
+        ArchivedBox<T>: CheckBytes<C>,
+    {
+        type Error = <ArchivedBox<T> as CheckBytes<C>>::Error;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            // `None` is stored as a null pointer with default metadata, which may not be valid
+            // metadata for `T` (for example, it does not refer to any trait object impl)
+            if !RelPtr::<T>::manual_check_is_null(value.cast(), context) {
+                ArchivedBox::<T>::check_bytes(value.cast(), context)?;
+            }
+            Ok(&*value)
+        }
+    }
+};
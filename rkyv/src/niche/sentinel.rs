@@ -0,0 +1,213 @@
+//! A niched archived `Option<T>` that stores `None` as a sentinel value of `T`.
+
+use crate::{with::SentinelError, Archive, Fallible, Serialize};
+use core::{cmp, fmt, hash};
+
+/// A type with a sentinel value that can be used as the niche of an archived `Option`.
+///
+/// An `Option<T>` archived with the [`Sentinel`](crate::with::Sentinel) wrapper stores `None` as the
+/// sentinel value instead of adding a tag, so it takes exactly as much space as `T::Archived`. A
+/// `Some` containing the sentinel value would be indistinguishable from `None` once archived, so
+/// serializing one fails with [`SentinelError`]. The sentinel should be a value that is never used
+/// otherwise (like `u32::MAX` for an index).
+///
+/// This trait can be derived for structs with a single field with `#[archive(niche = "...")]`,
+/// where the string is an expression for the sentinel value of the field:
+///
+/// ```
+/// use core::mem::size_of;
+/// use rkyv::{with::Sentinel, Archive, Archived};
+///
+/// #[derive(Archive)]
+/// #[archive(niche = "u32::MAX")]
+/// struct NodeIndex(u32);
+///
+/// #[derive(Archive)]
+/// struct Node {
+///     #[with(Sentinel)]
+///     parent: Option<NodeIndex>,
+/// }
+///
+/// assert_eq!(size_of::<Archived<Node>>(), size_of::<Archived<u32>>());
+/// ```
+pub trait ArchiveNiche: Archive {
+    /// Returns whether the archived value is the sentinel value.
+    fn is_niche(archived: &Self::Archived) -> bool;
+
+    /// Returns whether the value would be archived as the sentinel value.
+    fn is_niche_value(&self) -> bool;
+
+    /// Writes the archived sentinel value to `out`.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `out` must point to memory suitable for holding a `Self::Archived`
+    unsafe fn resolve_niche(pos: usize, out: *mut Self::Archived);
+}
+
+/// A niched archived `Option<T>`.
+///
+/// It uses less space by storing the `None` variant as the sentinel value of `T`. See
+/// [`ArchiveNiche`] for more information.
+#[repr(transparent)]
+pub struct ArchivedOptionNiche<T: ArchiveNiche> {
+    inner: T::Archived,
+}
+
+impl<T: ArchiveNiche> ArchivedOptionNiche<T> {
+    /// Returns `true` if the option is a `None` value.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        T::is_niche(&self.inner)
+    }
+
+    /// Returns `true` if the option is a `Some` value.
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        !self.is_none()
+    }
+
+    /// Converts to an `Option<&T::Archived>`.
+    #[inline]
+    pub fn as_ref(&self) -> Option<&T::Archived> {
+        if self.is_none() {
+            None
+        } else {
+            Some(&self.inner)
+        }
+    }
+
+    /// Resolves an `ArchivedOptionNiche<T>` from an `Option<&T>`.
+    ///
+    /// # Safety
+    ///
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing `field`
+    #[inline]
+    pub unsafe fn resolve_from_option(
+        field: Option<&T>,
+        pos: usize,
+        resolver: Option<T::Resolver>,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.inner);
+        match (field, resolver) {
+            (Some(value), Some(resolver)) => value.resolve(pos + fp, resolver, fo),
+            _ => T::resolve_niche(pos + fp, fo),
+        }
+    }
+
+    /// Serializes an `ArchivedOptionNiche<T>` from an `Option<&T>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SentinelError::Collision`] if `field` is a `Some` containing the sentinel value,
+    /// since it would be read back as `None`.
+    #[inline]
+    pub fn serialize_from_option<S>(
+        field: Option<&T>,
+        serializer: &mut S,
+    ) -> Result<Option<T::Resolver>, S::Error>
+    where
+        T: Serialize<S>,
+        S: Fallible + ?Sized,
+        S::Error: From<SentinelError>,
+    {
+        match field {
+            Some(value) if value.is_niche_value() => Err(SentinelError::Collision.into()),
+            Some(value) => Ok(Some(value.serialize(serializer)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T: ArchiveNiche> fmt::Debug for ArchivedOptionNiche<T>
+where
+    T::Archived: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<T: ArchiveNiche> Eq for ArchivedOptionNiche<T> where T::Archived: Eq {}
+
+impl<T: ArchiveNiche> hash::Hash for ArchivedOptionNiche<T>
+where
+    T::Archived: hash::Hash,
+{
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state)
+    }
+}
+
+impl<T: ArchiveNiche> Ord for ArchivedOptionNiche<T>
+where
+    T::Archived: Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_ref().cmp(&other.as_ref())
+    }
+}
+
+impl<T: ArchiveNiche> PartialEq for ArchivedOptionNiche<T>
+where
+    T::Archived: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref().eq(&other.as_ref())
+    }
+}
+
+impl<T: ArchiveNiche> PartialOrd for ArchivedOptionNiche<T>
+where
+    T::Archived: PartialOrd,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.as_ref().partial_cmp(&other.as_ref())
+    }
+}
+
+impl<T: ArchiveNiche, U> PartialEq<Option<U>> for ArchivedOptionNiche<T>
+where
+    T::Archived: PartialEq<U>,
+{
+    #[inline]
+    fn eq(&self, other: &Option<U>) -> bool {
+        match (self.as_ref(), other) {
+            (Some(self_value), Some(other_value)) => self_value.eq(other_value),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "validation")]
+const _: () = {
+    use bytecheck::CheckBytes;
+
+    impl<T, C> CheckBytes<C> for ArchivedOptionNiche<T>
+    where
+        T: ArchiveNiche,
+        T::Archived: CheckBytes<C>,
+        C: Fallible + ?Sized,
+    {
+        type Error = <T::Archived as CheckBytes<C>>::Error;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            // The sentinel value is a valid `T::Archived`, so `None` needs no special handling
+            T::Archived::check_bytes(value.cast(), context)?;
+            Ok(&*value)
+        }
+    }
+};
@@ -0,0 +1,61 @@
+//! Archived versions of `num` types.
+
+use core::num::FpCategory;
+
+/// An archived [`FpCategory`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[repr(u8)]
+pub enum ArchivedFpCategory {
+    /// "Not a Number", often obtained by dividing by zero
+    Nan,
+    /// Positive or negative infinity
+    Infinite,
+    /// Positive or negative zero
+    Zero,
+    /// De-normalized floating point representation
+    Subnormal,
+    /// A regular floating point number
+    Normal,
+}
+
+impl ArchivedFpCategory {
+    /// Returns an [`FpCategory`] with the same value.
+    #[inline]
+    pub const fn as_fp_category(&self) -> FpCategory {
+        match self {
+            ArchivedFpCategory::Nan => FpCategory::Nan,
+            ArchivedFpCategory::Infinite => FpCategory::Infinite,
+            ArchivedFpCategory::Zero => FpCategory::Zero,
+            ArchivedFpCategory::Subnormal => FpCategory::Subnormal,
+            ArchivedFpCategory::Normal => FpCategory::Normal,
+        }
+    }
+}
+
+impl From<FpCategory> for ArchivedFpCategory {
+    #[inline]
+    fn from(value: FpCategory) -> Self {
+        match value {
+            FpCategory::Nan => ArchivedFpCategory::Nan,
+            FpCategory::Infinite => ArchivedFpCategory::Infinite,
+            FpCategory::Zero => ArchivedFpCategory::Zero,
+            FpCategory::Subnormal => ArchivedFpCategory::Subnormal,
+            FpCategory::Normal => ArchivedFpCategory::Normal,
+        }
+    }
+}
+
+impl PartialEq<FpCategory> for ArchivedFpCategory {
+    #[inline]
+    fn eq(&self, other: &FpCategory) -> bool {
+        self.as_fp_category() == *other
+    }
+}
+
+impl PartialEq<ArchivedFpCategory> for FpCategory {
+    #[inline]
+    fn eq(&self, other: &ArchivedFpCategory) -> bool {
+        other.eq(self)
+    }
+}
@@ -236,3 +236,71 @@ impl<T> RangeBounds<T> for ArchivedRangeToInclusive<T> {
         Bound::Included(&self.end)
     }
 }
+
+// Bound
+
+/// An archived [`Bound`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[repr(u8)]
+pub enum ArchivedBound<T> {
+    /// An inclusive bound
+    Included(T),
+    /// An exclusive bound
+    Excluded(T),
+    /// An infinite endpoint
+    Unbounded,
+}
+
+impl<T> ArchivedBound<T> {
+    /// Converts from `&ArchivedBound<T>` to `Bound<&T>`.
+    #[inline]
+    pub const fn as_ref(&self) -> Bound<&T> {
+        match self {
+            ArchivedBound::Included(value) => Bound::Included(value),
+            ArchivedBound::Excluded(value) => Bound::Excluded(value),
+            ArchivedBound::Unbounded => Bound::Unbounded,
+        }
+    }
+}
+
+// Bounds
+
+/// A pair of archived start and end [`Bound`]s.
+///
+/// This is the archived form of range-like types archived with
+/// [`AsBounds`](crate::with::AsBounds), and can be used like any other range through its
+/// [`RangeBounds`] implementation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedBounds<T> {
+    /// The start bound of the range.
+    pub start: ArchivedBound<T>,
+    /// The end bound of the range.
+    pub end: ArchivedBound<T>,
+}
+
+impl<T: PartialOrd<T>> ArchivedBounds<T> {
+    /// Returns `true` if `item` is contained in the range.
+    #[inline]
+    pub fn contains<U>(&self, item: &U) -> bool
+    where
+        T: PartialOrd<U>,
+        U: PartialOrd<T> + ?Sized,
+    {
+        <Self as RangeBounds<T>>::contains(self, item)
+    }
+}
+
+impl<T> RangeBounds<T> for ArchivedBounds<T> {
+    #[inline]
+    fn start_bound(&self) -> Bound<&T> {
+        self.start.as_ref()
+    }
+
+    #[inline]
+    fn end_bound(&self) -> Bound<&T> {
+        self.end.as_ref()
+    }
+}
@@ -12,7 +12,7 @@ use core::{
 ///
 /// It functions identically to [`Option`] but has a different internal
 /// representation to allow for archiving.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
 #[repr(u8)]
 pub enum ArchivedOption<T> {
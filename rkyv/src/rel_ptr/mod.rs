@@ -273,10 +273,29 @@ pub struct RelPtr<T: ArchivePointee + ?Sized, O> {
 impl<T, O: Offset> RelPtr<T, O> {
     /// Attempts to create a relative pointer from one position to another.
     ///
+    /// The pointer is written directly into `out`, so custom resolvers can build archived structs
+    /// field-by-field in uninitialized memory without creating a temporary first.
+    ///
     /// # Safety
     ///
     /// - `from` must be the position of `out` within the archive
     /// - `to` must be the position of some valid `T`
+    ///
+    /// # Example
+    /// ```
+    /// use core::mem::MaybeUninit;
+    /// use rkyv::rel_ptr::{OffsetError, RelPtr};
+    ///
+    /// let mut out = MaybeUninit::<RelPtr<u32, i8>>::uninit();
+    /// unsafe {
+    ///     RelPtr::try_emplace(16, 24, out.as_mut_ptr()).unwrap();
+    ///     assert_eq!(out.assume_init_ref().offset(), 8);
+    ///
+    ///     // Offsets that don't fit in the offset storage are reported instead of truncated
+    ///     let result = RelPtr::try_emplace(0, 1000, out.as_mut_ptr());
+    ///     assert!(matches!(result, Err(OffsetError::ExceedsStorageRange)));
+    /// }
+    /// ```
     #[inline]
     pub unsafe fn try_emplace(from: usize, to: usize, out: *mut Self) -> Result<(), OffsetError> {
         let (fp, fo) = out_field!(out.raw_ptr);
@@ -312,8 +331,9 @@ where
     /// `pos` must be the position of `out` within the archive.
     #[inline]
     pub unsafe fn try_emplace_null(pos: usize, out: *mut Self) -> Result<(), OffsetError> {
+        // The raw pointer may not be the first field, so it must point to itself to be null
         let (fp, fo) = out_field!(out.raw_ptr);
-        RawRelPtr::try_emplace(pos + fp, pos, fo)?;
+        RawRelPtr::try_emplace(pos + fp, pos + fp, fo)?;
         let (_, fo) = out_field!(out.metadata);
         fo.write(Default::default());
         Ok(())
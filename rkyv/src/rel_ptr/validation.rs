@@ -59,4 +59,25 @@ impl<T: ArchivePointee + ?Sized, O: Offset> RelPtr<T, O> {
         PhantomData::<T>::check_bytes(ptr::addr_of!((*value)._phantom), context).unwrap();
         Ok(&*value)
     }
+
+    /// Checks the offset of the given relative pointer and returns whether it is null.
+    ///
+    /// The metadata of the relative pointer is not checked. Nullable pointers can use this to skip
+    /// checking the metadata of null pointers, which is not required to be valid.
+    ///
+    /// # Safety
+    ///
+    /// The given pointer must be aligned and point to enough bytes to represent a `RelPtr<T>`.
+    #[inline]
+    pub unsafe fn manual_check_is_null<C: Fallible + ?Sized>(
+        value: *const Self,
+        context: &mut C,
+    ) -> bool
+    where
+        O: CheckBytes<C>,
+    {
+        RawRelPtr::manual_check_bytes(ptr::addr_of!((*value).raw_ptr), context)
+            .unwrap()
+            .is_null()
+    }
 }
@@ -31,6 +31,24 @@ impl<T, E> ArchivedResult<T, E> {
         matches!(self, ArchivedResult::Err(_))
     }
 
+    /// Returns a reference to the success value, if any.
+    #[inline]
+    pub fn ok(&self) -> Option<&T> {
+        match self {
+            ArchivedResult::Ok(value) => Some(value),
+            ArchivedResult::Err(_) => None,
+        }
+    }
+
+    /// Returns a reference to the error value, if any.
+    #[inline]
+    pub fn err(&self) -> Option<&E> {
+        match self {
+            ArchivedResult::Ok(_) => None,
+            ArchivedResult::Err(err) => Some(err),
+        }
+    }
+
     /// Returns a `Result` containing the success and error values of this `ArchivedResult`.
     #[inline]
     pub fn as_ref(&self) -> Result<&T, &E> {
@@ -1,9 +1,13 @@
 //! Serialization traits, serializers, and adapters.
 
+#[cfg(feature = "alloc")]
+pub mod resumable;
 pub mod serializers;
 
 use crate::{Archive, ArchiveUnsized, Fallible, RelPtr, Serialize, SerializeUnsized};
-use core::{alloc::Layout, mem, ptr::NonNull, slice};
+use core::{alloc::Layout, fmt, mem, ptr::NonNull, slice};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
 
 /// A byte sink that knows where it is.
 ///
@@ -30,11 +34,32 @@ pub trait Serializer: Fallible {
         self.write(&ZEROES[0..padding])
     }
 
+    /// Marks the start of writing out-of-line bytes for a value of the named type.
+    ///
+    /// Serialize implementations can call this before writing bytes that aren't part of an
+    /// archived value (like the contents of a string) and call [`end_type`](Serializer::end_type)
+    /// afterward. It does nothing by default, but serializers can override it to attribute the
+    /// bytes written in between to the type (see
+    /// [`ProfilingSerializer`](serializers::ProfilingSerializer)).
+    #[inline]
+    fn begin_type(&mut self, type_name: &'static str) {
+        let _ = type_name;
+    }
+
+    /// Marks the end of writing the bytes for the type most recently passed to
+    /// [`begin_type`](Serializer::begin_type).
+    #[inline]
+    fn end_type(&mut self) {}
+
     /// Aligns the position of the serializer to the given alignment.
     #[inline]
     fn align(&mut self, align: usize) -> Result<usize, Self::Error> {
         let mask = align - 1;
-        debug_assert_eq!(align & mask, 0);
+        check_align!(
+            align & mask == 0,
+            "alignment {} is not a power of two",
+            align
+        );
 
         self.pad((align - (self.pos() & mask)) & mask)?;
         Ok(self.pos())
@@ -60,7 +85,12 @@ pub trait Serializer: Fallible {
         resolver: T::Resolver,
     ) -> Result<usize, Self::Error> {
         let pos = self.pos();
-        debug_assert_eq!(pos & (mem::align_of::<T::Archived>() - 1), 0);
+        check_align!(
+            pos & (mem::align_of::<T::Archived>() - 1) == 0,
+            "position {} is not aligned to {}",
+            pos,
+            mem::align_of::<T::Archived>(),
+        );
 
         let mut resolved = mem::MaybeUninit::<T::Archived>::uninit();
         resolved.as_mut_ptr().write_bytes(0, 1);
@@ -80,6 +110,123 @@ pub trait Serializer: Fallible {
         unsafe { self.resolve_aligned(value, resolver) }
     }
 
+    /// Archives the given object as the root of the archive and returns the position of the root
+    /// object together with the total length of the archive.
+    ///
+    /// This is useful when the archive needs to be framed, for example with a length prefix.
+    ///
+    /// # Examples
+    /// ```
+    /// use rkyv::{
+    ///     archived_value,
+    ///     ser::{serializers::AllocSerializer, Serializer},
+    /// };
+    ///
+    /// let mut serializer = AllocSerializer::<256>::default();
+    /// let info = serializer.serialize_root(&vec![1u32, 2, 3]).unwrap();
+    /// let bytes = serializer.into_serializer().into_inner();
+    /// assert_eq!(info.total_len, bytes.len());
+    ///
+    /// let archived = unsafe { archived_value::<Vec<u32>>(&bytes, info.root_pos) };
+    /// assert_eq!(archived.len(), 3);
+    /// ```
+    #[inline]
+    fn serialize_root<T: Serialize<Self>>(
+        &mut self,
+        value: &T,
+    ) -> Result<ArchiveInfo, Self::Error> {
+        let root_pos = self.serialize_value(value)?;
+        Ok(ArchiveInfo {
+            root_pos,
+            total_len: self.pos(),
+        })
+    }
+
+    /// Archives the given object so that it starts exactly at the given offset, returning that
+    /// offset.
+    ///
+    /// This is useful for file formats with fixed-layout regions, like a header at offset 0 and a
+    /// table at offset 64. Any out-of-line data the object has (like the bytes of a `String`) is
+    /// written first, then the serializer is padded up to `offset` and the archived object is
+    /// written there. A serializer that is exactly at `offset` is not padded.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`SerializeAtError::Misaligned`] without writing anything if `offset` is not
+    ///   aligned for the archived object.
+    /// - Returns [`SerializeAtError::PastOffset`] if the serializer is already beyond `offset`, or
+    ///   if the out-of-line data of the object ends beyond it. In the second case, the out-of-line
+    ///   data has already been written.
+    ///
+    /// # Examples
+    /// ```
+    /// use rkyv::{
+    ///     archived_value,
+    ///     ser::{serializers::AllocSerializer, SerializeAtError, Serializer},
+    /// };
+    ///
+    /// let mut serializer = AllocSerializer::<256>::default();
+    /// let header = serializer.serialize_value_at(0, &[0xfeu8, 0xed, 0xfa, 0xce]).unwrap();
+    /// let table = serializer.serialize_value_at(64, &[1u32, 2, 3, 4]).unwrap();
+    /// assert_eq!(header, 0);
+    /// assert_eq!(table, 64);
+    ///
+    /// // The serializer can't go backwards
+    /// match serializer.serialize_value_at(32, &0u32) {
+    ///     Err(SerializeAtError::PastOffset { offset, pos }) => {
+    ///         assert_eq!(offset, 32);
+    ///         assert_eq!(pos, 80);
+    ///     }
+    ///     _ => panic!("expected an error"),
+    /// }
+    ///
+    /// // The bytes of the string are written before the offset, and the string itself at it
+    /// let name = "a string that is too long to be inlined".to_string();
+    /// assert_eq!(serializer.serialize_value_at(128, &name).unwrap(), 128);
+    ///
+    /// let bytes = serializer.into_serializer().into_inner();
+    /// let archived = unsafe { archived_value::<[u32; 4]>(&bytes, table) };
+    /// assert_eq!(archived[2], 3);
+    /// let archived = unsafe { archived_value::<String>(&bytes, 128) };
+    /// assert_eq!(archived, &name);
+    /// ```
+    #[inline]
+    fn serialize_value_at<T: Serialize<Self>>(
+        &mut self,
+        offset: usize,
+        value: &T,
+    ) -> Result<usize, SerializeAtError<Self::Error>> {
+        // Padding is written in chunks because serializers only pad small amounts at a time
+        const MAX_PADDING: usize = 16;
+
+        let align = mem::align_of::<T::Archived>();
+        if offset % align != 0 {
+            return Err(SerializeAtError::Misaligned { offset, align });
+        }
+        let pos = self.pos();
+        if pos > offset {
+            return Err(SerializeAtError::PastOffset { offset, pos });
+        }
+
+        let resolver = value
+            .serialize(self)
+            .map_err(SerializeAtError::SerializerError)?;
+        let pos = self.pos();
+        if pos > offset {
+            return Err(SerializeAtError::PastOffset { offset, pos });
+        }
+
+        let mut remaining = offset - pos;
+        while remaining > 0 {
+            let padding = remaining.min(MAX_PADDING);
+            self.pad(padding)
+                .map_err(SerializeAtError::SerializerError)?;
+            remaining -= padding;
+        }
+
+        unsafe { self.resolve_aligned(value, resolver) }.map_err(SerializeAtError::SerializerError)
+    }
+
     /// Resolves the given reference with its resolver and writes the archived reference.
     ///
     /// Returns the position of the written archived `RelPtr`.
@@ -96,7 +243,12 @@ pub trait Serializer: Fallible {
         metadata_resolver: T::MetadataResolver,
     ) -> Result<usize, Self::Error> {
         let from = self.pos();
-        debug_assert_eq!(from & (mem::align_of::<RelPtr<T::Archived>>() - 1), 0);
+        check_align!(
+            from & (mem::align_of::<RelPtr<T::Archived>>() - 1) == 0,
+            "position {} is not aligned to {}",
+            from,
+            mem::align_of::<RelPtr<T::Archived>>(),
+        );
 
         let mut resolved = mem::MaybeUninit::<RelPtr<T::Archived>>::uninit();
         resolved.as_mut_ptr().write_bytes(0, 1);
@@ -121,6 +273,72 @@ pub trait Serializer: Fallible {
     }
 }
 
+/// The position of the root object and the total length of a finished archive.
+///
+/// Returned by [`Serializer::serialize_root`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArchiveInfo {
+    /// The position of the root object in the archive
+    pub root_pos: usize,
+    /// The total length of the archive in bytes
+    pub total_len: usize,
+}
+
+/// An error that occurred while archiving an object at an offset.
+///
+/// Returned by [`Serializer::serialize_value_at`].
+#[derive(Debug)]
+pub enum SerializeAtError<E> {
+    /// The serializer was already past the requested offset.
+    PastOffset {
+        /// The requested offset
+        offset: usize,
+        /// The position of the serializer
+        pos: usize,
+    },
+    /// The offset was not aligned for the archived object.
+    Misaligned {
+        /// The requested offset
+        offset: usize,
+        /// The alignment of the archived object
+        align: usize,
+    },
+    /// The serializer returned an error.
+    SerializerError(E),
+}
+
+impl<E: fmt::Display> fmt::Display for SerializeAtError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerializeAtError::PastOffset { offset, pos } => write!(
+                f,
+                "cannot archive at offset {}: the serializer is already at position {}",
+                offset, pos
+            ),
+            SerializeAtError::Misaligned { offset, align } => write!(
+                f,
+                "cannot archive at offset {}: the archived object must be aligned to {} bytes",
+                offset, align
+            ),
+            SerializeAtError::SerializerError(e) => write!(f, "serialization error: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<E: Error + 'static> Error for SerializeAtError<E> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                SerializeAtError::PastOffset { .. } | SerializeAtError::Misaligned { .. } => None,
+                SerializeAtError::SerializerError(e) => Some(e as &dyn Error),
+            }
+        }
+    }
+};
+
 // Someday this can probably be replaced with alloc::Allocator
 
 /// A serializer that can allocate scratch space.
@@ -186,3 +404,120 @@ pub trait SharedSerializeRegistry: Fallible {
         }
     }
 }
+
+/// A serializer that archives [`Instant`]s relative to a base instant.
+///
+/// An `Instant` has no absolute meaning: it can only be compared to other instants from the same
+/// process. To archive relative timings, each `Instant` is stored as the [`Duration`] elapsed since
+/// a base instant that the serializer captures when archiving starts. Archived instants can only be
+/// meaningfully compared to other instants archived with the same base.
+///
+/// This trait is required to serialize `Instant`s. The
+/// [`InstantSerializer`](serializers::InstantSerializer) adapter adds it to any serializer.
+#[cfg(feature = "std")]
+pub trait InstantEpoch: Fallible {
+    /// Returns the offset of the given instant from the base instant.
+    fn instant_offset(&mut self, instant: Instant) -> Result<Duration, Self::Error>;
+}
+
+/// A serializer that can intern strings.
+///
+/// Interning stores each distinct string once and refers to it by its index in a table. This trait
+/// is required to serialize fields with the [`Intern`](crate::with::Intern) wrapper. The
+/// [`InternSerializer`](serializers::InternSerializer) adapter adds it to any serializer.
+pub trait StringInterner: Fallible {
+    /// Interns the given string and returns its index in the intern table.
+    fn intern(&mut self, value: &str) -> Result<usize, Self::Error>;
+}
+
+/// A serializer adapter that wraps an inner serializer and observes the bytes written through it.
+///
+/// Invoking [`impl_serializer_layer!`](crate::impl_serializer_layer) for a layer implements
+/// [`Serializer`] by forwarding [`pos`](Serializer::pos) to the inner serializer and passing every
+/// write through [`write_layer`](SerializerLayer::write_layer). It also forwards
+/// [`ScratchSpace`], [`SharedSerializeRegistry`], [`StringInterner`], and `InstantEpoch` to the
+/// inner serializer when it implements them. The layers in this crate
+/// ([`InternSerializer`](serializers::InternSerializer) and `InstantSerializer`) use it too. This
+/// lets adapters like hashers, limiters, or tracers be written once and stacked in any order:
+/// `Hashing<Limit<WriteSerializer<File>>>`.
+///
+/// # Stacking order
+///
+/// - Writes travel from the outermost layer to the innermost serializer. Each layer sees the bytes
+///   before the layers it wraps, and may alter or reject them before passing them on.
+/// - Padding is written as zero bytes through [`write`](Serializer::write), so every layer sees
+///   padding in the same byte stream as the archived data.
+/// - Positions always come from the innermost serializer. A layer that changes the number of bytes
+///   it passes on (for example, by compressing them) must not be used below layers that depend on
+///   positions.
+///
+/// # Example
+/// ```
+/// use rkyv::{
+///     impl_serializer_layer,
+///     ser::{serializers::AllocSerializer, Serializer, SerializerLayer},
+///     Fallible,
+/// };
+///
+/// struct Counting<S> {
+///     inner: S,
+///     count: usize,
+/// }
+///
+/// impl<S: Fallible> Fallible for Counting<S> {
+///     type Error = S::Error;
+/// }
+///
+/// impl<S: Serializer> SerializerLayer for Counting<S> {
+///     type Inner = S;
+///
+///     fn inner(&self) -> &S {
+///         &self.inner
+///     }
+///
+///     fn inner_mut(&mut self) -> &mut S {
+///         &mut self.inner
+///     }
+///
+///     fn map_inner_error(error: S::Error) -> Self::Error {
+///         error
+///     }
+///
+///     fn write_layer(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+///         self.count += bytes.len();
+///         self.inner.write(bytes)
+///     }
+/// }
+///
+/// impl_serializer_layer!(Counting);
+///
+/// let mut serializer = Counting {
+///     inner: AllocSerializer::<256>::default(),
+///     count: 0,
+/// };
+/// // Strings need scratch space, which is forwarded to the inner serializer
+/// serializer.serialize_value(&vec!["hello".to_string(), "world".to_string()]).unwrap();
+/// let bytes = serializer.inner.into_serializer().into_inner();
+/// assert_eq!(serializer.count, bytes.len());
+/// ```
+pub trait SerializerLayer: Fallible {
+    /// The serializer wrapped by this layer.
+    type Inner: Serializer + ?Sized;
+
+    /// Returns a reference to the inner serializer.
+    fn inner(&self) -> &Self::Inner;
+
+    /// Returns a mutable reference to the inner serializer.
+    fn inner_mut(&mut self) -> &mut Self::Inner;
+
+    /// Converts an error from the inner serializer into an error for this layer.
+    fn map_inner_error(error: <Self::Inner as Fallible>::Error) -> Self::Error;
+
+    /// Writes the given bytes through this layer.
+    ///
+    /// By default, this writes the bytes to the inner serializer unchanged.
+    #[inline]
+    fn write_layer(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.inner_mut().write(bytes).map_err(Self::map_inner_error)
+    }
+}
@@ -0,0 +1,291 @@
+//! Archiving that can be paused after a budget of work and resumed later.
+
+use crate::{
+    ser::{ArchiveInfo, Serializer},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Serialize,
+};
+#[cfg(not(feature = "std"))]
+use ::alloc::vec::{self, Vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::vec;
+
+/// The progress made by a step of a [`ResumableArchiver`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArchiveProgress {
+    /// The archive is not finished yet and more steps are needed.
+    Pending {
+        /// The number of bytes written by the step
+        written: usize,
+    },
+    /// The archive is finished.
+    Complete(ArchiveInfo),
+}
+
+impl ArchiveProgress {
+    /// Returns `true` if the archive is finished.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        matches!(self, ArchiveProgress::Complete(_))
+    }
+}
+
+enum State<R> {
+    // Serializing the dependencies of each item and collecting their resolvers
+    Dependencies {
+        resolvers: Vec<R>,
+    },
+    // Writing the archived items into a contiguous array
+    Items {
+        array_pos: usize,
+        resolvers: vec::IntoIter<R>,
+        next: usize,
+    },
+    // Writing the root archived vec
+    Root {
+        array_pos: usize,
+    },
+    Complete(ArchiveInfo),
+    Poisoned,
+}
+
+// Archives a slice as an archived vec given the position of its items.
+struct Root<'a, T>(&'a [T]);
+
+impl<T: Archive> Archive for Root<'_, T> {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedVec::resolve_from_slice(self.0, pos, resolver, out);
+    }
+}
+
+/// Archives a slice of values as an archived `Vec` over multiple calls, yielding after a budget of
+/// work.
+///
+/// Serializing a large collection with [`serialize_value`](Serializer::serialize_value) does all
+/// of the work in one call. When archiving has to share a thread with other work (like a
+/// frame-budgeted game loop or a single-threaded event loop), a resumable archiver can be stepped
+/// a little at a time instead. Each call to [`step`](ResumableArchiver::step) writes roughly a
+/// budget of bytes and then returns, and the archive is finished after enough steps. The finished
+/// archive is identical to serializing the items as a `Vec` with `serialize_value`, so its root can
+/// be accessed as an archived `Vec<T>`.
+///
+/// Archiving happens in three phases: first the out-of-line data of each item (like the bytes of
+/// strings) is serialized, then the archived items are written as one contiguous array, and
+/// finally the root archived vec is written. A step can yield between any two items in either of
+/// the first two phases. Items are never split across steps, so a step may write more than its
+/// budget if an item is large, and always makes progress on at least one item even if the budget
+/// is zero.
+///
+/// The archiver owns its serializer until archiving is finished. The bytes written so far can be
+/// read through [`serializer`](ResumableArchiver::serializer) between steps, for example to flush
+/// them to a socket or file as they're produced instead of all at once. If a step returns an
+/// error, the archive can't be finished and stepping the archiver again panics.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     archived_root,
+///     ser::{
+///         resumable::{ArchiveProgress, ResumableArchiver},
+///         serializers::AlignedSerializer,
+///         Serializer,
+///     },
+///     AlignedVec,
+/// };
+///
+/// let items = (0..1000).map(|i| format!("item #{}", i)).collect::<Vec<_>>();
+///
+/// let mut archiver = ResumableArchiver::new(&items, AlignedSerializer::new(AlignedVec::new()));
+/// let mut steps = 0;
+/// loop {
+///     steps += 1;
+///     // Do at most about 1KiB of work per frame
+///     if let ArchiveProgress::Complete(info) = archiver.step(1024).unwrap() {
+///         assert_eq!(info.total_len, archiver.serializer().pos());
+///         break;
+///     }
+///     // ... do the rest of the frame's work ...
+/// }
+/// assert!(steps > 1);
+///
+/// let bytes = archiver.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<Vec<String>>(&bytes) };
+/// assert_eq!(archived.len(), 1000);
+/// assert_eq!(archived[999], "item #999");
+/// ```
+pub struct ResumableArchiver<'a, T: Archive, S> {
+    items: &'a [T],
+    serializer: S,
+    state: State<T::Resolver>,
+}
+
+impl<'a, T: Serialize<S>, S: Serializer> ResumableArchiver<'a, T, S> {
+    /// Creates a new resumable archiver that archives the given items with the given serializer.
+    ///
+    /// Nothing is written until the archiver is stepped.
+    #[inline]
+    pub fn new(items: &'a [T], serializer: S) -> Self {
+        Self {
+            items,
+            serializer,
+            state: State::Dependencies {
+                resolvers: Vec::with_capacity(items.len()),
+            },
+        }
+    }
+
+    /// Returns a reference to the serializer.
+    #[inline]
+    pub fn serializer(&self) -> &S {
+        &self.serializer
+    }
+
+    /// Consumes the archiver and returns the serializer.
+    ///
+    /// If the archive is not finished, the serializer holds an incomplete archive.
+    #[inline]
+    pub fn into_serializer(self) -> S {
+        self.serializer
+    }
+
+    /// Returns `true` if the archive is finished.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        matches!(self.state, State::Complete(_))
+    }
+
+    /// Returns the number of items that have been fully archived.
+    ///
+    /// Each item is counted once its archived form has been written into the array of items, so
+    /// this stays at zero while the out-of-line data of the items is being serialized.
+    #[inline]
+    pub fn items_archived(&self) -> usize {
+        match self.state {
+            State::Dependencies { .. } | State::Poisoned => 0,
+            State::Items { next, .. } => next,
+            State::Root { .. } | State::Complete(_) => self.items.len(),
+        }
+    }
+
+    /// Does about `budget` bytes of archiving work and returns how far the archive has gotten.
+    ///
+    /// Once the archive is finished, stepping again returns the same completed progress without
+    /// writing anything.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a previous step returned an error.
+    pub fn step(&mut self, budget: usize) -> Result<ArchiveProgress, S::Error> {
+        let start = self.serializer.pos();
+        let state = core::mem::replace(&mut self.state, State::Poisoned);
+        self.state = self.step_state(state, start, budget)?;
+
+        match self.state {
+            State::Complete(info) => Ok(ArchiveProgress::Complete(info)),
+            _ => Ok(ArchiveProgress::Pending {
+                written: self.serializer.pos() - start,
+            }),
+        }
+    }
+
+    /// Steps the archiver until the archive is finished and returns the serializer along with the
+    /// position and length of the archive.
+    #[inline]
+    pub fn finish(mut self) -> Result<(S, ArchiveInfo), S::Error> {
+        loop {
+            if let ArchiveProgress::Complete(info) = self.step(usize::MAX)? {
+                return Ok((self.serializer, info));
+            }
+        }
+    }
+
+    #[inline]
+    fn over_budget(&self, start: usize, budget: usize) -> bool {
+        self.serializer.pos() - start >= budget
+    }
+
+    fn step_state(
+        &mut self,
+        mut state: State<T::Resolver>,
+        start: usize,
+        budget: usize,
+    ) -> Result<State<T::Resolver>, S::Error> {
+        loop {
+            state = match state {
+                State::Dependencies { mut resolvers } => {
+                    while resolvers.len() < self.items.len() {
+                        resolvers
+                            .push(self.items[resolvers.len()].serialize(&mut self.serializer)?);
+                        if self.over_budget(start, budget) {
+                            return Ok(State::Dependencies { resolvers });
+                        }
+                    }
+                    State::Items {
+                        array_pos: self.serializer.align_for::<T::Archived>()?,
+                        resolvers: resolvers.into_iter(),
+                        next: 0,
+                    }
+                }
+                State::Items {
+                    array_pos,
+                    mut resolvers,
+                    mut next,
+                } => {
+                    while let Some(resolver) = resolvers.next() {
+                        unsafe {
+                            self.serializer
+                                .resolve_aligned(&self.items[next], resolver)?;
+                        }
+                        next += 1;
+                        if next < self.items.len() && self.over_budget(start, budget) {
+                            return Ok(State::Items {
+                                array_pos,
+                                resolvers,
+                                next,
+                            });
+                        }
+                    }
+                    State::Root { array_pos }
+                }
+                State::Root { array_pos } => {
+                    self.serializer.align_for::<ArchivedVec<T::Archived>>()?;
+                    let root_pos = unsafe {
+                        self.serializer
+                            .resolve_aligned(&Root(self.items), VecResolver { pos: array_pos })?
+                    };
+                    return Ok(State::Complete(ArchiveInfo {
+                        root_pos,
+                        total_len: self.serializer.pos(),
+                    }));
+                }
+                State::Complete(info) => return Ok(State::Complete(info)),
+                State::Poisoned => {
+                    panic!("stepped a resumable archiver after it returned an error")
+                }
+            };
+        }
+    }
+}
+
+impl<'a, T: Archive, S> fmt::Debug for ResumableArchiver<'a, T, S> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self.state {
+            State::Dependencies { .. } => "dependencies",
+            State::Items { .. } => "items",
+            State::Root { .. } => "root",
+            State::Complete(_) => "complete",
+            State::Poisoned => "poisoned",
+        };
+        f.debug_struct("ResumableArchiver")
+            .field("len", &self.items.len())
+            .field("state", &state)
+            .finish()
+    }
+}
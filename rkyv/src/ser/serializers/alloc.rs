@@ -1,13 +1,27 @@
 use crate::{
-    ser::{serializers::BufferScratch, ScratchSpace, Serializer, SharedSerializeRegistry},
-    AlignedBytes, AlignedVec, Archive, ArchiveUnsized, Fallible, RelPtr,
+    ser::{
+        serializers::{BufferScratch, StrideHeader},
+        ScratchSpace, Serializer, SerializerLayer, SharedSerializeRegistry, StringInterner,
+    },
+    string::intern::InternTable,
+    AlignedBytes, AlignedVec, Archive, ArchiveFixedSize, ArchiveUnsized, Fallible, FixedUsize,
+    RelPtr, Serialize,
 };
 #[cfg(not(feature = "std"))]
-use ::alloc::{alloc, boxed::Box, vec::Vec};
+use ::alloc::{
+    alloc,
+    boxed::Box,
+    collections::{btree_map, vec_deque, BTreeMap, VecDeque},
+    vec::Vec,
+};
 #[cfg(feature = "std")]
-use ::std::alloc;
+use ::std::{
+    alloc,
+    collections::{btree_map, vec_deque, BTreeMap, VecDeque},
+};
 use core::{
     alloc::Layout,
+    any,
     borrow::{Borrow, BorrowMut},
     convert::Infallible,
     fmt, mem,
@@ -34,6 +48,12 @@ impl<A: Borrow<AlignedVec>> AlignedSerializer<A> {
         Self { inner }
     }
 
+    /// Returns a reference to the underlying type.
+    #[inline]
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
     /// Consumes the serializer and returns the underlying type.
     #[inline]
     pub fn into_inner(self) -> A {
@@ -41,6 +61,41 @@ impl<A: Borrow<AlignedVec>> AlignedSerializer<A> {
     }
 }
 
+impl AlignedSerializer<AlignedVec> {
+    /// Creates a new `AlignedSerializer` with exactly enough capacity to serialize a `T`.
+    ///
+    /// Serializing a `T` into the returned serializer does not reallocate. This only applies to
+    /// types with a bounded archived size, see [`ArchiveFixedSize`] for more details.
+    ///
+    /// # Examples
+    /// ```
+    /// use rkyv::{
+    ///     archived_root,
+    ///     ser::{serializers::AlignedSerializer, Serializer},
+    ///     ArchiveFixedSize,
+    /// };
+    ///
+    /// let value = [Some(1u32), None, Some(3)];
+    ///
+    /// let mut serializer = AlignedSerializer::for_value::<[Option<u32>; 3]>();
+    /// serializer.serialize_value(&value).unwrap();
+    /// let bytes = serializer.into_inner();
+    /// assert_eq!(bytes.len(), <[Option<u32>; 3]>::ARCHIVED_SIZE);
+    ///
+    /// let archived = unsafe { archived_root::<[Option<u32>; 3]>(&bytes) };
+    /// assert_eq!(archived[2], Some(3));
+    /// ```
+    #[inline]
+    pub fn for_value<T: ArchiveFixedSize>() -> Self {
+        check_align!(
+            T::ARCHIVED_ALIGN <= AlignedVec::ALIGNMENT,
+            "archived alignment {} is greater than the alignment of AlignedVec",
+            T::ARCHIVED_ALIGN,
+        );
+        Self::new(AlignedVec::with_capacity(T::ARCHIVED_SIZE))
+    }
+}
+
 impl<A: Default> Default for AlignedSerializer<A> {
     #[inline]
     fn default() -> Self {
@@ -73,15 +128,23 @@ impl<A: Borrow<AlignedVec> + BorrowMut<AlignedVec>> Serializer for AlignedSerial
         resolver: T::Resolver,
     ) -> Result<usize, Self::Error> {
         let pos = self.pos();
-        debug_assert_eq!(pos & (mem::align_of::<T::Archived>() - 1), 0);
+        check_align!(
+            pos & (mem::align_of::<T::Archived>() - 1) == 0,
+            "position {} is not aligned to {}",
+            pos,
+            mem::align_of::<T::Archived>(),
+        );
         let vec = self.inner.borrow_mut();
         let additional = mem::size_of::<T::Archived>();
         vec.reserve(additional);
         vec.set_len(vec.len() + additional);
 
-        let ptr = vec.as_mut_ptr().add(pos).cast::<T::Archived>();
-        ptr.write_bytes(0, 1);
-        value.resolve(pos, resolver, ptr);
+        // The buffer is only aligned to `AlignedVec::ALIGNMENT`, so the archived type may be
+        // misaligned in memory if its alignment is greater. Zero it bytewise instead of through a
+        // typed pointer.
+        let ptr = vec.as_mut_ptr().add(pos);
+        ptr.write_bytes(0, additional);
+        value.resolve(pos, resolver, ptr.cast::<T::Archived>());
 
         Ok(pos)
     }
@@ -94,7 +157,12 @@ impl<A: Borrow<AlignedVec> + BorrowMut<AlignedVec>> Serializer for AlignedSerial
         metadata_resolver: T::MetadataResolver,
     ) -> Result<usize, Self::Error> {
         let from = self.pos();
-        debug_assert_eq!(from & (mem::align_of::<RelPtr<T::Archived>>() - 1), 0);
+        check_align!(
+            from & (mem::align_of::<RelPtr<T::Archived>>() - 1) == 0,
+            "position {} is not aligned to {}",
+            from,
+            mem::align_of::<RelPtr<T::Archived>>(),
+        );
         let vec = self.inner.borrow_mut();
         let additional = mem::size_of::<RelPtr<T::Archived>>();
         vec.reserve(additional);
@@ -365,3 +433,953 @@ impl SharedSerializeRegistry for SharedSerializeMap {
         }
     }
 }
+
+/// Errors that can occur while serializing records into a [`RingSerializer`].
+#[derive(Debug)]
+pub enum RingSerializerError {
+    /// A record was larger than the capacity of the ring buffer.
+    RecordTooLarge {
+        /// The length of the record in bytes
+        len: usize,
+        /// The capacity of the ring buffer in bytes
+        capacity: usize,
+    },
+    /// An error occurred while using scratch space
+    ScratchSpaceError(AllocScratchError),
+}
+
+impl fmt::Display for RingSerializerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RecordTooLarge { len, capacity } => write!(
+                f,
+                "record too large for ring buffer: length {}, capacity {}",
+                len, capacity
+            ),
+            Self::ScratchSpaceError(e) => write!(f, "scratch space error: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl Error for RingSerializerError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                Self::RecordTooLarge { .. } => None,
+                Self::ScratchSpaceError(e) => Some(e as &dyn Error),
+            }
+        }
+    }
+};
+
+#[derive(Clone, Copy, Debug)]
+struct RingRecord {
+    start: usize,
+    len: usize,
+}
+
+/// A serializer that archives records into a fixed-size ring buffer, overwriting the oldest
+/// records when it runs out of space.
+///
+/// Each call to [`serialize_record`](RingSerializer::serialize_record) archives a value as a
+/// separate, self-contained record with its root at the end. A record is fully serialized before
+/// it is copied into the ring, so the relative pointers inside of it only ever point to other
+/// bytes in the same record. Records never straddle the end of the ring: if a record doesn't fit
+/// in the space left before the end, it is written to the start of the ring instead. Records are
+/// placed at positions aligned to [`AlignedVec::ALIGNMENT`], so types with a greater alignment
+/// should not be archived in a ring.
+///
+/// # Examples
+/// ```
+/// use rkyv::{archived_root, ser::serializers::RingSerializer, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Event {
+///     frame: u32,
+///     name: String,
+/// }
+///
+/// let mut ring = RingSerializer::new(128);
+/// for frame in 0..10 {
+///     ring.serialize_record(&Event {
+///         frame,
+///         name: format!("event number {}", frame),
+///     })
+///     .unwrap();
+/// }
+///
+/// // Only the most recent events are still live
+/// let frames = ring
+///     .records()
+///     .map(|bytes| u32::from(unsafe { archived_root::<Event>(bytes) }.frame))
+///     .collect::<Vec<_>>();
+/// assert_eq!(frames.last(), Some(&9));
+/// assert!(frames.len() < 10);
+/// ```
+#[derive(Debug)]
+pub struct RingSerializer {
+    ring: AlignedVec,
+    records: VecDeque<RingRecord>,
+    tail: usize,
+    record: AlignedVec,
+    scratch: AllocScratch,
+}
+
+impl RingSerializer {
+    /// Creates a new ring serializer with space for `capacity` bytes of records.
+    ///
+    /// The capacity is rounded up to a multiple of [`AlignedVec::ALIGNMENT`].
+    pub fn new(capacity: usize) -> Self {
+        let capacity = (capacity + AlignedVec::ALIGNMENT - 1) & !(AlignedVec::ALIGNMENT - 1);
+        let mut ring = AlignedVec::with_capacity(capacity);
+        // Safety: the ring has enough capacity and all of its bytes are initialized to zero
+        unsafe {
+            ring.as_mut_ptr().write_bytes(0, capacity);
+            ring.set_len(capacity);
+        }
+
+        Self {
+            ring,
+            records: VecDeque::new(),
+            tail: 0,
+            record: AlignedVec::new(),
+            scratch: AllocScratch::new(),
+        }
+    }
+
+    /// Returns the capacity of the ring buffer in bytes.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// Returns the number of live records in the ring buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns whether the ring buffer has no live records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Archives the given value as a new record, overwriting the oldest records if there is not
+    /// enough space left in the ring.
+    ///
+    /// If the archived record is larger than the capacity of the ring, the ring is left unchanged
+    /// and an error is returned.
+    pub fn serialize_record<T: Serialize<Self>>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), RingSerializerError> {
+        self.record.clear();
+        self.serialize_value(value)?;
+
+        let len = self.record.len();
+        let capacity = self.capacity();
+        if len > capacity {
+            return Err(RingSerializerError::RecordTooLarge { len, capacity });
+        }
+
+        let mut start = (self.tail + AlignedVec::ALIGNMENT - 1) & !(AlignedVec::ALIGNMENT - 1);
+        if start + len > capacity {
+            // The records between the tail and the end of the ring are the oldest ones, and they
+            // are overwritten when the ring wraps around
+            while matches!(self.records.front(), Some(front) if front.start >= self.tail) {
+                self.records.pop_front();
+            }
+            start = 0;
+        }
+        while matches!(
+            self.records.front(),
+            Some(front) if front.start < start + len && start < front.start + front.len
+        ) {
+            self.records.pop_front();
+        }
+
+        self.ring.as_mut_slice()[start..start + len].copy_from_slice(self.record.as_slice());
+        self.records.push_back(RingRecord { start, len });
+        self.tail = start + len;
+
+        Ok(())
+    }
+
+    /// Returns an iterator over the bytes of the live records, from oldest to newest.
+    ///
+    /// The root of each record is located at the end of its bytes.
+    #[inline]
+    pub fn records(&self) -> RingRecords<'_> {
+        RingRecords {
+            ring: self.ring.as_slice(),
+            records: self.records.iter(),
+        }
+    }
+}
+
+impl Fallible for RingSerializer {
+    type Error = RingSerializerError;
+}
+
+impl Serializer for RingSerializer {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.record.len()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.record.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+impl ScratchSpace for RingSerializer {
+    #[inline]
+    unsafe fn push_scratch(&mut self, layout: Layout) -> Result<NonNull<[u8]>, Self::Error> {
+        self.scratch
+            .push_scratch(layout)
+            .map_err(RingSerializerError::ScratchSpaceError)
+    }
+
+    #[inline]
+    unsafe fn pop_scratch(&mut self, ptr: NonNull<u8>, layout: Layout) -> Result<(), Self::Error> {
+        self.scratch
+            .pop_scratch(ptr, layout)
+            .map_err(RingSerializerError::ScratchSpaceError)
+    }
+}
+
+/// An iterator over the live records of a [`RingSerializer`], from oldest to newest.
+pub struct RingRecords<'a> {
+    ring: &'a [u8],
+    records: vec_deque::Iter<'a, RingRecord>,
+}
+
+impl<'a> Iterator for RingRecords<'a> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.records
+            .next()
+            .map(|record| &self.ring[record.start..record.start + record.len])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.records.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for RingRecords<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.records
+            .next_back()
+            .map(|record| &self.ring[record.start..record.start + record.len])
+    }
+}
+
+impl<'a> ExactSizeIterator for RingRecords<'a> {}
+
+/// Errors that can occur while serializing elements into a [`StrideSerializer`].
+#[derive(Debug)]
+pub enum StrideSerializerError {
+    /// An archived element was larger than the stride of the array.
+    ElementTooLarge {
+        /// The length of the archived element in bytes
+        len: usize,
+        /// The stride of the array in bytes
+        stride: usize,
+    },
+    /// The alignment of an archived element does not evenly divide the stride of the array, or is
+    /// greater than [`AlignedVec::ALIGNMENT`].
+    InvalidAlignment {
+        /// The alignment of the archived element
+        align: usize,
+        /// The stride of the array in bytes
+        stride: usize,
+    },
+    /// An error occurred while using scratch space
+    ScratchSpaceError(AllocScratchError),
+}
+
+impl fmt::Display for StrideSerializerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ElementTooLarge { len, stride } => write!(
+                f,
+                "element too large for stride: length {}, stride {}",
+                len, stride
+            ),
+            Self::InvalidAlignment { align, stride } => write!(
+                f,
+                "element alignment incompatible with stride: alignment {}, stride {}",
+                align, stride
+            ),
+            Self::ScratchSpaceError(e) => write!(f, "scratch space error: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl Error for StrideSerializerError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                Self::ElementTooLarge { .. } | Self::InvalidAlignment { .. } => None,
+                Self::ScratchSpaceError(e) => Some(e as &dyn Error),
+            }
+        }
+    }
+};
+
+#[inline]
+fn extend_zeroed(vec: &mut AlignedVec, len: usize) {
+    vec.reserve(len);
+    // Safety: the vec has enough capacity for `len` more bytes, and they are all initialized
+    unsafe {
+        vec.as_mut_ptr().add(vec.len()).write_bytes(0, len);
+        vec.set_len(vec.len() + len);
+    }
+}
+
+/// A serializer that archives elements into an array where each one occupies exactly `stride`
+/// bytes.
+///
+/// The output begins with a [`StrideHeader`] recording the stride and number of elements, padded
+/// to [`StrideHeader::LEN`] bytes. The elements follow it back-to-back, so element `i` occupies the
+/// `stride` bytes starting at `StrideHeader::LEN + i * stride` and can be found with pointer
+/// arithmetic instead of an offset table. [`StrideArray`](super::StrideArray) reads arrays in this
+/// format.
+///
+/// Each element is archived as a self-contained archive: any out-of-line data it owns is written
+/// at the start of its slot, followed by zero padding, and its root is placed at the _end_ of the
+/// slot. Relative pointers inside of an element only ever point to other bytes in the same slot,
+/// and the bytes of each slot can be passed to [`archived_root`](crate::archived_root) directly.
+/// The slots of the array are only aligned to the stride, so it should be a multiple of the
+/// alignment of any out-of-line data in the elements as well.
+///
+/// # Errors
+///
+/// [`serialize_element`](StrideSerializer::serialize_element) returns
+/// [`ElementTooLarge`](StrideSerializerError::ElementTooLarge) if an archived element, including
+/// its out-of-line data, is longer than the stride. It returns
+/// [`InvalidAlignment`](StrideSerializerError::InvalidAlignment) if the alignment of the archived
+/// type does not evenly divide the stride or is greater than [`AlignedVec::ALIGNMENT`]. In both
+/// cases the array is left unchanged.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     ser::serializers::{StrideArray, StrideSerializer},
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Point {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+/// }
+///
+/// let mut serializer = StrideSerializer::new(16);
+/// for i in 0..4 {
+///     let f = i as f32;
+///     serializer.serialize_element(&Point { x: f, y: f * 2.0, z: f * 3.0 }).unwrap();
+/// }
+/// let bytes = serializer.into_inner();
+///
+/// let array = StrideArray::new(&bytes).unwrap();
+/// assert_eq!(array.stride(), 16);
+/// assert_eq!(array.len(), 4);
+/// let point = unsafe { array.get_archived::<Point>(3) }.unwrap();
+/// assert_eq!(point.z, 9.0);
+/// ```
+#[derive(Debug)]
+pub struct StrideSerializer {
+    bytes: AlignedVec,
+    stride: usize,
+    len: usize,
+    element: AlignedVec,
+    scratch: AllocScratch,
+}
+
+impl StrideSerializer {
+    /// Creates a new stride serializer that archives each element into `stride` bytes.
+    pub fn new(stride: usize) -> Self {
+        let mut bytes = AlignedVec::new();
+        extend_zeroed(&mut bytes, StrideHeader::LEN);
+        let mut result = Self {
+            bytes,
+            stride,
+            len: 0,
+            element: AlignedVec::new(),
+            scratch: AllocScratch::new(),
+        };
+        result.write_header();
+        result
+    }
+
+    #[inline]
+    fn write_header(&mut self) {
+        let header = StrideHeader {
+            stride: to_archived!(self.stride as FixedUsize),
+            len: to_archived!(self.len as FixedUsize),
+        };
+        // Safety: the output always starts with `StrideHeader::LEN` bytes reserved for the header,
+        // and the output is aligned to `AlignedVec::ALIGNMENT`
+        unsafe {
+            self.bytes.as_mut_ptr().cast::<StrideHeader>().write(header);
+        }
+    }
+
+    /// Returns the stride of the array in bytes.
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns the number of elements in the array.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the array has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Archives the given value as the next element of the array.
+    ///
+    /// If the archived value does not fit in the stride, the array is left unchanged and an error
+    /// is returned.
+    pub fn serialize_element<T: Serialize<Self>>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), StrideSerializerError> {
+        let align = mem::align_of::<T::Archived>();
+        if align > AlignedVec::ALIGNMENT || self.stride & (align - 1) != 0 {
+            return Err(StrideSerializerError::InvalidAlignment {
+                align,
+                stride: self.stride,
+            });
+        }
+
+        self.element.clear();
+        let resolver = value.serialize(self)?;
+
+        // The root is placed at the end of the slot, after any out-of-line data and padding
+        let data_len = self.element.len();
+        let root_len = mem::size_of::<T::Archived>();
+        let len = data_len + root_len;
+        if len > self.stride {
+            return Err(StrideSerializerError::ElementTooLarge {
+                len,
+                stride: self.stride,
+            });
+        }
+        let root_pos = self.stride - root_len;
+        extend_zeroed(&mut self.element, self.stride - data_len);
+        // Safety: the bytes of the root are reserved at the end of the element, and `root_pos` is
+        // aligned for the root because both the stride and its size are multiples of its alignment
+        unsafe {
+            value.resolve(
+                root_pos,
+                resolver,
+                self.element
+                    .as_mut_ptr()
+                    .add(root_pos)
+                    .cast::<T::Archived>(),
+            );
+        }
+
+        self.bytes.extend_from_slice(self.element.as_slice());
+        self.len += 1;
+        self.write_header();
+
+        Ok(())
+    }
+
+    /// Consumes the serializer and returns the bytes of the array, starting with its header.
+    #[inline]
+    pub fn into_inner(self) -> AlignedVec {
+        self.bytes
+    }
+}
+
+impl Fallible for StrideSerializer {
+    type Error = StrideSerializerError;
+}
+
+impl Serializer for StrideSerializer {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.element.len()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.element.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+impl ScratchSpace for StrideSerializer {
+    #[inline]
+    unsafe fn push_scratch(&mut self, layout: Layout) -> Result<NonNull<[u8]>, Self::Error> {
+        self.scratch
+            .push_scratch(layout)
+            .map_err(StrideSerializerError::ScratchSpaceError)
+    }
+
+    #[inline]
+    unsafe fn pop_scratch(&mut self, ptr: NonNull<u8>, layout: Layout) -> Result<(), Self::Error> {
+        self.scratch
+            .pop_scratch(ptr, layout)
+            .map_err(StrideSerializerError::ScratchSpaceError)
+    }
+}
+
+/// The number of bytes that each type contributed to an archive, as recorded by a
+/// [`ProfilingSerializer`].
+///
+/// Bytes that were written while no type was being serialized are recorded under
+/// [`UNTYPED`](ArchiveProfile::UNTYPED), and padding inserted to align values is recorded under
+/// [`PADDING`](ArchiveProfile::PADDING). The bytes recorded for all types add up to the number of
+/// bytes written to the serializer.
+///
+/// The `Display` implementation formats the profile as a table sorted from the largest contributor
+/// to the smallest.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ArchiveProfile {
+    bytes: BTreeMap<&'static str, usize>,
+}
+
+impl ArchiveProfile {
+    /// The name that bytes written outside of any type are recorded under.
+    pub const UNTYPED: &'static str = "<untyped>";
+    /// The name that padding bytes are recorded under.
+    pub const PADDING: &'static str = "<padding>";
+
+    /// Creates a new empty profile.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn record(&mut self, type_name: &'static str, len: usize) {
+        if len != 0 {
+            *self.bytes.entry(type_name).or_insert(0) += len;
+        }
+    }
+
+    /// Returns the number of bytes recorded for the type with the given name.
+    ///
+    /// Type names are the ones returned by [`core::any::type_name`], except for `str` which is
+    /// recorded as `"str"`.
+    #[inline]
+    pub fn get(&self, type_name: &str) -> usize {
+        self.bytes.get(type_name).copied().unwrap_or(0)
+    }
+
+    /// Returns the total number of bytes recorded for all types.
+    #[inline]
+    pub fn total_bytes(&self) -> usize {
+        self.bytes.values().sum()
+    }
+
+    /// Returns whether no bytes have been recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Gets an iterator over the type names and byte counts of the profile, ordered by type name.
+    #[inline]
+    pub fn iter(&self) -> ArchiveProfileIter<'_> {
+        ArchiveProfileIter {
+            inner: self.bytes.iter(),
+        }
+    }
+
+    /// Returns the type names and byte counts of the profile, ordered from the largest byte count
+    /// to the smallest.
+    pub fn sorted_by_size(&self) -> Vec<(&'static str, usize)> {
+        let mut result = self.iter().collect::<Vec<_>>();
+        result.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        result
+    }
+}
+
+impl fmt::Display for ArchiveProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total_bytes();
+        writeln!(f, "{:>12}  {:>6}  type", "bytes", "%")?;
+        for (type_name, bytes) in self.sorted_by_size() {
+            let percent = bytes as f64 * 100.0 / total as f64;
+            writeln!(f, "{:>12}  {:>5.1}%  {}", bytes, percent, type_name)?;
+        }
+        write!(f, "{:>12}  {:>5.1}%  total", total, 100.0)
+    }
+}
+
+/// An iterator over the type names and byte counts of an [`ArchiveProfile`].
+pub struct ArchiveProfileIter<'a> {
+    inner: btree_map::Iter<'a, &'static str, usize>,
+}
+
+impl<'a> Iterator for ArchiveProfileIter<'a> {
+    type Item = (&'static str, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (*k, *v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for ArchiveProfileIter<'a> {}
+
+/// A serializer that records how many bytes each type contributes to an archive.
+///
+/// This is a debugging tool for finding out what makes an archive large. It wraps another
+/// serializer and forwards everything to it, while attributing every byte written to a type:
+///
+/// - The archived form of a value written with
+///   [`resolve_aligned`](Serializer::resolve_aligned) (for example, the root or each element of
+///   an archived `Vec`) is attributed to the type of the value. The fields of a struct are part of
+///   its archived form, so they are attributed to the struct.
+/// - Out-of-line bytes written while serializing a value (for example, the contents of a string or
+///   a `Vec` of plain data) are attributed to the innermost value being serialized with
+///   [`serialize_value`](Serializer::serialize_value) (like the root or the contents of a `Box`),
+///   or to `str` for the contents of strings.
+/// - Padding is attributed to [`ArchiveProfile::PADDING`].
+///
+/// Serialize implementations can attribute the out-of-line bytes they write to a type of their
+/// choosing with the [`begin_type`](Serializer::begin_type) and [`end_type`](Serializer::end_type)
+/// markers, like the implementation for `str` does.
+///
+/// # Overhead
+///
+/// Every write looks up its type in a map and every serialized value pushes and pops a stack
+/// entry, so serializing with a profiler is noticeably slower than without. The markers are no-ops
+/// for every other serializer, so they cost nothing when profiling is not in use.
+///
+/// The profiler must be the outermost serializer: wrappers such as
+/// [`CompositeSerializer`](super::CompositeSerializer) do not forward the markers to the
+/// serializers they contain. Wrap the whole serializer with a profiler instead.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     ser::{
+///         serializers::{AllocSerializer, ProfilingSerializer},
+///         Serializer,
+///     },
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Document {
+///     title: String,
+///     words: Vec<u32>,
+/// }
+///
+/// let mut serializer = ProfilingSerializer::new(AllocSerializer::<256>::default());
+/// serializer
+///     .serialize_value(&Document {
+///         title: "a title long enough to be stored out-of-line".to_string(),
+///         words: (0..16).collect(),
+///     })
+///     .unwrap();
+/// let (serializer, profile) = serializer.into_parts();
+/// let bytes = serializer.into_serializer().into_inner();
+///
+/// assert_eq!(profile.total_bytes(), bytes.len());
+/// assert_eq!(profile.get("str"), 44);
+/// println!("{}", profile);
+/// ```
+#[derive(Debug)]
+pub struct ProfilingSerializer<S> {
+    inner: S,
+    stack: Vec<&'static str>,
+    profile: ArchiveProfile,
+}
+
+impl<S> ProfilingSerializer<S> {
+    /// Creates a new profiling serializer that wraps the given serializer.
+    #[inline]
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            stack: Vec::new(),
+            profile: ArchiveProfile::new(),
+        }
+    }
+
+    /// Returns the profile recorded so far.
+    #[inline]
+    pub fn profile(&self) -> &ArchiveProfile {
+        &self.profile
+    }
+
+    /// Consumes the profiling serializer and returns the wrapped serializer.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// Consumes the profiling serializer and returns the wrapped serializer and the recorded
+    /// profile.
+    #[inline]
+    pub fn into_parts(self) -> (S, ArchiveProfile) {
+        (self.inner, self.profile)
+    }
+
+    #[inline]
+    fn current_type(&self) -> &'static str {
+        self.stack
+            .last()
+            .copied()
+            .unwrap_or(ArchiveProfile::UNTYPED)
+    }
+}
+
+impl<S: Default> Default for ProfilingSerializer<S> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S: Fallible> Fallible for ProfilingSerializer<S> {
+    type Error = S::Error;
+}
+
+impl<S: Serializer> Serializer for ProfilingSerializer<S> {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+
+    #[inline]
+    fn begin_type(&mut self, type_name: &'static str) {
+        self.stack.push(type_name);
+    }
+
+    #[inline]
+    fn end_type(&mut self) {
+        self.stack.pop();
+    }
+
+    #[inline]
+    fn serialize_value<T: Serialize<Self>>(&mut self, value: &T) -> Result<usize, Self::Error> {
+        self.begin_type(any::type_name::<T>());
+        let resolver = value.serialize(self);
+        self.end_type();
+        let resolver = resolver?;
+        self.align_for::<T::Archived>()?;
+        unsafe { self.resolve_aligned(value, resolver) }
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        let type_name = self.current_type();
+        self.profile.record(type_name, bytes.len());
+        self.inner.write(bytes)
+    }
+
+    #[inline]
+    fn pad(&mut self, padding: usize) -> Result<(), Self::Error> {
+        self.profile.record(ArchiveProfile::PADDING, padding);
+        self.inner.pad(padding)
+    }
+
+    #[inline]
+    unsafe fn resolve_aligned<T: Archive + ?Sized>(
+        &mut self,
+        value: &T,
+        resolver: T::Resolver,
+    ) -> Result<usize, Self::Error> {
+        self.profile
+            .record(any::type_name::<T>(), mem::size_of::<T::Archived>());
+        self.inner.resolve_aligned(value, resolver)
+    }
+
+    #[inline]
+    unsafe fn resolve_unsized_aligned<T: ArchiveUnsized + ?Sized>(
+        &mut self,
+        value: &T,
+        to: usize,
+        metadata_resolver: T::MetadataResolver,
+    ) -> Result<usize, Self::Error> {
+        self.profile
+            .record(any::type_name::<T>(), mem::size_of::<RelPtr<T::Archived>>());
+        self.inner
+            .resolve_unsized_aligned(value, to, metadata_resolver)
+    }
+}
+
+impl<S: ScratchSpace> ScratchSpace for ProfilingSerializer<S> {
+    #[inline]
+    unsafe fn push_scratch(&mut self, layout: Layout) -> Result<NonNull<[u8]>, Self::Error> {
+        self.inner.push_scratch(layout)
+    }
+
+    #[inline]
+    unsafe fn pop_scratch(&mut self, ptr: NonNull<u8>, layout: Layout) -> Result<(), Self::Error> {
+        self.inner.pop_scratch(ptr, layout)
+    }
+}
+
+impl<S: SharedSerializeRegistry> SharedSerializeRegistry for ProfilingSerializer<S> {
+    #[inline]
+    fn get_shared_ptr(&mut self, value: *const u8) -> Option<usize> {
+        self.inner.get_shared_ptr(value)
+    }
+
+    #[inline]
+    fn add_shared_ptr(&mut self, value: *const u8, pos: usize) -> Result<(), Self::Error> {
+        self.inner.add_shared_ptr(value, pos)
+    }
+}
+
+/// An adapter that adds support for interning strings to a serializer.
+///
+/// Every string interned while serializing is collected into an [`InternTable`], and the table is
+/// written into the archive with [`serialize_intern_table`](InternSerializer::serialize_intern_table)
+/// after all of the interned values have been serialized. See the
+/// [`intern`](crate::string::intern) module for how to access the table from the archive.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     archived_root, archived_value,
+///     ser::{serializers::{AllocSerializer, InternSerializer}, Serializer},
+///     string::intern::InternTable,
+///     with::Intern,
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Event {
+///     #[with(Intern)]
+///     kind: String,
+///     value: u32,
+/// }
+///
+/// let events = vec![
+///     Event { kind: "click".to_string(), value: 1 },
+///     Event { kind: "scroll".to_string(), value: 2 },
+///     Event { kind: "click".to_string(), value: 3 },
+/// ];
+///
+/// let mut serializer = InternSerializer::new(AllocSerializer::<256>::default());
+/// let events_pos = serializer.serialize_value(&events).unwrap();
+/// serializer.serialize_intern_table().unwrap();
+/// let bytes = serializer.into_inner().into_serializer().into_inner();
+///
+/// let archived = unsafe { archived_value::<Vec<Event>>(&bytes, events_pos) };
+/// let table = unsafe { archived_root::<InternTable>(&bytes) };
+/// assert_eq!(table.len(), 2);
+/// assert_eq!(archived[0].kind, archived[2].kind);
+/// assert_eq!(archived[1].kind.resolve(table), Some("scroll"));
+/// ```
+#[derive(Debug)]
+pub struct InternSerializer<S> {
+    inner: S,
+    table: InternTable,
+}
+
+impl<S> InternSerializer<S> {
+    /// Wraps the given serializer with an empty intern table.
+    #[inline]
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            table: InternTable::new(),
+        }
+    }
+
+    /// Returns the strings interned so far.
+    #[inline]
+    pub fn table(&self) -> &InternTable {
+        &self.table
+    }
+
+    /// Consumes the adapter and returns the wrapped serializer.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// Consumes the adapter and returns the wrapped serializer and the intern table.
+    #[inline]
+    pub fn into_parts(self) -> (S, InternTable) {
+        (self.inner, self.table)
+    }
+}
+
+impl<S: Serializer> InternSerializer<S> {
+    /// Writes the strings interned so far as an archived intern table and returns its position.
+    ///
+    /// Strings interned after the table is written are not in the archived table.
+    #[inline]
+    pub fn serialize_intern_table(&mut self) -> Result<usize, S::Error> {
+        self.inner.serialize_value(&self.table)
+    }
+}
+
+impl<S: Default> Default for InternSerializer<S> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(S::default())
+    }
+}
+
+impl<S: Fallible> Fallible for InternSerializer<S> {
+    type Error = S::Error;
+}
+
+impl<S: Serializer> SerializerLayer for InternSerializer<S> {
+    type Inner = S;
+
+    #[inline]
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        &mut self.inner
+    }
+
+    #[inline]
+    fn map_inner_error(error: S::Error) -> Self::Error {
+        error
+    }
+}
+
+impl_serializer_layer!(InternSerializer, except(StringInterner));
+
+impl<S: Fallible> StringInterner for InternSerializer<S> {
+    #[inline]
+    fn intern(&mut self, value: &str) -> Result<usize, Self::Error> {
+        Ok(self.table.intern(value))
+    }
+}
@@ -1,11 +1,11 @@
 use crate::{
-    ser::{ScratchSpace, Serializer},
-    Fallible,
+    ser::{ScratchSpace, Serializer, SharedSerializeRegistry},
+    Archive, Archived, Fallible,
 };
 use core::{
     alloc::Layout,
     fmt,
-    ops::DerefMut,
+    ops::{DerefMut, Range},
     ptr::{copy_nonoverlapping, NonNull},
 };
 
@@ -109,6 +109,45 @@ impl<T> BufferSerializer<T> {
     }
 }
 
+impl BufferSerializer<&mut [u8]> {
+    /// Creates a new archive buffer from a raw region of memory, such as a shared memory segment.
+    ///
+    /// Archives only contain relative pointers, so the bytes written to the region can be read back
+    /// from a different base address. This makes it possible to serialize into a shared memory
+    /// segment in one process and read it from another process that maps the segment elsewhere.
+    /// The base address of each mapping must be aligned at least as strictly as the types in the
+    /// archive.
+    ///
+    /// # Safety
+    ///
+    /// - `ptr` must be valid for reads and writes of `len` bytes
+    /// - The region must not be accessed through any other pointer while the serializer is alive
+    ///
+    /// # Examples
+    /// ```
+    /// use rkyv::{
+    ///     archived_value,
+    ///     ser::{serializers::BufferSerializer, Serializer},
+    ///     AlignedBytes,
+    /// };
+    ///
+    /// let mut region = AlignedBytes([0u8; 64]);
+    /// let mut serializer =
+    ///     unsafe { BufferSerializer::from_raw_parts(region.as_mut().as_mut_ptr(), 64) };
+    /// let pos = serializer.serialize_value(&[1u32, 2, 3]).unwrap();
+    ///
+    /// // The archive can be read from another address
+    /// let mut copy = AlignedBytes([0u8; 64]);
+    /// copy.as_mut().copy_from_slice(region.as_ref());
+    /// let archived = unsafe { archived_value::<[u32; 3]>(copy.as_ref(), pos) };
+    /// assert_eq!(archived, &[1, 2, 3]);
+    /// ```
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: *mut u8, len: usize) -> Self {
+        Self::new(core::slice::from_raw_parts_mut(ptr, len))
+    }
+}
+
 impl<T: Default> Default for BufferSerializer<T> {
     #[inline]
     fn default() -> Self {
@@ -149,6 +188,199 @@ impl<T: AsMut<[u8]>> Serializer for BufferSerializer<T> {
     }
 }
 
+/// The alignment of the mapped memory that a [`MappedSerializer`] writes to.
+///
+/// This is the largest alignment that graphics APIs commonly require for buffer offsets (like
+/// uniform buffer offsets and the `nonCoherentAtomSize` of Vulkan).
+pub const MAPPED_ALIGNMENT: usize = 256;
+
+/// Writes an archive directly into mapped memory, like a GPU staging buffer, with an explicit hook
+/// for flushing the bytes that were written.
+///
+/// Archiving into a `Vec` and then copying it into a mapped buffer costs an extra copy of every
+/// byte. A `MappedSerializer` writes straight into the mapped memory instead. Whenever bytes need
+/// to be made visible to the device, [`flush`](MappedSerializer::flush) calls the flush hook with
+/// the range of bytes that were written since the last flush (for example, to call
+/// `vkFlushMappedMemoryRanges` for memory that isn't host-coherent). Once the archive is written,
+/// [`finish`](MappedSerializer::finish) pads it and flushes the rest of it, after which the memory
+/// can be unmapped.
+///
+/// # Alignment
+///
+/// The mapped memory must be aligned to [`MAPPED_ALIGNMENT`] (256 bytes). Since positions in the
+/// archive are relative to the start of the mapped memory, every value in the archive is at an
+/// address that's aligned to its archived type, and [`align`](Serializer::align) can be used to
+/// place values at offsets with any alignment up to 256 bytes (like the start of a uniform block).
+/// Every range passed to the flush hook starts and ends on a 256-byte boundary or at the end of the
+/// mapped memory, and [`finish`](MappedSerializer::finish) pads the archive to a multiple of 256
+/// bytes.
+///
+/// When the `std` feature is enabled, the serializer also implements
+/// [`io::Write`](std::io::Write), where [`flush`](std::io::Write::flush) calls the flush hook.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     archived_value,
+///     ser::{serializers::MappedSerializer, Serializer},
+///     Archived,
+/// };
+///
+/// // Stands in for the memory of a mapped staging buffer
+/// #[repr(C, align(256))]
+/// struct Staging([u8; 1024]);
+/// let mut staging = Staging([0; 1024]);
+///
+/// let mut flushed = Vec::new();
+/// let mut serializer = unsafe {
+///     MappedSerializer::new(staging.0.as_mut_ptr(), staging.0.len(), |range| {
+///         // Flush the mapped memory range here
+///         flushed.push(range);
+///     })
+/// };
+/// let vertices = serializer.serialize_value(&[[0.0f32, 1.0, 0.0], [1.0, 0.0, 0.0]]).unwrap();
+/// // Start the indices on an offset that's suitable for binding on their own
+/// serializer.align(256).unwrap();
+/// let indices = serializer.serialize_value(&[0u16, 1, 0]).unwrap();
+/// let len = serializer.finish().unwrap();
+///
+/// assert_eq!(indices, 256);
+/// assert_eq!(len, 512);
+/// assert_eq!(flushed.len(), 1);
+/// assert_eq!(flushed[0], 0..512);
+/// let archived = unsafe { archived_value::<[[f32; 3]; 2]>(&staging.0, vertices) };
+/// assert_eq!(archived[1][0], 1.0);
+/// ```
+pub struct MappedSerializer<F> {
+    ptr: NonNull<u8>,
+    len: usize,
+    pos: usize,
+    flushed: usize,
+    flush: F,
+}
+
+impl<F: FnMut(Range<usize>)> MappedSerializer<F> {
+    /// Creates a new serializer that writes to the mapped memory at `ptr` and calls `flush` with
+    /// the ranges of bytes that need to be flushed.
+    ///
+    /// # Safety
+    ///
+    /// - `ptr` must be valid for writes of `len` bytes for as long as the serializer is alive, so
+    ///   the memory must stay mapped until the serializer is finished or dropped
+    /// - The memory must not be read or written through any other pointer while the serializer is
+    ///   alive. The device must not read the memory until the bytes it reads have been flushed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ptr` is null or not aligned to [`MAPPED_ALIGNMENT`].
+    #[inline]
+    pub unsafe fn new(ptr: *mut u8, len: usize, flush: F) -> Self {
+        assert!(
+            ptr as usize % MAPPED_ALIGNMENT == 0,
+            "mapped memory must be aligned to {} bytes",
+            MAPPED_ALIGNMENT,
+        );
+        Self {
+            ptr: NonNull::new(ptr).expect("mapped memory must not be null"),
+            len,
+            pos: 0,
+            flushed: 0,
+            flush,
+        }
+    }
+
+    /// Returns the number of bytes of mapped memory that the serializer can write to.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.len
+    }
+
+    /// Calls the flush hook with the range of bytes that were written since the last flush.
+    ///
+    /// The range is widened to start and end on a 256-byte boundary (or at the end of the mapped
+    /// memory), so it may overlap with the previously flushed range. The hook isn't called if
+    /// nothing was written since the last flush.
+    #[inline]
+    pub fn flush(&mut self) {
+        if self.pos > self.flushed {
+            let start = self.flushed & !(MAPPED_ALIGNMENT - 1);
+            let end = ((self.pos + MAPPED_ALIGNMENT - 1) & !(MAPPED_ALIGNMENT - 1)).min(self.len);
+            (self.flush)(start..end);
+            self.flushed = self.pos;
+        }
+    }
+
+    /// Pads the archive to a multiple of 256 bytes, flushes the rest of it, and returns its length.
+    ///
+    /// After this returns, the mapped memory can be unmapped and the archive can be used by the
+    /// device. If there isn't enough mapped memory left to pad the archive, it's padded to the end
+    /// of the mapped memory instead.
+    #[inline]
+    pub fn finish(mut self) -> Result<usize, BufferSerializerError> {
+        let padded = (self.pos + MAPPED_ALIGNMENT - 1) & !(MAPPED_ALIGNMENT - 1);
+        self.pad(padded.min(self.len) - self.pos)?;
+        self.flush();
+        Ok(self.pos)
+    }
+}
+
+impl<F> fmt::Debug for MappedSerializer<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedSerializer")
+            .field("ptr", &self.ptr)
+            .field("len", &self.len)
+            .field("pos", &self.pos)
+            .field("flushed", &self.flushed)
+            .finish()
+    }
+}
+
+impl<F> Fallible for MappedSerializer<F> {
+    type Error = BufferSerializerError;
+}
+
+impl<F> Serializer for MappedSerializer<F> {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.pos
+    }
+
+    // Aligning to GPU offsets can take much more padding than the default implementation supports
+    fn pad(&mut self, padding: usize) -> Result<(), Self::Error> {
+        let end_pos = self.pos + padding;
+        if end_pos > self.len {
+            Err(BufferSerializerError::Overflow {
+                pos: self.pos,
+                bytes_needed: padding,
+                archive_len: self.len,
+            })
+        } else {
+            unsafe {
+                self.ptr.as_ptr().add(self.pos).write_bytes(0, padding);
+            }
+            self.pos = end_pos;
+            Ok(())
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        let end_pos = self.pos + bytes.len();
+        if end_pos > self.len {
+            Err(BufferSerializerError::Overflow {
+                pos: self.pos,
+                bytes_needed: bytes.len(),
+                archive_len: self.len,
+            })
+        } else {
+            unsafe {
+                copy_nonoverlapping(bytes.as_ptr(), self.ptr.as_ptr().add(self.pos), bytes.len());
+            }
+            self.pos = end_pos;
+            Ok(())
+        }
+    }
+}
+
 /// Errors that can occur when using a fixed-size allocator.
 ///
 /// Pairing a fixed-size allocator with a fallback allocator can help prevent running out of scratch
@@ -319,3 +551,218 @@ impl<M: ScratchSpace, F: ScratchSpace> ScratchSpace for FallbackScratch<M, F> {
             .or_else(|_| self.fallback.pop_scratch(ptr, layout))
     }
 }
+
+/// A serializer adapter that writes a delta archive to be layered on top of a base archive.
+///
+/// A delta archive is appended to the end of an existing base archive. All positions are anchored
+/// to the start of the base: the first byte written to the inner serializer is at position
+/// `base_len`, and positions below `base_len` refer to bytes in the base. Because relative pointers
+/// only depend on positions, objects in the delta can point to unchanged objects in the base
+/// without copying them.
+///
+/// To load the combined archive, place the delta bytes directly after the `base_len` bytes of the
+/// base in a single buffer with the usual alignment for archives. Positions returned while
+/// serializing the delta (like the root position) are positions in the combined buffer. The base
+/// is never modified, so it can still be read on its own and can have several deltas built on it.
+///
+/// The inner serializer must start empty. Scratch space and shared pointer tracking are forwarded
+/// to the inner serializer. Adding the positions of values in the base to the shared registry
+/// before serializing makes shared pointers to those values point into the base.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     archived_value,
+///     ser::{serializers::{AllocSerializer, DeltaSerializer}, Serializer},
+///     AlignedVec,
+/// };
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// let base_pos = serializer.serialize_value(&vec![1, 2, 3]).unwrap();
+/// let base = serializer.into_serializer().into_inner();
+///
+/// let mut serializer = DeltaSerializer::new(AllocSerializer::<256>::default(), base.len());
+/// let delta_pos = serializer.serialize_value(&vec![4, 5]).unwrap();
+/// let delta = serializer.into_inner().into_serializer().into_inner();
+///
+/// let mut combined = AlignedVec::new();
+/// combined.extend_from_slice(&base);
+/// combined.extend_from_slice(&delta);
+///
+/// let base_value = unsafe { archived_value::<Vec<i32>>(&combined, base_pos) };
+/// assert_eq!(base_value.as_slice(), &[1, 2, 3]);
+/// let delta_value = unsafe { archived_value::<Vec<i32>>(&combined, delta_pos) };
+/// assert_eq!(delta_value.as_slice(), &[4, 5]);
+/// ```
+#[derive(Debug)]
+pub struct DeltaSerializer<S> {
+    inner: S,
+    base_len: usize,
+}
+
+impl<S> DeltaSerializer<S> {
+    /// Wraps the given serializer to write a delta for a base archive that is `base_len` bytes
+    /// long.
+    #[inline]
+    pub fn new(inner: S, base_len: usize) -> Self {
+        Self { inner, base_len }
+    }
+
+    /// Returns the length of the base archive that the delta is anchored to.
+    #[inline]
+    pub fn base_len(&self) -> usize {
+        self.base_len
+    }
+
+    /// Consumes the adapter and returns the wrapped serializer.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Fallible> Fallible for DeltaSerializer<S> {
+    type Error = S::Error;
+}
+
+impl<S: Serializer> Serializer for DeltaSerializer<S> {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.base_len + self.inner.pos()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.inner.write(bytes)
+    }
+}
+
+impl<S: ScratchSpace> ScratchSpace for DeltaSerializer<S> {
+    #[inline]
+    unsafe fn push_scratch(&mut self, layout: Layout) -> Result<NonNull<[u8]>, Self::Error> {
+        self.inner.push_scratch(layout)
+    }
+
+    #[inline]
+    unsafe fn pop_scratch(&mut self, ptr: NonNull<u8>, layout: Layout) -> Result<(), Self::Error> {
+        self.inner.pop_scratch(ptr, layout)
+    }
+}
+
+impl<S: SharedSerializeRegistry> SharedSerializeRegistry for DeltaSerializer<S> {
+    #[inline]
+    fn get_shared_ptr(&mut self, value: *const u8) -> Option<usize> {
+        self.inner.get_shared_ptr(value)
+    }
+
+    #[inline]
+    fn add_shared_ptr(&mut self, value: *const u8, pos: usize) -> Result<(), Self::Error> {
+        self.inner.add_shared_ptr(value, pos)
+    }
+}
+
+/// The header at the start of a fixed-stride array.
+///
+/// The header is padded to [`StrideHeader::LEN`] bytes, and the elements of the array start
+/// immediately after it.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct StrideHeader {
+    /// The number of bytes occupied by each element
+    pub stride: Archived<usize>,
+    /// The number of elements in the array
+    pub len: Archived<usize>,
+}
+
+impl StrideHeader {
+    /// The number of bytes reserved for the header at the start of a fixed-stride array.
+    pub const LEN: usize = 16;
+}
+
+/// A view of an array written by a [`StrideSerializer`](crate::ser::serializers::StrideSerializer).
+///
+/// Element `i` of the array occupies the `stride` bytes starting at `StrideHeader::LEN + i * stride`,
+/// with its archived root at the end of that range.
+#[derive(Clone, Copy, Debug)]
+pub struct StrideArray<'a> {
+    bytes: &'a [u8],
+    stride: usize,
+    len: usize,
+}
+
+impl<'a> StrideArray<'a> {
+    /// Reads the header of a fixed-stride array from the given bytes.
+    ///
+    /// Returns `None` if the bytes are too short to hold the header and all of the elements it
+    /// describes.
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        if bytes.len() < StrideHeader::LEN {
+            return None;
+        }
+        // Safety: the bytes are long enough to hold a header, and the read is unaligned
+        let header = unsafe { bytes.as_ptr().cast::<StrideHeader>().read_unaligned() };
+        let stride = from_archived!(header.stride) as usize;
+        let len = from_archived!(header.len) as usize;
+        let end = stride
+            .checked_mul(len)
+            .and_then(|size| size.checked_add(StrideHeader::LEN))?;
+        if bytes.len() < end {
+            return None;
+        }
+
+        Some(Self {
+            bytes: &bytes[StrideHeader::LEN..end],
+            stride,
+            len,
+        })
+    }
+
+    /// Returns the stride of the array in bytes.
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns the number of elements in the array.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the array has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bytes of the element at the given index.
+    ///
+    /// The bytes of each element are a self-contained archive with the root at the end, so they
+    /// can be accessed with [`archived_root`](crate::archived_root) and validated with
+    /// `check_archived_root`.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&'a [u8]> {
+        if index < self.len {
+            let start = index * self.stride;
+            Some(&self.bytes[start..start + self.stride])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the archived element at the given index.
+    ///
+    /// # Safety
+    ///
+    /// The array must have been written by a `StrideSerializer` with elements of type `T`, and
+    /// the bytes passed to [`new`](StrideArray::new) must be aligned to at least the alignment of
+    /// `T::Archived`.
+    #[inline]
+    pub unsafe fn get_archived<T: Archive>(&self, index: usize) -> Option<&'a T::Archived> {
+        let root_pos = self
+            .stride
+            .checked_sub(core::mem::size_of::<T::Archived>())?;
+        self.get(index)
+            .map(|bytes| &*bytes.as_ptr().add(root_pos).cast::<T::Archived>())
+    }
+}
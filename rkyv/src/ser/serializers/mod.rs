@@ -10,6 +10,7 @@ mod std;
 use crate::AlignedVec;
 use crate::{
     ser::{ScratchSpace, Serializer, SharedSerializeRegistry},
+    with::{AsInlineVecError, SentinelError},
     AlignedBytes, Archive, ArchiveUnsized, Fallible, Infallible,
 };
 use ::core::{alloc::Layout, fmt, ptr::NonNull};
@@ -32,6 +33,24 @@ pub enum CompositeSerializerError<S, C, H> {
     ScratchSpaceError(C),
     /// An error occurred while serializing shared memory
     SharedError(H),
+    /// An inline vec had more elements than its capacity
+    InlineVecError(AsInlineVecError),
+    /// A `Some` archived with a sentinel contained the sentinel value
+    SentinelError(SentinelError),
+}
+
+impl<S, C, H> From<AsInlineVecError> for CompositeSerializerError<S, C, H> {
+    #[inline]
+    fn from(e: AsInlineVecError) -> Self {
+        Self::InlineVecError(e)
+    }
+}
+
+impl<S, C, H> From<SentinelError> for CompositeSerializerError<S, C, H> {
+    #[inline]
+    fn from(e: SentinelError) -> Self {
+        Self::SentinelError(e)
+    }
 }
 
 impl<S, C, H> fmt::Display for CompositeSerializerError<S, C, H>
@@ -45,6 +64,8 @@ where
             Self::SerializerError(e) => write!(f, "serialization error: {}", e),
             Self::ScratchSpaceError(e) => write!(f, "scratch space error: {}", e),
             Self::SharedError(e) => write!(f, "shared memory error: {}", e),
+            Self::InlineVecError(e) => write!(f, "inline vec error: {}", e),
+            Self::SentinelError(e) => write!(f, "sentinel error: {}", e),
         }
     }
 }
@@ -64,6 +85,8 @@ const _: () = {
                 Self::SerializerError(e) => Some(e as &dyn Error),
                 Self::ScratchSpaceError(e) => Some(e as &dyn Error),
                 Self::SharedError(e) => Some(e as &dyn Error),
+                Self::InlineVecError(e) => Some(e as &dyn Error),
+                Self::SentinelError(e) => Some(e as &dyn Error),
             }
         }
     }
@@ -94,6 +117,12 @@ impl<S, C, H> CompositeSerializer<S, C, H> {
         (self.serializer, self.scratch, self.shared)
     }
 
+    /// Returns a reference to the serializer component.
+    #[inline]
+    pub fn serializer(&self) -> &S {
+        &self.serializer
+    }
+
     /// Consumes the composite serializer and returns the serializer.
     ///
     /// The scratch space and shared component are discarded.
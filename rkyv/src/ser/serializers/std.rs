@@ -1,5 +1,13 @@
-use crate::{ser::Serializer, Fallible};
-use std::io;
+use crate::{
+    ser::{serializers::MappedSerializer, InstantEpoch, Serializer, SerializerLayer},
+    Fallible,
+};
+use std::{
+    error::Error,
+    fmt, io,
+    ops::Range,
+    time::{Duration, Instant},
+};
 
 /// Wraps a type that implements [`io::Write`](std::io::Write) and equips it with [`Serializer`].
 ///
@@ -42,6 +50,56 @@ impl<W: io::Write> WriteSerializer<W> {
     }
 }
 
+impl<W: io::Write + io::Seek> WriteSerializer<W> {
+    /// Creates a new serializer that appends to the end of a seekable writer, such as an existing
+    /// archive file.
+    ///
+    /// The writer is seeked to its end and the serializer starts at that position, so the relative
+    /// pointers of newly serialized values are correct within the whole file. Values are aligned
+    /// relative to the start of the file, so they can be accessed as long as the entire file is
+    /// loaded into a suitably aligned buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// use rkyv::{
+    ///     archived_value,
+    ///     ser::{serializers::WriteSerializer, Serializer},
+    ///     AlignedVec, Archive, Serialize,
+    /// };
+    /// use std::io::Cursor;
+    ///
+    /// #[derive(Archive, Serialize)]
+    /// struct Record {
+    ///     id: u32,
+    ///     message: String,
+    /// }
+    ///
+    /// // An existing log that already has some bytes in it
+    /// let log = Cursor::new(vec![0xff; 3]);
+    ///
+    /// let mut serializer = WriteSerializer::append(log).unwrap();
+    /// assert_eq!(serializer.pos(), 3);
+    /// let pos = serializer
+    ///     .serialize_value(&Record {
+    ///         id: 1,
+    ///         message: "appended to the end of the log".to_string(),
+    ///     })
+    ///     .unwrap();
+    /// let log = serializer.into_inner().into_inner();
+    ///
+    /// let mut buf = AlignedVec::new();
+    /// buf.extend_from_slice(&log);
+    /// let archived = unsafe { archived_value::<Record>(&buf, pos) };
+    /// assert_eq!(archived.id, 1);
+    /// assert_eq!(archived.message, "appended to the end of the log");
+    /// ```
+    #[inline]
+    pub fn append(mut inner: W) -> io::Result<Self> {
+        let pos = inner.seek(io::SeekFrom::End(0))?;
+        Ok(Self::with_pos(inner, pos as usize))
+    }
+}
+
 impl<W: io::Write> Fallible for WriteSerializer<W> {
     type Error = io::Error;
 }
@@ -59,3 +117,141 @@ impl<W: io::Write> Serializer for WriteSerializer<W> {
         Ok(())
     }
 }
+
+impl<F: FnMut(Range<usize>)> io::Write for MappedSerializer<F> {
+    // `io::Error::other` requires a newer compiler than the minimum supported version
+    #[allow(clippy::io_other_error)]
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.capacity() - self.pos());
+        if len == 0 && !buf.is_empty() {
+            return Err(io::ErrorKind::WriteZero.into());
+        }
+        Serializer::write(self, &buf[..len])
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        Ok(len)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        MappedSerializer::flush(self);
+        Ok(())
+    }
+}
+
+/// An error that can occur while serializing with an [`InstantSerializer`].
+#[derive(Debug)]
+pub enum InstantSerializerError<E> {
+    /// An instant was earlier than the base instant
+    BeforeBase,
+    /// An error occurred in the wrapped serializer
+    Inner(E),
+}
+
+impl<E: fmt::Display> fmt::Display for InstantSerializerError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BeforeBase => write!(f, "instant is earlier than the base instant"),
+            Self::Inner(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for InstantSerializerError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::BeforeBase => None,
+            Self::Inner(e) => Some(e as &dyn Error),
+        }
+    }
+}
+
+/// An adapter that adds support for serializing [`Instant`]s to a serializer.
+///
+/// Each `Instant` is archived as the duration elapsed since the base instant. Instants that are
+/// earlier than the base instant fail to serialize. See [`InstantEpoch`] for more information.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     archived_root,
+///     de::deserializers::InstantDeserializer,
+///     ser::{serializers::{AllocSerializer, InstantSerializer}, Serializer},
+///     Deserialize, Infallible,
+/// };
+/// use std::time::{Duration, Instant};
+///
+/// let base = Instant::now();
+/// let timings = vec![base + Duration::from_millis(5), base + Duration::from_secs(2)];
+///
+/// let mut serializer = InstantSerializer::new(AllocSerializer::<256>::default(), base);
+/// serializer.serialize_value(&timings).unwrap();
+/// let bytes = serializer.into_inner().into_serializer().into_inner();
+///
+/// let archived = unsafe { archived_root::<Vec<Instant>>(&bytes) };
+/// assert_eq!(archived[1].as_secs(), 2);
+///
+/// let new_base = Instant::now();
+/// let mut deserializer = InstantDeserializer::new(Infallible, new_base);
+/// let deserialized: Vec<Instant> = archived.deserialize(&mut deserializer).unwrap();
+/// assert_eq!(deserialized[1] - deserialized[0], timings[1] - timings[0]);
+/// ```
+#[derive(Debug)]
+pub struct InstantSerializer<S> {
+    inner: S,
+    base: Instant,
+}
+
+impl<S> InstantSerializer<S> {
+    /// Wraps the given serializer and archives instants relative to `base`.
+    #[inline]
+    pub fn new(inner: S, base: Instant) -> Self {
+        Self { inner, base }
+    }
+
+    /// Returns the base instant that instants are archived relative to.
+    #[inline]
+    pub fn base(&self) -> Instant {
+        self.base
+    }
+
+    /// Consumes the adapter and returns the wrapped serializer.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Fallible> Fallible for InstantSerializer<S> {
+    type Error = InstantSerializerError<S::Error>;
+}
+
+impl<S: Serializer> SerializerLayer for InstantSerializer<S> {
+    type Inner = S;
+
+    #[inline]
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        &mut self.inner
+    }
+
+    #[inline]
+    fn map_inner_error(error: S::Error) -> Self::Error {
+        InstantSerializerError::Inner(error)
+    }
+}
+
+impl_serializer_layer!(InstantSerializer, except(InstantEpoch));
+
+impl<S: Fallible> InstantEpoch for InstantSerializer<S> {
+    #[inline]
+    fn instant_offset(&mut self, instant: Instant) -> Result<Duration, Self::Error> {
+        instant
+            .checked_duration_since(self.base)
+            .ok_or(InstantSerializerError::BeforeBase)
+    }
+}
@@ -0,0 +1,335 @@
+//! Cheap estimates of archived sizes for pre-sizing buffers.
+
+use crate::Archive;
+use core::{
+    cmp::Ordering,
+    marker::{PhantomData, PhantomPinned},
+    mem::{align_of, size_of},
+    num::{
+        FpCategory, NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize,
+        NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+    ops::Bound,
+    sync::atomic,
+};
+
+/// A type that can cheaply estimate how many bytes it will archive to.
+///
+/// The estimate is a _hint_, not an exact size. It is computed from the sizes of the archived types
+/// and the lengths of any collections, without serializing anything. It includes some padding for
+/// each out-of-line allocation and is usually large enough to archive the value without growing
+/// the buffer, but it may be smaller than the actual archived size (for example, shared pointers
+/// are estimated as if they were unique, and the layout of archived B-trees is approximated). Use
+/// it to choose an initial buffer capacity, not to size a fixed buffer.
+///
+/// This trait can be derived for a type along with `Archive` by adding `#[archive(size_hint)]`.
+/// Fields archived with a wrapper are not included in the estimate.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{size_hint::ArchivedSizeHint, AlignedVec, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// #[archive(size_hint)]
+/// struct Example {
+///     id: u32,
+///     name: String,
+///     values: Vec<u64>,
+/// }
+///
+/// let value = Example {
+///     id: 42,
+///     name: "a name that is stored out-of-line".to_string(),
+///     values: vec![1, 2, 3, 4],
+/// };
+///
+/// let buffer = AlignedVec::with_capacity(value.archived_size_hint());
+/// # let _ = buffer;
+/// ```
+pub trait ArchivedSizeHint: Archive {
+    /// Returns an estimate of the number of bytes that the out-of-line data of this value (e.g.
+    /// the contents of a `Vec`) archives to, not including the archived value itself.
+    fn out_of_line_size_hint(&self) -> usize;
+
+    /// Returns an estimate of the number of bytes needed to archive this value as the root of an
+    /// archive.
+    #[inline]
+    fn archived_size_hint(&self) -> usize {
+        self.out_of_line_size_hint() + size_of::<Self::Archived>() + align_of::<Self::Archived>()
+            - 1
+    }
+}
+
+/// Estimates the size of an out-of-line slice of `len` archived `T`s, including padding to align
+/// it and the out-of-line data of each of the elements.
+#[inline]
+pub fn slice_size_hint<'a, T, I>(len: usize, iter: I) -> usize
+where
+    T: 'a + ArchivedSizeHint,
+    I: Iterator<Item = &'a T>,
+{
+    if len == 0 {
+        0
+    } else {
+        align_of::<T::Archived>() - 1
+            + len * size_of::<T::Archived>()
+            + iter.map(T::out_of_line_size_hint).sum::<usize>()
+    }
+}
+
+macro_rules! impl_fixed_size_hint {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl ArchivedSizeHint for $type {
+                #[inline]
+                fn out_of_line_size_hint(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_size_hint!(
+    (),
+    bool,
+    char,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    PhantomPinned,
+    Ordering,
+    FpCategory,
+    atomic::Ordering,
+);
+
+impl<T: ?Sized> ArchivedSizeHint for PhantomData<T> {
+    #[inline]
+    fn out_of_line_size_hint(&self) -> usize {
+        0
+    }
+}
+
+impl<T: ArchivedSizeHint> ArchivedSizeHint for Option<T> {
+    #[inline]
+    fn out_of_line_size_hint(&self) -> usize {
+        self.as_ref().map_or(0, T::out_of_line_size_hint)
+    }
+}
+
+impl<T: ArchivedSizeHint> ArchivedSizeHint for Bound<T> {
+    #[inline]
+    fn out_of_line_size_hint(&self) -> usize {
+        match self {
+            Bound::Included(value) | Bound::Excluded(value) => value.out_of_line_size_hint(),
+            Bound::Unbounded => 0,
+        }
+    }
+}
+
+impl<T: ArchivedSizeHint, const N: usize> ArchivedSizeHint for [T; N] {
+    #[inline]
+    fn out_of_line_size_hint(&self) -> usize {
+        self.iter().map(T::out_of_line_size_hint).sum()
+    }
+}
+
+#[cfg(not(feature = "strict"))]
+macro_rules! impl_tuple_size_hint {
+    ($($type:ident $index:tt),*) => {
+        impl<$($type: ArchivedSizeHint),*> ArchivedSizeHint for ($($type,)*) {
+            #[inline]
+            fn out_of_line_size_hint(&self) -> usize {
+                0 $(+ self.$index.out_of_line_size_hint())*
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "strict"))]
+const _: () = {
+    impl_tuple_size_hint!(T0 0);
+    impl_tuple_size_hint!(T0 0, T1 1);
+    impl_tuple_size_hint!(T0 0, T1 1, T2 2);
+    impl_tuple_size_hint!(T0 0, T1 1, T2 2, T3 3);
+    impl_tuple_size_hint!(T0 0, T1 1, T2 2, T3 3, T4 4);
+    impl_tuple_size_hint!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+};
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use crate::{collections::util::Entry, string::repr::INLINE_CAPACITY};
+    #[cfg(not(feature = "std"))]
+    use ::alloc::{
+        boxed::Box,
+        collections::{BTreeMap, BTreeSet},
+        rc::Rc,
+        string::String,
+        sync::Arc,
+        vec::Vec,
+    };
+    #[cfg(feature = "std")]
+    use ::std::{
+        collections::{BTreeMap, BTreeSet},
+        rc::Rc,
+        sync::Arc,
+    };
+
+    impl ArchivedSizeHint for String {
+        #[inline]
+        fn out_of_line_size_hint(&self) -> usize {
+            if self.len() <= INLINE_CAPACITY {
+                0
+            } else {
+                self.len()
+            }
+        }
+    }
+
+    impl<T: ArchivedSizeHint> ArchivedSizeHint for Vec<T> {
+        #[inline]
+        fn out_of_line_size_hint(&self) -> usize {
+            slice_size_hint(self.len(), self.iter())
+        }
+    }
+
+    impl<T: ArchivedSizeHint> ArchivedSizeHint for Box<T> {
+        #[inline]
+        fn out_of_line_size_hint(&self) -> usize {
+            slice_size_hint(1, core::iter::once(&**self))
+        }
+    }
+
+    impl<T: ArchivedSizeHint> ArchivedSizeHint for Rc<T> {
+        #[inline]
+        fn out_of_line_size_hint(&self) -> usize {
+            slice_size_hint(1, core::iter::once(&**self))
+        }
+    }
+
+    impl<T: ArchivedSizeHint> ArchivedSizeHint for Arc<T> {
+        #[inline]
+        fn out_of_line_size_hint(&self) -> usize {
+            slice_size_hint(1, core::iter::once(&**self))
+        }
+    }
+
+    // Archived B-trees store their entries in leaf nodes of up to 4KiB, with a small header per
+    // node and one inner node entry per leaf node in the layers above.
+    #[inline]
+    fn btree_size_hint<K: Archive>(len: usize, entry_size: usize, entry_align: usize) -> usize {
+        if len == 0 {
+            0
+        } else {
+            let entries = len * entry_size;
+            let nodes = entries / 4096 + 1;
+            entries + nodes * (entry_align + 2 * size_of::<K::Archived>() + 32)
+        }
+    }
+
+    impl<K: ArchivedSizeHint + Ord, V: ArchivedSizeHint> ArchivedSizeHint for BTreeMap<K, V>
+    where
+        K::Archived: Ord,
+    {
+        #[inline]
+        fn out_of_line_size_hint(&self) -> usize {
+            btree_size_hint::<K>(
+                self.len(),
+                size_of::<Entry<K::Archived, V::Archived>>(),
+                align_of::<Entry<K::Archived, V::Archived>>(),
+            ) + self
+                .iter()
+                .map(|(k, v)| k.out_of_line_size_hint() + v.out_of_line_size_hint())
+                .sum::<usize>()
+        }
+    }
+
+    impl<K: ArchivedSizeHint + Ord> ArchivedSizeHint for BTreeSet<K>
+    where
+        K::Archived: Ord,
+    {
+        #[inline]
+        fn out_of_line_size_hint(&self) -> usize {
+            btree_size_hint::<K>(
+                self.len(),
+                size_of::<Entry<K::Archived, ()>>(),
+                align_of::<Entry<K::Archived, ()>>(),
+            ) + self.iter().map(K::out_of_line_size_hint).sum::<usize>()
+        }
+    }
+};
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::collections::util::Entry;
+    use core::hash::Hash;
+    use std::collections::{HashMap, HashSet};
+
+    // Archived hash maps store one 32-bit displacement and one entry per key, in separate arrays.
+    #[inline]
+    fn hash_size_hint(len: usize, entry_size: usize, entry_align: usize) -> usize {
+        if len == 0 {
+            0
+        } else {
+            3 + len * 4 + entry_align - 1 + len * entry_size
+        }
+    }
+
+    impl<K, V, S> ArchivedSizeHint for HashMap<K, V, S>
+    where
+        K: ArchivedSizeHint + Hash + Eq,
+        K::Archived: Hash + Eq,
+        V: ArchivedSizeHint,
+    {
+        #[inline]
+        fn out_of_line_size_hint(&self) -> usize {
+            hash_size_hint(
+                self.len(),
+                size_of::<Entry<K::Archived, V::Archived>>(),
+                align_of::<Entry<K::Archived, V::Archived>>(),
+            ) + self
+                .iter()
+                .map(|(k, v)| k.out_of_line_size_hint() + v.out_of_line_size_hint())
+                .sum::<usize>()
+        }
+    }
+
+    impl<K, S> ArchivedSizeHint for HashSet<K, S>
+    where
+        K: ArchivedSizeHint + Hash + Eq,
+        K::Archived: Hash + Eq,
+    {
+        #[inline]
+        fn out_of_line_size_hint(&self) -> usize {
+            hash_size_hint(
+                self.len(),
+                size_of::<Entry<K::Archived, ()>>(),
+                align_of::<Entry<K::Archived, ()>>(),
+            ) + self.iter().map(K::out_of_line_size_hint).sum::<usize>()
+        }
+    }
+};
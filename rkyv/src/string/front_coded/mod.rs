@@ -0,0 +1,427 @@
+//! An archived vector of sorted strings that stores each string as a suffix of the one before it.
+
+#[cfg(feature = "validation")]
+pub mod validation;
+
+use crate::{
+    collections::delta_vec::{read_varint, varint_len, write_varint, MAX_VARINT_LEN},
+    ser::Serializer,
+    vec::{ArchivedVec, VecResolver},
+    Archive, Archived, FixedUsize,
+};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use ::alloc::string::String;
+use core::{cmp::Ordering, mem::size_of, slice, str};
+
+/// An archived vector of strings, laid out as the length of the prefix that each string shares
+/// with the string before it followed by the rest of the string.
+///
+/// Sorted strings (like the keys of a dictionary, paths in a file listing, or URLs) tend to share
+/// long prefixes with their neighbors. Front coding only stores the part of each string that's
+/// different from the one before it, so a sorted list of paths under a few directories takes a
+/// fraction of the space of an [`ArchivedStringTable`](crate::string::table::ArchivedStringTable).
+/// Strings that aren't sorted can still be front-coded, but they won't compress as well and
+/// [`index_of`](ArchivedFrontCodedVec::index_of) won't be able to find them.
+///
+/// The [`AsFrontCodedVec`](crate::with::AsFrontCodedVec) wrapper can be used to archive a
+/// `Vec` of strings as a front-coded vector.
+///
+/// # Layout
+///
+/// The strings are split into blocks of a fixed number of strings called the restart interval.
+/// Each string is stored as two varints (the length in bytes of the prefix it shares with the
+/// previous string and the length of the rest of the string) followed by the rest of the string.
+/// The first string of each block shares nothing with the string before it, so it's stored whole
+/// and the block can be decoded without reading any other blocks. The byte offset of the start of
+/// each block is stored in a separate array.
+///
+/// # Access complexity
+///
+/// Strings aren't stored whole, so they have to be reconstructed from the start of their block:
+///
+/// - [`get`] decodes up to a restart interval's worth of strings to build the requested one.
+/// - [`iter`] decodes each string once, so iterating over the whole vector takes linear time and
+///   is the fastest way to read many strings.
+/// - [`index_of`] binary searches the first strings of the blocks (which are stored whole and
+///   don't need to be decoded) and then scans one block without allocating.
+///
+/// A larger restart interval compresses better because fewer strings are stored whole, and a
+/// smaller restart interval makes random access faster because fewer strings have to be decoded.
+///
+/// [`get`]: ArchivedFrontCodedVec::get
+/// [`iter`]: ArchivedFrontCodedVec::iter
+/// [`index_of`]: ArchivedFrontCodedVec::index_of
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     archived_root,
+///     ser::{serializers::AllocSerializer, Serializer},
+///     with::AsFrontCodedVec,
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Listing {
+///     #[with(AsFrontCodedVec)]
+///     paths: Vec<String>,
+/// }
+///
+/// let paths = (0..1_000)
+///     .map(|i| format!("/usr/share/doc/package-{:04}", i))
+///     .collect::<Vec<_>>();
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&Listing { paths: paths.clone() }).unwrap();
+/// let buf = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<Listing>(&buf) };
+///
+/// assert_eq!(archived.paths.len(), 1_000);
+/// assert_eq!(archived.paths.get(123).as_deref(), Some("/usr/share/doc/package-0123"));
+/// assert_eq!(archived.paths.index_of("/usr/share/doc/package-0456"), Some(456));
+/// assert!(archived.paths.iter().eq(paths.iter().cloned()));
+///
+/// // Each path is 27 bytes long, but most of them only store their last digit or two
+/// assert!(buf.len() < 1_000 * 27 / 4);
+/// ```
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedFrontCodedVec {
+    len: Archived<usize>,
+    restart_interval: Archived<usize>,
+    restarts: ArchivedVec<Archived<usize>>,
+    data: ArchivedVec<u8>,
+}
+
+impl ArchivedFrontCodedVec {
+    /// Returns the number of strings in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        from_archived!(self.len) as usize
+    }
+
+    /// Returns `true` if the vector contains no strings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of strings in each block of the vector.
+    #[inline]
+    pub fn restart_interval(&self) -> usize {
+        from_archived!(self.restart_interval) as usize
+    }
+
+    /// Returns the encoded strings of the vector.
+    ///
+    /// This is the bulk of the archived vector, and its length is a good measure of how well the
+    /// strings compressed.
+    #[inline]
+    pub fn data_bytes(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+
+    /// Reads the string at `pos` and advances `pos` past it.
+    ///
+    /// Returns the length of the prefix shared with the previous string and the rest of the
+    /// string.
+    #[inline]
+    fn read_entry(&self, pos: &mut usize) -> (usize, &str) {
+        let data = self.data.as_slice();
+        let shared = read_varint(data, pos).expect("invalid shared prefix length") as usize;
+        let suffix_len = read_varint(data, pos).expect("invalid suffix length") as usize;
+        let suffix = &data[*pos..*pos + suffix_len];
+        *pos += suffix_len;
+        // The suffixes are written from `str`s and split on character boundaries, and validation
+        // checks that they're valid UTF-8
+        (shared, unsafe { str::from_utf8_unchecked(suffix) })
+    }
+
+    /// Returns the first string of the given block, which is stored whole.
+    #[inline]
+    fn block_head(&self, block: usize) -> &str {
+        let mut pos = from_archived!(self.restarts[block]) as usize;
+        self.read_entry(&mut pos).1
+    }
+
+    /// Returns the index of the given string, or `None` if the vector doesn't contain it.
+    ///
+    /// The strings must have been sorted when they were serialized. This binary searches the first
+    /// strings of the blocks and then scans the block that could contain the string, so it
+    /// doesn't need to allocate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the strings are not validly encoded, which can only happen if the archive was not
+    /// validated.
+    pub fn index_of(&self, value: &str) -> Option<usize> {
+        // Find the last block that starts with a string that's not greater than the value
+        let (mut low, mut high) = (0, self.restarts.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.block_head(mid) <= value {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        let block = low.checked_sub(1)?;
+
+        let interval = self.restart_interval();
+        let start = block * interval;
+        let count = (self.len() - start).min(interval);
+        let value = value.as_bytes();
+        let mut pos = from_archived!(self.restarts[block]) as usize;
+        // The length of the prefix that the previous string shares with the value
+        let mut matched = 0;
+        for i in 0..count {
+            let (shared, suffix) = self.read_entry(&mut pos);
+            // If this string shares more with the previous string than the previous string shared
+            // with the value, then it differs from the value in the same place and compares the
+            // same way
+            if shared > matched {
+                continue;
+            }
+
+            let suffix = suffix.as_bytes();
+            let rest = &value[shared..];
+            let common = suffix
+                .iter()
+                .zip(rest.iter())
+                .take_while(|(a, b)| a == b)
+                .count();
+            matched = shared + common;
+            match suffix.cmp(rest) {
+                Ordering::Less => (),
+                Ordering::Equal => return Some(start + i),
+                Ordering::Greater => return None,
+            }
+        }
+        None
+    }
+
+    /// Returns `true` if the vector contains the given string.
+    ///
+    /// The strings must have been sorted when they were serialized. See
+    /// [`index_of`](ArchivedFrontCodedVec::index_of) for details.
+    #[inline]
+    pub fn contains(&self, value: &str) -> bool {
+        self.index_of(value).is_some()
+    }
+
+    /// Resolves an archived front-coded vector from a given length and parameters.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of strings that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a front-coded vector
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: FrontCodedVecResolver,
+        out: *mut Self,
+    ) {
+        let interval = resolver.restart_interval;
+        let blocks_len = len / interval + (len % interval != 0) as usize;
+        let (fp, fo) = out_field!(out.len);
+        usize::resolve(&len, pos + fp, (), fo);
+        let (fp, fo) = out_field!(out.restart_interval);
+        usize::resolve(&interval, pos + fp, (), fo);
+        let (fp, fo) = out_field!(out.restarts);
+        ArchivedVec::resolve_from_len(blocks_len, pos + fp, resolver.restarts, fo);
+        let (fp, fo) = out_field!(out.data);
+        ArchivedVec::resolve_from_len(resolver.data_len, pos + fp, resolver.data, fo);
+    }
+
+    /// Serializes an iterator of strings as a front-coded vector with the given restart interval.
+    ///
+    /// The iterator is traversed twice: once to write the offsets of the blocks and once to write
+    /// the strings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `restart_interval` is zero.
+    pub fn serialize_from_iter<'a, I, S>(
+        iter: I,
+        restart_interval: usize,
+        serializer: &mut S,
+    ) -> Result<FrontCodedVecResolver, S::Error>
+    where
+        I: Clone + ExactSizeIterator<Item = &'a str>,
+        S: Serializer + ?Sized,
+    {
+        assert!(
+            restart_interval != 0,
+            "the restart interval must be at least 1"
+        );
+
+        let restarts_pos = serializer.align_for::<Archived<usize>>()?;
+        let mut data_len = 0;
+        for (index, shared, suffix) in entries(iter.clone(), restart_interval) {
+            if index % restart_interval == 0 {
+                let offset: Archived<usize> = to_archived!(data_len as FixedUsize);
+                let bytes = unsafe {
+                    slice::from_raw_parts(
+                        (&offset as *const Archived<usize>).cast::<u8>(),
+                        size_of::<Archived<usize>>(),
+                    )
+                };
+                serializer.write(bytes)?;
+            }
+            data_len += varint_len(shared as u64) + varint_len(suffix.len() as u64) + suffix.len();
+        }
+
+        let data_pos = serializer.align_for::<u8>()?;
+        let mut buf = [0; MAX_VARINT_LEN];
+        for (_, shared, suffix) in entries(iter, restart_interval) {
+            let len = write_varint(shared as u64, &mut buf);
+            serializer.write(&buf[..len])?;
+            let len = write_varint(suffix.len() as u64, &mut buf);
+            serializer.write(&buf[..len])?;
+            serializer.write(suffix.as_bytes())?;
+        }
+
+        Ok(FrontCodedVecResolver {
+            restart_interval,
+            restarts: VecResolver { pos: restarts_pos },
+            data: VecResolver { pos: data_pos },
+            data_len,
+        })
+    }
+}
+
+/// Returns the index of each string, the length of the prefix it shares with the string before it,
+/// and the rest of the string.
+fn entries<'a, I: Iterator<Item = &'a str>>(
+    iter: I,
+    restart_interval: usize,
+) -> impl Iterator<Item = (usize, usize, &'a str)> {
+    iter.enumerate().scan("", move |prev, (index, string)| {
+        let shared = if index % restart_interval == 0 {
+            0
+        } else {
+            let mut shared = prev
+                .bytes()
+                .zip(string.bytes())
+                .take_while(|(a, b)| a == b)
+                .count();
+            // The shared prefix must end on a character boundary so that the suffix is a valid
+            // string on its own
+            while !string.is_char_boundary(shared) {
+                shared -= 1;
+            }
+            shared
+        };
+        *prev = string;
+        Some((index, shared, &string[shared..]))
+    })
+}
+
+/// The resolver for archived front-coded vectors.
+pub struct FrontCodedVecResolver {
+    restart_interval: usize,
+    restarts: VecResolver,
+    data: VecResolver,
+    data_len: usize,
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use core::{fmt, iter::FusedIterator};
+
+    impl ArchivedFrontCodedVec {
+        /// Gets the string at the given index.
+        ///
+        /// This decodes the strings from the start of the string's block, so it reads at most a
+        /// restart interval's worth of strings. Use [`iter`](ArchivedFrontCodedVec::iter) to read
+        /// many consecutive strings.
+        ///
+        /// Returns `None` if the index is out of bounds.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the strings are not validly encoded, which can only happen if the archive was
+        /// not validated.
+        pub fn get(&self, index: usize) -> Option<String> {
+            if index >= self.len() {
+                return None;
+            }
+
+            let interval = self.restart_interval();
+            let mut pos = from_archived!(self.restarts[index / interval]) as usize;
+            let mut string = String::new();
+            for _ in 0..=index % interval {
+                let (shared, suffix) = self.read_entry(&mut pos);
+                string.truncate(shared);
+                string.push_str(suffix);
+            }
+            Some(string)
+        }
+
+        /// Gets an iterator over the strings in the vector.
+        #[inline]
+        pub fn iter(&self) -> Iter<'_> {
+            Iter {
+                vec: self,
+                index: 0,
+                pos: 0,
+                current: String::new(),
+            }
+        }
+    }
+
+    impl fmt::Debug for ArchivedFrontCodedVec {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+
+    impl<T: AsRef<str>> PartialEq<[T]> for ArchivedFrontCodedVec {
+        #[inline]
+        fn eq(&self, other: &[T]) -> bool {
+            self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a == b.as_ref())
+        }
+    }
+
+    impl<'a> Iterator for Iter<'a> {
+        type Item = String;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index >= self.vec.len() {
+                return None;
+            }
+
+            let interval = self.vec.restart_interval();
+            if self.index % interval == 0 {
+                self.pos = from_archived!(self.vec.restarts[self.index / interval]) as usize;
+            }
+            let (shared, suffix) = self.vec.read_entry(&mut self.pos);
+            self.current.truncate(shared);
+            self.current.push_str(suffix);
+            self.index += 1;
+            Some(self.current.clone())
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let remaining = self.vec.len() - self.index;
+            (remaining, Some(remaining))
+        }
+    }
+
+    impl<'a> ExactSizeIterator for Iter<'a> {}
+    impl<'a> FusedIterator for Iter<'a> {}
+};
+
+/// An iterator over the strings in an archived front-coded vector.
+#[cfg(feature = "alloc")]
+pub struct Iter<'a> {
+    vec: &'a ArchivedFrontCodedVec,
+    index: usize,
+    pos: usize,
+    current: String,
+}
@@ -0,0 +1,179 @@
+//! Validation implementation for ArchivedFrontCodedVec.
+
+use super::ArchivedFrontCodedVec;
+use crate::{
+    collections::delta_vec::read_varint,
+    validation::{owned::CheckOwnedPointerError, ArchiveContext},
+    vec::ArchivedVec,
+    Archived,
+};
+#[cfg(not(feature = "std"))]
+use ::alloc::string::String;
+use bytecheck::{CheckBytes, Error};
+use core::{convert::Infallible, fmt, ptr, str};
+
+/// Errors that can occur while checking an archived front-coded vector.
+#[derive(Debug)]
+pub enum FrontCodedVecError<R, D> {
+    /// An error occurred while checking the offsets of the blocks
+    RestartsError(R),
+    /// An error occurred while checking the encoded strings
+    DataError(D),
+    /// The restart interval was zero
+    InvalidRestartInterval,
+    /// The number of blocks did not match the length of the vector
+    InvalidBlockCount {
+        /// The length of the vector
+        len: usize,
+        /// The number of blocks that were archived
+        blocks: usize,
+    },
+    /// The encoded strings of a block did not decode to the number of strings in the block
+    InvalidBlock {
+        /// The index of the block
+        index: usize,
+    },
+    /// A string was truncated, shared more than the previous string, or was not valid UTF-8
+    InvalidEntry {
+        /// The index of the string
+        index: usize,
+    },
+}
+
+impl<R, D> From<Infallible> for FrontCodedVecError<R, D> {
+    #[inline]
+    fn from(_: Infallible) -> Self {
+        unsafe { core::hint::unreachable_unchecked() }
+    }
+}
+
+impl<R: fmt::Display, D: fmt::Display> fmt::Display for FrontCodedVecError<R, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrontCodedVecError::RestartsError(e) => {
+                write!(f, "error while checking block offsets: {}", e)
+            }
+            FrontCodedVecError::DataError(e) => {
+                write!(f, "error while checking encoded strings: {}", e)
+            }
+            FrontCodedVecError::InvalidRestartInterval => {
+                write!(f, "invalid restart interval: must be at least 1")
+            }
+            FrontCodedVecError::InvalidBlockCount { len, blocks } => write!(
+                f,
+                "invalid block count: {} blocks can't describe {} strings",
+                blocks, len
+            ),
+            FrontCodedVecError::InvalidBlock { index } => {
+                write!(f, "invalid encoded strings in block {}", index)
+            }
+            FrontCodedVecError::InvalidEntry { index } => {
+                write!(f, "invalid encoded string at index {}", index)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<R: Error + 'static, D: Error + 'static> Error for FrontCodedVecError<R, D> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                FrontCodedVecError::RestartsError(e) => Some(e as &dyn Error),
+                FrontCodedVecError::DataError(e) => Some(e as &dyn Error),
+                FrontCodedVecError::InvalidRestartInterval
+                | FrontCodedVecError::InvalidBlockCount { .. }
+                | FrontCodedVecError::InvalidBlock { .. }
+                | FrontCodedVecError::InvalidEntry { .. } => None,
+            }
+        }
+    }
+};
+
+impl<C> CheckBytes<C> for ArchivedFrontCodedVec
+where
+    C: ArchiveContext + ?Sized,
+    C::Error: Error,
+{
+    type Error = FrontCodedVecError<
+        CheckOwnedPointerError<[Archived<usize>], C>,
+        CheckOwnedPointerError<[u8], C>,
+    >;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        let len = from_archived!(*Archived::<usize>::check_bytes(
+            ptr::addr_of!((*value).len),
+            context,
+        )?) as usize;
+        let interval = from_archived!(*Archived::<usize>::check_bytes(
+            ptr::addr_of!((*value).restart_interval),
+            context,
+        )?) as usize;
+        let restarts =
+            ArchivedVec::<Archived<usize>>::check_bytes(ptr::addr_of!((*value).restarts), context)
+                .map_err(FrontCodedVecError::RestartsError)?;
+        let data = ArchivedVec::<u8>::check_bytes(ptr::addr_of!((*value).data), context)
+            .map_err(FrontCodedVecError::DataError)?;
+
+        if interval == 0 {
+            return Err(FrontCodedVecError::InvalidRestartInterval);
+        }
+        let blocks = len / interval + (len % interval != 0) as usize;
+        if restarts.len() != blocks {
+            return Err(FrontCodedVecError::InvalidBlockCount {
+                len,
+                blocks: restarts.len(),
+            });
+        }
+
+        // Each block must decode exactly its number of strings and end where the next block
+        // starts. The strings are rebuilt as they're decoded so that each shared prefix can be
+        // checked against the string before it.
+        let data = data.as_slice();
+        let mut string = String::new();
+        for block in 0..blocks {
+            let mut pos = from_archived!(restarts[block]) as usize;
+            let end = if block + 1 < blocks {
+                from_archived!(restarts[block + 1]) as usize
+            } else {
+                data.len()
+            };
+            if pos > end || end > data.len() {
+                return Err(FrontCodedVecError::InvalidBlock { index: block });
+            }
+            let bytes = &data[..end];
+
+            let start = block * interval;
+            string.clear();
+            for index in start..(start + interval).min(len) {
+                let entry = read_varint(bytes, &mut pos).and_then(|shared| {
+                    let suffix_len = read_varint(bytes, &mut pos)? as usize;
+                    let suffix = bytes.get(pos..pos.checked_add(suffix_len)?)?;
+                    Some((shared as usize, str::from_utf8(suffix).ok()?))
+                });
+                // The first string of each block must be stored whole, and the others can only
+                // share a prefix that ends on a character boundary of the previous string
+                match entry {
+                    Some((shared, suffix))
+                        if string.is_char_boundary(shared) && (index != start || shared == 0) =>
+                    {
+                        pos += suffix.len();
+                        string.truncate(shared);
+                        string.push_str(suffix);
+                    }
+                    _ => return Err(FrontCodedVecError::InvalidEntry { index }),
+                }
+            }
+            if pos != end {
+                return Err(FrontCodedVecError::InvalidBlock { index: block });
+            }
+        }
+
+        Ok(&*value)
+    }
+}
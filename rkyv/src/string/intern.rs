@@ -0,0 +1,210 @@
+//! Interned strings and the tables they are resolved against.
+//!
+//! Interning stores each distinct string once in an [`ArchivedInternTable`] and replaces every
+//! occurrence with its index in the table. Fields archived with the
+//! [`Intern`](crate::with::Intern) wrapper are archived as an [`ArchivedInterned`] index, which is
+//! resolved back to a string with [`ArchivedInterned::resolve`].
+//!
+//! # Where the table lives
+//!
+//! The table is not part of any interned value. While serializing, an
+//! [`InternSerializer`](crate::ser::serializers::InternSerializer) collects the interned strings,
+//! and [`serialize_intern_table`](crate::ser::serializers::InternSerializer::serialize_intern_table)
+//! writes them as an [`ArchivedInternTable`] at the serializer's current position once all of the
+//! interned values have been serialized. If the table is the last thing written, it is the root of
+//! the archive and can be accessed with `archived_root::<InternTable>`. Otherwise, its position
+//! must be stored alongside the archive (for example, in a header) and it can be accessed with
+//! `archived_value::<InternTable>`.
+//!
+//! An archive can contain multiple tables, and every interned index is relative to the table that
+//! was written by the serializer that interned it.
+
+#[cfg(feature = "alloc")]
+use crate::{ser::Serializer, string::table::StringTableResolver, Archive, Serialize};
+use crate::{string::table::ArchivedStringTable, Archived, FixedUsize};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use ::alloc::{string::String, vec::Vec};
+use core::fmt;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use hashbrown::HashMap;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// An archived table of interned strings.
+///
+/// This is the archived version of an [`InternTable`]. See the [module-level
+/// documentation](self) for where tables are stored in an archive.
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[repr(transparent)]
+pub struct ArchivedInternTable(ArchivedStringTable);
+
+impl ArchivedInternTable {
+    /// Returns the number of strings in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the table contains no strings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the interned string with the given index, or `None` if the index is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.0.get(index)
+    }
+
+    /// Returns an iterator over the interned strings in order of their indices.
+    #[inline]
+    pub fn iter(&self) -> crate::string::table::Iter<'_> {
+        self.0.iter()
+    }
+
+    /// Returns the underlying string table.
+    #[inline]
+    pub fn as_table(&self) -> &ArchivedStringTable {
+        &self.0
+    }
+}
+
+impl fmt::Debug for ArchivedInternTable {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An archived interned string.
+///
+/// Only the index of the string in its [`ArchivedInternTable`] is stored. The string itself can
+/// be looked up with [`resolve`](ArchivedInterned::resolve).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[repr(transparent)]
+pub struct ArchivedInterned {
+    index: Archived<usize>,
+}
+
+impl ArchivedInterned {
+    /// Returns the index of the string in its intern table.
+    #[inline]
+    pub fn index(&self) -> usize {
+        from_archived!(self.index) as usize
+    }
+
+    /// Looks up the interned string in the given table.
+    ///
+    /// Returns `None` if the index is out of bounds, which can only happen if the table is not the
+    /// one that the string was interned into.
+    #[inline]
+    pub fn resolve<'a>(&self, table: &'a ArchivedInternTable) -> Option<&'a str> {
+        table.get(self.index())
+    }
+
+    /// Resolves an archived interned string from the index of the string.
+    ///
+    /// # Safety
+    ///
+    /// `out` must point to memory suitable for holding an `ArchivedInterned`.
+    #[inline]
+    pub unsafe fn resolve_from_index(index: usize, out: *mut Self) {
+        let (_, fo) = out_field!(out.index);
+        fo.write(to_archived!(index as FixedUsize));
+    }
+}
+
+/// A table of interned strings that is built while serializing.
+///
+/// Each distinct string is stored once, and its index is the order in which it was first
+/// interned. The table archives as an [`ArchivedInternTable`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default)]
+pub struct InternTable {
+    strings: Vec<String>,
+    indices: HashMap<String, usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl InternTable {
+    /// Creates a new, empty intern table.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of strings in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns whether the table contains no strings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Interns the given string and returns its index in the table.
+    ///
+    /// Interning a string that is already in the table returns the index it was first given.
+    pub fn intern(&mut self, value: &str) -> usize {
+        if let Some(&index) = self.indices.get(value) {
+            return index;
+        }
+        let index = self.strings.len();
+        self.strings.push(String::from(value));
+        self.indices.insert(String::from(value), index);
+        index
+    }
+
+    /// Returns the interned string with the given index, or `None` if the index is out of
+    /// bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.strings.get(index).map(String::as_str)
+    }
+
+    /// Returns the interned strings in order of their indices.
+    #[inline]
+    pub fn strings(&self) -> &[String] {
+        &self.strings
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Archive for InternTable {
+    type Archived = ArchivedInternTable;
+    type Resolver = StringTableResolver;
+
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedStringTable::resolve_from_len(
+            self.strings.len(),
+            pos,
+            resolver,
+            out.cast::<ArchivedStringTable>(),
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S: Serializer + ?Sized> Serialize<S> for InternTable {
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedStringTable::serialize_from_iter(
+            self.strings.iter().map(String::as_str),
+            serializer,
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PartialEq<InternTable> for ArchivedInternTable {
+    #[inline]
+    fn eq(&self, other: &InternTable) -> bool {
+        self.0 == other.strings[..]
+    }
+}
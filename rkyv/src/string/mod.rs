@@ -1,6 +1,11 @@
 //! Archived versions of string types.
 
+pub mod front_coded;
+pub mod intern;
 pub mod repr;
+pub mod table;
+#[cfg(feature = "validation")]
+pub mod validation;
 
 use crate::{Fallible, SerializeUnsized};
 use core::{
@@ -184,51 +189,3 @@ impl PartialEq<ArchivedString> for &str {
 pub struct StringResolver {
     pos: usize,
 }
-
-#[cfg(feature = "validation")]
-const _: () = {
-    use crate::validation::{
-        owned::{CheckOwnedPointerError, OwnedPointerError},
-        ArchiveContext,
-    };
-    use bytecheck::{CheckBytes, Error};
-
-    impl<C: ArchiveContext + ?Sized> CheckBytes<C> for ArchivedString
-    where
-        C::Error: Error,
-    {
-        type Error = CheckOwnedPointerError<str, C>;
-
-        #[inline]
-        unsafe fn check_bytes<'a>(
-            value: *const Self,
-            context: &mut C,
-        ) -> Result<&'a Self, Self::Error> {
-            // The repr is always valid
-            let repr = &*value.cast::<ArchivedStringRepr>();
-
-            if repr.is_inline() {
-                str::check_bytes(repr.as_str_ptr(), context)
-                    .map_err(OwnedPointerError::ValueCheckBytesError)?;
-            } else {
-                let base = value.cast();
-                let offset = repr.out_of_line_offset();
-                let metadata = repr.len();
-
-                let ptr = context
-                    .check_subtree_ptr::<str>(base, offset, metadata)
-                    .map_err(OwnedPointerError::ContextError)?;
-
-                let range = context
-                    .push_prefix_subtree(ptr)
-                    .map_err(OwnedPointerError::ContextError)?;
-                str::check_bytes(ptr, context).map_err(OwnedPointerError::ValueCheckBytesError)?;
-                context
-                    .pop_prefix_range(range)
-                    .map_err(OwnedPointerError::ContextError)?;
-            }
-
-            Ok(&*value)
-        }
-    }
-};
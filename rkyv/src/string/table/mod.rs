@@ -0,0 +1,250 @@
+//! An archived table of strings that share one contiguous character pool.
+
+#[cfg(feature = "validation")]
+pub mod validation;
+
+use crate::{Archive, Archived, FixedUsize, RelPtr};
+use core::{fmt, iter::FusedIterator, ops::Index, slice, str};
+
+/// The location of one string in the character pool of an [`ArchivedStringTable`].
+#[cfg_attr(feature = "strict", repr(C))]
+struct Span {
+    start: Archived<usize>,
+    len: Archived<usize>,
+}
+
+impl Span {
+    #[inline]
+    fn start(&self) -> usize {
+        from_archived!(self.start) as usize
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        from_archived!(self.len) as usize
+    }
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.start() + self.len()
+    }
+}
+
+/// An archived sequence of strings stored in a shared character pool.
+///
+/// A regular archived `Vec<String>` stores the bytes of each string separately. A string table
+/// writes the bytes of every string back-to-back into one pool and stores an array of
+/// `(start, len)` spans into it. Iterating over the strings only reads two contiguous regions, and
+/// validation checks the bounds and UTF-8 encoding of the whole pool at once instead of checking
+/// each string individually.
+///
+/// The [`AsStringTable`](crate::with::AsStringTable) wrapper can be used to archive a `Vec` or
+/// slice of strings as a string table.
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedStringTable {
+    pool: RelPtr<u8>,
+    pool_len: Archived<usize>,
+    spans: RelPtr<Span>,
+    len: Archived<usize>,
+}
+
+impl ArchivedStringTable {
+    /// Returns the number of strings in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        from_archived!(self.len) as usize
+    }
+
+    /// Returns whether the table contains no strings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the character pool that all of the strings are stored in.
+    #[inline]
+    pub fn pool(&self) -> &str {
+        unsafe {
+            str::from_utf8_unchecked(slice::from_raw_parts(
+                self.pool.as_ptr(),
+                from_archived!(self.pool_len) as usize,
+            ))
+        }
+    }
+
+    #[inline]
+    fn spans(&self) -> &[Span] {
+        unsafe { slice::from_raw_parts(self.spans.as_ptr(), self.len()) }
+    }
+
+    #[inline]
+    fn span_str(&self, span: &Span) -> &str {
+        unsafe { self.pool().get_unchecked(span.start()..span.end()) }
+    }
+
+    /// Returns the string at the given index, or `None` if the index is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.spans().get(index).map(|span| self.span_str(span))
+    }
+
+    /// Gets an iterator over the strings in the table.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            table: self,
+            spans: self.spans().iter(),
+        }
+    }
+
+    /// Resolves an archived string table from the number of strings it contains.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of strings that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing a string table
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        pos: usize,
+        resolver: StringTableResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.pool);
+        RelPtr::emplace(pos + fp, resolver.pool_pos, fo);
+        let (fp, fo) = out_field!(out.pool_len);
+        resolver.pool_len.resolve(pos + fp, (), fo);
+        let (fp, fo) = out_field!(out.spans);
+        RelPtr::emplace(pos + fp, resolver.spans_pos, fo);
+        let (fp, fo) = out_field!(out.len);
+        len.resolve(pos + fp, (), fo);
+    }
+}
+
+#[cfg(feature = "alloc")]
+const _: () = {
+    use crate::ser::Serializer;
+    use core::mem::size_of;
+
+    impl ArchivedStringTable {
+        /// Serializes an iterator of strings as a string table.
+        ///
+        /// The iterator is traversed twice: once to write the character pool and once to write
+        /// the spans.
+        pub fn serialize_from_iter<'a, I, S>(
+            iter: I,
+            serializer: &mut S,
+        ) -> Result<StringTableResolver, S::Error>
+        where
+            I: Clone + ExactSizeIterator<Item = &'a str>,
+            S: Serializer + ?Sized,
+        {
+            let pool_pos = serializer.pos();
+            for string in iter.clone() {
+                serializer.write(string.as_bytes())?;
+            }
+            let pool_len = serializer.pos() - pool_pos;
+
+            let spans_pos = serializer.align_for::<Span>()?;
+            let mut start = 0;
+            for string in iter {
+                let span = Span {
+                    start: to_archived!(start as FixedUsize),
+                    len: to_archived!(string.len() as FixedUsize),
+                };
+                let bytes = unsafe {
+                    slice::from_raw_parts((&span as *const Span).cast::<u8>(), size_of::<Span>())
+                };
+                serializer.write(bytes)?;
+                start += string.len();
+            }
+
+            Ok(StringTableResolver {
+                pool_pos,
+                pool_len,
+                spans_pos,
+            })
+        }
+    }
+};
+
+impl fmt::Debug for ArchivedStringTable {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for ArchivedStringTable {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &str {
+        self.span_str(&self.spans()[index])
+    }
+}
+
+impl<'a> IntoIterator for &'a ArchivedStringTable {
+    type Item = &'a str;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Eq for ArchivedStringTable {}
+
+impl PartialEq for ArchivedStringTable {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: AsRef<str>> PartialEq<[T]> for ArchivedStringTable {
+    #[inline]
+    fn eq(&self, other: &[T]) -> bool {
+        self.iter().eq(other.iter().map(AsRef::as_ref))
+    }
+}
+
+/// The resolver for archived string tables.
+pub struct StringTableResolver {
+    pool_pos: usize,
+    pool_len: usize,
+    spans_pos: usize,
+}
+
+/// An iterator over the strings of an archived string table.
+pub struct Iter<'a> {
+    table: &'a ArchivedStringTable,
+    spans: slice::Iter<'a, Span>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.spans.next().map(|span| self.table.span_str(span))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.spans.next_back().map(|span| self.table.span_str(span))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
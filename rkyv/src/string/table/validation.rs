@@ -0,0 +1,145 @@
+//! Validation implementation for ArchivedStringTable.
+
+use crate::{
+    string::table::{ArchivedStringTable, Span},
+    validation::ArchiveContext,
+    Archived, RelPtr,
+};
+use bytecheck::{CheckBytes, Error, StrCheckError};
+use core::{
+    alloc::{Layout, LayoutError},
+    convert::Infallible,
+    fmt, ptr,
+};
+
+/// Errors that can occur while checking an archived string table.
+#[derive(Debug)]
+pub enum StringTableError<C> {
+    /// The character pool was not valid UTF-8
+    StrCheckError(StrCheckError),
+    /// An error occured while checking the layout of the spans
+    LayoutError(LayoutError),
+    /// A span was out of bounds of the character pool or was not on character boundaries
+    InvalidSpan {
+        /// The index of the invalid span
+        index: usize,
+    },
+    /// A bounds error occurred
+    ContextError(C),
+}
+
+impl<C> From<StrCheckError> for StringTableError<C> {
+    #[inline]
+    fn from(e: StrCheckError) -> Self {
+        Self::StrCheckError(e)
+    }
+}
+
+impl<C> From<LayoutError> for StringTableError<C> {
+    #[inline]
+    fn from(e: LayoutError) -> Self {
+        Self::LayoutError(e)
+    }
+}
+
+impl<C> From<Infallible> for StringTableError<C> {
+    #[inline]
+    fn from(_: Infallible) -> Self {
+        unsafe { core::hint::unreachable_unchecked() }
+    }
+}
+
+impl<C: fmt::Display> fmt::Display for StringTableError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StringTableError::StrCheckError(e) => e.fmt(f),
+            StringTableError::LayoutError(e) => write!(f, "layout error: {}", e),
+            StringTableError::InvalidSpan { index } => {
+                write!(f, "invalid span: at index {}", index)
+            }
+            StringTableError::ContextError(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<C: Error + 'static> Error for StringTableError<C> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                StringTableError::StrCheckError(e) => Some(e as &dyn Error),
+                StringTableError::LayoutError(e) => Some(e as &dyn Error),
+                StringTableError::InvalidSpan { .. } => None,
+                StringTableError::ContextError(e) => Some(e as &dyn Error),
+            }
+        }
+    }
+};
+
+impl<C: ArchiveContext + ?Sized> CheckBytes<C> for ArchivedStringTable
+where
+    C::Error: Error,
+{
+    type Error = StringTableError<C::Error>;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        // The whole pool is bounds checked and validated as UTF-8 once
+        let pool_len = from_archived!(*Archived::<usize>::check_bytes(
+            ptr::addr_of!((*value).pool_len),
+            context,
+        )?) as usize;
+        let pool_rel_ptr = RelPtr::manual_check_bytes(ptr::addr_of!((*value).pool), context)?;
+        let pool_ptr = context
+            .check_subtree_ptr::<str>(pool_rel_ptr.base(), pool_rel_ptr.offset(), pool_len)
+            .map_err(StringTableError::ContextError)?;
+
+        let range = context
+            .push_prefix_subtree(pool_ptr)
+            .map_err(StringTableError::ContextError)?;
+        let pool = str::check_bytes(pool_ptr, context)?;
+        context
+            .pop_prefix_range(range)
+            .map_err(StringTableError::ContextError)?;
+
+        let len = from_archived!(*Archived::<usize>::check_bytes(
+            ptr::addr_of!((*value).len),
+            context,
+        )?) as usize;
+        Layout::array::<Span>(len)?;
+
+        let spans_rel_ptr = RelPtr::manual_check_bytes(ptr::addr_of!((*value).spans), context)?;
+        let spans_ptr = context
+            .check_subtree_ptr::<[Span]>(spans_rel_ptr.base(), spans_rel_ptr.offset(), len)
+            .map_err(StringTableError::ContextError)?;
+
+        let range = context
+            .push_prefix_subtree(spans_ptr)
+            .map_err(StringTableError::ContextError)?;
+        // Spans only contain integers, so any bytes are valid
+        let spans = &*spans_ptr;
+        context
+            .pop_prefix_range(range)
+            .map_err(StringTableError::ContextError)?;
+
+        // Each span only has to be checked against the pool, not the archive
+        for (index, span) in spans.iter().enumerate() {
+            let start = span.start();
+            let valid = match start.checked_add(span.len()) {
+                Some(end) => {
+                    end <= pool.len() && pool.is_char_boundary(start) && pool.is_char_boundary(end)
+                }
+                None => false,
+            };
+            if !valid {
+                return Err(StringTableError::InvalidSpan { index });
+            }
+        }
+
+        Ok(&*value)
+    }
+}
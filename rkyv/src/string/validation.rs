@@ -0,0 +1,108 @@
+//! Validation implementation for ArchivedString.
+
+use crate::{
+    string::{
+        repr::{ArchivedStringRepr, INLINE_CAPACITY},
+        ArchivedString,
+    },
+    validation::ArchiveContext,
+};
+use bytecheck::{CheckBytes, Error, StrCheckError};
+use core::fmt;
+
+/// Errors that can occur while checking an archived string.
+///
+/// This replaces `CheckOwnedPointerError<str, C>` as the [`CheckBytes`] error of
+/// [`ArchivedString`], which had no way to report an inline length past the inline capacity. Code
+/// that matched on the old error type must match on this one instead.
+#[derive(Debug)]
+pub enum ArchivedStringError<C> {
+    /// An inline string claimed to be longer than the inline capacity
+    InlineLengthTooLarge {
+        /// The length of the inline string
+        len: usize,
+    },
+    /// The string was not valid UTF-8
+    StrCheckError(StrCheckError),
+    /// A bounds error occurred
+    ContextError(C),
+}
+
+impl<C: fmt::Display> fmt::Display for ArchivedStringError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArchivedStringError::InlineLengthTooLarge { len } => write!(
+                f,
+                "inline string length too large: {} bytes with a capacity of {}",
+                len, INLINE_CAPACITY
+            ),
+            ArchivedStringError::StrCheckError(e) => e.fmt(f),
+            ArchivedStringError::ContextError(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl<C: Error + 'static> Error for ArchivedStringError<C> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                ArchivedStringError::InlineLengthTooLarge { .. } => None,
+                ArchivedStringError::StrCheckError(e) => Some(e as &dyn Error),
+                ArchivedStringError::ContextError(e) => Some(e as &dyn Error),
+            }
+        }
+    }
+};
+
+impl<C> From<StrCheckError> for ArchivedStringError<C> {
+    #[inline]
+    fn from(e: StrCheckError) -> Self {
+        Self::StrCheckError(e)
+    }
+}
+
+impl<C: ArchiveContext + ?Sized> CheckBytes<C> for ArchivedString
+where
+    C::Error: Error,
+{
+    type Error = ArchivedStringError<C::Error>;
+
+    #[inline]
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        // The repr is always valid
+        let repr = &*value.cast::<ArchivedStringRepr>();
+
+        if repr.is_inline() {
+            // The inline length can claim up to 127 bytes, but only `INLINE_CAPACITY` bytes are
+            // actually stored inside the repr.
+            if repr.len() > INLINE_CAPACITY {
+                return Err(ArchivedStringError::InlineLengthTooLarge { len: repr.len() });
+            }
+            str::check_bytes(repr.as_str_ptr(), context)?;
+        } else {
+            let base = value.cast();
+            let offset = repr.out_of_line_offset();
+            let metadata = repr.len();
+
+            let ptr = context
+                .check_subtree_ptr::<str>(base, offset, metadata)
+                .map_err(ArchivedStringError::ContextError)?;
+
+            let range = context
+                .push_prefix_subtree(ptr)
+                .map_err(ArchivedStringError::ContextError)?;
+            str::check_bytes(ptr, context)?;
+            context
+                .pop_prefix_range(range)
+                .map_err(ArchivedStringError::ContextError)?;
+        }
+
+        Ok(&*value)
+    }
+}
@@ -0,0 +1,63 @@
+//! Archived versions of `sync` types.
+
+use core::sync::atomic::Ordering;
+
+/// An archived atomic memory [`Ordering`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "validation", derive(bytecheck::CheckBytes))]
+#[repr(u8)]
+pub enum ArchivedAtomicOrdering {
+    /// No ordering constraints, only atomic operations
+    Relaxed,
+    /// Release ordering for stores
+    Release,
+    /// Acquire ordering for loads
+    Acquire,
+    /// Both acquire and release ordering
+    AcqRel,
+    /// Sequentially consistent ordering
+    SeqCst,
+}
+
+impl ArchivedAtomicOrdering {
+    /// Returns an atomic [`Ordering`] with the same value.
+    #[inline]
+    pub const fn as_ordering(&self) -> Ordering {
+        match self {
+            ArchivedAtomicOrdering::Relaxed => Ordering::Relaxed,
+            ArchivedAtomicOrdering::Release => Ordering::Release,
+            ArchivedAtomicOrdering::Acquire => Ordering::Acquire,
+            ArchivedAtomicOrdering::AcqRel => Ordering::AcqRel,
+            ArchivedAtomicOrdering::SeqCst => Ordering::SeqCst,
+        }
+    }
+}
+
+impl From<Ordering> for ArchivedAtomicOrdering {
+    #[inline]
+    fn from(value: Ordering) -> Self {
+        match value {
+            Ordering::Relaxed => ArchivedAtomicOrdering::Relaxed,
+            Ordering::Release => ArchivedAtomicOrdering::Release,
+            Ordering::Acquire => ArchivedAtomicOrdering::Acquire,
+            Ordering::AcqRel => ArchivedAtomicOrdering::AcqRel,
+            Ordering::SeqCst => ArchivedAtomicOrdering::SeqCst,
+            // `Ordering` is non-exhaustive, but all of its current variants are covered above
+            _ => ArchivedAtomicOrdering::SeqCst,
+        }
+    }
+}
+
+impl PartialEq<Ordering> for ArchivedAtomicOrdering {
+    #[inline]
+    fn eq(&self, other: &Ordering) -> bool {
+        self.as_ordering() == *other
+    }
+}
+
+impl PartialEq<ArchivedAtomicOrdering> for Ordering {
+    #[inline]
+    fn eq(&self, other: &ArchivedAtomicOrdering) -> bool {
+        other.eq(self)
+    }
+}
@@ -109,3 +109,12 @@ impl ArchivedDuration {
         addr_of_mut!((*out).nanos).write(to_archived!(nanos));
     }
 }
+
+/// The resolver for an archived [`Instant`](std::time::Instant).
+///
+/// `Instant`s are archived as an [`ArchivedDuration`] offset from a base instant chosen by the
+/// serializer. See [`InstantEpoch`](crate::ser::InstantEpoch) for more information.
+#[cfg(feature = "std")]
+pub struct InstantResolver {
+    pub(crate) offset: core::time::Duration,
+}
@@ -0,0 +1,149 @@
+use crate::{
+    ser::{serializers::AllocSerializer, Serializer},
+    AlignedVec, Archive, Fallible, Serialize,
+};
+#[cfg(not(feature = "std"))]
+use ::alloc::vec::Vec;
+use core::{fmt, marker::PhantomData, mem, ops::Deref};
+
+/// An owned archive and the position of its root object.
+///
+/// `ArchivedRoot` keeps the bytes of an archive together with the position of the archived value
+/// so that it can be passed around and read from without any manual pointer math. It dereferences
+/// to the archived value.
+///
+/// # Examples
+/// ```
+/// use rkyv::{to_archived_root, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     name: String,
+///     value: i32,
+/// }
+///
+/// let value = Example {
+///     name: "pi".to_string(),
+///     value: 31415926,
+/// };
+///
+/// let root = to_archived_root::<_, 256>(&value).unwrap();
+/// assert_eq!(root.name, "pi");
+/// assert_eq!(root.value, 31415926);
+///
+/// // The bytes can be taken back out of the root when they need to be written somewhere
+/// let bytes = root.into_bytes();
+/// assert!(!bytes.is_empty());
+/// ```
+pub struct ArchivedRoot<T: Archive> {
+    bytes: AlignedVec,
+    pos: usize,
+    _phantom: PhantomData<T::Archived>,
+}
+
+impl<T: Archive> ArchivedRoot<T> {
+    /// Creates a new archived root from a byte buffer and the position of the root object.
+    ///
+    /// # Safety
+    ///
+    /// A `T::Archived` must be archived at the given position in the byte buffer.
+    #[inline]
+    pub unsafe fn new(bytes: AlignedVec, pos: usize) -> Self {
+        debug_assert!(pos + mem::size_of::<T::Archived>() <= bytes.len());
+
+        Self {
+            bytes,
+            pos,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a new archived root from a byte buffer with the root object stored at the end.
+    ///
+    /// # Safety
+    ///
+    /// - The byte buffer must represent an archived object
+    /// - The root of the object must be stored at the end of the buffer (this is the default
+    ///   behavior)
+    #[inline]
+    pub unsafe fn from_root(bytes: AlignedVec) -> Self {
+        let pos = bytes.len() - mem::size_of::<T::Archived>();
+        Self::new(bytes, pos)
+    }
+
+    /// Creates a new archived root by copying a `Vec<u8>` into aligned memory.
+    ///
+    /// A `Vec<u8>` is only guaranteed to be aligned for bytes, so its contents are copied into an
+    /// [`AlignedVec`] before they are read from.
+    ///
+    /// # Safety
+    ///
+    /// A `T::Archived` must be archived at the given position in the byte buffer.
+    #[inline]
+    pub unsafe fn from_vec(bytes: Vec<u8>, pos: usize) -> Self {
+        let mut aligned = AlignedVec::with_capacity(bytes.len());
+        aligned.extend_from_slice(&bytes);
+        Self::new(aligned, pos)
+    }
+
+    /// Returns the position of the root object in the byte buffer.
+    #[inline]
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the bytes of the archive.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Consumes the archived root and returns the underlying bytes.
+    #[inline]
+    pub fn into_bytes(self) -> AlignedVec {
+        self.bytes
+    }
+}
+
+impl<T: Archive> AsRef<[u8]> for ArchivedRoot<T> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<T: Archive> Deref for ArchivedRoot<T> {
+    type Target = T::Archived;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { crate::archived_value::<T>(self.bytes.as_slice(), self.pos) }
+    }
+}
+
+impl<T: Archive> fmt::Debug for ArchivedRoot<T>
+where
+    T::Archived: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+/// Serializes the given value and returns an owned [`ArchivedRoot`] for it.
+///
+/// This uses an [`AllocSerializer`] with `N` bytes of scratch space.
+#[inline]
+pub fn to_archived_root<T, const N: usize>(
+    value: &T,
+) -> Result<ArchivedRoot<T>, <AllocSerializer<N> as Fallible>::Error>
+where
+    T: Serialize<AllocSerializer<N>>,
+{
+    let mut serializer = AllocSerializer::<N>::default();
+    let pos = serializer.serialize_value(value)?;
+    let bytes = serializer.into_serializer().into_inner();
+    // Safety: `pos` is the position that the value was serialized at
+    unsafe { Ok(ArchivedRoot::new(bytes, pos)) }
+}
@@ -8,10 +8,19 @@
 //!
 //! Alignment helpers ensure that byte buffers are properly aligned when accessing and deserializing
 //! data.
+//!
+//! ## Text encoding
+//!
+//! Helper functions to encode archives as hex or base64 text so they can be embedded in text
+//! formats, and to decode them back into aligned buffers.
 
 #[cfg(feature = "alloc")]
 mod aligned_vec;
+#[cfg(feature = "alloc")]
+mod archived_root;
 mod scratch_vec;
+#[cfg(feature = "alloc")]
+mod text;
 
 use crate::{Archive, ArchiveUnsized, RelPtr};
 use core::{
@@ -24,7 +33,13 @@ use core::{
 #[cfg(feature = "alloc")]
 pub use self::aligned_vec::*;
 #[doc(inline)]
+#[cfg(feature = "alloc")]
+pub use self::archived_root::*;
+#[doc(inline)]
 pub use self::scratch_vec::*;
+#[doc(inline)]
+#[cfg(feature = "alloc")]
+pub use self::text::*;
 
 #[cfg(debug_assertions)]
 #[inline]
@@ -75,6 +90,75 @@ pub unsafe fn archived_value_mut<T: Archive + ?Sized>(
     Pin::new_unchecked(&mut *bytes.get_unchecked_mut().as_mut_ptr().add(pos).cast())
 }
 
+/// Casts an archived value from an archive that is embedded at the given base offset of a larger
+/// byte slice.
+///
+/// This is useful when an archive is stored as one section of a larger file, such as a container
+/// format with a header before the archive. `root_pos` is the position of the value within the
+/// embedded archive (e.g. the position returned from
+/// [`serialize_value`](crate::ser::Serializer::serialize_value) when the archive was written), so
+/// the value is read from `base + root_pos` in `bytes`. If the root of the embedded archive is
+/// stored at the end of the archive (the default behavior), its position is the length of the
+/// archive minus `size_of::<T::Archived>()`.
+///
+/// Relative pointers are not affected by the base offset. Each [`RelPtr`] stores an offset from
+/// its own position, so it points to the same data no matter where the archive is placed. Only
+/// positions that are relative to the start of the archive, like `root_pos`, need to be adjusted.
+///
+/// Archives are aligned relative to their start, so `bytes` must be aligned such that
+/// `bytes.as_ptr() + base` has the same alignment that the archive was written with.
+///
+/// # Panics
+///
+/// Panics if `base` is greater than the length of `bytes`.
+///
+/// # Safety
+///
+/// A `T::Archived` must be archived at position `root_pos` of an archive that starts at position
+/// `base` in the byte slice.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     access_at,
+///     ser::{serializers::AllocSerializer, Serializer},
+///     AlignedVec, Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Section {
+///     name: String,
+///     values: Vec<u32>,
+/// }
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// let root_pos = serializer
+///     .serialize_value(&Section {
+///         name: "embedded section".to_string(),
+///         values: vec![1, 2, 3],
+///     })
+///     .unwrap();
+/// let archive = serializer.into_serializer().into_inner();
+///
+/// // Embed the archive after a 4KiB header
+/// let base = 4096;
+/// let mut file = AlignedVec::new();
+/// file.extend_from_slice(&[0u8; 4096]);
+/// file.extend_from_slice(&archive);
+///
+/// let section = unsafe { access_at::<Section>(&file, base, root_pos) };
+/// assert_eq!(section.name, "embedded section");
+/// assert_eq!(section.values, [1, 2, 3]);
+/// ```
+#[inline]
+pub unsafe fn access_at<T: Archive + ?Sized>(
+    bytes: &[u8],
+    base: usize,
+    root_pos: usize,
+) -> &T::Archived {
+    archived_value::<T>(&bytes[base..], root_pos)
+}
+
 /// Casts a [`RelPtr`] to the given unsized type from the given byte slice at the given position and
 /// returns the value it points to.
 ///
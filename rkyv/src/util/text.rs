@@ -0,0 +1,199 @@
+use crate::AlignedVec;
+#[cfg(not(feature = "std"))]
+use ::alloc::string::String;
+use core::fmt;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const BASE64_DIGITS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_PAD: u8 = b'=';
+
+/// An error that occurred while decoding text-encoded archive bytes.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TextDecodeError {
+    /// The text contained a character that is not valid in the encoding
+    InvalidCharacter {
+        /// The byte index of the invalid character in the text
+        index: usize,
+        /// The invalid character
+        character: char,
+    },
+    /// The length of the text is not valid for the encoding
+    InvalidLength(usize),
+}
+
+impl fmt::Display for TextDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextDecodeError::InvalidCharacter { index, character } => {
+                write!(f, "invalid character {:?} at index {}", character, index)
+            }
+            TextDecodeError::InvalidLength(len) => write!(f, "invalid text length {}", len),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use std::error::Error;
+
+    impl Error for TextDecodeError {}
+};
+
+#[inline]
+fn invalid_character(text: &str, index: usize) -> TextDecodeError {
+    TextDecodeError::InvalidCharacter {
+        index,
+        character: text[index..].chars().next().unwrap_or('\u{FFFD}'),
+    }
+}
+
+/// Encodes archive bytes as a lowercase hexadecimal string.
+///
+/// The bytes can be decoded into an aligned buffer with [`decode_hex`].
+pub fn encode_hex(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        result.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        result.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+    }
+    result
+}
+
+/// Decodes a hexadecimal string into an aligned buffer of archive bytes.
+///
+/// Both uppercase and lowercase digits are accepted. The decoded bytes are written into an
+/// [`AlignedVec`], so the archive can be accessed from the returned buffer directly. See
+/// [`decode_base64`] for more details on alignment.
+pub fn decode_hex(text: &str) -> Result<AlignedVec, TextDecodeError> {
+    let digits = text.as_bytes();
+    if digits.len() & 1 != 0 {
+        return Err(TextDecodeError::InvalidLength(digits.len()));
+    }
+
+    let digit = |index: usize| match digits[index] {
+        c @ b'0'..=b'9' => Ok(c - b'0'),
+        c @ b'a'..=b'f' => Ok(c - b'a' + 10),
+        c @ b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(invalid_character(text, index)),
+    };
+
+    let mut result = AlignedVec::with_capacity(digits.len() / 2);
+    for i in (0..digits.len()).step_by(2) {
+        result.push((digit(i)? << 4) | digit(i + 1)?);
+    }
+    Ok(result)
+}
+
+/// Encodes archive bytes as a padded base64 string using the standard alphabet.
+///
+/// The bytes can be decoded into an aligned buffer with [`decode_base64`].
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     archived_root,
+///     ser::{serializers::AllocSerializer, Serializer},
+///     util::{decode_base64, encode_base64},
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Settings {
+///     name: String,
+///     retries: u32,
+/// }
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer
+///     .serialize_value(&Settings {
+///         name: "embedded in a config file".to_string(),
+///         retries: 3,
+///     })
+///     .unwrap();
+/// let bytes = serializer.into_serializer().into_inner();
+///
+/// // The text can be stored in a JSON or TOML string field
+/// let text = encode_base64(&bytes);
+///
+/// let decoded = decode_base64(&text).unwrap();
+/// let archived = unsafe { archived_root::<Settings>(&decoded) };
+/// assert_eq!(archived.name, "embedded in a config file");
+/// assert_eq!(archived.retries, 3);
+/// ```
+pub fn encode_base64(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.chunks(3).len() * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(BASE64_DIGITS[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                result.push(BASE64_PAD as char);
+            }
+        }
+    }
+    result
+}
+
+/// Decodes a padded base64 string using the standard alphabet into an aligned buffer of archive
+/// bytes.
+///
+/// # Alignment
+///
+/// Archived types must be read from properly aligned memory, but a buffer of decoded bytes (like a
+/// `Vec<u8>`) is only guaranteed to be aligned to one byte. To make the decoded archive readable in
+/// place, the bytes are decoded directly into an [`AlignedVec`], which is always aligned to
+/// [`AlignedVec::ALIGNMENT`] (16) bytes. Because archives are written starting at an aligned
+/// position, every value in the archive ends up aligned just as it was in the serializer's output.
+/// Archives containing types with an alignment greater than 16 bytes must be copied into a buffer
+/// with a stricter alignment before they can be accessed.
+pub fn decode_base64(text: &str) -> Result<AlignedVec, TextDecodeError> {
+    let digits = text.as_bytes();
+    if digits.len() & 3 != 0 {
+        return Err(TextDecodeError::InvalidLength(digits.len()));
+    }
+
+    // Padding may only appear at the end of the last group
+    let padding = digits
+        .iter()
+        .rev()
+        .take(2)
+        .filter(|&&c| c == BASE64_PAD)
+        .count();
+    let data_len = digits.len() - padding;
+
+    let digit = |index: usize| {
+        let c = digits[index];
+        match c {
+            b'A'..=b'Z' => Ok(c - b'A'),
+            b'a'..=b'z' => Ok(c - b'a' + 26),
+            b'0'..=b'9' => Ok(c - b'0' + 52),
+            b'+' => Ok(62),
+            b'/' => Ok(63),
+            _ => Err(invalid_character(text, index)),
+        }
+    };
+
+    let mut result = AlignedVec::with_capacity(digits.len() / 4 * 3);
+    for start in (0..digits.len()).step_by(4) {
+        let mut n = 0u32;
+        let mut count = 0;
+        for i in start..start + 4 {
+            n <<= 6;
+            if i < data_len {
+                n |= digit(i)? as u32;
+                count += 1;
+            }
+        }
+        for i in 0..count - 1 {
+            result.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(result)
+}
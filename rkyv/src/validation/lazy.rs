@@ -0,0 +1,208 @@
+//! Lazy validation for large archives that are only partially read.
+
+use crate::{
+    validation::{
+        validators::{check_archived_value, DefaultValidator},
+        CheckTypeError,
+    },
+    Archive,
+};
+use bytecheck::CheckBytes;
+use core::{any::TypeId, mem::size_of, ops::Range};
+use std::{
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+};
+
+/// An archive reader that validates values the first time they are accessed.
+///
+/// Checking a whole archive up front with [`check_archived_root`](super::validators::check_archived_root)
+/// touches every byte of it. For very large archives that are memory-mapped and only sparsely read,
+/// most of that work is wasted and the whole file gets paged in. A lazy archive instead checks each
+/// value when it is first accessed with [`access`](LazyArchive::access), and remembers which values
+/// it has checked so that accessing them again costs only a lookup.
+///
+/// Validating a value checks the value and everything it points to, just like
+/// [`check_archived_value`]. The bytes of a value can only be validated relative to the type that
+/// reads them, so the archive tracks checked values by position _and_ type: accessing the same
+/// position as a different type validates it again. Values that share out-of-line data (for
+/// example, two values that are both reachable from the root) are validated independently.
+///
+/// The bytes can come from anywhere, but are typically a memory map of an archive file (for
+/// example, a `memmap2::Mmap`, which dereferences to `[u8]`). They must be aligned like any other
+/// archive.
+///
+/// # Thread safety
+///
+/// A lazy archive is `Sync`, and can be shared between threads to access the archive
+/// concurrently. The set of checked values is guarded by a read-write lock: looking up whether a
+/// value has been checked takes a shared lock, and recording a newly-checked value takes an
+/// exclusive lock for the duration of a single insertion. The lock is never held while a value is
+/// being validated, so threads validating different values don't block each other. If multiple
+/// threads access the same unchecked value at the same time, each of them may validate it; this
+/// wastes some work but is never unsound, because the value is only recorded after it has been
+/// validated successfully. Values that fail validation are never recorded.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     ser::{serializers::AllocSerializer, Serializer},
+///     validation::lazy::LazyArchive,
+///     Archive, Serialize,
+/// };
+/// use bytecheck::CheckBytes;
+///
+/// #[derive(Archive, Serialize)]
+/// #[archive_attr(derive(CheckBytes))]
+/// struct Record {
+///     name: String,
+///     values: Vec<u32>,
+/// }
+///
+/// // Write many records and remember where each one is
+/// let mut serializer = AllocSerializer::<256>::default();
+/// let positions = (0..100)
+///     .map(|i| {
+///         let record = Record {
+///             name: format!("record #{}", i),
+///             values: vec![i; 16],
+///         };
+///         serializer.serialize_value(&record).unwrap()
+///     })
+///     .collect::<Vec<_>>();
+/// let bytes = serializer.into_serializer().into_inner();
+///
+/// let archive = LazyArchive::new(&bytes);
+///
+/// // Only the accessed record is validated
+/// let record = archive.access::<Record>(positions[42]).unwrap();
+/// assert_eq!(record.name, "record #42");
+/// assert!(archive.is_checked::<Record>(positions[42]));
+/// assert!(!archive.is_checked::<Record>(positions[43]));
+///
+/// // Accessing it again doesn't validate it again
+/// let record = archive.access::<Record>(positions[42]).unwrap();
+/// assert_eq!(record.values[0], 42);
+/// ```
+#[derive(Debug)]
+pub struct LazyArchive<'a> {
+    bytes: &'a [u8],
+    checked: RwLock<HashMap<(usize, TypeId), usize>>,
+}
+
+impl<'a> LazyArchive<'a> {
+    /// Creates a new lazy archive over the given bytes. Nothing is validated until it is accessed.
+    #[inline]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            checked: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the bytes of the archive.
+    #[inline]
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns whether the value at the given position has already been validated as a `T`.
+    #[inline]
+    pub fn is_checked<T: Archive>(&self, pos: usize) -> bool
+    where
+        T::Archived: 'static,
+    {
+        self.checked
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(&(pos, TypeId::of::<T::Archived>()))
+    }
+
+    /// Accesses the archived `T` at the given position, validating it if it has not been
+    /// validated as a `T` before.
+    ///
+    /// Returns an error if the value fails validation. The value is checked again the next time it
+    /// is accessed.
+    pub fn access<T: Archive>(
+        &self,
+        pos: usize,
+    ) -> Result<&'a T::Archived, CheckTypeError<T::Archived, DefaultValidator<'a>>>
+    where
+        T::Archived: CheckBytes<DefaultValidator<'a>> + 'static,
+    {
+        if self.is_checked::<T>(pos) {
+            // Safety: the value at this position was validated as a `T` and the bytes of the
+            // archive can't change while they are borrowed
+            return Ok(unsafe { crate::archived_value::<T>(self.bytes, pos) });
+        }
+
+        let result = check_archived_value::<T>(self.bytes, pos)?;
+        self.checked
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                (pos, TypeId::of::<T::Archived>()),
+                pos + size_of::<T::Archived>(),
+            );
+        Ok(result)
+    }
+
+    /// Accesses the archived root `T`, validating it if it has not been validated as a `T`
+    /// before.
+    ///
+    /// The root is located at the end of the archive, as with
+    /// [`archived_root`](crate::archived_root).
+    #[inline]
+    pub fn access_root<T: Archive>(
+        &self,
+    ) -> Result<&'a T::Archived, CheckTypeError<T::Archived, DefaultValidator<'a>>>
+    where
+        T::Archived: CheckBytes<DefaultValidator<'a>> + 'static,
+    {
+        self.access::<T>(self.bytes.len().saturating_sub(size_of::<T::Archived>()))
+    }
+
+    /// Returns the number of values that have been validated.
+    #[inline]
+    pub fn checked_count(&self) -> usize {
+        self.checked
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns the byte ranges of the values that have been validated, sorted and with
+    /// overlapping or adjacent ranges merged.
+    ///
+    /// Each range covers the bytes of a validated value itself. The out-of-line data that a value
+    /// points to is validated along with it, but is not included in its range.
+    pub fn checked_ranges(&self) -> Vec<Range<usize>> {
+        let mut ranges = self
+            .checked
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(&(start, _), &end)| start..end)
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|range| range.start);
+
+        let mut result = Vec::<Range<usize>>::with_capacity(ranges.len());
+        for range in ranges {
+            match result.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => result.push(range),
+            }
+        }
+        result
+    }
+
+    /// Forgets which values have been validated, so they are validated again on their next
+    /// access.
+    #[inline]
+    pub fn clear(&self) {
+        self.checked
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
@@ -1,5 +1,7 @@
 //! Validation implementations and helper types.
 
+#[cfg(feature = "std")]
+pub mod lazy;
 pub mod owned;
 pub mod validators;
 
@@ -287,6 +289,34 @@ impl<T: Error + 'static, C: Error + 'static> Error for CheckArchiveError<T, C> {
     }
 }
 
+/// Errors that can occur when checking an archive and then deserializing it.
+#[derive(Debug)]
+pub enum CheckDeserializeError<C, D> {
+    /// An error that occurred while checking the archive
+    CheckError(C),
+    /// An error that occurred while deserializing the checked archive
+    DeserializeError(D),
+}
+
+impl<C: fmt::Display, D: fmt::Display> fmt::Display for CheckDeserializeError<C, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckDeserializeError::CheckError(e) => write!(f, "check error: {}", e),
+            CheckDeserializeError::DeserializeError(e) => write!(f, "deserialize error: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: Error + 'static, D: Error + 'static> Error for CheckDeserializeError<C, D> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CheckDeserializeError::CheckError(e) => Some(e as &dyn Error),
+            CheckDeserializeError::DeserializeError(e) => Some(e as &dyn Error),
+        }
+    }
+}
+
 /// The error type that can be produced by checking the given type with the given validator.
 pub type CheckTypeError<T, C> =
     CheckArchiveError<<T as CheckBytes<C>>::Error, <C as Fallible>::Error>;
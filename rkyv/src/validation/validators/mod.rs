@@ -7,9 +7,9 @@ mod shared;
 use crate::{
     validation::{
         check_archived_root_with_context, check_archived_value_with_context, ArchiveContext,
-        CheckTypeError, SharedContext,
+        CheckDeserializeError, CheckTypeError, SharedContext,
     },
-    Archive, Fallible,
+    Archive, Deserialize, Fallible,
 };
 pub use archive::*;
 use bytecheck::CheckBytes;
@@ -221,6 +221,14 @@ where
 /// This is a safe alternative to [`archived_value`](crate::archived_value) for types that implement
 /// `CheckBytes`.
 ///
+/// The returned reference has been fully validated, so it can be deserialized directly with
+/// [`Deserialize`] without checking the archive again. Use [`deserialize_checked`] to check and
+/// deserialize an archive in one step.
+///
+/// For archives that start with an [`ArchiveHeader`](crate::header::ArchiveHeader), use
+/// [`check_archived_root_with_header`](crate::header::check_archived_root_with_header) instead. It
+/// also verifies the archive's checksum before checking it, if the header has one.
+///
 /// See [`check_archived_value`] for more details.
 #[inline]
 pub fn check_archived_root<'a, T: Archive>(
@@ -232,3 +240,59 @@ where
     let mut validator = DefaultValidator::new(bytes);
     check_archived_root_with_context::<T, DefaultValidator>(bytes, &mut validator)
 }
+
+/// Checks the given archive for an archived version of the given type, then deserializes it.
+///
+/// Validation happens exactly once: the archive is checked with [`check_archived_root`], and the
+/// validated archived value is then deserialized with [`Deserialize`], which never checks the
+/// archive itself. If the archive has already been checked, deserialize the reference returned by
+/// [`check_archived_root`] directly instead to avoid checking it twice.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     ser::{serializers::AllocSerializer, Serializer},
+///     validation::{validators::deserialize_checked, CheckDeserializeError},
+///     Archive, Deserialize, Infallible, Serialize,
+/// };
+/// use bytecheck::CheckBytes;
+///
+/// #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+/// #[archive_attr(derive(CheckBytes))]
+/// struct Example {
+///     name: String,
+///     value: i32,
+/// }
+///
+/// let value = Example {
+///     name: "pi".to_string(),
+///     value: 31415926,
+/// };
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&value).unwrap();
+/// let bytes = serializer.into_serializer().into_inner();
+///
+/// let deserialized = deserialize_checked::<Example, _>(&bytes, &mut Infallible).unwrap();
+/// assert_eq!(deserialized, value);
+///
+/// // Invalid archives are rejected before deserializing
+/// let result = deserialize_checked::<Example, _>(&bytes[1..], &mut Infallible);
+/// assert!(matches!(result, Err(CheckDeserializeError::CheckError(_))));
+/// ```
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn deserialize_checked<'a, T, D>(
+    bytes: &'a [u8],
+    deserializer: &mut D,
+) -> Result<T, CheckDeserializeError<CheckTypeError<T::Archived, DefaultValidator<'a>>, D::Error>>
+where
+    T: Archive,
+    T::Archived: 'a + CheckBytes<DefaultValidator<'a>> + Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    check_archived_root::<T>(bytes)
+        .map_err(CheckDeserializeError::CheckError)?
+        .deserialize(deserializer)
+        .map_err(CheckDeserializeError::DeserializeError)
+}
@@ -2,20 +2,43 @@
 
 use crate::{
     ser::{ScratchSpace, Serializer},
-    Archive, Archived, RelPtr, Serialize, SerializeUnsized,
+    Archive, ArchivePrimitive, Archived, RelPtr, Serialize, SerializeUnsized,
 };
 use core::{
     borrow::Borrow,
     cmp, fmt, hash,
     ops::{Deref, Index, IndexMut},
     pin::Pin,
-    slice::SliceIndex,
+    slice::{self, SliceIndex},
 };
 
 /// An archived [`Vec`].
 ///
 /// This uses a [`RelPtr`] to a `[T]` under the hood. Unlike
 /// [`ArchivedString`](crate::string::ArchivedString), it does not have an inline representation.
+///
+/// Archived vecs dereference to slices, so [`iter`](slice::iter) returns a standard
+/// [`slice::Iter`]. It borrows the elements in place and works with all of the `Iterator`
+/// adapters, so filtered and mapped views of an archived vec can be built without allocating.
+///
+/// The elements are stored as a single array that starts at a multiple of `align_of::<T>()`
+/// relative to the start of the archive, and each element takes exactly `size_of::<T>()` bytes
+/// (which is always a multiple of its alignment). Elements with an alignment greater than
+/// [`AlignedVec::ALIGNMENT`](crate::AlignedVec::ALIGNMENT) are only aligned in memory if the
+/// archive bytes are also placed in a buffer with at least that alignment; validating an archive
+/// reports an error if they aren't.
+///
+/// # Thread safety
+///
+/// An archived vec only points to its elements with a relative offset, so it is `Sync` exactly
+/// when `T` is and `Send` exactly when `T` is. The same holds for the slices it dereferences to.
+/// With native endianness, archived primitives, strings, and vecs of them are all `Sync`, so shared
+/// references to their elements can be handed to other threads. The endian-aware primitives used
+/// by the `archive_le` and `archive_be` features are not `Sync`, so neither are archived types that
+/// contain them. Disjoint sub-slices from
+/// [`split_at`](ArchivedVec::split_at) or [`chunks`](slice::chunks) can be processed in parallel,
+/// for example with rayon's `join` or `par_iter`, as long as the archive bytes outlive the threads
+/// that borrow them.
 #[cfg_attr(feature = "strict", repr(C))]
 pub struct ArchivedVec<T> {
     ptr: RelPtr<T>,
@@ -24,6 +47,52 @@ pub struct ArchivedVec<T> {
 
 impl<T> ArchivedVec<T> {
     /// Returns a pointer to the first element of the archived vec.
+    ///
+    /// Together with [`len`](ArchivedVec::len), the pointer describes the elements as one
+    /// contiguous array of `T`, so they can be handed to a C library or uploaded to a GPU without
+    /// copying them out of the archive. Code that uses the pointer must respect these guarantees
+    /// and limits:
+    ///
+    /// - The pointer is never null. It is aligned to `align_of::<T>()` whenever the archive bytes
+    ///   are aligned, as described in the [type-level documentation](ArchivedVec), and is valid for
+    ///   reads of `len() * size_of::<T>()` bytes. It must not be dereferenced if the vec is empty.
+    /// - The pointer is only valid while the archived vec is borrowed. The archive bytes must not
+    ///   be moved, freed, or modified while the pointer is in use, so foreign code must not hold on
+    ///   to it after the borrow ends.
+    /// - The elements must not be written through the pointer. Use
+    ///   [`pin_mut_slice`](ArchivedVec::pin_mut_slice) to modify archived elements.
+    /// - The elements are archived values, which only have the same layout as the native types a
+    ///   foreign library expects when both agree. Archived primitives are stored with the byte
+    ///   order selected by the `archive_le` and `archive_be` features and `Archived<usize>` is sized
+    ///   by the `size_*` features, and archived structs only have a defined layout when they are
+    ///   `#[archive_attr(repr(C))]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::mem::size_of;
+    /// use rkyv::{archived_root, ser::{Serializer, serializers::AllocSerializer}, Archived};
+    ///
+    /// // Stands in for a function from a native library, like a GPU buffer upload
+    /// unsafe extern "C" fn upload(data: *const u8, len: usize) -> u32 {
+    ///     let bytes = core::slice::from_raw_parts(data, len);
+    ///     bytes.iter().map(|b| *b as u32).sum()
+    /// }
+    ///
+    /// let value = vec![1u32, 2, 3, 4];
+    /// let mut serializer = AllocSerializer::<256>::default();
+    /// serializer.serialize_value(&value).unwrap();
+    /// let bytes = serializer.into_serializer().into_inner();
+    /// let archived = unsafe { archived_root::<Vec<u32>>(&bytes) };
+    ///
+    /// let ptr = archived.as_ptr();
+    /// assert_eq!(ptr as usize % core::mem::align_of::<Archived<u32>>(), 0);
+    ///
+    /// // The archive outlives the call, so the pointer stays valid for as long as it's used
+    /// let len = archived.len() * size_of::<Archived<u32>>();
+    /// let checksum = unsafe { upload(ptr.cast::<u8>(), len) };
+    /// assert_eq!(checksum, 1 + 2 + 3 + 4);
+    /// ```
     #[inline]
     pub fn as_ptr(&self) -> *const T {
         self.ptr.as_ptr()
@@ -47,6 +116,44 @@ impl<T> ArchivedVec<T> {
         unsafe { core::slice::from_raw_parts(self.as_ptr(), self.len()) }
     }
 
+    /// Divides the elements of the archived vec into two non-overlapping slices at an index.
+    ///
+    /// The first slice contains the elements in `[0, mid)` and the second contains the elements in
+    /// `[mid, len)`. When `T` is `Sync`, the two halves can be processed on different threads. See
+    /// the [thread safety](ArchivedVec#thread-safety) section for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rkyv::{archived_root, ser::{Serializer, serializers::AllocSerializer}};
+    ///
+    /// let value = (0..1000u32).collect::<Vec<_>>();
+    /// let mut serializer = AllocSerializer::<256>::default();
+    /// serializer.serialize_value(&value).unwrap();
+    /// let bytes = serializer.into_serializer().into_inner();
+    /// let archived = unsafe { archived_root::<Vec<u32>>(&bytes) };
+    ///
+    /// let (left, right) = archived.split_at(archived.len() / 2);
+    /// assert_eq!(left.len(), 500);
+    /// assert_eq!(right.len(), 500);
+    /// assert_eq!(rkyv::from_archived!(right[0]), 500);
+    ///
+    /// // Each half can be scanned independently, for example on another thread
+    /// let sum = |half: &[rkyv::Archived<u32>]| {
+    ///     half.iter().map(|x| rkyv::from_archived!(*x) as u64).sum::<u64>()
+    /// };
+    /// let (left_sum, right_sum) = (sum(left), sum(right));
+    /// assert_eq!(left_sum + right_sum, 999 * 1000 / 2);
+    /// ```
+    #[inline]
+    pub fn split_at(&self, mid: usize) -> (&[T], &[T]) {
+        self.as_slice().split_at(mid)
+    }
+
     /// Gets the elements of the archived vec as a pinned mutable slice.
     #[inline]
     pub fn pin_mut_slice(self: Pin<&mut Self>) -> Pin<&mut [T]> {
@@ -98,6 +205,10 @@ impl<T> ArchivedVec<T> {
     }
 
     /// Serializes an archived `Vec` from a given slice.
+    ///
+    /// The dependencies of every element (like the bytes of strings) are serialized first, and then
+    /// the archived elements are written as one contiguous, aligned array. Scanning the archived
+    /// elements only touches the array, and elements are never interleaved with their data.
     #[inline]
     pub fn serialize_from_slice<U: Serialize<S, Archived = T>, S: Serializer + ?Sized>(
         slice: &[U],
@@ -140,6 +251,97 @@ impl<T> ArchivedVec<T> {
         Ok(VecResolver { pos })
     }
 
+    /// Serializes an archived `Vec` from a given slice of primitives.
+    ///
+    /// The elements are converted to their archived form in batches of about 4KiB, and each batch
+    /// is written with a single call to [`write`](Serializer::write). When the archive's
+    /// endianness differs from the target's (for example with `archive_be` on a little-endian
+    /// target), the conversion is one pass of byte swaps over the batch rather than resolving each
+    /// element through the serializer. When the endianness matches, it amounts to a copy.
+    ///
+    /// # Examples
+    /// ```
+    /// use rkyv::{
+    ///     archived_root,
+    ///     ser::{serializers::AlignedSerializer, Serializer},
+    ///     vec::{ArchivedVec, VecResolver},
+    ///     AlignedVec, Archive, Archived, Serialize,
+    /// };
+    ///
+    /// struct Samples(Vec<u32>);
+    ///
+    /// impl Archive for Samples {
+    ///     type Archived = ArchivedVec<Archived<u32>>;
+    ///     type Resolver = VecResolver;
+    ///
+    ///     unsafe fn resolve(&self, pos: usize, resolver: VecResolver, out: *mut Self::Archived) {
+    ///         ArchivedVec::resolve_from_slice(self.0.as_slice(), pos, resolver, out);
+    ///     }
+    /// }
+    ///
+    /// impl<S: Serializer + ?Sized> Serialize<S> for Samples {
+    ///     fn serialize(&self, serializer: &mut S) -> Result<VecResolver, S::Error> {
+    ///         ArchivedVec::serialize_from_primitive_slice(self.0.as_slice(), serializer)
+    ///     }
+    /// }
+    ///
+    /// let samples = Samples((0..10_000).collect());
+    /// let mut serializer = AlignedSerializer::new(AlignedVec::new());
+    /// serializer.serialize_value(&samples).unwrap();
+    /// let bytes = serializer.into_inner();
+    ///
+    /// let archived = unsafe { archived_root::<Samples>(&bytes) };
+    /// assert_eq!(archived.len(), 10_000);
+    /// assert_eq!(archived[1234], 1234);
+    /// ```
+    pub fn serialize_from_primitive_slice<U, S>(
+        slice: &[U],
+        serializer: &mut S,
+    ) -> Result<VecResolver, S::Error>
+    where
+        U: ArchivePrimitive<Archived = T>,
+        S: Serializer + ?Sized,
+    {
+        use ::core::mem::{size_of, MaybeUninit};
+
+        const BATCH_SIZE: usize = 4096;
+
+        let pos = serializer.align_for::<T>()?;
+        if size_of::<T>() == 0 {
+            return Ok(VecResolver { pos });
+        }
+
+        let batch_len = BATCH_SIZE / size_of::<T>();
+        if batch_len == 0 {
+            // Values that don't fit in a batch are written one at a time
+            for value in slice {
+                let archived = value.archive_primitive();
+                let bytes = unsafe {
+                    slice::from_raw_parts((&archived as *const T).cast::<u8>(), size_of::<T>())
+                };
+                serializer.write(bytes)?;
+            }
+            return Ok(VecResolver { pos });
+        }
+
+        let mut batch = MaybeUninit::<[u8; BATCH_SIZE]>::uninit();
+        let batch_ptr = batch.as_mut_ptr().cast::<u8>();
+        for chunk in slice.chunks(batch_len) {
+            // Safety: the chunk fits in the batch, and archived primitives have no padding so every
+            // byte that gets written is initialized
+            let bytes = unsafe {
+                let out = batch_ptr.cast::<T>();
+                for (i, value) in chunk.iter().enumerate() {
+                    out.add(i).write_unaligned(value.archive_primitive());
+                }
+                slice::from_raw_parts(batch_ptr, chunk.len() * size_of::<T>())
+            };
+            serializer.write(bytes)?;
+        }
+
+        Ok(VecResolver { pos })
+    }
+
     /// Serializes an archived `Vec` from a given iterator.
     ///
     /// This method is unable to perform copy optimizations; prefer
@@ -207,6 +409,16 @@ impl<T> Deref for ArchivedVec<T> {
 
 impl<T: Eq> Eq for ArchivedVec<T> {}
 
+impl<'a, T> IntoIterator for &'a ArchivedVec<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
 impl<T: hash::Hash> hash::Hash for ArchivedVec<T> {
     #[inline]
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
@@ -223,6 +435,36 @@ impl<T, I: SliceIndex<[T]>> Index<I> for ArchivedVec<T> {
     }
 }
 
+/// Archived vecs are `!Unpin` because moving one would break its relative pointer, so a
+/// `&mut ArchivedVec` can only be obtained from a pinned reference with the unsafe
+/// [`Pin::get_unchecked_mut`]. When a mutable reference is available, elements that are [`Unpin`]
+/// (like primitives) can be indexed mutably to update them in place.
+///
+/// Without unsafe code, use [`index_pin`](ArchivedVec::index_pin) or
+/// [`pin_mut_slice`](ArchivedVec::pin_mut_slice) instead. Their results can be unwrapped with
+/// [`Pin::get_mut`] when the elements are [`Unpin`]:
+///
+/// ```
+/// use core::pin::Pin;
+/// use rkyv::{archived_root_mut, ser::Serializer, ser::serializers::AllocSerializer};
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&vec![1u32, 2, 3]).unwrap();
+/// let mut buf = serializer.into_serializer().into_inner();
+///
+/// let mut archived = unsafe { archived_root_mut::<Vec<u32>>(Pin::new(buf.as_mut())) };
+/// *archived.as_mut().index_pin(1).get_mut() = 20u32.into();
+/// archived.as_mut().pin_mut_slice().get_mut()[2] = 30u32.into();
+/// assert_eq!(archived.as_slice(), [1, 20, 30]);
+/// ```
+impl<T: Unpin, I: SliceIndex<[T]>> IndexMut<I> for ArchivedVec<T> {
+    #[inline]
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        let len = self.len();
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_mut_ptr(), len) }.index_mut(index)
+    }
+}
+
 impl<T: Ord> Ord for ArchivedVec<T> {
     #[inline]
     fn cmp(&self, other: &Self) -> cmp::Ordering {
@@ -288,9 +530,120 @@ impl<T: PartialOrd> PartialOrd<ArchivedVec<T>> for [T] {
 
 /// The resolver for [`ArchivedVec`].
 pub struct VecResolver {
-    pos: usize,
+    pub(crate) pos: usize,
+}
+
+/// An archived [`Vec`] that also stores the capacity of the original vec.
+///
+/// Archived vecs usually only store their length, so a deserialized `Vec` has exactly enough
+/// capacity for its elements and reallocates as soon as it grows. When a deserialized vec is going
+/// to grow right away (for example, state that is reloaded and then immediately appended to), the
+/// [`PreserveCapacity`](crate::with::PreserveCapacity) wrapper archives the capacity along with the
+/// length so that the vec can be deserialized with the same capacity it had when it was
+/// serialized. This costs one extra `Archived<usize>` word over an [`ArchivedVec`].
+///
+/// Archived vecs with capacity dereference to archived vecs, so they can be used in all of the
+/// same ways.
+#[cfg_attr(feature = "strict", repr(C))]
+pub struct ArchivedVecWithCapacity<T> {
+    vec: ArchivedVec<T>,
+    capacity: Archived<usize>,
 }
 
+impl<T> ArchivedVecWithCapacity<T> {
+    /// Returns the capacity of the vec when it was serialized.
+    ///
+    /// This is never less than the length of the vec in archives that were produced by rkyv, but
+    /// deserializing always allocates space for at least as many elements as the vec contains.
+    /// Validation doesn't limit the capacity, so deserializing untrusted archives should use a
+    /// deserializer that limits how much memory can be allocated (like
+    /// [`BudgetDeserializer`](crate::de::deserializers::BudgetDeserializer)).
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        from_archived!(self.capacity) as usize
+    }
+
+    /// Gets the elements of the archived vec as an archived vec without the capacity.
+    #[inline]
+    pub fn as_vec(&self) -> &ArchivedVec<T> {
+        &self.vec
+    }
+
+    /// Gets the elements of the archived vec as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self.vec.as_slice()
+    }
+
+    /// Resolves an archived `Vec` with capacity from a given length and capacity.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be the number of elements that were serialized
+    /// - `pos` must be the position of `out` within the archive
+    /// - `resolver` must be the result of serializing the elements
+    #[inline]
+    pub unsafe fn resolve_from_len(
+        len: usize,
+        capacity: usize,
+        pos: usize,
+        resolver: VecWithCapacityResolver,
+        out: *mut Self,
+    ) {
+        let (fp, fo) = out_field!(out.vec);
+        ArchivedVec::resolve_from_len(len, pos + fp, resolver.0, fo);
+        let (fp, fo) = out_field!(out.capacity);
+        usize::resolve(&capacity, pos + fp, (), fo);
+    }
+
+    /// Serializes the elements of an archived `Vec` with capacity from a given slice.
+    #[inline]
+    pub fn serialize_from_slice<U: Serialize<S, Archived = T>, S: Serializer + ?Sized>(
+        slice: &[U],
+        serializer: &mut S,
+    ) -> Result<VecWithCapacityResolver, S::Error>
+    where
+        [U]: SerializeUnsized<S>,
+    {
+        Ok(VecWithCapacityResolver(ArchivedVec::serialize_from_slice(
+            slice, serializer,
+        )?))
+    }
+}
+
+impl<T> AsRef<[T]> for ArchivedVecWithCapacity<T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedVecWithCapacity<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<T> Deref for ArchivedVecWithCapacity<T> {
+    type Target = ArchivedVec<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.vec
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for ArchivedVecWithCapacity<T> {
+    #[inline]
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice().eq(other)
+    }
+}
+
+/// The resolver for [`ArchivedVecWithCapacity`].
+pub struct VecWithCapacityResolver(VecResolver);
+
 #[cfg(feature = "validation")]
 const _: () = {
     use crate::validation::{
@@ -298,6 +651,7 @@ const _: () = {
         ArchiveContext,
     };
     use bytecheck::{CheckBytes, Error};
+    use core::ptr;
 
     impl<T, C> CheckBytes<C> for ArchivedVec<T>
     where
@@ -329,4 +683,23 @@ const _: () = {
             Ok(&*value)
         }
     }
+
+    impl<T, C> CheckBytes<C> for ArchivedVecWithCapacity<T>
+    where
+        T: CheckBytes<C>,
+        C: ArchiveContext + ?Sized,
+        C::Error: Error,
+    {
+        type Error = CheckOwnedPointerError<[T], C>;
+
+        #[inline]
+        unsafe fn check_bytes<'a>(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<&'a Self, Self::Error> {
+            // Every capacity is valid, it's only used as a hint when deserializing
+            ArchivedVec::<T>::check_bytes(ptr::addr_of!((*value).vec), context)?;
+            Ok(&*value)
+        }
+    }
 };
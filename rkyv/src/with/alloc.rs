@@ -1,24 +1,45 @@
 use crate::{
     boxed::{ArchivedBox, BoxResolver},
-    collections::util::Entry,
+    bytes::{ArchivedBytes, BytesResolver},
+    collections::{
+        btree_map::{ArchivedBTreeMap, BTreeMapResolver},
+        delta_vec::{ArchivedDeltaVec, DeltaEncode, DeltaVecResolver},
+        inline_vec::{ArchivedInlineVec, InlineVecResolver},
+        nullable_vec::{ArchivedNullableVec, NullableVecResolver},
+        result_vec::{ArchivedResultVec, ResultVecResolver},
+        rle_vec::{ArchivedRleVec, RleVecResolver},
+        sorted_index::{ArchivedSortedIndex, SortedIndexResolver},
+        sorted_vec::{ArchivedSortedVec, SortedVecResolver},
+        util::Entry,
+    },
+    compression::{ArchivedCompressed, CompressedResolver, Compression},
+    de::AllocationBudget,
     niche::option_box::{ArchivedOptionBox, OptionBoxResolver},
     ser::{ScratchSpace, Serializer},
-    string::{ArchivedString, StringResolver},
-    vec::{ArchivedVec, VecResolver},
+    string::{
+        front_coded::{ArchivedFrontCodedVec, FrontCodedVecResolver},
+        table::{ArchivedStringTable, StringTableResolver},
+        ArchivedString, StringResolver,
+    },
+    vec::{ArchivedVec, ArchivedVecWithCapacity, VecResolver, VecWithCapacityResolver},
     with::{
-        ArchiveWith, AsOwned, AsVec, CopyOptimize, DeserializeWith, Niche, RefAsBox, SerializeWith,
-        With,
+        ArchiveWith, AsBytes, AsDeltaVec, AsFrontCodedVec, AsInlineVec, AsInlineVecError,
+        AsNullable, AsOwned, AsResultVec, AsRleVec, AsSortedIndex, AsSortedVec, AsStringTable,
+        AsVec, BranchingFactor, Compress, CopyOptimize, DeserializeWith, Niche, PreserveCapacity,
+        RefAsBox, SerializeWith, SwapOptimize, With,
     },
-    Archive, ArchiveUnsized, ArchivedMetadata, Deserialize, DeserializeUnsized, Fallible,
-    MetadataResolver, Serialize, SerializeUnsized,
+    Archive, ArchivePrimitive, ArchiveUnsized, ArchivedMetadata, Deserialize, DeserializeUnsized,
+    Fallible, MetadataResolver, Serialize, SerializeUnsized,
 };
 #[cfg(not(feature = "std"))]
 use alloc::{
     borrow::Cow,
     boxed::Box,
     collections::{BTreeMap, BTreeSet},
+    string::String,
     vec::Vec,
 };
+use core::alloc::Layout;
 #[cfg(feature = "std")]
 use std::{
     borrow::Cow,
@@ -91,7 +112,7 @@ impl<'a, T, D> DeserializeWith<ArchivedVec<T::Archived>, Cow<'a, [T]>, D> for As
 where
     T: Archive + Clone,
     T::Archived: Deserialize<T, D>,
-    D: Fallible + ?Sized,
+    D: AllocationBudget + ?Sized,
 {
     #[inline]
     fn deserialize_with(
@@ -127,7 +148,9 @@ impl<'a, S: Serializer + ?Sized> SerializeWith<Cow<'a, str>, S> for AsOwned {
     }
 }
 
-impl<'a, D: Fallible + ?Sized> DeserializeWith<ArchivedString, Cow<'a, str>, D> for AsOwned {
+impl<'a, D: AllocationBudget + ?Sized> DeserializeWith<ArchivedString, Cow<'a, str>, D>
+    for AsOwned
+{
     #[inline]
     fn deserialize_with(
         field: &ArchivedString,
@@ -167,7 +190,9 @@ const _: () = {
         }
     }
 
-    impl<'a, D: Fallible + ?Sized> DeserializeWith<ArchivedCString, Cow<'a, CStr>, D> for AsOwned {
+    impl<'a, D: AllocationBudget + ?Sized> DeserializeWith<ArchivedCString, Cow<'a, CStr>, D>
+        for AsOwned
+    {
         #[inline]
         fn deserialize_with(
             field: &ArchivedCString,
@@ -218,7 +243,7 @@ where
     V: Archive,
     K::Archived: Deserialize<K, D>,
     V::Archived: Deserialize<V, D>,
-    D: Fallible + ?Sized,
+    D: AllocationBudget + ?Sized,
 {
     fn deserialize_with(
         field: &ArchivedVec<Entry<K::Archived, V::Archived>>,
@@ -226,6 +251,7 @@ where
     ) -> Result<BTreeMap<K, V>, D::Error> {
         let mut result = BTreeMap::new();
         for entry in field.iter() {
+            deserializer.reserve_allocation(Layout::new::<(K, V)>())?;
             result.insert(
                 entry.key.deserialize(deserializer)?,
                 entry.value.deserialize(deserializer)?,
@@ -263,7 +289,7 @@ impl<T, D> DeserializeWith<ArchivedVec<T::Archived>, BTreeSet<T>, D> for AsVec
 where
     T: Archive + Ord,
     T::Archived: Deserialize<T, D>,
-    D: Fallible + ?Sized,
+    D: AllocationBudget + ?Sized,
 {
     fn deserialize_with(
         field: &ArchivedVec<T::Archived>,
@@ -271,12 +297,679 @@ where
     ) -> Result<BTreeSet<T>, D::Error> {
         let mut result = BTreeSet::new();
         for key in field.iter() {
+            deserializer.reserve_allocation(Layout::new::<T>())?;
             result.insert(key.deserialize(deserializer)?);
         }
         Ok(result)
     }
 }
 
+// BranchingFactor
+
+impl<K, V, const B: usize> ArchiveWith<BTreeMap<K, V>> for BranchingFactor<B>
+where
+    K: Archive + Ord,
+    K::Archived: Ord,
+    V: Archive,
+{
+    type Archived = ArchivedBTreeMap<K::Archived, V::Archived>;
+    type Resolver = BTreeMapResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &BTreeMap<K, V>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        field.resolve(pos, resolver, out);
+    }
+}
+
+impl<K, V, S, const B: usize> SerializeWith<BTreeMap<K, V>, S> for BranchingFactor<B>
+where
+    K: Serialize<S> + Ord,
+    K::Archived: Ord,
+    V: Serialize<S>,
+    S: Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(
+        field: &BTreeMap<K, V>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        unsafe {
+            ArchivedBTreeMap::serialize_from_reverse_iter_with_branching_factor(
+                field.iter().rev(),
+                B,
+                serializer,
+            )
+        }
+    }
+}
+
+impl<K, V, D, const B: usize>
+    DeserializeWith<ArchivedBTreeMap<K::Archived, V::Archived>, BTreeMap<K, V>, D>
+    for BranchingFactor<B>
+where
+    K: Archive + Ord,
+    K::Archived: Deserialize<K, D> + Ord,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    D: AllocationBudget + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedBTreeMap<K::Archived, V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<BTreeMap<K, V>, D::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
+// AsSortedVec
+
+impl<K: Archive, V: Archive> ArchiveWith<Vec<(K, V)>> for AsSortedVec {
+    type Archived = ArchivedSortedVec<K::Archived, V::Archived>;
+    type Resolver = SortedVecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<(K, V)>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedSortedVec::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<K, V, S> SerializeWith<Vec<(K, V)>, S> for AsSortedVec
+where
+    K: Serialize<S> + Ord,
+    V: Serialize<S>,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(field: &Vec<(K, V)>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedSortedVec::serialize_from_iter(
+            field.iter().map(|(key, value)| (key, value)),
+            serializer,
+        )
+    }
+}
+
+impl<K, V, D> DeserializeWith<ArchivedSortedVec<K::Archived, V::Archived>, Vec<(K, V)>, D>
+    for AsSortedVec
+where
+    K: Archive,
+    V: Archive,
+    K::Archived: Deserialize<K, D>,
+    V::Archived: Deserialize<V, D>,
+    D: AllocationBudget + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedSortedVec<K::Archived, V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Vec<(K, V)>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<(K, V)>(field.len()).unwrap())?;
+        let mut result = Vec::with_capacity(field.len());
+        for (key, value) in field.iter() {
+            result.push((
+                key.deserialize(deserializer)?,
+                value.deserialize(deserializer)?,
+            ));
+        }
+        Ok(result)
+    }
+}
+
+// AsSortedIndex
+
+impl ArchiveWith<Vec<u32>> for AsSortedIndex {
+    type Archived = ArchivedSortedIndex;
+    type Resolver = SortedIndexResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<u32>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedSortedIndex::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<S: ScratchSpace + Serializer + ?Sized> SerializeWith<Vec<u32>, S> for AsSortedIndex {
+    #[inline]
+    fn serialize_with(field: &Vec<u32>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedSortedIndex::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> DeserializeWith<ArchivedSortedIndex, Vec<u32>, D> for AsSortedIndex {
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedSortedIndex,
+        deserializer: &mut D,
+    ) -> Result<Vec<u32>, D::Error> {
+        field
+            .as_slice()
+            .iter()
+            .map(|index| index.deserialize(deserializer))
+            .collect()
+    }
+}
+
+// AsNullable
+
+impl<T: Archive> ArchiveWith<Vec<Option<T>>> for AsNullable {
+    type Archived = ArchivedNullableVec<T::Archived>;
+    type Resolver = NullableVecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<Option<T>>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedNullableVec::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<Vec<Option<T>>, S> for AsNullable
+where
+    T: Serialize<S>,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(
+        field: &Vec<Option<T>>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedNullableVec::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedNullableVec<T::Archived>, Vec<Option<T>>, D> for AsNullable
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: AllocationBudget + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedNullableVec<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Vec<Option<T>>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<Option<T>>(field.len()).unwrap())?;
+        let mut result = Vec::with_capacity(field.len());
+        for value in field.iter() {
+            result.push(match value {
+                Some(value) => Some(value.deserialize(deserializer)?),
+                None => None,
+            });
+        }
+        Ok(result)
+    }
+}
+
+// AsResultVec
+
+impl<T: Archive, E: Archive> ArchiveWith<Vec<Result<T, E>>> for AsResultVec {
+    type Archived = ArchivedResultVec<T::Archived, E::Archived>;
+    type Resolver = ResultVecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<Result<T, E>>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedResultVec::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<T, E, S> SerializeWith<Vec<Result<T, E>>, S> for AsResultVec
+where
+    T: Serialize<S>,
+    E: Serialize<S>,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(
+        field: &Vec<Result<T, E>>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedResultVec::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<T, E, D> DeserializeWith<ArchivedResultVec<T::Archived, E::Archived>, Vec<Result<T, E>>, D>
+    for AsResultVec
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    E: Archive,
+    E::Archived: Deserialize<E, D>,
+    D: AllocationBudget + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedResultVec<T::Archived, E::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Vec<Result<T, E>>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<Result<T, E>>(field.len()).unwrap())?;
+        let mut result = Vec::with_capacity(field.len());
+        for value in field.iter() {
+            result.push(match value {
+                Ok(value) => Ok(value.deserialize(deserializer)?),
+                Err(value) => Err(value.deserialize(deserializer)?),
+            });
+        }
+        Ok(result)
+    }
+}
+
+// AsDeltaVec
+
+impl<T: DeltaEncode> ArchiveWith<Vec<T>> for AsDeltaVec {
+    type Archived = ArchivedDeltaVec<T>;
+    type Resolver = DeltaVecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedDeltaVec::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<Vec<T>, S> for AsDeltaVec
+where
+    T: DeltaEncode + Serialize<S>,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(field: &Vec<T>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedDeltaVec::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedDeltaVec<T>, Vec<T>, D> for AsDeltaVec
+where
+    T: DeltaEncode,
+    D: AllocationBudget + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedDeltaVec<T>,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<T>(field.len()).unwrap())?;
+        Ok(field.iter().collect())
+    }
+}
+
+// AsRleVec
+
+impl<T: Archive> ArchiveWith<Vec<T>> for AsRleVec {
+    type Archived = ArchivedRleVec<T::Archived>;
+    type Resolver = RleVecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        _: &Vec<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedRleVec::resolve_from_resolver(pos, resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<Vec<T>, S> for AsRleVec
+where
+    T: Serialize<S> + PartialEq,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(field: &Vec<T>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedRleVec::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedRleVec<T::Archived>, Vec<T>, D> for AsRleVec
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: AllocationBudget + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedRleVec<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<T>(field.len()).unwrap())?;
+        let mut result = Vec::with_capacity(field.len());
+        for value in field.iter() {
+            result.push(value.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// PreserveCapacity
+
+impl<T: Archive> ArchiveWith<Vec<T>> for PreserveCapacity {
+    type Archived = ArchivedVecWithCapacity<T::Archived>;
+    type Resolver = VecWithCapacityResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedVecWithCapacity::resolve_from_len(
+            field.len(),
+            field.capacity(),
+            pos,
+            resolver,
+            out,
+        );
+    }
+}
+
+impl<T, S> SerializeWith<Vec<T>, S> for PreserveCapacity
+where
+    T: Serialize<S>,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(field: &Vec<T>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedVecWithCapacity::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedVecWithCapacity<T::Archived>, Vec<T>, D> for PreserveCapacity
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: AllocationBudget + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedVecWithCapacity<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        // The capacity is only a hint, so clamp the spare capacity and fall back to the length if
+        // it's still too large to allocate
+        let len = field.len();
+        let max_spare = len.max(PreserveCapacity::MAX_SPARE_CAPACITY);
+        let capacity = field.capacity().clamp(len, len.saturating_add(max_spare));
+        let capacity = match Layout::array::<T>(capacity) {
+            Ok(layout) => {
+                deserializer.reserve_allocation(layout)?;
+                capacity
+            }
+            Err(_) => {
+                deserializer.reserve_allocation(Layout::array::<T>(len).unwrap())?;
+                len
+            }
+        };
+        let mut result = Vec::with_capacity(capacity);
+        for value in field.iter() {
+            result.push(value.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// AsInlineVec
+
+impl<T: Archive, const N: usize> ArchiveWith<Vec<T>> for AsInlineVec<N> {
+    type Archived = ArchivedInlineVec<T::Archived, N>;
+    type Resolver = InlineVecResolver<T::Resolver>;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedInlineVec::resolve_from_slice(field.as_slice(), pos, resolver, out);
+    }
+}
+
+impl<T, S, const N: usize> SerializeWith<Vec<T>, S> for AsInlineVec<N>
+where
+    T: Serialize<S>,
+    S: Fallible + ?Sized,
+    S::Error: From<AsInlineVecError>,
+{
+    #[inline]
+    fn serialize_with(field: &Vec<T>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        if field.len() > N {
+            return Err(AsInlineVecError::CapacityExceeded {
+                len: field.len(),
+                capacity: N,
+            }
+            .into());
+        }
+        ArchivedInlineVec::<T::Archived, N>::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<T, D, const N: usize> DeserializeWith<ArchivedInlineVec<T::Archived, N>, Vec<T>, D>
+    for AsInlineVec<N>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: AllocationBudget + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedInlineVec<T::Archived, N>,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<T>(field.len()).unwrap())?;
+        let mut result = Vec::with_capacity(field.len());
+        for value in field.iter() {
+            result.push(value.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// AsBytes
+
+impl ArchiveWith<Vec<u8>> for AsBytes {
+    type Archived = ArchivedBytes;
+    type Resolver = BytesResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<u8>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedBytes::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<S: Serializer + ?Sized> SerializeWith<Vec<u8>, S> for AsBytes {
+    #[inline]
+    fn serialize_with(field: &Vec<u8>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedBytes::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<D: AllocationBudget + ?Sized> DeserializeWith<ArchivedBytes, Vec<u8>, D> for AsBytes {
+    #[inline]
+    fn deserialize_with(field: &ArchivedBytes, deserializer: &mut D) -> Result<Vec<u8>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<u8>(field.len()).unwrap())?;
+        Ok(field.as_slice().to_vec())
+    }
+}
+
+// Compress
+
+impl<C, const THRESHOLD: usize> ArchiveWith<Vec<u8>> for Compress<C, THRESHOLD> {
+    type Archived = ArchivedCompressed<C>;
+    type Resolver = CompressedResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        _: &Vec<u8>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedCompressed::resolve_from_slice(pos, resolver, out);
+    }
+}
+
+impl<C, S, const THRESHOLD: usize> SerializeWith<Vec<u8>, S> for Compress<C, THRESHOLD>
+where
+    C: Compression,
+    S: Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(field: &Vec<u8>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedCompressed::<C>::serialize_from_slice(field.as_slice(), THRESHOLD, serializer)
+    }
+}
+
+impl<C, D, const THRESHOLD: usize> DeserializeWith<ArchivedCompressed<C>, Vec<u8>, D>
+    for Compress<C, THRESHOLD>
+where
+    C: Compression,
+    D: Fallible + ?Sized,
+    D::Error: From<C::Error>,
+{
+    #[inline]
+    fn deserialize_with(field: &ArchivedCompressed<C>, _: &mut D) -> Result<Vec<u8>, D::Error> {
+        Ok(field.bytes()?.into_owned())
+    }
+}
+
+// AsStringTable
+
+impl<T: AsRef<str>> ArchiveWith<Vec<T>> for AsStringTable {
+    type Archived = ArchivedStringTable;
+    type Resolver = StringTableResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedStringTable::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<T: AsRef<str>, S: Serializer + ?Sized> SerializeWith<Vec<T>, S> for AsStringTable {
+    #[inline]
+    fn serialize_with(field: &Vec<T>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedStringTable::serialize_from_iter(field.iter().map(AsRef::as_ref), serializer)
+    }
+}
+
+impl<'a, T: AsRef<str>> ArchiveWith<&'a [T]> for AsStringTable {
+    type Archived = ArchivedStringTable;
+    type Resolver = StringTableResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &&'a [T],
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedStringTable::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<'a, T: AsRef<str>, S: Serializer + ?Sized> SerializeWith<&'a [T], S> for AsStringTable {
+    #[inline]
+    fn serialize_with(field: &&'a [T], serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedStringTable::serialize_from_iter(field.iter().map(AsRef::as_ref), serializer)
+    }
+}
+
+impl<D: AllocationBudget + ?Sized> DeserializeWith<ArchivedStringTable, Vec<String>, D>
+    for AsStringTable
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedStringTable,
+        deserializer: &mut D,
+    ) -> Result<Vec<String>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<String>(field.len()).unwrap())?;
+        Ok(field.iter().map(String::from).collect())
+    }
+}
+
+// AsFrontCodedVec
+
+impl<T: AsRef<str>, const RESTART: usize> ArchiveWith<Vec<T>> for AsFrontCodedVec<RESTART> {
+    type Archived = ArchivedFrontCodedVec;
+    type Resolver = FrontCodedVecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedFrontCodedVec::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<T, S, const RESTART: usize> SerializeWith<Vec<T>, S> for AsFrontCodedVec<RESTART>
+where
+    T: AsRef<str>,
+    S: Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(field: &Vec<T>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedFrontCodedVec::serialize_from_iter(
+            field.iter().map(AsRef::as_ref),
+            RESTART,
+            serializer,
+        )
+    }
+}
+
+impl<D, const RESTART: usize> DeserializeWith<ArchivedFrontCodedVec, Vec<String>, D>
+    for AsFrontCodedVec<RESTART>
+where
+    D: AllocationBudget + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedFrontCodedVec,
+        deserializer: &mut D,
+    ) -> Result<Vec<String>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<String>(field.len()).unwrap())?;
+        Ok(field.iter().collect())
+    }
+}
+
 // Niche
 
 impl<T: ArchiveUnsized + ?Sized> ArchiveWith<Option<Box<T>>> for Niche
@@ -363,14 +1056,18 @@ impl<T, D> DeserializeWith<ArchivedVec<T::Archived>, Vec<T>, D> for CopyOptimize
 where
     T: Archive,
     T::Archived: Deserialize<T, D>,
-    D: Fallible + ?Sized,
+    D: AllocationBudget + ?Sized,
 {
-    fn deserialize_with(field: &ArchivedVec<T::Archived>, _: &mut D) -> Result<Vec<T>, D::Error> {
+    fn deserialize_with(
+        field: &ArchivedVec<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
         use ::core::{mem::size_of, ptr::copy_nonoverlapping};
 
         // Basic debug assert that T and T::Archived are at least the same size
         debug_assert_eq!(size_of::<T>(), size_of::<T::Archived>());
 
+        deserializer.reserve_allocation(Layout::array::<T>(field.len()).unwrap())?;
         let mut result = Vec::with_capacity(field.len());
         unsafe {
             copy_nonoverlapping(field.as_ptr().cast(), result.as_mut_ptr(), field.len());
@@ -414,17 +1111,18 @@ impl<T, D> DeserializeWith<ArchivedBox<[T::Archived]>, Box<[T]>, D> for CopyOpti
 where
     T: Archive,
     T::Archived: Deserialize<T, D>,
-    D: Fallible + ?Sized,
+    D: AllocationBudget + ?Sized,
 {
     fn deserialize_with(
         field: &ArchivedBox<[T::Archived]>,
-        _: &mut D,
+        deserializer: &mut D,
     ) -> Result<Box<[T]>, D::Error> {
         use ::core::{mem::size_of, ptr::copy_nonoverlapping};
 
         // Basic debug assert that T and T::Archived are at least the same size
         debug_assert_eq!(size_of::<T>(), size_of::<T::Archived>());
 
+        deserializer.reserve_allocation(Layout::array::<T>(field.len()).unwrap())?;
         let mut result = Vec::with_capacity(field.len());
         unsafe {
             copy_nonoverlapping(field.as_ptr().cast(), result.as_mut_ptr(), field.len());
@@ -468,3 +1166,162 @@ where
         }
     }
 }
+
+// SwapOptimize
+
+impl<T: ArchivePrimitive> ArchiveWith<Vec<T>> for SwapOptimize {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Vec<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedVec::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<Vec<T>, S> for SwapOptimize
+where
+    T: ArchivePrimitive,
+    S: Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(field: &Vec<T>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedVec::serialize_from_primitive_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedVec<T::Archived>, Vec<T>, D> for SwapOptimize
+where
+    T: ArchivePrimitive,
+    T::Archived: Deserialize<T, D>,
+    D: AllocationBudget + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedVec<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<T>(field.len()).unwrap())?;
+        field
+            .iter()
+            .map(|value| value.deserialize(deserializer))
+            .collect()
+    }
+}
+
+// AsFst
+
+#[cfg(feature = "fst")]
+const _: () = {
+    use crate::{
+        collections::fst::{ArchivedFstMap, ArchivedFstSet, FstMapResolver, FstSetResolver},
+        de::ScratchPool,
+        with::AsFst,
+    };
+
+    impl<V: Archive> ArchiveWith<BTreeMap<String, V>> for AsFst {
+        type Archived = ArchivedFstMap<V::Archived>;
+        type Resolver = FstMapResolver;
+
+        #[inline]
+        unsafe fn resolve_with(
+            field: &BTreeMap<String, V>,
+            pos: usize,
+            resolver: Self::Resolver,
+            out: *mut Self::Archived,
+        ) {
+            ArchivedFstMap::resolve_from_len(field.len(), pos, resolver, out);
+        }
+    }
+
+    impl<V, S> SerializeWith<BTreeMap<String, V>, S> for AsFst
+    where
+        V: Serialize<S>,
+        S: ScratchSpace + Serializer + ?Sized,
+    {
+        #[inline]
+        fn serialize_with(
+            field: &BTreeMap<String, V>,
+            serializer: &mut S,
+        ) -> Result<Self::Resolver, S::Error> {
+            ArchivedFstMap::serialize_from_iter(
+                field.iter().map(|(key, value)| (key.as_str(), value)),
+                serializer,
+            )
+        }
+    }
+
+    impl<V, D> DeserializeWith<ArchivedFstMap<V::Archived>, BTreeMap<String, V>, D> for AsFst
+    where
+        V: Archive,
+        V::Archived: Deserialize<V, D>,
+        D: AllocationBudget + ScratchPool + ?Sized,
+    {
+        fn deserialize_with(
+            field: &ArchivedFstMap<V::Archived>,
+            deserializer: &mut D,
+        ) -> Result<BTreeMap<String, V>, D::Error> {
+            let mut result = BTreeMap::new();
+            let mut iter = field.iter_with_scratch(deserializer);
+            for (key, value) in iter.by_ref() {
+                deserializer.reserve_allocation(Layout::new::<(String, V)>())?;
+                deserializer.reserve_allocation(Layout::array::<u8>(key.len()).unwrap())?;
+                result.insert(key, value.deserialize(deserializer)?);
+            }
+            iter.return_scratch(deserializer);
+            Ok(result)
+        }
+    }
+
+    impl ArchiveWith<BTreeSet<String>> for AsFst {
+        type Archived = ArchivedFstSet;
+        type Resolver = FstSetResolver;
+
+        #[inline]
+        unsafe fn resolve_with(
+            field: &BTreeSet<String>,
+            pos: usize,
+            resolver: Self::Resolver,
+            out: *mut Self::Archived,
+        ) {
+            ArchivedFstSet::resolve_from_len(field.len(), pos, resolver, out);
+        }
+    }
+
+    impl<S> SerializeWith<BTreeSet<String>, S> for AsFst
+    where
+        S: ScratchSpace + Serializer + ?Sized,
+    {
+        #[inline]
+        fn serialize_with(
+            field: &BTreeSet<String>,
+            serializer: &mut S,
+        ) -> Result<Self::Resolver, S::Error> {
+            ArchivedFstSet::serialize_from_iter(field.iter().map(String::as_str), serializer)
+        }
+    }
+
+    impl<D: AllocationBudget + ScratchPool + ?Sized>
+        DeserializeWith<ArchivedFstSet, BTreeSet<String>, D> for AsFst
+    {
+        fn deserialize_with(
+            field: &ArchivedFstSet,
+            deserializer: &mut D,
+        ) -> Result<BTreeSet<String>, D::Error> {
+            let mut result = BTreeSet::new();
+            let mut iter = field.iter_with_scratch(deserializer);
+            for key in iter.by_ref() {
+                deserializer.reserve_allocation(Layout::new::<String>())?;
+                deserializer.reserve_allocation(Layout::array::<u8>(key.len()).unwrap())?;
+                result.insert(key);
+            }
+            iter.return_scratch(deserializer);
+            Ok(result)
+        }
+    }
+};
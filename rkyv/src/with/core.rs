@@ -1,8 +1,17 @@
 use crate::{
     boxed::{ArchivedBox, BoxResolver},
-    with::{ArchiveWith, AsBox, DeserializeWith, Inline, RefAsBox, SerializeWith},
+    bytes::ArchivedByteArray,
+    niche::sentinel::{ArchiveNiche, ArchivedOptionNiche},
+    ops::{ArchivedBound, ArchivedBounds},
+    ser::StringInterner,
+    string::intern::ArchivedInterned,
+    with::{
+        ArchiveWith, AsBounds, AsBox, AsBytes, DeserializeWith, Inline, Intern, RefAsBox, Sentinel,
+        SentinelError, SerializeWith,
+    },
     Archive, ArchiveUnsized, Deserialize, Fallible, Serialize, SerializeUnsized,
 };
+use core::ops::{Bound, RangeBounds};
 
 // Inline
 
@@ -81,3 +90,151 @@ where
         field.deserialize(deserializer)
     }
 }
+
+// AsBytes
+
+impl<const N: usize> ArchiveWith<[u8; N]> for AsBytes {
+    type Archived = ArchivedByteArray<N>;
+    type Resolver = ();
+
+    #[inline]
+    unsafe fn resolve_with(field: &[u8; N], _: usize, _: Self::Resolver, out: *mut Self::Archived) {
+        out.write(ArchivedByteArray::new(*field));
+    }
+}
+
+impl<S: Fallible + ?Sized, const N: usize> SerializeWith<[u8; N], S> for AsBytes {
+    #[inline]
+    fn serialize_with(_: &[u8; N], _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized, const N: usize> DeserializeWith<ArchivedByteArray<N>, [u8; N], D>
+    for AsBytes
+{
+    #[inline]
+    fn deserialize_with(field: &ArchivedByteArray<N>, _: &mut D) -> Result<[u8; N], D::Error> {
+        Ok(*field.as_array())
+    }
+}
+
+// AsBounds
+
+impl<F: RangeBounds<T>, T: Archive> ArchiveWith<F> for AsBounds<T> {
+    type Archived = ArchivedBounds<T::Archived>;
+    type Resolver = (Bound<T::Resolver>, Bound<T::Resolver>);
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &F,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        let (fp, fo) = out_field!(out.start);
+        ArchivedBound::resolve_from_bound(field.start_bound(), pos + fp, resolver.0, fo);
+        let (fp, fo) = out_field!(out.end);
+        ArchivedBound::resolve_from_bound(field.end_bound(), pos + fp, resolver.1, fo);
+    }
+}
+
+impl<F, T, S> SerializeWith<F, S> for AsBounds<T>
+where
+    F: RangeBounds<T>,
+    T: Serialize<S>,
+    S: Fallible + ?Sized,
+{
+    #[inline]
+    fn serialize_with(field: &F, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok((
+            ArchivedBound::serialize_from_bound(field.start_bound(), serializer)?,
+            ArchivedBound::serialize_from_bound(field.end_bound(), serializer)?,
+        ))
+    }
+}
+
+impl<F, T, D> DeserializeWith<ArchivedBounds<T::Archived>, F, D> for AsBounds<T>
+where
+    F: From<(Bound<T>, Bound<T>)>,
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedBounds<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<F, D::Error> {
+        Ok(F::from((
+            field.start.deserialize(deserializer)?,
+            field.end.deserialize(deserializer)?,
+        )))
+    }
+}
+
+// Intern
+
+impl<F: AsRef<str>> ArchiveWith<F> for Intern {
+    type Archived = ArchivedInterned;
+    type Resolver = usize;
+
+    #[inline]
+    unsafe fn resolve_with(_: &F, _: usize, index: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedInterned::resolve_from_index(index, out);
+    }
+}
+
+impl<F: AsRef<str>, S: StringInterner + ?Sized> SerializeWith<F, S> for Intern {
+    #[inline]
+    fn serialize_with(field: &F, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        serializer.intern(field.as_ref())
+    }
+}
+
+// Sentinel
+
+impl<T: ArchiveNiche> ArchiveWith<Option<T>> for Sentinel {
+    type Archived = ArchivedOptionNiche<T>;
+    type Resolver = Option<T::Resolver>;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &Option<T>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedOptionNiche::resolve_from_option(field.as_ref(), pos, resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<Option<T>, S> for Sentinel
+where
+    T: ArchiveNiche + Serialize<S>,
+    S: Fallible + ?Sized,
+    S::Error: From<SentinelError>,
+{
+    #[inline]
+    fn serialize_with(field: &Option<T>, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedOptionNiche::serialize_from_option(field.as_ref(), serializer)
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedOptionNiche<T>, Option<T>, D> for Sentinel
+where
+    T: ArchiveNiche,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedOptionNiche<T>,
+        deserializer: &mut D,
+    ) -> Result<Option<T>, D::Error> {
+        field
+            .as_ref()
+            .map(|value| value.deserialize(deserializer))
+            .transpose()
+    }
+}
@@ -500,6 +500,567 @@ pub struct AsOwned;
 #[derive(Debug)]
 pub struct AsVec;
 
+/// A wrapper that serializes hash maps with all of their keys stored in one contiguous pool.
+///
+/// Normally, the out-of-line data for each key is written right next to the data for its value.
+/// For maps with string keys, this scatters the keys throughout the buffer. This wrapper writes the
+/// data for all of the keys first so that the bytes compared while probing are close together. The
+/// archived map is a regular `ArchivedHashMap` and can be used like any other.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use rkyv::{Archive, with::PooledKeys};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(PooledKeys)]
+///     values: HashMap<String, u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PooledKeys;
+
+/// A wrapper that serializes hash maps with each of their values stored behind a relative pointer.
+///
+/// Normally, the entries of an archived hash map store their keys and values inline next to each
+/// other. This is the best choice when values are small or are usually read along with their keys,
+/// since finding a key also brings its value into cache. When values are large and rarely accessed,
+/// inline values spread the keys out and scanning the keys (for example with
+/// [`keys`](crate::collections::hash_map::ArchivedHashMap::keys)) pulls all of the value bytes
+/// through the cache as well.
+///
+/// With this wrapper, each entry stores its key next to an
+/// [`ArchivedBox`](crate::boxed::ArchivedBox) pointing to its value, so the entries stay small and
+/// reading a key touches no value bytes. Looking up a key returns the box, and its value is only
+/// read when the box is dereferenced. The tradeoff is an extra relative pointer per entry and an
+/// extra indirection to reach each value, which usually lands on a different cache line than the
+/// entry.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use rkyv::{archived_root, ser::{serializers::AllocSerializer, Serializer}, with::BoxedValues};
+/// use rkyv::{Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     #[with(BoxedValues)]
+///     blobs: HashMap<String, [u8; 4096]>,
+/// }
+///
+/// let mut blobs = HashMap::new();
+/// blobs.insert("first".to_string(), [1; 4096]);
+/// blobs.insert("second".to_string(), [2; 4096]);
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&Example { blobs }).unwrap();
+/// let bytes = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<Example>(&bytes) };
+///
+/// // Each entry only holds a key and a pointer to its value
+/// let blob = archived.blobs.get("second").unwrap();
+/// assert_eq!(blob[0], 2);
+/// ```
+#[derive(Debug)]
+pub struct BoxedValues;
+
+/// A wrapper that archives a `HashMap` as an open-addressed hash table with a tunable probe sequence
+/// and load factor.
+///
+/// By default, hash maps are archived with a perfect hash that never needs to probe. With this
+/// wrapper, they are archived as an
+/// [`ArchivedOpenHashMap`](crate::collections::ArchivedOpenHashMap) instead, which probes buckets
+/// with the sequence `P` and keeps its load factor at or below `LOAD` percent. `P` can be
+/// [`Linear`](crate::collections::open_hash_map::Linear),
+/// [`Quadratic`](crate::collections::open_hash_map::Quadratic), or
+/// [`RobinHood`](crate::collections::open_hash_map::RobinHood) (the default). See the
+/// [`open_hash_map`](crate::collections::open_hash_map) module for when each of them wins.
+///
+/// Serializing panics if `LOAD` is not between 1 and 100.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{collections::open_hash_map::Linear, with::OpenAddressed, Archive};
+/// use std::collections::HashMap;
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     // Robin Hood hashing with a load factor of at most 80%
+///     #[with(OpenAddressed)]
+///     by_name: HashMap<String, u32>,
+///     // Linear probing with a load factor of at most 50%
+///     #[with(OpenAddressed<Linear, 50>)]
+///     by_id: HashMap<u64, u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct OpenAddressed<P = crate::collections::open_hash_map::RobinHood, const LOAD: usize = 80>(
+    PhantomData<P>,
+);
+
+/// A wrapper that archives a `Vec` of key-value pairs as a lookup table sorted by key.
+///
+/// The pairs are sorted while serializing, and the archived
+/// [`ArchivedSortedVec`](crate::collections::ArchivedSortedVec) supports `O(log n)` lookups by
+/// key. For small tables, this avoids the overhead of building a full hash map.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsSortedVec};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsSortedVec)]
+///     values: Vec<(String, u32)>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsSortedVec;
+
+/// A wrapper that archives a `BTreeMap` as a B-tree with a fixed branching factor.
+///
+/// By default, B-tree maps are archived with nodes that span about 4KiB each. With this wrapper,
+/// each node instead holds up to `B` entries (or points to up to `B` nodes in the next layer), so
+/// the shape of the tree can be tuned to the size of its entries and the cache of the target.
+/// The archived map is an ordinary [`ArchivedBTreeMap`](crate::collections::ArchivedBTreeMap).
+/// See
+/// [`serialize_from_reverse_iter_with_branching_factor`](crate::collections::ArchivedBTreeMap::serialize_from_reverse_iter_with_branching_factor)
+/// for more details.
+///
+/// Serializing panics if `B` is less than
+/// [`MIN_BRANCHING_FACTOR`](crate::collections::btree_map::MIN_BRANCHING_FACTOR) or greater than
+/// [`MAX_BRANCHING_FACTOR`](crate::collections::btree_map::MAX_BRANCHING_FACTOR).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::BranchingFactor};
+/// use std::collections::BTreeMap;
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(BranchingFactor<16>)]
+///     index: BTreeMap<u64, u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BranchingFactor<const B: usize>;
+
+/// A wrapper that archives a string-keyed set or map as a finite state transducer.
+///
+/// This can be used with `HashSet<String>` and `BTreeSet<String>`, which are archived as an
+/// [`ArchivedFstSet`](crate::collections::ArchivedFstSet), and with `HashMap<String, V>` and
+/// `BTreeMap<String, V>`, which are archived as an
+/// [`ArchivedFstMap`](crate::collections::ArchivedFstMap). For very large dictionaries of keys,
+/// these are much smaller than archived hash maps and support prefix queries, at the cost of a more
+/// expensive build and slower lookups.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsFst};
+/// use std::collections::{BTreeSet, HashMap};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsFst)]
+///     words: BTreeSet<String>,
+///     #[with(AsFst)]
+///     frequencies: HashMap<String, u32>,
+/// }
+/// ```
+#[cfg(feature = "fst")]
+#[derive(Debug)]
+pub struct AsFst;
+
+/// A wrapper that archives a `Vec<u32>` of indices into another slice as a sorted index.
+///
+/// The indices must already be sorted by the ordering of the elements they refer to. The archived
+/// [`ArchivedSortedIndex`](crate::collections::ArchivedSortedIndex) can be paired with the archived
+/// data to perform `O(log n)` lookups using a key extracted from each element.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsSortedIndex};
+///
+/// #[derive(Archive)]
+/// struct Entry {
+///     name: String,
+///     value: u32,
+/// }
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     entries: Vec<Entry>,
+///     #[with(AsSortedIndex)]
+///     by_value: Vec<u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsSortedIndex;
+
+/// A wrapper that archives a `Vec<Option<T>>` as a dense array of the present values and a
+/// validity bitmap.
+///
+/// The archived [`ArchivedNullableVec`](crate::collections::ArchivedNullableVec) stores one bit per
+/// element instead of a tag and a full value slot, which is much more compact for large columns of
+/// data with missing values.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsNullable};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsNullable)]
+///     values: Vec<Option<f64>>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsNullable;
+
+/// A wrapper that archives a `Vec<Result<T, E>>` as dense arrays of the `Ok` and `Err` values and
+/// a bitmap of which elements are `Ok`.
+///
+/// The archived [`ArchivedResultVec`](crate::collections::ArchivedResultVec) stores one bit per
+/// element instead of a tag and a slot big enough for either value. This is much more compact for
+/// logs of operations that mostly succeed, especially when `T` is `()`.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsResultVec};
+///
+/// #[derive(Archive)]
+/// struct AuditLog {
+///     #[with(AsResultVec)]
+///     entries: Vec<Result<(), u32>>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsResultVec;
+
+/// A wrapper that archives a `Vec<T>` of integers as the differences between consecutive values.
+///
+/// The archived [`ArchivedDeltaVec`](crate::collections::ArchivedDeltaVec) stores each difference
+/// as a varint, which is much smaller than the values for sorted or slowly-changing sequences like
+/// timestamps and increasing IDs. Values are decoded on access, which is slower than reading a
+/// slice.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsDeltaVec};
+///
+/// #[derive(Archive)]
+/// struct Samples {
+///     #[with(AsDeltaVec)]
+///     timestamps: Vec<u64>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsDeltaVec;
+
+/// A wrapper that archives a `Vec<T>` as runs of equal values.
+///
+/// The archived [`ArchivedRleVec`](crate::collections::ArchivedRleVec) stores the value of each
+/// run once along with where the run ends, so repetitive sequences take space proportional to the
+/// number of runs instead of the number of elements.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsRleVec};
+///
+/// #[derive(Archive)]
+/// struct Readings {
+///     #[with(AsRleVec)]
+///     states: Vec<u8>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsRleVec;
+
+/// A wrapper that archives a `Vec<T>` along with its capacity, so that it's deserialized with the
+/// same capacity it had when it was serialized.
+///
+/// Deserialized vecs normally have exactly enough capacity for their elements. When a vec is going
+/// to grow right after it's deserialized (like a buffer that's reloaded and then appended to),
+/// preserving its capacity avoids reallocating it on the first push. The archived
+/// [`ArchivedVecWithCapacity`](crate::vec::ArchivedVecWithCapacity) stores the capacity as one
+/// extra `Archived<usize>` word after the length, and otherwise works like an archived vec. Only the
+/// elements are written to the archive: the unused capacity takes no space.
+///
+/// The archived capacity is only a hint. Since it isn't backed by any data in the archive, it's
+/// clamped when deserializing so that the spare capacity is at most the larger of the length and
+/// [`PreserveCapacity::MAX_SPARE_CAPACITY`] elements. This keeps a corrupted or malicious archive
+/// from making deserialization allocate an arbitrary amount of memory.
+///
+/// The derive macros also accept `#[archive(preserve_capacity)]` on a field as a shorthand for this
+/// wrapper.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     archived_root,
+///     ser::{serializers::AllocSerializer, Serializer},
+///     Archive, Deserialize, Infallible, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize, Deserialize)]
+/// struct Log {
+///     #[archive(preserve_capacity)]
+///     entries: Vec<u32>,
+/// }
+///
+/// let mut entries = Vec::with_capacity(64);
+/// entries.extend_from_slice(&[1, 2, 3]);
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer.serialize_value(&Log { entries }).unwrap();
+/// let buf = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<Log>(&buf) };
+/// assert_eq!(archived.entries.len(), 3);
+/// assert_eq!(archived.entries.capacity(), 64);
+///
+/// let deserialized: Log = archived.deserialize(&mut Infallible).unwrap();
+/// assert_eq!(deserialized.entries, [1, 2, 3]);
+/// assert!(deserialized.entries.capacity() >= 64);
+/// ```
+#[derive(Debug)]
+pub struct PreserveCapacity;
+
+impl PreserveCapacity {
+    /// The spare capacity that can always be restored, even for vecs with fewer elements.
+    pub const MAX_SPARE_CAPACITY: usize = 1024;
+}
+
+/// A wrapper that archives a `Vec<T>` with at most `N` elements inline, as an array of `N` archived
+/// elements and a length.
+///
+/// The archived [`ArchivedInlineVec`](crate::collections::ArchivedInlineVec) stores its elements
+/// directly in the containing value instead of behind a relative pointer. For small vectors with a
+/// known bound on their length, this improves locality and removes a pointer chase at the cost of
+/// always reserving space for `N` elements.
+///
+/// The derive macros also accept `#[archive(inline(capacity = N))]` on a field as a shorthand for
+/// this wrapper.
+///
+/// # Errors
+///
+/// Serializing a vec with more than `N` elements fails with an [`AsInlineVecError`] before anything
+/// is written, so the serializer's error type must implement `From<AsInlineVecError>`. Elements are
+/// never silently dropped. The error of
+/// [`CompositeSerializer`](crate::ser::serializers::CompositeSerializer), and so of the default
+/// serializers, implements this conversion.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsInlineVec};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsInlineVec<8>)]
+///     neighbors: Vec<u32>,
+///     #[archive(inline(capacity = 4))]
+///     tags: Vec<u8>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsInlineVec<const N: usize>;
+
+/// Errors that can occur when serializing an [`AsInlineVec`] wrapper.
+#[derive(Debug)]
+pub enum AsInlineVecError {
+    /// The vec had more elements than the inline capacity
+    CapacityExceeded {
+        /// The number of elements in the vec
+        len: usize,
+        /// The inline capacity
+        capacity: usize,
+    },
+}
+
+impl fmt::Display for AsInlineVecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsInlineVecError::CapacityExceeded { len, capacity } => write!(
+                f,
+                "inline capacity exceeded: {} elements can't fit in a capacity of {}",
+                len, capacity
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for AsInlineVecError {}
+
+/// A wrapper that archives a range-like type as a pair of start and end bounds of `T`.
+///
+/// Any type that implements [`RangeBounds<T>`](::core::ops::RangeBounds) can be archived with this
+/// wrapper, and is archived as an [`ArchivedBounds`](crate::ops::ArchivedBounds). To deserialize
+/// it, the type must also implement `From<(Bound<T>, Bound<T>)>`.
+///
+/// # Example
+///
+/// ```
+/// use core::ops::{Bound, RangeBounds};
+/// use rkyv::{with::AsBounds, Archive};
+///
+/// struct Span {
+///     start: u32,
+///     len: u32,
+/// }
+///
+/// impl RangeBounds<u32> for Span {
+///     // ...
+/// #   fn start_bound(&self) -> Bound<&u32> {
+/// #       Bound::Included(&self.start)
+/// #   }
+/// #
+/// #   fn end_bound(&self) -> Bound<&u32> {
+/// #       Bound::Unbounded
+/// #   }
+/// }
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(AsBounds<u32>)]
+///     span: Span,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsBounds<T>(PhantomData<T>);
+
+/// A wrapper that archives a string as an index into an intern table.
+///
+/// Each distinct string is stored once in the intern table of the serializer, so repeated strings
+/// only cost one index each. Any type that implements `AsRef<str>` can be archived with this
+/// wrapper, and is archived as an [`ArchivedInterned`](crate::string::intern::ArchivedInterned).
+/// Serializing requires a [`StringInterner`](crate::ser::StringInterner) like
+/// [`InternSerializer`](crate::ser::serializers::InternSerializer).
+///
+/// Interned strings can't be deserialized on their own because the archived value is only an
+/// index. Instead, resolve them against the archived intern table with
+/// [`ArchivedInterned::resolve`](crate::string::intern::ArchivedInterned::resolve). See the
+/// [`intern`](crate::string::intern) module for where the table is stored.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::Intern, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(Intern)]
+///     category: String,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Intern;
+
+/// A wrapper that archives a byte buffer compressed with `C` if it is longer than `THRESHOLD`
+/// bytes.
+///
+/// Small buffers are stored uncompressed to avoid the overhead of compressing tiny payloads, and
+/// buffers that don't get smaller when compressed are stored uncompressed as well. The archived
+/// [`ArchivedCompressed`](crate::compression::ArchivedCompressed) records which representation was
+/// chosen in a one-byte flag, and only decompresses the stored bytes on access when the flag is
+/// set. See its documentation for the exact layout. `Vec<u8>` can be archived with this wrapper.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{compression::Compression, with::Compress, Archive};
+///
+/// // A compression algorithm backed by the compression library of your choice
+/// struct Lz;
+///
+/// impl Compression for Lz {
+///     type Error = ();
+///
+///     fn compress(bytes: &[u8]) -> Vec<u8> {
+///         // ...
+/// #       bytes.to_vec()
+///     }
+///
+///     fn decompress(bytes: &[u8]) -> Result<Vec<u8>, ()> {
+///         // ...
+/// #       Ok(bytes.to_vec())
+///     }
+/// }
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(Compress<Lz, 64>)]
+///     payload: Vec<u8>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Compress<C, const THRESHOLD: usize>(PhantomData<C>);
+
+/// A wrapper that archives a `Vec` or slice of strings as a table with a shared character pool.
+///
+/// The bytes of all of the strings are stored back-to-back in one pool, and the archived
+/// [`ArchivedStringTable`](crate::string::table::ArchivedStringTable) holds the span of each string
+/// within it. This improves locality when iterating over all of the strings and lets validation
+/// check the whole pool at once. Both `Vec<T>` and `&[T]` can be archived for any `T: AsRef<str>`,
+/// and tables can be deserialized into a `Vec<String>`.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsStringTable};
+///
+/// #[derive(Archive)]
+/// struct Example<'a> {
+///     #[with(AsStringTable)]
+///     names: Vec<String>,
+///     #[with(AsStringTable)]
+///     labels: &'a [&'a str],
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsStringTable;
+
+/// A wrapper that archives a `Vec` of sorted strings with front coding.
+///
+/// The archived [`ArchivedFrontCodedVec`](crate::string::front_coded::ArchivedFrontCodedVec)
+/// stores each string as the length of the prefix it shares with the string before it and the
+/// rest of the string. Every `RESTART` strings, a string is stored whole so that strings can be
+/// decoded without reading the whole vector. Larger restart intervals take less space and smaller
+/// ones make random access faster. The default restart interval is 16.
+///
+/// This is best suited to read-only sets of sorted strings with long common prefixes, like paths
+/// or URLs. `Vec<T>` can be archived for any `T: AsRef<str>`, and front-coded vectors can be
+/// deserialized into a `Vec<String>`.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::AsFrontCodedVec};
+///
+/// #[derive(Archive)]
+/// struct Dictionary {
+///     #[with(AsFrontCodedVec)]
+///     words: Vec<String>,
+///     #[with(AsFrontCodedVec<4>)]
+///     urls: Vec<String>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsFrontCodedVec<const RESTART: usize = 16>;
+
 /// A wrapper that niches some type combinations.
 ///
 /// A common type combination is `Option<Box<T>>`. By using a null pointer, the archived version can
@@ -527,6 +1088,65 @@ pub struct AsVec;
 #[derive(Debug)]
 pub struct Niche;
 
+/// A wrapper that niches an `Option<T>` by storing `None` as a sentinel value of `T`.
+///
+/// The archived option takes exactly as much space as `T::Archived` instead of adding a tag. `T`
+/// must implement [`ArchiveNiche`](crate::niche::sentinel::ArchiveNiche), which can be derived
+/// for single-field structs with `#[archive(niche = "...")]`.
+///
+/// # Errors
+///
+/// Serializing a `Some` containing the sentinel value fails with [`SentinelError::Collision`]
+/// before the value is serialized, since it would be read back as `None`. The serializer's error
+/// type must implement `From<SentinelError>`. The error of
+/// [`CompositeSerializer`](crate::ser::serializers::CompositeSerializer), and so of the default
+/// serializers, implements this conversion.
+///
+/// # Example
+///
+/// ```
+/// use core::mem::size_of;
+/// use rkyv::{Archive, Archived, with::Sentinel};
+///
+/// #[derive(Archive)]
+/// #[archive(niche = "u32::MAX")]
+/// struct Index(u32);
+///
+/// #[derive(Archive)]
+/// struct BasicExample {
+///     value: Option<Index>,
+/// }
+///
+/// #[derive(Archive)]
+/// struct NichedExample {
+///     #[with(Sentinel)]
+///     value: Option<Index>,
+/// }
+///
+/// assert!(size_of::<Archived<BasicExample>>() > size_of::<Archived<NichedExample>>());
+/// ```
+#[derive(Debug)]
+pub struct Sentinel;
+
+/// Errors that can occur when serializing a [`Sentinel`] wrapper.
+#[derive(Debug)]
+pub enum SentinelError {
+    /// A `Some` contained the sentinel value, so it couldn't be told apart from `None`
+    Collision,
+}
+
+impl fmt::Display for SentinelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sentinel collision: a `Some` contained the sentinel value"
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for SentinelError {}
+
 /// A wrapper that provides specialized, performant implementations of serialization and
 /// deserialization.
 ///
@@ -554,3 +1174,74 @@ pub struct Niche;
 /// ```
 #[derive(Debug)]
 pub struct CopyOptimize;
+
+/// A wrapper that archives a `Vec` of primitives in batches.
+///
+/// Unlike [`CopyOptimize`], this wrapper is safe to use and produces the same archive as the
+/// default implementation for every choice of endianness. The elements are converted to their
+/// archived form a batch at a time, which turns into a copy when the archive has the target's
+/// endianness and into a single vectorizable pass of byte swaps when it doesn't. See
+/// [`ArchivedVec::serialize_from_primitive_slice`](crate::vec::ArchivedVec::serialize_from_primitive_slice)
+/// for more details.
+///
+/// This can be used with `Vec<T>` where `T` implements
+/// [`ArchivePrimitive`](crate::ArchivePrimitive), which includes all of the integer and floating
+/// point types, `bool`, and `char`.
+///
+/// Batching is opt-in: the default `Vec<T>` implementation is generic over any serializable `T`
+/// and can't choose a different strategy for primitives without specialization, so it still
+/// resolves the elements one at a time.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{Archive, with::SwapOptimize};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[with(SwapOptimize)]
+///     samples: Vec<f32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SwapOptimize;
+
+/// A wrapper that archives a byte buffer as a raw, contiguous region of bytes.
+///
+/// `Vec<u8>` is archived as an [`ArchivedBytes`](crate::bytes::ArchivedBytes), which stores its
+/// bytes out-of-line, and `[u8; N]` is archived as an
+/// [`ArchivedByteArray`](crate::bytes::ArchivedByteArray), which stores its bytes inline. Both
+/// are guaranteed to copy the bytes as-is, with no per-element overhead, and provide byte-level
+/// access through `as_slice()`. Using this wrapper on a field that is not a byte buffer is a
+/// compile error.
+///
+/// The derive macros also accept `#[archive(bytes)]` on a field as a shorthand for this wrapper.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{archived_root, ser::{serializers::AllocSerializer, Serializer}, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Packet {
+///     #[archive(bytes)]
+///     header: [u8; 4],
+///     #[archive(bytes)]
+///     payload: Vec<u8>,
+/// }
+///
+/// let mut serializer = AllocSerializer::<256>::default();
+/// serializer
+///     .serialize_value(&Packet {
+///         header: *b"RKYV",
+///         payload: vec![1, 2, 3, 4, 5],
+///     })
+///     .unwrap();
+/// let buf = serializer.into_serializer().into_inner();
+/// let archived = unsafe { archived_root::<Packet>(&buf) };
+///
+/// assert_eq!(archived.header.as_slice(), b"RKYV");
+/// assert_eq!(archived.payload.as_slice(), &[1, 2, 3, 4, 5]);
+/// ```
+#[derive(Debug)]
+pub struct AsBytes;
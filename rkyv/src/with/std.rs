@@ -1,15 +1,25 @@
 use crate::{
-    collections::util::Entry,
+    boxed::ArchivedBox,
+    collections::{
+        hash_map::{ArchivedHashMap, HashMapResolver},
+        open_hash_map::{ArchivedOpenHashMap, OpenHashMapResolver, Probe},
+        util::Entry,
+    },
+    de::AllocationBudget,
     ser::{ScratchSpace, Serializer},
     string::{ArchivedString, StringResolver},
     vec::{ArchivedVec, VecResolver},
     with::{
-        ArchiveWith, AsString, AsStringError, AsVec, DeserializeWith, Immutable, Lock, LockError,
-        SerializeWith,
+        ArchiveWith, AsBox, AsString, AsStringError, AsVec, BoxedValues, DeserializeWith,
+        Immutable, Lock, LockError, OpenAddressed, PooledKeys, SerializeWith, With,
     },
     Archive, Deserialize, Fallible, Serialize, SerializeUnsized,
 };
-use core::{hash::Hash, str::FromStr};
+use core::{
+    alloc::Layout,
+    hash::{BuildHasher, Hash},
+    str::FromStr,
+};
 use std::{
     collections::{HashMap, HashSet},
     ffi::OsString,
@@ -229,7 +239,7 @@ where
     V: Archive,
     K::Archived: Deserialize<K, D>,
     V::Archived: Deserialize<V, D>,
-    D: Fallible + ?Sized,
+    D: AllocationBudget + ?Sized,
 {
     fn deserialize_with(
         field: &ArchivedVec<Entry<K::Archived, V::Archived>>,
@@ -237,6 +247,7 @@ where
     ) -> Result<HashMap<K, V>, D::Error> {
         let mut result = HashMap::new();
         for entry in field.iter() {
+            deserializer.reserve_allocation(Layout::new::<(K, V)>())?;
             result.insert(
                 entry.key.deserialize(deserializer)?,
                 entry.value.deserialize(deserializer)?,
@@ -274,7 +285,7 @@ impl<T, D> DeserializeWith<ArchivedVec<T::Archived>, HashSet<T>, D> for AsVec
 where
     T: Archive + Hash + Eq,
     T::Archived: Deserialize<T, D>,
-    D: Fallible + ?Sized,
+    D: AllocationBudget + ?Sized,
 {
     fn deserialize_with(
         field: &ArchivedVec<T::Archived>,
@@ -282,8 +293,317 @@ where
     ) -> Result<HashSet<T>, D::Error> {
         let mut result = HashSet::new();
         for key in field.iter() {
+            deserializer.reserve_allocation(Layout::new::<T>())?;
             result.insert(key.deserialize(deserializer)?);
         }
         Ok(result)
     }
 }
+
+// PooledKeys
+
+impl<K: Archive, V: Archive, H> ArchiveWith<HashMap<K, V, H>> for PooledKeys
+where
+    K::Archived: Hash + Eq,
+{
+    type Archived = ArchivedHashMap<K::Archived, V::Archived>;
+    type Resolver = HashMapResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &HashMap<K, V, H>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedHashMap::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<K, V, H, S> SerializeWith<HashMap<K, V, H>, S> for PooledKeys
+where
+    K: Serialize<S> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Serialize<S>,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(
+        field: &HashMap<K, V, H>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        unsafe { ArchivedHashMap::serialize_from_iter_pooled_keys(field.iter(), serializer) }
+    }
+}
+
+impl<K, V, H, D> DeserializeWith<ArchivedHashMap<K::Archived, V::Archived>, HashMap<K, V, H>, D>
+    for PooledKeys
+where
+    K: Archive + Hash + Eq,
+    V: Archive,
+    H: Default + BuildHasher,
+    K::Archived: Deserialize<K, D> + Hash + Eq,
+    V::Archived: Deserialize<V, D>,
+    D: AllocationBudget + ?Sized,
+{
+    #[inline]
+    fn deserialize_with(
+        field: &ArchivedHashMap<K::Archived, V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, H>, D::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
+// BoxedValues
+
+impl<K: Archive, V: Archive, H> ArchiveWith<HashMap<K, V, H>> for BoxedValues
+where
+    K::Archived: Hash + Eq,
+{
+    type Archived = ArchivedHashMap<K::Archived, ArchivedBox<V::Archived>>;
+    type Resolver = HashMapResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &HashMap<K, V, H>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedHashMap::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<K, V, H, S> SerializeWith<HashMap<K, V, H>, S> for BoxedValues
+where
+    K: Serialize<S> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Serialize<S>,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(
+        field: &HashMap<K, V, H>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        unsafe {
+            ArchivedHashMap::serialize_from_iter(
+                field
+                    .iter()
+                    .map(|(key, value)| (key, With::<_, AsBox>::cast(value))),
+                serializer,
+            )
+        }
+    }
+}
+
+impl<K, V, H, D>
+    DeserializeWith<ArchivedHashMap<K::Archived, ArchivedBox<V::Archived>>, HashMap<K, V, H>, D>
+    for BoxedValues
+where
+    K: Archive + Hash + Eq,
+    V: Archive,
+    H: Default + BuildHasher,
+    K::Archived: Deserialize<K, D> + Hash + Eq,
+    V::Archived: Deserialize<V, D>,
+    D: AllocationBudget + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashMap<K::Archived, ArchivedBox<V::Archived>>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, H>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<(K, V)>(field.len()).unwrap())?;
+        let mut result = HashMap::with_capacity_and_hasher(field.len(), H::default());
+        for (key, value) in field.iter() {
+            result.insert(
+                key.deserialize(deserializer)?,
+                value.get().deserialize(deserializer)?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+// OpenAddressed
+
+impl<K: Archive, V: Archive, H, P, const LOAD: usize> ArchiveWith<HashMap<K, V, H>>
+    for OpenAddressed<P, LOAD>
+{
+    type Archived = ArchivedOpenHashMap<K::Archived, V::Archived, P>;
+    type Resolver = OpenHashMapResolver;
+
+    #[inline]
+    unsafe fn resolve_with(
+        field: &HashMap<K, V, H>,
+        pos: usize,
+        resolver: Self::Resolver,
+        out: *mut Self::Archived,
+    ) {
+        ArchivedOpenHashMap::resolve_from_len(field.len(), pos, resolver, out);
+    }
+}
+
+impl<K, V, H, P, S, const LOAD: usize> SerializeWith<HashMap<K, V, H>, S> for OpenAddressed<P, LOAD>
+where
+    K: Serialize<S> + Hash + Eq,
+    V: Serialize<S>,
+    P: Probe,
+    S: ScratchSpace + Serializer + ?Sized,
+{
+    #[inline]
+    fn serialize_with(
+        field: &HashMap<K, V, H>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        unsafe {
+            ArchivedOpenHashMap::<K::Archived, V::Archived, P>::serialize_from_iter(
+                field.iter(),
+                LOAD,
+                serializer,
+            )
+        }
+    }
+}
+
+impl<K, V, H, P, D, const LOAD: usize>
+    DeserializeWith<ArchivedOpenHashMap<K::Archived, V::Archived, P>, HashMap<K, V, H>, D>
+    for OpenAddressed<P, LOAD>
+where
+    K: Archive + Hash + Eq,
+    V: Archive,
+    H: Default + BuildHasher,
+    K::Archived: Deserialize<K, D>,
+    V::Archived: Deserialize<V, D>,
+    D: AllocationBudget + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedOpenHashMap<K::Archived, V::Archived, P>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, H>, D::Error> {
+        deserializer.reserve_allocation(Layout::array::<(K, V)>(field.len()).unwrap())?;
+        let mut result = HashMap::with_capacity_and_hasher(field.len(), H::default());
+        for (key, value) in field.iter() {
+            result.insert(
+                key.deserialize(deserializer)?,
+                value.deserialize(deserializer)?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+// AsFst
+
+#[cfg(feature = "fst")]
+const _: () = {
+    use crate::{
+        collections::fst::{ArchivedFstMap, ArchivedFstSet, FstMapResolver, FstSetResolver},
+        de::ScratchPool,
+        with::AsFst,
+    };
+
+    impl<V: Archive, H> ArchiveWith<HashMap<String, V, H>> for AsFst {
+        type Archived = ArchivedFstMap<V::Archived>;
+        type Resolver = FstMapResolver;
+
+        #[inline]
+        unsafe fn resolve_with(
+            field: &HashMap<String, V, H>,
+            pos: usize,
+            resolver: Self::Resolver,
+            out: *mut Self::Archived,
+        ) {
+            ArchivedFstMap::resolve_from_len(field.len(), pos, resolver, out);
+        }
+    }
+
+    impl<V, H, S> SerializeWith<HashMap<String, V, H>, S> for AsFst
+    where
+        V: Serialize<S>,
+        S: ScratchSpace + Serializer + ?Sized,
+    {
+        #[inline]
+        fn serialize_with(
+            field: &HashMap<String, V, H>,
+            serializer: &mut S,
+        ) -> Result<Self::Resolver, S::Error> {
+            ArchivedFstMap::serialize_from_iter(
+                field.iter().map(|(key, value)| (key.as_str(), value)),
+                serializer,
+            )
+        }
+    }
+
+    impl<V, H, D> DeserializeWith<ArchivedFstMap<V::Archived>, HashMap<String, V, H>, D> for AsFst
+    where
+        V: Archive,
+        V::Archived: Deserialize<V, D>,
+        H: BuildHasher + Default,
+        D: AllocationBudget + ScratchPool + ?Sized,
+    {
+        fn deserialize_with(
+            field: &ArchivedFstMap<V::Archived>,
+            deserializer: &mut D,
+        ) -> Result<HashMap<String, V, H>, D::Error> {
+            deserializer.reserve_allocation(Layout::array::<(String, V)>(field.len()).unwrap())?;
+            let mut result = HashMap::with_capacity_and_hasher(field.len(), H::default());
+            let mut iter = field.iter_with_scratch(deserializer);
+            for (key, value) in iter.by_ref() {
+                deserializer.reserve_allocation(Layout::array::<u8>(key.len()).unwrap())?;
+                result.insert(key, value.deserialize(deserializer)?);
+            }
+            iter.return_scratch(deserializer);
+            Ok(result)
+        }
+    }
+
+    impl<H> ArchiveWith<HashSet<String, H>> for AsFst {
+        type Archived = ArchivedFstSet;
+        type Resolver = FstSetResolver;
+
+        #[inline]
+        unsafe fn resolve_with(
+            field: &HashSet<String, H>,
+            pos: usize,
+            resolver: Self::Resolver,
+            out: *mut Self::Archived,
+        ) {
+            ArchivedFstSet::resolve_from_len(field.len(), pos, resolver, out);
+        }
+    }
+
+    impl<H, S> SerializeWith<HashSet<String, H>, S> for AsFst
+    where
+        S: ScratchSpace + Serializer + ?Sized,
+    {
+        #[inline]
+        fn serialize_with(
+            field: &HashSet<String, H>,
+            serializer: &mut S,
+        ) -> Result<Self::Resolver, S::Error> {
+            ArchivedFstSet::serialize_from_iter(field.iter().map(String::as_str), serializer)
+        }
+    }
+
+    impl<H, D> DeserializeWith<ArchivedFstSet, HashSet<String, H>, D> for AsFst
+    where
+        H: BuildHasher + Default,
+        D: AllocationBudget + ScratchPool + ?Sized,
+    {
+        fn deserialize_with(
+            field: &ArchivedFstSet,
+            deserializer: &mut D,
+        ) -> Result<HashSet<String, H>, D::Error> {
+            deserializer.reserve_allocation(Layout::array::<String>(field.len()).unwrap())?;
+            let mut result = HashSet::with_capacity_and_hasher(field.len(), H::default());
+            let mut iter = field.iter_with_scratch(deserializer);
+            for key in iter.by_ref() {
+                deserializer.reserve_allocation(Layout::array::<u8>(key.len()).unwrap())?;
+                result.insert(key);
+            }
+            iter.return_scratch(deserializer);
+            Ok(result)
+        }
+    }
+};
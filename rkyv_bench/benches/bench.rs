@@ -1,16 +1,26 @@
 use bytecheck::CheckBytes;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{measurement::WallTime, BenchmarkGroup};
 use rand::Rng;
 use rand_pcg::Lcg64Xsh32;
 use rkyv::{
     archived_root, check_archived_root,
+    collections::{
+        hash_index::ArchivedHashIndex,
+        open_hash_map::{Linear, Probe, Quadratic, RobinHood},
+        ArchivedHashMap, ArchivedSortedVec,
+    },
     ser::{
         serializers::{AlignedSerializer, BufferScratch, CompositeSerializer},
         Serializer,
     },
-    AlignedVec, Archive, Deserialize, Infallible, Serialize,
+    with::{
+        AsFrontCodedVec, AsSortedVec, AsStringTable, BranchingFactor, OpenAddressed, PooledKeys,
+        SwapOptimize, With,
+    },
+    AlignedVec, Archive, Archived, Deserialize, Infallible, Serialize,
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 trait Generate {
     fn generate<R: Rng>(rng: &mut R) -> Self;
@@ -352,6 +362,100 @@ fn generate_player_name<R: Rng>(rng: &mut R) -> String {
     result
 }
 
+#[derive(Archive, Serialize)]
+struct PooledPlayers {
+    #[with(PooledKeys)]
+    players: HashMap<String, Player>,
+}
+
+#[derive(Archive, Serialize)]
+struct Named {
+    id: u32,
+    name: String,
+}
+
+fn bench_open_hash_map<P: Probe>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    name: &str,
+    map: &HashMap<u32, u32>,
+    hits: &[Archived<u32>],
+    misses: &[Archived<u32>],
+    serialize_scratch: &mut AlignedVec,
+) {
+    // The maximum load factor is 100% so that the number of entries alone picks the load factor
+    type Map<P> = With<HashMap<u32, u32>, OpenAddressed<P, 100>>;
+
+    let mut buffer = AlignedVec::new();
+    let mut serializer = CompositeSerializer::new(
+        AlignedSerializer::new(&mut buffer),
+        BufferScratch::new(serialize_scratch),
+        Infallible,
+    );
+    serializer
+        .serialize_value(With::<_, OpenAddressed<P, 100>>::cast(map))
+        .unwrap();
+
+    group.bench_function(format!("hit {}", name), |b| {
+        let value = unsafe { archived_root::<Map<P>>(buffer.as_ref()) };
+        b.iter(|| {
+            for key in hits.iter() {
+                black_box(value.get(black_box(key)).unwrap());
+            }
+        })
+    });
+    group.bench_function(format!("miss {}", name), |b| {
+        let value = unsafe { archived_root::<Map<P>>(buffer.as_ref()) };
+        b.iter(|| {
+            for key in misses.iter() {
+                assert!(value.get(black_box(key)).is_none());
+            }
+        })
+    });
+}
+
+fn bench_front_coded_vec<const RESTART: usize>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    paths: &Vec<String>,
+    indices: &[usize],
+    serialize_scratch: &mut AlignedVec,
+) {
+    type Paths<const RESTART: usize> = With<Vec<String>, AsFrontCodedVec<RESTART>>;
+
+    let mut buffer = AlignedVec::new();
+    let mut serializer = CompositeSerializer::new(
+        AlignedSerializer::new(&mut buffer),
+        BufferScratch::new(serialize_scratch),
+        Infallible,
+    );
+    serializer
+        .serialize_value(With::<_, AsFrontCodedVec<RESTART>>::cast(paths))
+        .unwrap();
+    println!("front coded (restart {}): {} bytes", RESTART, buffer.len());
+
+    group.bench_function(format!("get front coded (restart {})", RESTART), |b| {
+        let value = unsafe { archived_root::<Paths<RESTART>>(buffer.as_ref()) };
+        b.iter(|| {
+            for &index in indices.iter() {
+                black_box(value.get(black_box(index)).unwrap().len());
+            }
+        })
+    });
+    group.bench_function(format!("iterate front coded (restart {})", RESTART), |b| {
+        let value = unsafe { archived_root::<Paths<RESTART>>(buffer.as_ref()) };
+        b.iter(|| {
+            black_box(value.iter().map(|path| path.len()).sum::<usize>());
+        })
+    });
+    group.bench_function(format!("index of front coded (restart {})", RESTART), |b| {
+        let value = unsafe { archived_root::<Paths<RESTART>>(buffer.as_ref()) };
+        b.iter(|| {
+            for &index in indices.iter() {
+                black_box(value.index_of(black_box(&paths[index])).unwrap());
+            }
+        })
+    });
+}
+
 pub fn criterion_benchmark(c: &mut Criterion) {
     const PLAYERS: usize = 500;
     const STATE: u64 = 3141592653;
@@ -450,6 +554,522 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         });
     }
     group.finish();
+
+    let mut group = c.benchmark_group("rkyv map construction");
+    {
+        type Entries<'a> = (&'a String, &'a Player);
+
+        let mut serialize_buffer = AlignedVec::with_capacity(BUFFER_LEN);
+        let mut serialize_scratch = AlignedVec::with_capacity(SCRATCH_LEN);
+        unsafe {
+            serialize_scratch.set_len(SCRATCH_LEN);
+        }
+
+        let hashes = players
+            .keys()
+            .map(ArchivedHashIndex::hash_key)
+            .collect::<Vec<_>>();
+        let mut sorted = players.iter().collect::<Vec<Entries<'_>>>();
+        sorted.sort_by_key(|&(key, _)| key);
+
+        group.bench_function("hash map", |b| {
+            b.iter(|| {
+                serialize_buffer.clear();
+
+                let mut serializer = CompositeSerializer::new(
+                    AlignedSerializer::new(black_box(&mut serialize_buffer)),
+                    BufferScratch::new(black_box(&mut serialize_scratch)),
+                    Infallible,
+                );
+                black_box(
+                    unsafe {
+                        ArchivedHashMap::<Archived<String>, Archived<Player>>::serialize_from_iter(
+                            black_box(&players).iter(),
+                            &mut serializer,
+                        )
+                    }
+                    .unwrap(),
+                );
+            });
+        });
+        group.bench_function("hash map prehashed", |b| {
+            b.iter(|| {
+                serialize_buffer.clear();
+
+                let mut serializer = CompositeSerializer::new(
+                    AlignedSerializer::new(black_box(&mut serialize_buffer)),
+                    BufferScratch::new(black_box(&mut serialize_scratch)),
+                    Infallible,
+                );
+                black_box(
+                    unsafe {
+                        ArchivedHashMap::<Archived<String>, Archived<Player>>::serialize_from_prehashed_iter(
+                            black_box(&hashes)
+                                .iter()
+                                .copied()
+                                .zip(black_box(&players).iter())
+                                .map(|(hash, (key, value))| (hash, key, value)),
+                            &mut serializer,
+                        )
+                    }
+                    .unwrap(),
+                );
+            });
+        });
+        group.bench_function("sorted vec", |b| {
+            b.iter(|| {
+                serialize_buffer.clear();
+
+                let mut serializer = CompositeSerializer::new(
+                    AlignedSerializer::new(black_box(&mut serialize_buffer)),
+                    BufferScratch::new(black_box(&mut serialize_scratch)),
+                    Infallible,
+                );
+                black_box(
+                    ArchivedSortedVec::<Archived<String>, Archived<Player>>::serialize_from_iter(
+                        black_box(&sorted).iter().copied(),
+                        &mut serializer,
+                    )
+                    .unwrap(),
+                );
+            });
+        });
+        group.bench_function("sorted vec presorted", |b| {
+            b.iter(|| {
+                serialize_buffer.clear();
+
+                let mut serializer = CompositeSerializer::new(
+                    AlignedSerializer::new(black_box(&mut serialize_buffer)),
+                    BufferScratch::new(black_box(&mut serialize_scratch)),
+                    Infallible,
+                );
+                black_box(
+                    unsafe {
+                        ArchivedSortedVec::<Archived<String>, Archived<Player>>::serialize_from_sorted_iter(
+                            black_box(&sorted).iter().copied(),
+                            &mut serializer,
+                        )
+                    }
+                    .unwrap(),
+                );
+            });
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("rkyv string keys");
+    {
+        let mut serialize_scratch = AlignedVec::with_capacity(SCRATCH_LEN);
+        unsafe {
+            serialize_scratch.set_len(SCRATCH_LEN);
+        }
+
+        let pooled = PooledPlayers { players };
+
+        let mut buffer = AlignedVec::new();
+        let mut serializer = CompositeSerializer::new(
+            AlignedSerializer::new(&mut buffer),
+            BufferScratch::new(&mut serialize_scratch),
+            Infallible,
+        );
+        serializer.serialize_value(&pooled.players).unwrap();
+
+        let mut pooled_buffer = AlignedVec::new();
+        let mut serializer = CompositeSerializer::new(
+            AlignedSerializer::new(&mut pooled_buffer),
+            BufferScratch::new(&mut serialize_scratch),
+            Infallible,
+        );
+        serializer.serialize_value(&pooled).unwrap();
+
+        let names = pooled.players.keys().collect::<Vec<_>>();
+
+        group.bench_function("lookup", |b| {
+            let value = unsafe { archived_root::<Players>(buffer.as_ref()) };
+            b.iter(|| {
+                for name in names.iter() {
+                    black_box(value.get(black_box(name.as_str())).unwrap());
+                }
+            })
+        });
+        group.bench_function("lookup pooled", |b| {
+            let value = unsafe { archived_root::<PooledPlayers>(pooled_buffer.as_ref()) };
+            b.iter(|| {
+                for name in names.iter() {
+                    black_box(value.players.get(black_box(name.as_str())).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("rkyv vec scan");
+    {
+        const NAMED: usize = 10_000;
+
+        let named = (0..NAMED)
+            .map(|_| Named {
+                id: rng.gen(),
+                name: generate_player_name(&mut rng),
+            })
+            .collect::<Vec<_>>();
+
+        let mut serialize_scratch = AlignedVec::with_capacity(SCRATCH_LEN);
+        unsafe {
+            serialize_scratch.set_len(SCRATCH_LEN);
+        }
+
+        let mut buffer = AlignedVec::new();
+        let mut serializer = CompositeSerializer::new(
+            AlignedSerializer::new(&mut buffer),
+            BufferScratch::new(&mut serialize_scratch),
+            Infallible,
+        );
+        serializer.serialize_value(&named).unwrap();
+
+        group.bench_function("scan ids", |b| {
+            let value = unsafe { archived_root::<Vec<Named>>(buffer.as_ref()) };
+            b.iter(|| {
+                black_box(value.iter().fold(0u32, |acc, item| {
+                    acc.wrapping_add(rkyv::from_archived!(item.id))
+                }));
+            })
+        });
+        group.bench_function("scan names", |b| {
+            let value = unsafe { archived_root::<Vec<Named>>(buffer.as_ref()) };
+            b.iter(|| {
+                black_box(value.iter().map(|item| item.name.len()).sum::<usize>());
+            })
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("rkyv string table");
+    {
+        const NAMES: usize = 10_000;
+
+        let names = (0..NAMES)
+            .map(|_| generate_player_name(&mut rng))
+            .collect::<Vec<_>>();
+
+        let mut serialize_scratch = AlignedVec::with_capacity(SCRATCH_LEN);
+        unsafe {
+            serialize_scratch.set_len(SCRATCH_LEN);
+        }
+
+        let mut buffer = AlignedVec::new();
+        let mut serializer = CompositeSerializer::new(
+            AlignedSerializer::new(&mut buffer),
+            BufferScratch::new(&mut serialize_scratch),
+            Infallible,
+        );
+        serializer.serialize_value(&names).unwrap();
+
+        let mut table_buffer = AlignedVec::new();
+        let mut serializer = CompositeSerializer::new(
+            AlignedSerializer::new(&mut table_buffer),
+            BufferScratch::new(&mut serialize_scratch),
+            Infallible,
+        );
+        serializer
+            .serialize_value(With::<_, AsStringTable>::cast(&names))
+            .unwrap();
+
+        group.bench_function("iterate vec", |b| {
+            let value = unsafe { archived_root::<Vec<String>>(buffer.as_ref()) };
+            b.iter(|| {
+                black_box(value.iter().map(|name| name.len()).sum::<usize>());
+            })
+        });
+        group.bench_function("iterate table", |b| {
+            let value =
+                unsafe { archived_root::<With<Vec<String>, AsStringTable>>(table_buffer.as_ref()) };
+            b.iter(|| {
+                black_box(value.iter().map(|name| name.len()).sum::<usize>());
+            })
+        });
+        group.bench_function("validate vec", |b| {
+            b.iter(|| {
+                check_archived_root::<Vec<String>>(black_box(buffer.as_ref())).unwrap();
+            })
+        });
+        group.bench_function("validate table", |b| {
+            b.iter(|| {
+                check_archived_root::<With<Vec<String>, AsStringTable>>(black_box(
+                    table_buffer.as_ref(),
+                ))
+                .unwrap();
+            })
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("rkyv front coded vec");
+    {
+        const PATHS: usize = 10_000;
+        const LOOKUPS: usize = 1_000;
+
+        // Sorted paths that share long prefixes, like a file listing
+        let mut paths = (0..PATHS)
+            .map(|_| {
+                format!(
+                    "/home/{}/projects/{}/src/{}.rs",
+                    rng.gen_range(0..4),
+                    rng.gen_range(0..50),
+                    generate_player_name(&mut rng),
+                )
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+        let indices = (0..LOOKUPS)
+            .map(|_| rng.gen_range(0..PATHS))
+            .collect::<Vec<_>>();
+
+        let mut serialize_scratch = AlignedVec::with_capacity(SCRATCH_LEN);
+        unsafe {
+            serialize_scratch.set_len(SCRATCH_LEN);
+        }
+
+        let mut table_buffer = AlignedVec::new();
+        let mut serializer = CompositeSerializer::new(
+            AlignedSerializer::new(&mut table_buffer),
+            BufferScratch::new(&mut serialize_scratch),
+            Infallible,
+        );
+        serializer
+            .serialize_value(With::<_, AsStringTable>::cast(&paths))
+            .unwrap();
+        println!("string table: {} bytes", table_buffer.len());
+
+        group.bench_function("get table", |b| {
+            let value =
+                unsafe { archived_root::<With<Vec<String>, AsStringTable>>(table_buffer.as_ref()) };
+            b.iter(|| {
+                for &index in indices.iter() {
+                    black_box(value.get(black_box(index)).unwrap().len());
+                }
+            })
+        });
+        group.bench_function("iterate table", |b| {
+            let value =
+                unsafe { archived_root::<With<Vec<String>, AsStringTable>>(table_buffer.as_ref()) };
+            b.iter(|| {
+                black_box(value.iter().map(|path| path.len()).sum::<usize>());
+            })
+        });
+
+        bench_front_coded_vec::<4>(&mut group, &paths, &indices, &mut serialize_scratch);
+        bench_front_coded_vec::<16>(&mut group, &paths, &indices, &mut serialize_scratch);
+        bench_front_coded_vec::<64>(&mut group, &paths, &indices, &mut serialize_scratch);
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("rkyv ordered map");
+    {
+        const ENTRIES: u32 = 1_000_000;
+        const LOOKUPS: usize = 10_000;
+
+        let map = (0..ENTRIES)
+            .map(|i| (i * 2, rng.gen::<u32>()))
+            .collect::<BTreeMap<_, _>>();
+        let sorted = map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+        // Archived keys can be compared directly regardless of the archive's endianness
+        let keys = (0..LOOKUPS)
+            .map(|_| rkyv::to_archived!(rng.gen_range(0..ENTRIES) * 2))
+            .collect::<Vec<Archived<u32>>>();
+
+        // Archiving a million entries needs much more scratch space than the other groups
+        const ORDERED_SCRATCH_LEN: usize = 64_000_000;
+
+        let mut serialize_scratch = AlignedVec::with_capacity(ORDERED_SCRATCH_LEN);
+        unsafe {
+            serialize_scratch.set_len(ORDERED_SCRATCH_LEN);
+        }
+
+        let mut sorted_buffer = AlignedVec::new();
+        let mut serializer = CompositeSerializer::new(
+            AlignedSerializer::new(&mut sorted_buffer),
+            BufferScratch::new(&mut serialize_scratch),
+            Infallible,
+        );
+        serializer
+            .serialize_value(With::<_, AsSortedVec>::cast(&sorted))
+            .unwrap();
+
+        let mut btree_buffer = AlignedVec::new();
+        let mut serializer = CompositeSerializer::new(
+            AlignedSerializer::new(&mut btree_buffer),
+            BufferScratch::new(&mut serialize_scratch),
+            Infallible,
+        );
+        serializer.serialize_value(&map).unwrap();
+
+        let mut btree_16_buffer = AlignedVec::new();
+        let mut serializer = CompositeSerializer::new(
+            AlignedSerializer::new(&mut btree_16_buffer),
+            BufferScratch::new(&mut serialize_scratch),
+            Infallible,
+        );
+        serializer
+            .serialize_value(With::<_, BranchingFactor<16>>::cast(&map))
+            .unwrap();
+
+        let mut btree_64_buffer = AlignedVec::new();
+        let mut serializer = CompositeSerializer::new(
+            AlignedSerializer::new(&mut btree_64_buffer),
+            BufferScratch::new(&mut serialize_scratch),
+            Infallible,
+        );
+        serializer
+            .serialize_value(With::<_, BranchingFactor<64>>::cast(&map))
+            .unwrap();
+
+        group.bench_function("lookup sorted vec", |b| {
+            let value = unsafe {
+                archived_root::<With<Vec<(u32, u32)>, AsSortedVec>>(sorted_buffer.as_ref())
+            };
+            b.iter(|| {
+                for key in keys.iter() {
+                    black_box(value.get(black_box(key)).unwrap());
+                }
+            })
+        });
+        group.bench_function("lookup btree", |b| {
+            let value = unsafe { archived_root::<BTreeMap<u32, u32>>(btree_buffer.as_ref()) };
+            b.iter(|| {
+                for key in keys.iter() {
+                    black_box(value.get(black_box(key)).unwrap());
+                }
+            })
+        });
+        group.bench_function("lookup btree branching factor 16", |b| {
+            let value = unsafe {
+                archived_root::<With<BTreeMap<u32, u32>, BranchingFactor<16>>>(
+                    btree_16_buffer.as_ref(),
+                )
+            };
+            b.iter(|| {
+                for key in keys.iter() {
+                    black_box(value.get(black_box(key)).unwrap());
+                }
+            })
+        });
+        group.bench_function("lookup btree branching factor 64", |b| {
+            let value = unsafe {
+                archived_root::<With<BTreeMap<u32, u32>, BranchingFactor<64>>>(
+                    btree_64_buffer.as_ref(),
+                )
+            };
+            b.iter(|| {
+                for key in keys.iter() {
+                    black_box(value.get(black_box(key)).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("rkyv open hash map");
+    {
+        const CAPACITY: usize = 1 << 16;
+        const LOOKUPS: usize = 10_000;
+        const OPEN_SCRATCH_LEN: usize = 4_000_000;
+
+        let mut serialize_scratch = AlignedVec::with_capacity(OPEN_SCRATCH_LEN);
+        unsafe {
+            serialize_scratch.set_len(OPEN_SCRATCH_LEN);
+        }
+
+        for load in [50, 75, 90] {
+            let len = CAPACITY * load / 100;
+            let mut map = HashMap::with_capacity(len);
+            while map.len() < len {
+                map.insert(rng.gen::<u32>(), rng.gen::<u32>());
+            }
+            let keys = map.keys().copied().collect::<Vec<_>>();
+            let hits = (0..LOOKUPS)
+                .map(|_| rkyv::to_archived!(keys[rng.gen_range(0..keys.len())]))
+                .collect::<Vec<Archived<u32>>>();
+            let mut misses = Vec::with_capacity(LOOKUPS);
+            while misses.len() < LOOKUPS {
+                let key = rng.gen::<u32>();
+                if !map.contains_key(&key) {
+                    misses.push(rkyv::to_archived!(key));
+                }
+            }
+
+            bench_open_hash_map::<Linear>(
+                &mut group,
+                &format!("linear {}%", load),
+                &map,
+                &hits,
+                &misses,
+                &mut serialize_scratch,
+            );
+            bench_open_hash_map::<Quadratic>(
+                &mut group,
+                &format!("quadratic {}%", load),
+                &map,
+                &hits,
+                &misses,
+                &mut serialize_scratch,
+            );
+            bench_open_hash_map::<RobinHood>(
+                &mut group,
+                &format!("robin hood {}%", load),
+                &map,
+                &hits,
+                &misses,
+                &mut serialize_scratch,
+            );
+        }
+    }
+    group.finish();
+
+    // Run with `--features archive_be` (or `archive_le` on big-endian targets) to measure
+    // converting the endianness of each element
+    let mut group = c.benchmark_group("rkyv primitive slice");
+    {
+        const SAMPLES: usize = 1_000_000;
+
+        let samples = (0..SAMPLES).map(|_| rng.gen::<u32>()).collect::<Vec<_>>();
+
+        let mut serialize_buffer = AlignedVec::with_capacity(BUFFER_LEN);
+        let mut serialize_scratch = AlignedVec::with_capacity(SCRATCH_LEN);
+        unsafe {
+            serialize_scratch.set_len(SCRATCH_LEN);
+        }
+
+        group.bench_function("serialize element by element", |b| {
+            b.iter(|| {
+                serialize_buffer.clear();
+
+                let mut serializer = CompositeSerializer::new(
+                    AlignedSerializer::new(black_box(&mut serialize_buffer)),
+                    BufferScratch::new(black_box(&mut serialize_scratch)),
+                    Infallible,
+                );
+                black_box(serializer.serialize_value(black_box(&samples)).unwrap());
+            });
+        });
+        group.bench_function("serialize batched", |b| {
+            b.iter(|| {
+                serialize_buffer.clear();
+
+                let mut serializer = CompositeSerializer::new(
+                    AlignedSerializer::new(black_box(&mut serialize_buffer)),
+                    BufferScratch::new(black_box(&mut serialize_scratch)),
+                    Infallible,
+                );
+                black_box(
+                    serializer
+                        .serialize_value(With::<_, SwapOptimize>::cast(black_box(&samples)))
+                        .unwrap(),
+                );
+            });
+        });
+    }
+    group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
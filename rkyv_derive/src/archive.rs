@@ -2,13 +2,16 @@ use crate::{
     attributes::{parse_attributes, Attributes},
     repr::{BaseRepr, IntRepr, Repr},
     util::{add_bounds, strip_raw},
-    with::{make_with_cast, make_with_ty},
+    with::{
+        inline_capacity, is_bytes, is_preserve_capacity, is_wrapper_arg, make_with_cast,
+        make_with_ty,
+    },
 };
-use proc_macro2::{Span, TokenStream};
-use quote::{quote, quote_spanned};
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{
-    parse_quote, spanned::Spanned, Attribute, Data, DeriveInput, Error, Field, Fields, Ident,
-    Index, Meta, NestedMeta, Type,
+    parse_quote, spanned::Spanned, Attribute, Data, DataEnum, DeriveInput, Error, Expr, Field,
+    Fields, Ident, Index, Meta, NestedMeta, Path, Type,
 };
 
 pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
@@ -34,6 +37,39 @@ fn field_archive_attrs(field: &Field) -> impl '_ + Iterator<Item = NestedMeta> {
         .flatten()
 }
 
+fn field_check_on_access(field: &Field) -> Result<bool, Error> {
+    let mut result = false;
+    for attr in field.attrs.iter() {
+        if let Ok(Meta::List(list)) = attr.parse_meta() {
+            if list.path.is_ident("archive") {
+                for nested in list.nested.iter() {
+                    // Wrapper shorthands are parsed by `with`
+                    if is_wrapper_arg(nested) {
+                        continue;
+                    }
+                    if let NestedMeta::Meta(Meta::Path(path)) = nested {
+                        if path.is_ident("check_on_access") {
+                            if result {
+                                return Err(Error::new_spanned(
+                                    path,
+                                    "check_on_access already specified",
+                                ));
+                            }
+                            result = true;
+                            continue;
+                        }
+                    }
+                    return Err(Error::new_spanned(
+                        nested,
+                        "unrecognized archive field argument",
+                    ));
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
 fn derive_archive_impl(
     mut input: DeriveInput,
     attributes: &Attributes,
@@ -98,6 +134,67 @@ fn derive_archive_impl(
         }
     }
 
+    let unnamed_fields: Box<dyn Iterator<Item = &Field>> = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(_) => Box::new(core::iter::empty()),
+            ref fields => Box::new(fields.iter()),
+        },
+        Data::Enum(ref data) => Box::new(data.variants.iter().flat_map(|v| v.fields.iter())),
+        Data::Union(_) => Box::new(core::iter::empty()),
+    };
+    for field in unnamed_fields {
+        if field_check_on_access(field)? {
+            return Err(Error::new_spanned(
+                field,
+                "check_on_access may only be used on the named fields of a struct",
+            ));
+        }
+    }
+
+    let all_fields: Box<dyn Iterator<Item = &Field>> = match input.data {
+        Data::Struct(ref data) => Box::new(data.fields.iter()),
+        Data::Enum(ref data) => Box::new(data.variants.iter().flat_map(|v| v.fields.iter())),
+        Data::Union(_) => Box::new(core::iter::empty()),
+    };
+    for field in all_fields {
+        if is_bytes(field) {
+            if let Some(attr) = field.attrs.iter().find(|a| a.path.is_ident("with")) {
+                return Err(Error::new_spanned(
+                    attr,
+                    "bytes may not be used with #[with(...)]\nuse the AsBytes wrapper instead",
+                ));
+            }
+        }
+        if let Some(capacity) = inline_capacity(field)? {
+            if is_bytes(field) || is_preserve_capacity(field) {
+                return Err(Error::new_spanned(
+                    capacity,
+                    "inline may not be used with bytes or preserve_capacity",
+                ));
+            }
+            if let Some(attr) = field.attrs.iter().find(|a| a.path.is_ident("with")) {
+                return Err(Error::new_spanned(
+                    attr,
+                    "inline may not be used with #[with(...)]\nuse the AsInlineVec wrapper instead",
+                ));
+            }
+        }
+        if is_preserve_capacity(field) {
+            if is_bytes(field) {
+                return Err(Error::new_spanned(
+                    field,
+                    "bytes may not be used with preserve_capacity",
+                ));
+            }
+            if let Some(attr) = field.attrs.iter().find(|a| a.path.is_ident("with")) {
+                return Err(Error::new_spanned(
+                    attr,
+                    "preserve_capacity may not be used with #[with(...)]\nuse the PreserveCapacity wrapper instead",
+                ));
+            }
+        }
+    }
+
     let archived_name = attributes.archived.as_ref().map_or_else(
         || Ident::new(&format!("Archived{}", strip_raw(name)), name.span()),
         |value| value.clone(),
@@ -115,7 +212,38 @@ fn derive_archive_impl(
     );
     let resolver_doc = format!("The resolver for an archived [`{}`]", name);
 
+    if let Some(ref tag_position) = attributes.tag_position {
+        if !matches!(input.data, Data::Enum(_)) {
+            return Err(Error::new_spanned(
+                tag_position,
+                "tag_position may only be used on enums",
+            ));
+        }
+        if attributes.archive_as.is_some() {
+            return Err(Error::new_spanned(
+                tag_position,
+                "tag_position may not be used with as = \"...\"",
+            ));
+        }
+    }
+    // Enums without fields are just their tag, so there's nowhere else to put it
+    let trailing_tag = match input.data {
+        Data::Enum(ref data) if attributes.trailing_tag() => data
+            .variants
+            .iter()
+            .any(|v| !matches!(v.fields, Fields::Unit)),
+        _ => false,
+    };
+
     let (archive_types, archive_impls) = match input.data {
+        Data::Enum(ref data) if trailing_tag => derive_trailing_tag_enum(
+            &input,
+            data,
+            attributes,
+            &archived_name,
+            &resolver,
+            rkyv_path,
+        )?,
         Data::Struct(ref data) => {
             let base_repr = if cfg!(feature = "strict") {
                 Some(match attributes.archived_repr.base_repr {
@@ -188,6 +316,63 @@ fn derive_archive_impl(
                         None
                     };
 
+                    let mut check_on_access_fields = Vec::new();
+                    for field in fields.named.iter() {
+                        if field_check_on_access(field)? {
+                            check_on_access_fields.push(field);
+                        }
+                    }
+                    let check_on_access_impl = if check_on_access_fields.is_empty() {
+                        None
+                    } else if let Some(ref archive_as) = attributes.archive_as {
+                        return Err(Error::new_spanned(
+                            archive_as,
+                            "check_on_access may not be used with as = \"...\" because no type is generated",
+                        ));
+                    } else {
+                        let accessors = check_on_access_fields.iter().map(|f| {
+                            let field_name = f.ident.as_ref().unwrap();
+                            let accessor = Ident::new(
+                                &format!("try_{}", strip_raw(field_name)),
+                                field_name.span(),
+                            );
+                            let ty = with_ty(f);
+                            let vis = &f.vis;
+                            let accessor_doc = format!(
+                                "Checks and returns the archived counterpart of [`{}::{}`] from a \
+                                pointer to an archived `{}`.\n\n\
+                                # Safety\n\n\
+                                `ptr` must be properly aligned and point to `size_of::<Self>()` \
+                                bytes that are valid for reads for the lifetime `'a`. The bytes \
+                                do not need to be a valid `{}`; only the field is checked.",
+                                name, field_name, name, archived_name,
+                            );
+                            quote_spanned! { f.span() =>
+                                #[doc = #accessor_doc]
+                                #[inline]
+                                #[allow(clippy::type_complexity)]
+                                #vis unsafe fn #accessor<'a>(ptr: *const Self) -> ::core::result::Result<
+                                    &'a #rkyv_path::Archived<#ty>,
+                                    <#rkyv_path::Archived<#ty> as #rkyv_path::bytecheck::CheckBytes<()>>::Error,
+                                >
+                                where
+                                    #rkyv_path::Archived<#ty>: #rkyv_path::bytecheck::CheckBytes<()>,
+                                {
+                                    <#rkyv_path::Archived<#ty> as #rkyv_path::bytecheck::CheckBytes<()>>::check_bytes(
+                                        ::core::ptr::addr_of!((*ptr).#field_name),
+                                        &mut (),
+                                    )
+                                }
+                            }
+                        });
+
+                        Some(quote! {
+                            impl #impl_generics #archived_name #ty_generics #archive_where {
+                                #(#accessors)*
+                            }
+                        })
+                    };
+
                     let resolve_fields = fields.named.iter().map(|f| {
                         let name = &f.ident;
                         let field = with_cast(f, parse_quote! { (&self.#name) });
@@ -321,6 +506,7 @@ fn derive_archive_impl(
                             #partial_eq_impl
                             #partial_ord_impl
                             #copy_safe_impl
+                            #check_on_access_impl
                         },
                     )
                 }
@@ -630,60 +816,7 @@ fn derive_archive_impl(
                 }
             }
 
-            let resolver_variants = data.variants.iter().map(|v| {
-                let variant = &v.ident;
-                match v.fields {
-                    Fields::Named(ref fields) => {
-                        let fields = fields.named.iter().map(|f| {
-                            let field_name = f.ident.as_ref();
-                            let ty = with_ty(f);
-                            let field_doc = format!(
-                                "The resolver for [`{}::{}::{}`]",
-                                name,
-                                variant,
-                                field_name.unwrap(),
-                            );
-                            quote_spanned! { f.span() =>
-                                #[doc = #field_doc]
-                                #field_name: #rkyv_path::Resolver<#ty>
-                            }
-                        });
-                        let variant_doc = format!("The resolver for [`{}::{}`]", name, variant);
-                        quote_spanned! { variant.span() =>
-                            #[doc = #variant_doc]
-                            #[allow(dead_code)]
-                            #variant {
-                                #(#fields,)*
-                            }
-                        }
-                    }
-                    Fields::Unnamed(ref fields) => {
-                        let fields = fields.unnamed.iter().enumerate().map(|(i, f)| {
-                            let ty = with_ty(f);
-                            let field_doc =
-                                format!("The resolver for [`{}::{}::{}`]", name, variant, i);
-                            quote_spanned! { f.span() =>
-                                #[doc = #field_doc]
-                                #rkyv_path::Resolver<#ty>
-                            }
-                        });
-                        let variant_doc = format!("The resolver for [`{}::{}`]", name, variant);
-                        quote_spanned! { variant.span() =>
-                            #[doc = #variant_doc]
-                            #[allow(dead_code)]
-                            #variant(#(#fields,)*)
-                        }
-                    }
-                    Fields::Unit => {
-                        let variant_doc = format!("The resolver for [`{}::{}`]", name, variant);
-                        quote_spanned! { variant.span() =>
-                            #[doc = #variant_doc]
-                            #[allow(dead_code)]
-                            #variant
-                        }
-                    }
-                }
-            });
+            let resolver_variants = enum_resolver_variants(name, data, rkyv_path);
 
             let resolve_arms = data.variants.iter().map(|v| {
                 let variant = &v.ident;
@@ -766,26 +899,7 @@ fn derive_archive_impl(
                 }
             });
 
-            let (int_repr, int_repr_span) = match attributes.archived_repr.base_repr {
-                // The base repr for enums may not be Rust, transparent, or C
-                Some((BaseRepr::Transparent | BaseRepr::C, span)) => {
-                    return Err(Error::new(span, "enums may only be repr(i*) or repr(u*)"))
-                }
-                // The base repr for enums may be i*/u*
-                Some((BaseRepr::Int(int_repr), span)) => (int_repr, span),
-                // If unspecified, the base repr is set to u* with the smallest unsigned integer
-                // that can represent the number of variants
-                None => {
-                    let int_repr = match data.variants.len() {
-                        0..=255 => IntRepr::U8,
-                        256..=65_535 => IntRepr::U16,
-                        65_536..=4_294_967_295 => IntRepr::U32,
-                        4_294_967_296..=18_446_744_073_709_551_615 => IntRepr::U64,
-                        _ => IntRepr::U128,
-                    };
-                    (int_repr, Span::call_site())
-                }
-            };
+            let (int_repr, int_repr_span) = enum_int_repr(attributes, data)?;
             let repr = Repr {
                 base_repr: Some((BaseRepr::Int(int_repr), int_repr_span)),
                 modifier: attributes.archived_repr.modifier.clone(),
@@ -1274,6 +1388,41 @@ fn derive_archive_impl(
         }
     };
 
+    let size_hint_impl = if attributes.size_hint.is_some() {
+        Some(derive_size_hint_impl(&input, rkyv_path)?)
+    } else {
+        None
+    };
+
+    let niche_impl = if let Some(ref niche) = attributes.niche {
+        Some(derive_niche_impl(&input, attributes, niche, rkyv_path)?)
+    } else {
+        None
+    };
+
+    let copy_impl = if let Some(ref copy) = attributes.copy {
+        if attributes.archive_as.is_some() {
+            return Err(Error::new_spanned(
+                copy,
+                "copy may not be used with as = \"...\" because no type is generated",
+            ));
+        }
+        if trailing_tag {
+            return Err(Error::new_spanned(
+                copy,
+                "copy may not be used with a trailing enum tag",
+            ));
+        }
+        Some(derive_copy_impl(
+            &input,
+            attributes,
+            &archived_name,
+            rkyv_path,
+        )?)
+    } else {
+        None
+    };
+
     Ok(quote! {
         #archive_types
 
@@ -1283,6 +1432,943 @@ fn derive_archive_impl(
             use #rkyv_path::{out_field, Archive, Archived};
 
             #archive_impls
+            #size_hint_impl
+            #niche_impl
+            #copy_impl
         };
     })
 }
+
+// Returns the integer repr of the tag of an archived enum
+fn enum_int_repr(attributes: &Attributes, data: &DataEnum) -> Result<(IntRepr, Span), Error> {
+    match attributes.archived_repr.base_repr {
+        // The base repr for enums may not be Rust, transparent, or C
+        Some((BaseRepr::Transparent | BaseRepr::C, span)) => {
+            Err(Error::new(span, "enums may only be repr(i*) or repr(u*)"))
+        }
+        // The base repr for enums may be i*/u*
+        Some((BaseRepr::Int(int_repr), span)) => Ok((int_repr, span)),
+        // If unspecified, the base repr is set to u* with the smallest unsigned integer
+        // that can represent the number of variants
+        None => {
+            let int_repr = match data.variants.len() {
+                0..=255 => IntRepr::U8,
+                256..=65_535 => IntRepr::U16,
+                65_536..=4_294_967_295 => IntRepr::U32,
+                4_294_967_296..=18_446_744_073_709_551_615 => IntRepr::U64,
+                _ => IntRepr::U128,
+            };
+            Ok((int_repr, Span::call_site()))
+        }
+    }
+}
+
+// Generates the variants of the resolver for an enum
+fn enum_resolver_variants(name: &Ident, data: &DataEnum, rkyv_path: &Path) -> Vec<TokenStream> {
+    let with_ty = make_with_ty(rkyv_path);
+    data.variants
+        .iter()
+        .map(|v| {
+            let variant = &v.ident;
+            match v.fields {
+                Fields::Named(ref fields) => {
+                    let fields = fields.named.iter().map(|f| {
+                        let field_name = f.ident.as_ref();
+                        let ty = with_ty(f);
+                        let field_doc = format!(
+                            "The resolver for [`{}::{}::{}`]",
+                            name,
+                            variant,
+                            field_name.unwrap(),
+                        );
+                        quote_spanned! { f.span() =>
+                            #[doc = #field_doc]
+                            #field_name: #rkyv_path::Resolver<#ty>
+                        }
+                    });
+                    let variant_doc = format!("The resolver for [`{}::{}`]", name, variant);
+                    quote_spanned! { variant.span() =>
+                        #[doc = #variant_doc]
+                        #[allow(dead_code)]
+                        #variant {
+                            #(#fields,)*
+                        }
+                    }
+                }
+                Fields::Unnamed(ref fields) => {
+                    let fields = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                        let ty = with_ty(f);
+                        let field_doc =
+                            format!("The resolver for [`{}::{}::{}`]", name, variant, i);
+                        quote_spanned! { f.span() =>
+                            #[doc = #field_doc]
+                            #rkyv_path::Resolver<#ty>
+                        }
+                    });
+                    let variant_doc = format!("The resolver for [`{}::{}`]", name, variant);
+                    quote_spanned! { variant.span() =>
+                        #[doc = #variant_doc]
+                        #[allow(dead_code)]
+                        #variant(#(#fields,)*)
+                    }
+                }
+                Fields::Unit => {
+                    let variant_doc = format!("The resolver for [`{}::{}`]", name, variant);
+                    quote_spanned! { variant.span() =>
+                        #[doc = #variant_doc]
+                        #[allow(dead_code)]
+                        #variant
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+// Derives `Archive` for an enum with `tag_position = "trailing"`. The archived type is a `repr(C)`
+// struct with a union of the variants' fields followed by the tag, and it's matched on through a
+// generated enum of references to the fields.
+fn derive_trailing_tag_enum(
+    input: &DeriveInput,
+    data: &DataEnum,
+    attributes: &Attributes,
+    archived_name: &Ident,
+    resolver: &Ident,
+    rkyv_path: &Path,
+) -> Result<(TokenStream, TokenStream), Error> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let with_ty = make_with_ty(rkyv_path);
+    let with_cast = make_with_cast(rkyv_path);
+
+    if let Some(param) = input.generics.params.first() {
+        return Err(Error::new_spanned(
+            param,
+            "tag_position = \"trailing\" may not be used with generic enums",
+        ));
+    }
+    if let Some(ref path) = attributes.copy_safe {
+        return Err(Error::new_spanned(
+            path,
+            "copy_safe may not be used with tag_position = \"trailing\"",
+        ));
+    }
+    if let Some((_, ref compares)) = attributes.compares {
+        if let Some(compare) = compares.iter().find(|c| !c.is_ident("PartialEq")) {
+            return Err(Error::new_spanned(
+                compare,
+                "only PartialEq may be compared with tag_position = \"trailing\"",
+            ));
+        }
+    }
+
+    let (int_repr, _) = enum_int_repr(attributes, data)?;
+    let where_clause = input.generics.where_clause.as_ref().unwrap();
+    let mut archive_where = where_clause.clone();
+    for field in data
+        .variants
+        .iter()
+        .flat_map(|v| v.fields.iter())
+        .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
+    {
+        let ty = with_ty(field);
+        archive_where
+            .predicates
+            .push(parse_quote! { #ty: #rkyv_path::Archive });
+    }
+
+    let payload_name = Ident::new(
+        &format!("__{}Payload", strip_raw(archived_name)),
+        name.span(),
+    );
+    let ref_name = Ident::new(&format!("{}Ref", strip_raw(archived_name)), name.span());
+    let variant_struct_name = |v: &Ident| {
+        Ident::new(
+            &format!("__{}Variant{}", strip_raw(archived_name), strip_raw(v)),
+            v.span(),
+        )
+    };
+    let field_variants = data
+        .variants
+        .iter()
+        .filter(|v| !matches!(v.fields, Fields::Unit))
+        .collect::<Vec<_>>();
+
+    // The archived type can't derive `CheckBytes` through a union, so it's implemented below
+    let mut check_bytes = false;
+    let archive_attrs = attributes
+        .attrs
+        .iter()
+        .filter_map(|meta| match meta {
+            Meta::List(list) if list.path.is_ident("derive") => {
+                let mut list = list.clone();
+                let len = list.nested.len();
+                list.nested = list
+                    .nested
+                    .into_iter()
+                    .filter(|nested| {
+                        !matches!(
+                            nested,
+                            NestedMeta::Meta(Meta::Path(path))
+                                if matches!(path.segments.last(), Some(s) if s.ident == "CheckBytes")
+                        )
+                    })
+                    .collect();
+                check_bytes |= list.nested.len() != len;
+                if list.nested.is_empty() {
+                    None
+                } else {
+                    Some(Meta::List(list))
+                }
+            }
+            meta => Some(meta.clone()),
+        })
+        .map::<Attribute, _>(|d| parse_quote! { #[#d] })
+        .collect::<Vec<_>>();
+
+    let variant_structs = field_variants.iter().map(|v| {
+        let struct_name = variant_struct_name(&v.ident);
+        match v.fields {
+            Fields::Named(ref fields) => {
+                let fields = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    let ty = with_ty(f);
+                    quote_spanned! { f.span() => #name: #rkyv_path::Archived<#ty> }
+                });
+                quote! {
+                    #[repr(C)]
+                    struct #struct_name #archive_where {
+                        #(#fields,)*
+                    }
+                }
+            }
+            Fields::Unnamed(ref fields) => {
+                let fields = fields.unnamed.iter().map(|f| {
+                    let ty = with_ty(f);
+                    quote_spanned! { f.span() => #rkyv_path::Archived<#ty> }
+                });
+                quote! {
+                    #[repr(C)]
+                    struct #struct_name (#(#fields,)*) #archive_where;
+                }
+            }
+            Fields::Unit => quote! {},
+        }
+    });
+
+    let payload_fields = field_variants.iter().map(|v| {
+        let variant = &v.ident;
+        let struct_name = variant_struct_name(variant);
+        quote_spanned! { variant.span() => #variant: ::core::mem::ManuallyDrop<#struct_name> }
+    });
+
+    let ref_variants = data.variants.iter().map(|v| {
+        let variant = &v.ident;
+        let variant_doc = format!("The archived counterpart of [`{}::{}`]", name, variant);
+        match v.fields {
+            Fields::Named(ref fields) => {
+                let fields = fields.named.iter().map(|f| {
+                    let field_name = &f.ident;
+                    let ty = with_ty(f);
+                    let field_doc = format!(
+                        "The archived counterpart of [`{}::{}::{}`]",
+                        name,
+                        variant,
+                        field_name.as_ref().unwrap(),
+                    );
+                    quote_spanned! { f.span() =>
+                        #[doc = #field_doc]
+                        #field_name: &'__rkyv #rkyv_path::Archived<#ty>
+                    }
+                });
+                quote_spanned! { variant.span() =>
+                    #[doc = #variant_doc]
+                    #variant { #(#fields,)* }
+                }
+            }
+            Fields::Unnamed(ref fields) => {
+                let fields = fields.unnamed.iter().map(|f| {
+                    let ty = with_ty(f);
+                    quote_spanned! { f.span() => &'__rkyv #rkyv_path::Archived<#ty> }
+                });
+                quote_spanned! { variant.span() =>
+                    #[doc = #variant_doc]
+                    #variant(#(#fields,)*)
+                }
+            }
+            Fields::Unit => quote_spanned! { variant.span() =>
+                #[doc = #variant_doc]
+                #variant
+            },
+        }
+    });
+
+    let tag = |i: usize| Literal::usize_unsuffixed(i);
+
+    let as_ref_arms = data.variants.iter().enumerate().map(|(i, v)| {
+        let variant = &v.ident;
+        let tag = tag(i);
+        match v.fields {
+            Fields::Named(ref fields) => {
+                let fields = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    quote! { #name: &payload.#name }
+                });
+                quote! {
+                    #tag => {
+                        let payload = &*self.__payload.#variant;
+                        #ref_name::#variant { #(#fields,)* }
+                    }
+                }
+            }
+            Fields::Unnamed(ref fields) => {
+                let fields = (0..fields.unnamed.len()).map(|i| {
+                    let index = Index::from(i);
+                    quote! { &payload.#index }
+                });
+                quote! {
+                    #tag => {
+                        let payload = &*self.__payload.#variant;
+                        #ref_name::#variant(#(#fields,)*)
+                    }
+                }
+            }
+            Fields::Unit => quote! { #tag => #ref_name::#variant },
+        }
+    });
+
+    let resolver_variants = enum_resolver_variants(name, data, rkyv_path);
+
+    let resolve_arms = data.variants.iter().enumerate().map(|(i, v)| {
+        let variant = &v.ident;
+        let tag = tag(i);
+        match v.fields {
+            Fields::Named(ref fields) => {
+                let struct_name = variant_struct_name(variant);
+                let self_bindings = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    let binding = Ident::new(
+                        &format!("self_{}", strip_raw(name.as_ref().unwrap())),
+                        name.span(),
+                    );
+                    quote_spanned! { name.span() => #name: #binding }
+                });
+                let resolver_bindings = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    let binding = Ident::new(
+                        &format!("resolver_{}", strip_raw(name.as_ref().unwrap())),
+                        name.span(),
+                    );
+                    quote_spanned! { binding.span() => #name: #binding }
+                });
+                let resolves = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    let self_binding = Ident::new(
+                        &format!("self_{}", strip_raw(name.as_ref().unwrap())),
+                        name.span(),
+                    );
+                    let resolver_binding = Ident::new(
+                        &format!("resolver_{}", strip_raw(name.as_ref().unwrap())),
+                        name.span(),
+                    );
+                    let value = with_cast(f, parse_quote! { #self_binding });
+                    quote! {
+                        let (fp, fo) = out_field!(payload.#name);
+                        #value.resolve(payload_pos + fp, #resolver_binding, fo);
+                    }
+                });
+                quote! {
+                    #resolver::#variant { #(#resolver_bindings,)* } => {
+                        match self {
+                            #name::#variant { #(#self_bindings,)* } => {
+                                let (fp, fo) = out_field!(out.__payload);
+                                let payload = fo.cast::<#struct_name>();
+                                let payload_pos = pos + fp;
+                                #(#resolves)*
+                            },
+                            #[allow(unreachable_patterns)]
+                            _ => ::core::hint::unreachable_unchecked(),
+                        }
+                        #tag
+                    }
+                }
+            }
+            Fields::Unnamed(ref fields) => {
+                let struct_name = variant_struct_name(variant);
+                let self_bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                    let name = Ident::new(&format!("self_{}", i), f.span());
+                    quote_spanned! { f.span() => #name }
+                });
+                let resolver_bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                    let name = Ident::new(&format!("resolver_{}", i), f.span());
+                    quote_spanned! { f.span() => #name }
+                });
+                let resolves = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                    let index = Index::from(i);
+                    let self_binding = Ident::new(&format!("self_{}", i), f.span());
+                    let resolver_binding = Ident::new(&format!("resolver_{}", i), f.span());
+                    let value = with_cast(f, parse_quote! { #self_binding });
+                    quote! {
+                        let (fp, fo) = out_field!(payload.#index);
+                        #value.resolve(payload_pos + fp, #resolver_binding, fo);
+                    }
+                });
+                quote! {
+                    #resolver::#variant(#(#resolver_bindings,)*) => {
+                        match self {
+                            #name::#variant(#(#self_bindings,)*) => {
+                                let (fp, fo) = out_field!(out.__payload);
+                                let payload = fo.cast::<#struct_name>();
+                                let payload_pos = pos + fp;
+                                #(#resolves)*
+                            },
+                            #[allow(unreachable_patterns)]
+                            _ => ::core::hint::unreachable_unchecked(),
+                        }
+                        #tag
+                    }
+                }
+            }
+            Fields::Unit => quote! { #resolver::#variant => #tag },
+        }
+    });
+
+    let partial_eq_impl = if attributes.compares.is_some() {
+        let mut partial_eq_where = archive_where.clone();
+        for field in data
+            .variants
+            .iter()
+            .flat_map(|v| v.fields.iter())
+            .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
+        {
+            let ty = with_ty(field);
+            partial_eq_where
+                .predicates
+                .push(parse_quote! { Archived<#ty>: PartialEq<#ty> });
+        }
+
+        let eq_arms = data.variants.iter().map(|v| {
+            let variant = &v.ident;
+            match v.fields {
+                Fields::Named(ref fields) => {
+                    let field_names = fields.named.iter().map(|f| &f.ident).collect::<Vec<_>>();
+                    let self_bindings = field_names
+                        .iter()
+                        .map(|n| {
+                            Ident::new(
+                                &format!("self_{}", strip_raw(n.as_ref().unwrap())),
+                                n.span(),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    let other_bindings = field_names
+                        .iter()
+                        .map(|n| {
+                            Ident::new(
+                                &format!("other_{}", strip_raw(n.as_ref().unwrap())),
+                                n.span(),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    quote! {
+                        (
+                            #ref_name::#variant { #(#field_names: #self_bindings,)* },
+                            #name::#variant { #(#field_names: #other_bindings,)* },
+                        ) => true #(&& #self_bindings.eq(#other_bindings))*
+                    }
+                }
+                Fields::Unnamed(ref fields) => {
+                    let self_bindings = (0..fields.unnamed.len())
+                        .map(|i| Ident::new(&format!("self_{}", i), variant.span()))
+                        .collect::<Vec<_>>();
+                    let other_bindings = (0..fields.unnamed.len())
+                        .map(|i| Ident::new(&format!("other_{}", i), variant.span()))
+                        .collect::<Vec<_>>();
+                    quote! {
+                        (
+                            #ref_name::#variant(#(#self_bindings,)*),
+                            #name::#variant(#(#other_bindings,)*),
+                        ) => true #(&& #self_bindings.eq(#other_bindings))*
+                    }
+                }
+                Fields::Unit => quote! {
+                    (#ref_name::#variant, #name::#variant) => true
+                },
+            }
+        });
+
+        Some(quote! {
+            impl PartialEq<#name> for #archived_name #partial_eq_where {
+                #[inline]
+                fn eq(&self, other: &#name) -> bool {
+                    match (self.as_ref(), other) {
+                        #(#eq_arms,)*
+                        #[allow(unreachable_patterns)]
+                        _ => false,
+                    }
+                }
+            }
+
+            impl PartialEq<#archived_name> for #name #partial_eq_where {
+                #[inline]
+                fn eq(&self, other: &#archived_name) -> bool {
+                    other.eq(self)
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let check_bytes_impl = if check_bytes {
+        let mut check_where = archive_where.clone();
+        for field in data
+            .variants
+            .iter()
+            .flat_map(|v| v.fields.iter())
+            .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
+        {
+            let ty = with_ty(field);
+            check_where
+                .predicates
+                .push(parse_quote! { Archived<#ty>: CheckBytes<__C> });
+        }
+
+        let check_arms = data.variants.iter().enumerate().map(|(i, v)| {
+            let variant = &v.ident;
+            let tag = tag(i);
+            match v.fields {
+                Fields::Named(ref fields) => {
+                    let struct_name = variant_struct_name(variant);
+                    let checks = fields.named.iter().map(|f| {
+                        let name = &f.ident;
+                        let ty = with_ty(f);
+                        quote! {
+                            <Archived<#ty> as CheckBytes<__C>>::check_bytes(
+                                ::core::ptr::addr_of!((*payload).#name),
+                                context,
+                            ).map_err(|e| EnumCheckError::InvalidStruct {
+                                variant_name: stringify!(#variant),
+                                inner: StructCheckError {
+                                    field_name: stringify!(#name),
+                                    inner: ErrorBox::new(e),
+                                },
+                            })?;
+                        }
+                    });
+                    quote! {
+                        #tag => {
+                            let payload = ::core::ptr::addr_of!((*value).__payload).cast::<#struct_name>();
+                            #(#checks)*
+                        }
+                    }
+                }
+                Fields::Unnamed(ref fields) => {
+                    let struct_name = variant_struct_name(variant);
+                    let checks = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                        let index = Index::from(i);
+                        let ty = with_ty(f);
+                        quote! {
+                            <Archived<#ty> as CheckBytes<__C>>::check_bytes(
+                                ::core::ptr::addr_of!((*payload).#index),
+                                context,
+                            ).map_err(|e| EnumCheckError::InvalidTuple {
+                                variant_name: stringify!(#variant),
+                                inner: TupleStructCheckError {
+                                    field_index: #i,
+                                    inner: ErrorBox::new(e),
+                                },
+                            })?;
+                        }
+                    });
+                    quote! {
+                        #tag => {
+                            let payload = ::core::ptr::addr_of!((*value).__payload).cast::<#struct_name>();
+                            #(#checks)*
+                        }
+                    }
+                }
+                Fields::Unit => quote! { #tag => () },
+            }
+        });
+
+        Some(quote! {
+            const _: () = {
+                use #rkyv_path::bytecheck::{
+                    CheckBytes, EnumCheckError, ErrorBox, StructCheckError, TupleStructCheckError,
+                };
+
+                impl<__C: ?Sized> CheckBytes<__C> for #archived_name #check_where {
+                    type Error = EnumCheckError<#int_repr>;
+
+                    unsafe fn check_bytes<'__bytecheck>(
+                        value: *const Self,
+                        context: &mut __C,
+                    ) -> ::core::result::Result<&'__bytecheck Self, EnumCheckError<#int_repr>> {
+                        let tag = <#int_repr as ::core::convert::From<Archived<#int_repr>>>::from(
+                            *::core::ptr::addr_of!((*value).__tag),
+                        );
+                        match tag {
+                            #(#check_arms,)*
+                            _ => return Err(EnumCheckError::InvalidTag(tag)),
+                        }
+                        Ok(&*value)
+                    }
+                }
+            };
+        })
+    } else {
+        None
+    };
+
+    let archived_doc = format!("An archived [`{}`]", name);
+    let ref_doc = format!(
+        "A reference to the fields of the active variant of an archived [`{}`]",
+        name
+    );
+    let resolver_doc = format!("The resolver for an archived [`{}`]", name);
+    let repr = Repr {
+        base_repr: Some((BaseRepr::C, Span::call_site())),
+        modifier: attributes.archived_repr.modifier.clone(),
+    };
+
+    Ok((
+        quote! {
+            #(#variant_structs)*
+
+            #[repr(C)]
+            #[allow(non_snake_case)]
+            union #payload_name #archive_where {
+                #(#payload_fields,)*
+            }
+
+            #[automatically_derived]
+            #[doc = #archived_doc]
+            #(#archive_attrs)*
+            #repr
+            #vis struct #archived_name #archive_where {
+                __payload: #payload_name,
+                __tag: #rkyv_path::Archived<#int_repr>,
+            }
+
+            #[automatically_derived]
+            #[doc = #ref_doc]
+            #vis enum #ref_name<'__rkyv> #archive_where {
+                #(#ref_variants,)*
+            }
+
+            #[automatically_derived]
+            #[doc = #resolver_doc]
+            #vis enum #resolver #archive_where {
+                #(#resolver_variants,)*
+            }
+        },
+        quote! {
+            impl #archived_name #archive_where {
+                /// Returns references to the fields of the active variant, which can be matched on.
+                #[inline]
+                pub fn as_ref(&self) -> #ref_name<'_> {
+                    unsafe {
+                        match <#int_repr as ::core::convert::From<Archived<#int_repr>>>::from(self.__tag) {
+                            #(#as_ref_arms,)*
+                            _ => ::core::hint::unreachable_unchecked(),
+                        }
+                    }
+                }
+            }
+
+            impl Archive for #name #archive_where {
+                type Archived = #archived_name;
+                type Resolver = #resolver;
+
+                // Some resolvers will be (), this allow is to prevent clippy from complaining
+                #[allow(clippy::unit_arg)]
+                #[inline]
+                unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+                    let tag: #int_repr = match resolver {
+                        #(#resolve_arms,)*
+                    };
+                    let (fp, fo) = out_field!(out.__tag);
+                    tag.resolve(pos + fp, (), fo);
+                }
+            }
+
+            #partial_eq_impl
+            #check_bytes_impl
+        },
+    ))
+}
+
+// Returns whether the archived type derives the given trait with `#[archive_attr(derive(...))]`
+fn archive_attrs_derive(attributes: &Attributes, name: &str) -> bool {
+    attributes.attrs.iter().any(|meta| match meta {
+        Meta::List(list) if list.path.is_ident("derive") => {
+            list.nested.iter().any(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    matches!(path.segments.last(), Some(segment) if segment.ident == name)
+                }
+                _ => false,
+            })
+        }
+        _ => false,
+    })
+}
+
+// Implements `ArchiveNiche` for a single-field struct using a sentinel value of its field
+fn derive_niche_impl(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    niche: &Expr,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    if attributes.archive_as.is_some() {
+        return Err(Error::new_spanned(
+            niche,
+            "niche = \"...\" may not be used with as = \"...\"",
+        ));
+    }
+    let field = match input.data {
+        Data::Struct(ref data) if data.fields.len() == 1 => data.fields.iter().next().unwrap(),
+        _ => {
+            return Err(Error::new_spanned(
+                niche,
+                "niche = \"...\" can only be used on structs with a single field",
+            ))
+        }
+    };
+    if field.attrs.iter().any(|a| a.path.is_ident("with")) {
+        return Err(Error::new_spanned(
+            field,
+            "niche = \"...\" may not be used with a field archived with a wrapper",
+        ));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let ty = &field.ty;
+    let member = field.ident.as_ref().map_or_else(
+        || Index::from(0).into_token_stream(),
+        |ident| quote! { #ident },
+    );
+
+    let mut niche_where = where_clause.unwrap().clone();
+    niche_where
+        .predicates
+        .push(parse_quote! { #name #ty_generics: Archive });
+    niche_where
+        .predicates
+        .push(parse_quote! { #ty: Archive<Resolver = ()> });
+    niche_where
+        .predicates
+        .push(parse_quote! { Archived<#ty>: PartialEq<#ty> });
+    niche_where.predicates.push(parse_quote! { #ty: PartialEq });
+
+    Ok(quote! {
+        impl #impl_generics #rkyv_path::niche::sentinel::ArchiveNiche for #name #ty_generics #niche_where {
+            #[inline]
+            fn is_niche(archived: &Self::Archived) -> bool {
+                let niche: #ty = #niche;
+                archived.#member == niche
+            }
+
+            #[inline]
+            fn is_niche_value(&self) -> bool {
+                let niche: #ty = #niche;
+                self.#member == niche
+            }
+
+            #[inline]
+            unsafe fn resolve_niche(pos: usize, out: *mut Self::Archived) {
+                let niche: #ty = #niche;
+                let (fp, fo) = out_field!(out.#member);
+                niche.resolve(pos + fp, (), fo);
+            }
+        }
+    })
+}
+
+fn derive_copy_impl(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    archived_name: &Ident,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    let fields: Box<dyn Iterator<Item = &Field>> = match input.data {
+        Data::Struct(ref data) => Box::new(data.fields.iter()),
+        Data::Enum(ref data) => Box::new(data.variants.iter().flat_map(|v| v.fields.iter())),
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "Clone and Copy cannot be derived for archived unions",
+            ))
+        }
+    };
+    let fields = fields.collect::<Vec<_>>();
+
+    // Recursive types omit the bounds that would be needed to name their archived field types,
+    // and always archive through a pointer anyway
+    if let Some(field) = fields
+        .iter()
+        .find(|f| f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
+    {
+        return Err(Error::new_spanned(
+            field,
+            "copy may not be used with omit_bounds fields",
+        ));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let with_ty = make_with_ty(rkyv_path);
+
+    // Relative pointers are never `Copy`, so requiring every archived field to be `Copy` limits the
+    // impls to archived types without relative pointers. The bounds are higher-ranked so that they
+    // aren't rejected as trivially false for types that do have relative pointers.
+    let mut copy_where = where_clause.unwrap().clone();
+    for field in fields.iter() {
+        let ty = with_ty(field);
+        copy_where
+            .predicates
+            .push(parse_quote! { #ty: #rkyv_path::Archive });
+        copy_where
+            .predicates
+            .push(parse_quote! { for<'__rkyv> Archived<#ty>: ::core::marker::Copy });
+    }
+
+    let clone_impl = if archive_attrs_derive(attributes, "Clone") {
+        None
+    } else {
+        Some(quote! {
+            impl #impl_generics ::core::clone::Clone for #archived_name #ty_generics #copy_where {
+                #[inline]
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+        })
+    };
+    let copy_impl = if archive_attrs_derive(attributes, "Copy") {
+        None
+    } else {
+        Some(quote! {
+            impl #impl_generics ::core::marker::Copy for #archived_name #ty_generics #copy_where {}
+        })
+    };
+
+    Ok(quote! {
+        #clone_impl
+        #copy_impl
+    })
+}
+
+fn derive_size_hint_impl(input: &DeriveInput, rkyv_path: &Path) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Fields archived with a wrapper are left out of the estimate
+    let is_hinted = |f: &&Field| !f.attrs.iter().any(|a| a.path.is_ident("with"));
+
+    let mut size_hint_where = where_clause.unwrap().clone();
+    size_hint_where
+        .predicates
+        .push(parse_quote! { #name #ty_generics: Archive });
+    let fields: Box<dyn Iterator<Item = &Field>> = match input.data {
+        Data::Struct(ref data) => Box::new(data.fields.iter()),
+        Data::Enum(ref data) => Box::new(data.variants.iter().flat_map(|v| v.fields.iter())),
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "ArchivedSizeHint cannot be derived for unions",
+            ))
+        }
+    };
+    for field in fields
+        .filter(is_hinted)
+        .filter(|f| !f.attrs.iter().any(|a| a.path.is_ident("omit_bounds")))
+    {
+        let ty = &field.ty;
+        size_hint_where
+            .predicates
+            .push(parse_quote! { #ty: #rkyv_path::size_hint::ArchivedSizeHint });
+    }
+
+    let field_hint = |binding: TokenStream| {
+        quote! { #rkyv_path::size_hint::ArchivedSizeHint::out_of_line_size_hint(#binding) }
+    };
+
+    let body = match input.data {
+        Data::Struct(ref data) => {
+            let hints = data
+                .fields
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| is_hinted(f))
+                .map(|(i, f)| {
+                    if let Some(ref ident) = f.ident {
+                        field_hint(quote! { &self.#ident })
+                    } else {
+                        let index = Index::from(i);
+                        field_hint(quote! { &self.#index })
+                    }
+                });
+            quote! { 0 #(+ #hints)* }
+        }
+        Data::Enum(ref data) => {
+            let arms = data.variants.iter().map(|v| {
+                let variant = &v.ident;
+                match v.fields {
+                    Fields::Named(ref fields) => {
+                        let bindings = fields.named.iter().map(|f| {
+                            let ident = &f.ident;
+                            if is_hinted(&f) {
+                                quote! { #ident }
+                            } else {
+                                quote! { #ident: _ }
+                            }
+                        });
+                        let hints = fields.named.iter().filter(is_hinted).map(|f| {
+                            let ident = &f.ident;
+                            field_hint(quote! { #ident })
+                        });
+                        quote! { #name::#variant { #(#bindings,)* } => 0 #(+ #hints)* }
+                    }
+                    Fields::Unnamed(ref fields) => {
+                        let bindings = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                            if is_hinted(&f) {
+                                let binding = Ident::new(&format!("_{}", i), f.span());
+                                quote! { #binding }
+                            } else {
+                                quote! { _ }
+                            }
+                        });
+                        let hints = fields
+                            .unnamed
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, f)| is_hinted(f))
+                            .map(|(i, f)| {
+                                let binding = Ident::new(&format!("_{}", i), f.span());
+                                field_hint(quote! { #binding })
+                            });
+                        quote! { #name::#variant(#(#bindings,)*) => 0 #(+ #hints)* }
+                    }
+                    Fields::Unit => quote! { #name::#variant => 0 },
+                }
+            });
+            quote! {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+        Data::Union(_) => unreachable!(),
+    };
+
+    Ok(quote! {
+        impl #impl_generics #rkyv_path::size_hint::ArchivedSizeHint for #name #ty_generics #size_hint_where {
+            #[inline]
+            fn out_of_line_size_hint(&self) -> usize {
+                #body
+            }
+        }
+    })
+}
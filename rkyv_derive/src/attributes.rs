@@ -1,6 +1,6 @@
 use crate::repr::Repr;
 use quote::ToTokens;
-use syn::{AttrStyle, DeriveInput, Error, Ident, Lit, LitStr, Meta, NestedMeta, Path};
+use syn::{AttrStyle, DeriveInput, Error, Expr, Ident, Lit, LitStr, Meta, NestedMeta, Path};
 
 #[derive(Default)]
 pub struct Attributes {
@@ -13,10 +13,22 @@ pub struct Attributes {
     pub archive_bound: Option<LitStr>,
     pub serialize_bound: Option<LitStr>,
     pub deserialize_bound: Option<LitStr>,
+    pub copy: Option<Path>,
     pub copy_safe: Option<Path>,
+    pub freeze: Option<Path>,
+    pub size_hint: Option<Path>,
+    pub niche: Option<Expr>,
+    pub tag_position: Option<LitStr>,
     pub rkyv_path: Option<Path>,
 }
 
+impl Attributes {
+    // Returns whether the tag of an archived enum is placed after its fields
+    pub fn trailing_tag(&self) -> bool {
+        matches!(self.tag_position, Some(ref lit) if lit.value() == "trailing")
+    }
+}
+
 fn try_set_attribute<T: ToTokens>(
     attribute: &mut Option<T>,
     value: T,
@@ -36,8 +48,14 @@ fn try_set_attribute<T: ToTokens>(
 fn parse_archive_attributes(attributes: &mut Attributes, meta: &Meta) -> Result<(), Error> {
     match meta {
         Meta::Path(path) => {
-            if path.is_ident("copy_safe") {
+            if path.is_ident("copy") {
+                try_set_attribute(&mut attributes.copy, path.clone(), "copy")
+            } else if path.is_ident("copy_safe") {
                 try_set_attribute(&mut attributes.copy_safe, path.clone(), "copy_safe")
+            } else if path.is_ident("freeze") {
+                try_set_attribute(&mut attributes.freeze, path.clone(), "freeze")
+            } else if path.is_ident("size_hint") {
+                try_set_attribute(&mut attributes.size_hint, path.clone(), "size_hint")
             } else {
                 Err(Error::new_spanned(meta, "unrecognized archive argument"))
             }
@@ -140,6 +158,31 @@ fn parse_archive_attributes(attributes: &mut Attributes, meta: &Meta) -> Result<
                 } else {
                     Err(Error::new_spanned(meta, "archive as must be a string"))
                 }
+            } else if meta.path.is_ident("niche") {
+                if let Lit::Str(ref lit_str) = meta.lit {
+                    let stream = syn::parse_str(&lit_str.value())?;
+                    let tokens = crate::serde::respan::respan(stream, lit_str.span());
+                    let expr = syn::parse2(tokens)?;
+                    try_set_attribute(&mut attributes.niche, expr, "niche")
+                } else {
+                    Err(Error::new_spanned(meta, "niche must be a string"))
+                }
+            } else if meta.path.is_ident("tag_position") {
+                if let Lit::Str(ref lit_str) = meta.lit {
+                    match lit_str.value().as_str() {
+                        "leading" | "trailing" => try_set_attribute(
+                            &mut attributes.tag_position,
+                            lit_str.clone(),
+                            "tag_position",
+                        ),
+                        _ => Err(Error::new_spanned(
+                            lit_str,
+                            "tag_position must be either \"leading\" or \"trailing\"",
+                        )),
+                    }
+                } else {
+                    Err(Error::new_spanned(meta, "tag_position must be a string"))
+                }
             } else if meta.path.is_ident("crate") {
                 if let Lit::Str(ref lit_str) = meta.lit {
                     let stream = syn::parse_str(&lit_str.value())?;
@@ -1,6 +1,6 @@
 use crate::{
     attributes::{parse_attributes, Attributes},
-    util::add_bounds,
+    util::{add_bounds, strip_raw},
     with::{make_with_ty, with_inner},
 };
 use proc_macro2::TokenStream;
@@ -19,6 +19,13 @@ fn derive_deserialize_impl(
     mut input: DeriveInput,
     attributes: &Attributes,
 ) -> Result<TokenStream, Error> {
+    if let Some(ref freeze) = attributes.freeze {
+        return Err(Error::new_spanned(
+            freeze,
+            "Deserialize cannot be derived for frozen types",
+        ));
+    }
+
     let where_clause = input.generics.make_where_clause();
     if let Some(ref bounds) = attributes.archive_bound {
         add_bounds(bounds, where_clause)?;
@@ -71,6 +78,7 @@ fn derive_deserialize_impl(
                     let ty = with_ty(f);
                     let value = with_inner(
                         f,
+                        rkyv_path,
                         parse_quote! {
                             Deserialize::<#ty, __D>::deserialize(
                                 &self.#name,
@@ -113,6 +121,7 @@ fn derive_deserialize_impl(
                     let ty = with_ty(f);
                     let value = with_inner(
                         f,
+                        rkyv_path,
                         parse_quote! {
                             Deserialize::<#ty, __D>::deserialize(
                                 &self.#index,
@@ -181,6 +190,23 @@ fn derive_deserialize_impl(
                 }
             }
 
+            // Enums with a trailing tag are matched on through their generated reference enum
+            let trailing_tag = attributes.trailing_tag()
+                && data
+                    .variants
+                    .iter()
+                    .any(|v| !matches!(v.fields, Fields::Unit));
+            let (archived, variant_path) = if trailing_tag {
+                let archived_name = attributes
+                    .archived
+                    .as_ref()
+                    .map_or_else(|| format!("Archived{}", strip_raw(name)), strip_raw);
+                let ref_name = Ident::new(&format!("{}Ref", archived_name), name.span());
+                (quote! { self.as_ref() }, quote! { #ref_name })
+            } else {
+                (quote! { self }, quote! { Self })
+            };
+
             let deserialize_variants = data.variants.iter().map(|v| {
                 let variant = &v.ident;
                 match v.fields {
@@ -194,6 +220,7 @@ fn derive_deserialize_impl(
                             let ty = with_ty(f);
                             let value = with_inner(
                                 f,
+                                rkyv_path,
                                 parse_quote! {
                                     Deserialize::<#ty, __D>::deserialize(
                                         #name,
@@ -204,7 +231,7 @@ fn derive_deserialize_impl(
                             quote! { #name: #value }
                         });
                         quote_spanned! { variant.span() =>
-                            Self::#variant { #(#bindings,)* } => #name::#variant { #(#fields,)* }
+                            #variant_path::#variant { #(#bindings,)* } => #name::#variant { #(#fields,)* }
                         }
                     }
                     Fields::Unnamed(ref fields) => {
@@ -217,6 +244,7 @@ fn derive_deserialize_impl(
                             let ty = with_ty(f);
                             let value = with_inner(
                                 f,
+                                rkyv_path,
                                 parse_quote! {
                                     Deserialize::<#ty, __D>::deserialize(
                                         #binding,
@@ -227,11 +255,11 @@ fn derive_deserialize_impl(
                             quote! { #value }
                         });
                         quote_spanned! { variant.span() =>
-                            Self::#variant( #(#bindings,)* ) => #name::#variant(#(#fields,)*)
+                            #variant_path::#variant( #(#bindings,)* ) => #name::#variant(#(#fields,)*)
                         }
                     }
                     Fields::Unit => {
-                        quote_spanned! { name.span() => Self::#variant => #name::#variant }
+                        quote_spanned! { name.span() => #variant_path::#variant => #name::#variant }
                     }
                 }
             });
@@ -240,7 +268,7 @@ fn derive_deserialize_impl(
                 impl #impl_generics Deserialize<#name #ty_generics, __D> for Archived<#name #ty_generics> #deserialize_where {
                     #[inline]
                     fn deserialize(&self, deserializer: &mut __D) -> ::core::result::Result<#name #ty_generics, __D::Error> {
-                        Ok(match self {
+                        Ok(match #archived {
                             #(#deserialize_variants,)*
                         })
                     }
@@ -43,8 +43,24 @@ use syn::{parse_macro_input, DeriveInput};
 ///   recursive type definitions. Use `archive = "..."` to specify `Archive` bounds,
 ///   `serialize = "..."` to specify `Serialize` bounds, and `deserialize = "..."` to specify
 ///   `Deserialize` bounds.
+/// - `copy`: Implements `Clone` and `Copy` for the archived type when it doesn't contain any
+///   relative pointers. See "Copying archived types" below.
 /// - `copy_safe`: States that the archived type is tightly packed with no padding bytes. This
 ///   qualifies it for copy optimizations. (requires nightly)
+/// - `freeze`: States that the archived type is a read-only view that should only ever be accessed
+///   in place. Deriving `Deserialize` for a frozen type is a compile error, which prevents
+///   accidentally making expensive copies of it.
+/// - `size_hint`: Implements `ArchivedSizeHint` for the type, which estimates how many bytes it
+///   will archive to. The estimate sums the size of the archived type and the length-based size
+///   hints of its fields, and is only a hint: it may be larger or smaller than the actual archived
+///   size. Fields archived with a wrapper are not included.
+/// - `niche = "..."`: Implements `ArchiveNiche` for a struct with a single field, using the given
+///   expression as a sentinel value of the field. An `Option` of the type archived with the
+///   `Sentinel` wrapper stores `None` as the sentinel value instead of a tag (i.e.
+///   `#[archive(niche = "u32::MAX")]`).
+/// - `tag_position = "..."`: Chooses whether the tag of an archived enum is placed before
+///   (`"leading"`, the default) or after (`"trailing"`) the fields of its variants. See "Trailing
+///   enum tags" below.
 /// - `as = "..."`: Instead of generating a separate archived type, this type will archive as the
 ///   named type. This is useful for types which are generic over their parameters.
 /// - `crate = "..."`: Chooses an alternative crate path to import rkyv from.
@@ -53,6 +69,21 @@ use syn::{parse_macro_input, DeriveInput};
 /// type. This is commonly used with attributes like `derive(...)` to derive trait implementations
 /// for the archived type.
 ///
+/// # Copying archived types
+///
+/// Archived types that don't contain any relative pointers (for example, structs and enums made up
+/// of only integers, floats, arrays, and other pointer-free types) are plain data. Adding
+/// `#[archive(copy)]` implements `Clone` and `Copy` for them, which allows small archived values to
+/// be copied out of a buffer by value. Archived types that contain relative pointers (like
+/// `ArchivedString` and `ArchivedVec`) are never `Clone` or `Copy`, since moving a relative pointer
+/// to a new location would break its offset. The implementations are bounded on every archived
+/// field type being `Copy`, so generic archived types are only `Clone` and `Copy` when their
+/// archived fields are.
+///
+/// `copy` may not be used with `#[omit_bounds]` fields, `as = "..."`, or trailing enum tags. If
+/// `archive_attr(derive(...))` derives `Clone` or `Copy` for the archived type, that derive is used
+/// instead of the generated implementation.
+///
 /// # Recursive types
 ///
 /// This derive macro automatically adds a type bound `field: Archive` for each field type. This can
@@ -62,6 +93,30 @@ use syn::{parse_macro_input, DeriveInput};
 /// allow recursive structures. This may be too coarse for some types, in which case additional type
 /// bounds may be required with `bound(...)`.
 ///
+/// Recursive enums like an expression tree with `Box<Expr>` fields are archived with each `Box` as a
+/// relative pointer to its archived contents. The archived enum has the same variants as the
+/// original, so an archived tree can be matched on and walked recursively without deserializing.
+/// Since the omitted bounds usually include the serializer bounds that `Box` requires, recursive
+/// types typically also need `#[archive(bound(serialize = "__S: Serializer"))]`.
+///
+/// # Checking fields on access
+///
+/// Adding the attribute `#[archive(check_on_access)]` to a named field of a struct generates an
+/// `unsafe fn try_field(ptr: *const Self)` method on the archived type. This method validates the
+/// archived field in place and returns a `Result` instead of the field reference, which gives lazy
+/// validation for only the fields that are actually read from an archive. The method takes a
+/// pointer to the archived struct instead of a reference because the rest of the struct may not
+/// be valid: the pointer only has to point to enough readable bytes, and the field is located
+/// without reading any other part of the struct. The field is checked in isolation with a `()`
+/// context, so only self-contained types (e.g. bools, ranges, and other types without relative
+/// pointers) can be checked on access. Fields that point elsewhere in the archive still require
+/// whole-archive validation.
+///
+/// `check_on_access` does not change the archived type or its `CheckBytes` implementation. If the
+/// archive has already been validated with `check_archived_root`, the field is valid and can be
+/// accessed directly; `try_field()` will repeat the same check. This requires the `validation`
+/// feature.
+///
 /// # Wrappers
 ///
 /// Wrappers transparently customize archived types by providing different implementations of core
@@ -69,6 +124,10 @@ use syn::{parse_macro_input, DeriveInput};
 /// reference as if it were a field of the struct. Wrappers can be applied to fields using the
 /// `#[with(...)]` attribute. Mutliple wrappers can be used, and they are applied in reverse order
 /// (i.e. `#[with(A, B, C)]` will archive `MyType` as `With<With<With<MyType, C>, B, A>`).
+///
+/// Some wrappers also have a shorthand field attribute: `#[archive(bytes)]` archives a field with
+/// `AsBytes`, `#[archive(preserve_capacity)]` archives a `Vec` field with `PreserveCapacity`, and
+/// `#[archive(inline(capacity = N))]` archives a `Vec` field inline with `AsInlineVec<N>`.
 #[proc_macro_derive(Archive, attributes(archive, archive_attr, omit_bounds, with))]
 pub fn derive_archive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let mut derive_input = parse_macro_input!(input as DeriveInput);
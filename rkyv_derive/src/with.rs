@@ -1,22 +1,143 @@
-use syn::{parse_quote, Expr, Field, Meta, NestedMeta, Path, Type};
+use syn::{
+    parse_quote, punctuated::Punctuated, Error, Expr, Field, Lit, LitInt, Meta, NestedMeta, Path,
+    Token, Type,
+};
+
+// The `#[archive(...)]` field arguments that are shorthand for a wrapper
+const BYTES: &str = "bytes";
+const PRESERVE_CAPACITY: &str = "preserve_capacity";
+const INLINE: &str = "inline";
+
+/// Returns whether an `#[archive(...)]` field argument is shorthand for a wrapper.
+pub fn is_wrapper_arg(nested: &NestedMeta) -> bool {
+    match nested {
+        NestedMeta::Meta(Meta::Path(path)) => {
+            path.is_ident(BYTES) || path.is_ident(PRESERVE_CAPACITY) || path.is_ident(INLINE)
+        }
+        NestedMeta::Meta(Meta::List(list)) => list.path.is_ident(INLINE),
+        _ => false,
+    }
+}
+
+/// Returns whether a field is marked with `#[archive(<flag>)]`.
+#[inline]
+fn has_archive_flag(field: &Field, flag: &str) -> bool {
+    field.attrs.iter().any(|attr| match attr.parse_meta() {
+        Ok(Meta::List(list)) if list.path.is_ident("archive") => list.nested.iter().any(
+            |nested| matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident(flag)),
+        ),
+        _ => false,
+    })
+}
+
+/// Returns whether a field is marked with `#[archive(bytes)]`.
+#[inline]
+pub fn is_bytes(field: &Field) -> bool {
+    has_archive_flag(field, BYTES)
+}
+
+/// Returns whether a field is marked with `#[archive(preserve_capacity)]`.
+#[inline]
+pub fn is_preserve_capacity(field: &Field) -> bool {
+    has_archive_flag(field, PRESERVE_CAPACITY)
+}
+
+/// Returns the capacity of a field marked with `#[archive(inline(capacity = ...))]`.
+pub fn inline_capacity(field: &Field) -> Result<Option<LitInt>, Error> {
+    let mut result = None;
+    for attr in field.attrs.iter() {
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) if list.path.is_ident("archive") => list,
+            _ => continue,
+        };
+        for nested in list.nested.iter() {
+            let inline = match nested {
+                NestedMeta::Meta(Meta::List(inline)) if inline.path.is_ident(INLINE) => inline,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident(INLINE) => {
+                    return Err(Error::new_spanned(
+                        path,
+                        "inline requires a capacity, e.g. inline(capacity = 8)",
+                    ))
+                }
+                _ => continue,
+            };
+            if result.is_some() {
+                return Err(Error::new_spanned(inline, "inline already specified"));
+            }
+            for arg in inline.nested.iter() {
+                match arg {
+                    NestedMeta::Meta(Meta::NameValue(name_value))
+                        if name_value.path.is_ident("capacity") =>
+                    {
+                        if result.is_some() {
+                            return Err(Error::new_spanned(arg, "capacity already specified"));
+                        }
+                        if let Lit::Int(ref capacity) = name_value.lit {
+                            result = Some(capacity.clone());
+                        } else {
+                            return Err(Error::new_spanned(
+                                &name_value.lit,
+                                "capacity must be an integer",
+                            ));
+                        }
+                    }
+                    _ => {
+                        return Err(Error::new_spanned(
+                            arg,
+                            "unrecognized inline argument, the only argument is capacity",
+                        ))
+                    }
+                }
+            }
+            if result.is_none() {
+                return Err(Error::new_spanned(
+                    inline,
+                    "inline requires a capacity, e.g. inline(capacity = 8)",
+                ));
+            }
+        }
+    }
+    Ok(result)
+}
 
 #[inline]
-pub fn with<B, F: FnMut(B, NestedMeta) -> B>(field: &Field, init: B, f: F) -> B {
+pub fn with<B, F: FnMut(B, Type) -> B>(field: &Field, rkyv_path: &Path, init: B, f: F) -> B {
+    // `#[archive(bytes)]` is shorthand for the innermost wrapper being `AsBytes`
+    let bytes = if is_bytes(field) {
+        Some(parse_quote! { #rkyv_path::with::AsBytes })
+    } else {
+        None
+    };
+    // `#[archive(preserve_capacity)]` is shorthand for the innermost wrapper being
+    // `PreserveCapacity`
+    let preserve_capacity = if is_preserve_capacity(field) {
+        Some(parse_quote! { #rkyv_path::with::PreserveCapacity })
+    } else {
+        None
+    };
+    // `#[archive(inline(capacity = N))]` is shorthand for the innermost wrapper being
+    // `AsInlineVec<N>`
+    let inline = inline_capacity(field)
+        .ok()
+        .flatten()
+        .map(|capacity| parse_quote! { #rkyv_path::with::AsInlineVec<#capacity> });
+
     field
         .attrs
         .iter()
         .filter_map(|attr| {
-            if let Ok(Meta::List(list)) = attr.parse_meta() {
-                if list.path.is_ident("with") {
-                    Some(list.nested)
-                } else {
-                    None
-                }
+            if attr.path.is_ident("with") {
+                // Wrappers are parsed as types so that generic wrappers can be used
+                attr.parse_args_with(Punctuated::<Type, Token![,]>::parse_terminated)
+                    .ok()
             } else {
                 None
             }
         })
         .flatten()
+        .chain(bytes)
+        .chain(preserve_capacity)
+        .chain(inline)
         .rev()
         .fold(init, f)
 }
@@ -26,6 +147,7 @@ pub fn make_with_ty(rkyv_path: &Path) -> impl '_ + Fn(&Field) -> Type {
     move |field| {
         with(
             field,
+            rkyv_path,
             field.ty.clone(),
             |ty, wrapper| parse_quote! { #rkyv_path::with::With<#ty, #wrapper> },
         )
@@ -37,6 +159,7 @@ pub fn make_with_cast(rkyv_path: &Path) -> impl '_ + Fn(&Field, Expr) -> Expr {
     move |field, expr| {
         with(
             field,
+            rkyv_path,
             expr,
             |expr, wrapper| parse_quote! { #rkyv_path::with::With::<_, #wrapper>::cast(#expr) },
         )
@@ -44,6 +167,11 @@ pub fn make_with_cast(rkyv_path: &Path) -> impl '_ + Fn(&Field, Expr) -> Expr {
 }
 
 #[inline]
-pub fn with_inner(field: &Field, expr: Expr) -> Expr {
-    with(field, expr, |expr, _| parse_quote! { #expr.into_inner() })
+pub fn with_inner(field: &Field, rkyv_path: &Path, expr: Expr) -> Expr {
+    with(
+        field,
+        rkyv_path,
+        expr,
+        |expr, _| parse_quote! { #expr.into_inner() },
+    )
 }
@@ -35,6 +35,7 @@ use ptr_meta::{DynMetadata, Pointee};
 #[cfg(feature = "vtable_cache")]
 use rkyv::with::{Atomic, With};
 use rkyv::{
+    de::AllocationBudget,
     from_archived,
     ser::{ScratchSpace, Serializer},
     to_archived, Archived, Fallible, Serialize,
@@ -171,6 +172,22 @@ fn hash_type<T: TypeName + ?Sized>() -> u64 {
 /// are boxed as regular trait objects. This is because your deserialized values have to implement
 /// `SerializeDyn` but your archived values do not.
 ///
+/// # Shared trait objects
+///
+/// Serialize trait objects can also be shared with `Rc<dyn SerializeTrait>` and
+/// `Arc<dyn SerializeTrait>`. No extra registration is needed beyond the `#[archive_dyn]` impls:
+/// the serializer's shared registry deduplicates the pointers, and the trait object registry
+/// dispatches calls on the archived values. Each shared object is archived once, the first time it
+/// is serialized, and every other pointer to the same object points to that archived value. Like
+/// boxed trait objects, each archived pointer stores the type ID of the object in its metadata so
+/// that it can be dispatched on its own.
+///
+/// Serializing shared trait objects requires a serializer that implements
+/// `SharedSerializeRegistry` (such as `AllocSerializer`), and deserializing them requires a
+/// deserializer that implements `SharedDeserializeRegistry` (such as `SharedDeserializeMap`).
+/// Sharing is preserved when deserializing: pointers that shared an archived object will share a
+/// deserialized object.
+///
 /// # Examples
 ///
 /// See [`archive_dyn`](macro@archive_dyn) for customization options.
@@ -284,6 +301,8 @@ impl<'a> Fallible for dyn DynDeserializer + 'a {
     type Error = DynError;
 }
 
+impl<'a> AllocationBudget for dyn DynDeserializer + 'a {}
+
 impl<D: Fallible + ?Sized> DynDeserializer for &mut D {}
 
 /// A trait object that can be deserialized.
@@ -320,6 +339,19 @@ pub struct ArchivedDynMetadata<T: ?Sized> {
     phantom: PhantomData<T>,
 }
 
+impl<T: ?Sized> Default for ArchivedDynMetadata<T> {
+    /// Returns metadata that does not refer to any impl.
+    ///
+    /// This is the metadata of null trait object pointers, for example the archived `None` of an
+    /// `Option<Box<dyn Trait>>` archived with [`Niche`](rkyv::with::Niche). It must not be used to
+    /// look up a vtable.
+    #[inline]
+    fn default() -> Self {
+        // Safety: all-zero bytes are a valid type ID and an empty vtable cache
+        unsafe { core::mem::zeroed() }
+    }
+}
+
 impl<T: TypeName + ?Sized> ArchivedDynMetadata<T> {
     /// Creates a new `ArchivedDynMetadata` for the given type.
     ///
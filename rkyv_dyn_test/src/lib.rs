@@ -409,4 +409,176 @@ mod tests {
         value.as_mut().get_pin_mut().set_value(64);
         assert_eq!(value.value(), 64);
     }
+
+    #[test]
+    #[cfg(not(feature = "wasm"))]
+    fn shared_dyn() {
+        use rkyv::de::deserializers::SharedDeserializeMap;
+        use std::sync::Arc;
+
+        #[archive_dyn(deserialize)]
+        trait Component {
+            fn name(&self) -> String;
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[archive_attr(derive(TypeName))]
+        struct Position(i32, i32);
+
+        #[archive_dyn(deserialize)]
+        impl Component for Position {
+            fn name(&self) -> String {
+                format!("position ({}, {})", self.0, self.1)
+            }
+        }
+
+        impl Component for Archived<Position> {
+            fn name(&self) -> String {
+                format!("position ({}, {})", self.0, self.1)
+            }
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[archive_attr(derive(TypeName))]
+        struct Label(String);
+
+        #[archive_dyn(deserialize)]
+        impl Component for Label {
+            fn name(&self) -> String {
+                format!("label {}", self.0)
+            }
+        }
+
+        impl Component for Archived<Label> {
+            fn name(&self) -> String {
+                format!("label {}", self.0)
+            }
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Entities {
+            components: Vec<Arc<dyn SerializeComponent>>,
+        }
+
+        let position: Arc<dyn SerializeComponent> = Arc::new(Position(1, 2));
+        let label: Arc<dyn SerializeComponent> =
+            Arc::new(Label("a label long enough to be out of line".to_string()));
+        let value = Entities {
+            components: vec![position.clone(), label.clone(), position.clone(), label],
+        };
+
+        let mut serializer = AllocSerializer::<256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Entities>(buf.as_ref()) };
+
+        // Shared components are archived once and dispatch to the archived impl
+        let components = &archived.components;
+        assert_eq!(components[0].name(), "position (1, 2)");
+        assert_eq!(
+            components[1].name(),
+            "label a label long enough to be out of line"
+        );
+        assert!(core::ptr::eq(&*components[0], &*components[2]));
+        assert!(core::ptr::eq(&*components[1], &*components[3]));
+        assert!(!core::ptr::eq(&*components[0], &*components[1]));
+
+        // Deserializing preserves the sharing between pointers
+        let deserialized: Entities = archived
+            .deserialize(&mut SharedDeserializeMap::new())
+            .unwrap();
+        let components = &deserialized.components;
+        assert_eq!(components[0].name(), "position (1, 2)");
+        assert!(Arc::ptr_eq(&components[0], &components[2]));
+        assert!(Arc::ptr_eq(&components[1], &components[3]));
+        assert_eq!(Arc::strong_count(&components[0]), 2);
+    }
+
+    #[test]
+    #[cfg(not(feature = "wasm"))]
+    fn optional_dyn() {
+        use core::mem::size_of;
+        use rkyv::with::Niche;
+
+        #[archive_dyn(deserialize)]
+        trait Component {
+            fn name(&self) -> String;
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[archive_attr(derive(TypeName))]
+        struct Health(u32);
+
+        #[archive_dyn(deserialize)]
+        impl Component for Health {
+            fn name(&self) -> String {
+                format!("health {}", self.0)
+            }
+        }
+
+        impl Component for Archived<Health> {
+            fn name(&self) -> String {
+                format!("health {}", self.0)
+            }
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Entity {
+            #[with(Niche)]
+            primary: Option<Box<dyn SerializeComponent>>,
+            #[with(Niche)]
+            secondary: Option<Box<dyn SerializeComponent>>,
+            slots: Vec<Option<Box<dyn SerializeComponent>>>,
+        }
+
+        #[derive(Archive, Serialize)]
+        struct EntityNoNiching {
+            primary: Option<Box<dyn SerializeComponent>>,
+        }
+
+        let value = Entity {
+            primary: Some(Box::new(Health(10))),
+            secondary: None,
+            slots: vec![None, Some(Box::new(Health(20))), None],
+        };
+
+        let mut serializer = AllocSerializer::<256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Entity>(buf.as_ref()) };
+
+        // Niched optional trait objects are as large as a plain trait object pointer
+        assert_eq!(
+            size_of::<Archived<Option<Box<dyn SerializeComponent>>>>(),
+            size_of::<Archived<EntityNoNiching>>()
+        );
+        assert!(
+            size_of::<ArchivedEntity>()
+                < 2 * size_of::<Archived<EntityNoNiching>>()
+                    + size_of::<Archived<Vec<Option<Box<dyn SerializeComponent>>>>>()
+        );
+
+        assert_eq!(
+            archived.primary.as_deref().map(|c| c.name()).as_deref(),
+            Some("health 10")
+        );
+        assert!(archived.secondary.is_none());
+        assert!(archived.secondary.as_deref().is_none());
+        let names = archived
+            .slots
+            .iter()
+            .map(|slot| slot.as_ref().map(|c| c.name()))
+            .collect::<Vec<_>>();
+        assert_eq!(names, [None, Some("health 20".to_string()), None]);
+
+        let deserialized: Entity = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized.primary.unwrap().name(), "health 10");
+        assert!(deserialized.secondary.is_none());
+        let names = deserialized
+            .slots
+            .iter()
+            .map(|slot| slot.as_ref().map(|c| c.name()))
+            .collect::<Vec<_>>();
+        assert_eq!(names, [None, Some("health 20".to_string()), None]);
+    }
 }
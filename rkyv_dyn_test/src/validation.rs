@@ -84,4 +84,87 @@ mod tests {
             panic!("check passed for type that does not implement CheckBytes");
         }
     }
+
+    #[test]
+    #[cfg(not(feature = "wasm"))]
+    fn check_shared_dyn() {
+        use std::sync::Arc;
+
+        #[archive_dyn]
+        pub trait SharedTrait {
+            fn get_id(&self) -> i32;
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes, TypeName))]
+        pub struct SharedTest {
+            id: i32,
+        }
+
+        #[archive_dyn]
+        impl SharedTrait for SharedTest {
+            fn get_id(&self) -> i32 {
+                self.id
+            }
+        }
+
+        impl SharedTrait for Archived<SharedTest> {
+            fn get_id(&self) -> i32 {
+                self.id.into()
+            }
+        }
+
+        let shared: Arc<dyn SerializeSharedTrait> = Arc::new(SharedTest { id: 42 });
+        let value = vec![shared.clone(), Arc::new(SharedTest { id: 7 }), shared];
+
+        serialize_and_check(&value);
+    }
+
+    #[test]
+    #[cfg(not(feature = "wasm"))]
+    fn check_optional_dyn() {
+        use rkyv::with::Niche;
+
+        #[archive_dyn]
+        pub trait SlotTrait {
+            fn get_slot_id(&self) -> i32;
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes, TypeName))]
+        pub struct Item {
+            id: i32,
+        }
+
+        #[archive_dyn]
+        impl SlotTrait for Item {
+            fn get_slot_id(&self) -> i32 {
+                self.id
+            }
+        }
+
+        impl SlotTrait for Archived<Item> {
+            fn get_slot_id(&self) -> i32 {
+                self.id.into()
+            }
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        pub struct Slots {
+            #[with(Niche)]
+            some: Option<Box<dyn SerializeSlotTrait>>,
+            #[with(Niche)]
+            none: Option<Box<dyn SerializeSlotTrait>>,
+            plain: Vec<Option<Box<dyn SerializeSlotTrait>>>,
+        }
+
+        let value = Slots {
+            some: Some(Box::new(Item { id: 1 })),
+            none: None,
+            plain: vec![Some(Box::new(Item { id: 2 })), None],
+        };
+
+        serialize_and_check(&value);
+    }
 }
@@ -139,4 +139,452 @@ mod tests {
         test_archive::<r#virtual>(&r#virtual { r#virtual: 42 });
         test_archive::<r#try>(&r#try::r#try { r#try: 42 });
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_freeze() {
+        use rkyv::{archived_value, ser::Serializer, Archive, Serialize};
+
+        // Frozen types can be archived and accessed, but not derive Deserialize
+        #[derive(Archive, Serialize)]
+        #[archive(freeze)]
+        struct View {
+            a: u32,
+            b: [u8; 4],
+        }
+
+        let mut serializer = DefaultSerializer::default();
+        let pos = serializer
+            .serialize_value(&View {
+                a: 42,
+                b: [1, 2, 3, 4],
+            })
+            .unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_value::<View>(buf.as_ref(), pos) };
+        assert_eq!(archived.a, 42);
+        assert_eq!(archived.b, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_copy() {
+        use rkyv::{archived_value, option::ArchivedOption, ser::Serializer, Archive, Serialize};
+
+        #[derive(Archive, Serialize)]
+        #[archive(copy)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(copy)]
+        enum Shape<T> {
+            Circle { center: Point, radius: T },
+            Rect(Point, Point),
+            Empty,
+        }
+
+        fn assert_clone<T: Clone>(_: &T) {}
+
+        // Pointer-free archived values can be copied out of the buffer by value
+        let copied = {
+            let mut serializer = DefaultSerializer::default();
+            let pos = serializer
+                .serialize_value(&[
+                    Shape::Circle {
+                        center: Point { x: 1, y: 2 },
+                        radius: Some(3u16),
+                    },
+                    Shape::Rect(Point { x: 0, y: 0 }, Point { x: 4, y: 5 }),
+                    Shape::Empty,
+                ])
+                .unwrap();
+            let buf = serializer.into_serializer().into_inner();
+            *unsafe { archived_value::<[Shape<Option<u16>>; 3]>(buf.as_ref(), pos) }
+        };
+        assert_clone(&copied);
+
+        match copied[0] {
+            ArchivedShape::Circle { center, radius } => {
+                assert_eq!(center.x, 1);
+                assert_eq!(center.y, 2);
+                assert!(matches!(radius, ArchivedOption::Some(r) if r == 3));
+            }
+            _ => panic!("expected a circle"),
+        }
+        match copied[1] {
+            ArchivedShape::Rect(a, b) => {
+                assert_eq!(a.x, 0);
+                assert_eq!(a.y, 0);
+                assert_eq!(b.x, 4);
+                assert_eq!(b.y, 5);
+            }
+            _ => panic!("expected a rect"),
+        }
+        assert!(matches!(copied[2], ArchivedShape::Empty));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn serialize_root_info() {
+        use rkyv::{archived_value, ser::Serializer, Archive, Serialize};
+
+        #[derive(Archive, Serialize)]
+        struct Frame {
+            id: u16,
+            payload: [u32; 3],
+        }
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&1u8).unwrap();
+        let info = serializer
+            .serialize_root(&Frame {
+                id: 7,
+                payload: [1, 2, 3],
+            })
+            .unwrap();
+        assert_eq!(info.total_len, serializer.pos());
+        assert_eq!(
+            info.total_len - info.root_pos,
+            core::mem::size_of::<ArchivedFrame>()
+        );
+
+        let buf = serializer.into_serializer().into_inner();
+        let archived =
+            unsafe { archived_value::<Frame>(&buf.as_ref()[..info.total_len], info.root_pos) };
+        assert_eq!(archived.id, 7);
+        assert_eq!(archived.payload, [1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn serialize_value_at() {
+        use rkyv::{
+            archived_value,
+            ser::{SerializeAtError, Serializer},
+            Archive, Archived, Serialize,
+        };
+
+        #[derive(Archive, Serialize)]
+        struct Header {
+            magic: [u8; 4],
+            version: u32,
+        }
+
+        let mut serializer = DefaultSerializer::default();
+        let header_pos = serializer
+            .serialize_value_at(
+                0,
+                &Header {
+                    magic: *b"RKYV",
+                    version: 7,
+                },
+            )
+            .unwrap();
+        // Offsets must be aligned for the archived object
+        match serializer.serialize_value_at(100, &[1u64, 2, 3]) {
+            Err(SerializeAtError::Misaligned { offset, align }) => {
+                assert_eq!(offset, 100);
+                assert_eq!(align, core::mem::align_of::<Archived<u64>>());
+            }
+            _ => panic!("expected a misaligned error"),
+        }
+        assert_eq!(serializer.pos(), 8);
+        // Padding of more than one chunk
+        let table_pos = serializer.serialize_value_at(104, &[1u64, 2, 3]).unwrap();
+        // Archiving at the current position doesn't pad
+        let end = serializer.pos();
+        let tail_pos = serializer.serialize_value_at(end, &9u32).unwrap();
+
+        assert_eq!(header_pos, 0);
+        assert_eq!(table_pos, 104);
+        assert_eq!(tail_pos, end);
+
+        match serializer.serialize_value_at(64, &0u8) {
+            Err(SerializeAtError::PastOffset { offset, pos }) => {
+                assert_eq!(offset, 64);
+                assert_eq!(pos, end + 4);
+            }
+            _ => panic!("expected a past offset error"),
+        }
+        assert_eq!(serializer.pos(), end + 4);
+
+        let buf = serializer.into_serializer().into_inner();
+        let buf = buf.as_ref();
+        assert!(buf[8..104].iter().all(|&b| b == 0));
+        let header = unsafe { archived_value::<Header>(buf, header_pos) };
+        assert_eq!(header.magic, *b"RKYV");
+        assert_eq!(header.version, 7);
+        let table = unsafe { archived_value::<[u64; 3]>(buf, table_pos) };
+        assert_eq!(table[2], 3);
+        let tail = unsafe { archived_value::<u32>(buf, tail_pos) };
+        assert_eq!(*tail, 9);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_sentinel() {
+        use core::mem::size_of;
+        use rkyv::{
+            archived_root,
+            ser::{serializers::CompositeSerializerError, Serializer},
+            with::{Sentinel, SentinelError},
+            Archive, Archived, Deserialize, Infallible, Serialize,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+        #[archive(niche = "u32::MAX")]
+        struct NodeIndex(u32);
+
+        #[derive(Archive, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+        #[archive(niche = "-1")]
+        struct Offset {
+            value: i16,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Node {
+            #[with(Sentinel)]
+            parent: Option<NodeIndex>,
+            #[with(Sentinel)]
+            offset: Option<Offset>,
+        }
+
+        assert_eq!(
+            size_of::<Archived<Node>>(),
+            size_of::<Archived<u32>>() + size_of::<Archived<i16>>() + 2
+        );
+
+        for value in [
+            Node {
+                parent: Some(NodeIndex(42)),
+                offset: Some(Offset { value: 0 }),
+            },
+            Node {
+                parent: None,
+                offset: None,
+            },
+            Node {
+                parent: Some(NodeIndex(0)),
+                offset: None,
+            },
+        ] {
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(&value).unwrap();
+            let end = serializer.pos();
+            let buf = serializer.into_serializer().into_inner();
+            let archived = unsafe { archived_root::<Node>(&buf[0..end]) };
+
+            assert_eq!(archived.parent.is_some(), value.parent.is_some());
+            assert_eq!(archived.offset.is_none(), value.offset.is_none());
+            assert_eq!(
+                archived.parent.as_ref().map(|p| p.0),
+                value.parent.map(|p| Archived::<u32>::from(p.0))
+            );
+
+            let deserialized: Node = archived.deserialize(&mut Infallible).unwrap();
+            assert_eq!(deserialized, value);
+        }
+
+        // A `Some` containing the sentinel value can't be told apart from `None`, so it fails to
+        // serialize
+        for value in [
+            Node {
+                parent: Some(NodeIndex(u32::MAX)),
+                offset: None,
+            },
+            Node {
+                parent: None,
+                offset: Some(Offset { value: -1 }),
+            },
+        ] {
+            let mut serializer = DefaultSerializer::default();
+            assert!(matches!(
+                serializer.serialize_value(&value),
+                Err(CompositeSerializerError::SentinelError(
+                    SentinelError::Collision
+                ))
+            ));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_header() {
+        use core::mem::size_of;
+        use rkyv::{
+            archived_value,
+            header::{archived_root_with_header, ArchiveHeader, Endianness, HeaderError},
+            ser::Serializer,
+            AlignedBytes, FixedUsize,
+        };
+
+        let header = ArchiveHeader::new(0x0102_0304);
+        assert_eq!(header.format_version(), ArchiveHeader::FORMAT_VERSION);
+        assert_eq!(header.endianness(), Endianness::ARCHIVE);
+        assert_eq!(header.is_strict(), cfg!(feature = "strict"));
+        assert_eq!(header.pointer_width() as usize, size_of::<FixedUsize>());
+        assert_eq!(header.root_pos(), 0x0102_0304);
+
+        // The layout of the header doesn't depend on the features rkyv was built with
+        let bytes = header.to_bytes();
+        assert_eq!(bytes.len(), ArchiveHeader::LEN);
+        assert_eq!(&bytes[0..4], b"rkyv");
+        assert_eq!(&bytes[4..6], &ArchiveHeader::FORMAT_VERSION.to_le_bytes());
+        assert_eq!(
+            bytes[6],
+            (Endianness::ARCHIVE == Endianness::Big) as u8 | (cfg!(feature = "strict") as u8) << 1
+        );
+        assert_eq!(bytes[7] as usize, size_of::<FixedUsize>());
+        assert_eq!(&bytes[8..16], &[4, 3, 2, 1, 0, 0, 0, 0]);
+        assert_eq!(ArchiveHeader::from_bytes(&bytes), Ok(header));
+        assert_eq!(header.check_compatible(), Ok(()));
+
+        assert_eq!(
+            ArchiveHeader::from_bytes(&bytes[..15]),
+            Err(HeaderError::TooShort(15))
+        );
+
+        let mut invalid = bytes;
+        invalid[0] = b'R';
+        assert_eq!(
+            ArchiveHeader::from_bytes(&invalid),
+            Err(HeaderError::InvalidMagic(*b"Rkyv"))
+        );
+
+        // Headers from incompatible builds can still be decoded
+        let mut invalid = bytes;
+        invalid[4..6].copy_from_slice(&2u16.to_le_bytes());
+        let decoded = ArchiveHeader::from_bytes(&invalid).unwrap();
+        assert_eq!(decoded.format_version(), 2);
+        assert_eq!(
+            decoded.check_compatible(),
+            Err(HeaderError::UnsupportedVersion(2))
+        );
+
+        let mut invalid = bytes;
+        invalid[6] ^= 0b01;
+        let other_endianness = match Endianness::ARCHIVE {
+            Endianness::Little => Endianness::Big,
+            Endianness::Big => Endianness::Little,
+        };
+        assert_eq!(
+            ArchiveHeader::from_bytes(&invalid)
+                .unwrap()
+                .check_compatible(),
+            Err(HeaderError::EndiannessMismatch(other_endianness))
+        );
+
+        let mut invalid = bytes;
+        invalid[6] ^= 0b10;
+        assert_eq!(
+            ArchiveHeader::from_bytes(&invalid)
+                .unwrap()
+                .check_compatible(),
+            Err(HeaderError::StrictMismatch(!cfg!(feature = "strict")))
+        );
+
+        let mut invalid = bytes;
+        invalid[7] = 3;
+        assert_eq!(
+            ArchiveHeader::from_bytes(&invalid)
+                .unwrap()
+                .check_compatible(),
+            Err(HeaderError::PointerWidthMismatch(3))
+        );
+
+        let mut invalid = bytes;
+        invalid[6] |= 0b1000_0000;
+        assert_eq!(
+            ArchiveHeader::split(&invalid),
+            Err(HeaderError::UnknownFlags(invalid[6]))
+        );
+
+        assert_eq!(
+            ArchiveHeader::split(&bytes),
+            Err(HeaderError::RootOutOfBounds {
+                root_pos: 0x0102_0304,
+                len: 0,
+            })
+        );
+
+        // Write a header followed by an archive into one buffer
+        let mut buf = AlignedBytes([0u8; 256]);
+        let mut serializer = DefaultSerializer::default();
+        let pos = serializer.serialize_value(&[1i32, 2, 3, 4]).unwrap();
+        let end = serializer.pos();
+        let archive = serializer.into_serializer().into_inner();
+        buf[..ArchiveHeader::LEN].copy_from_slice(&ArchiveHeader::new(pos).to_bytes());
+        buf[ArchiveHeader::LEN..ArchiveHeader::LEN + end].copy_from_slice(&archive[..end]);
+        let bytes = &buf[..ArchiveHeader::LEN + end];
+
+        let (header, archive) = ArchiveHeader::split(bytes).unwrap();
+        assert_eq!(header.root_pos(), pos);
+        assert_eq!(archive.len(), end);
+        let archived = unsafe { archived_value::<[i32; 4]>(archive, header.root_pos()) };
+        assert_eq!(archived, &[1, 2, 3, 4]);
+
+        let archived = unsafe { archived_root_with_header::<[i32; 4]>(bytes).unwrap() };
+        assert_eq!(archived, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_header_checksum() {
+        use rkyv::{
+            header::{archived_root_with_header, ArchiveHeader, HeaderError},
+            ser::Serializer,
+            AlignedBytes,
+        };
+
+        let header = ArchiveHeader::new(0).with_checksum();
+        assert!(header.has_checksum());
+        assert!(!ArchiveHeader::new(0).has_checksum());
+        assert_eq!(header.to_bytes()[6] & 0b100, 0b100);
+        assert_eq!(ArchiveHeader::from_bytes(&header.to_bytes()), Ok(header));
+
+        // The checksum covers the header as well as the archive
+        assert_ne!(header.checksum(&[]), ArchiveHeader::new(0).checksum(&[]));
+        assert_ne!(header.checksum(&[0]), header.checksum(&[1]));
+
+        // Write a header, an archive, and its checksum into one buffer
+        let mut buf = AlignedBytes([0u8; 256]);
+        let mut serializer = DefaultSerializer::default();
+        let pos = serializer.serialize_value(&[1i32, 2, 3, 4]).unwrap();
+        let end = serializer.pos();
+        let archive = serializer.into_serializer().into_inner();
+        let header = ArchiveHeader::new(pos).with_checksum();
+        let checksum = header.checksum(&archive[..end]);
+        let len = ArchiveHeader::LEN + end + ArchiveHeader::CHECKSUM_LEN;
+        buf[..ArchiveHeader::LEN].copy_from_slice(&header.to_bytes());
+        buf[ArchiveHeader::LEN..ArchiveHeader::LEN + end].copy_from_slice(&archive[..end]);
+        buf[ArchiveHeader::LEN + end..len].copy_from_slice(&checksum.to_le_bytes());
+
+        // The checksum isn't part of the archive
+        let (split_header, split_archive) = ArchiveHeader::split(&buf[..len]).unwrap();
+        assert_eq!(split_header, header);
+        assert_eq!(split_archive, &archive[..end]);
+        let archived = unsafe { archived_root_with_header::<[i32; 4]>(&buf[..len]).unwrap() };
+        assert_eq!(archived, &[1, 2, 3, 4]);
+
+        // Flipping a bit anywhere is caught, including in the header and the checksum
+        for i in 0..len {
+            let mut corrupted = AlignedBytes([0u8; 256]);
+            corrupted[..len].copy_from_slice(&buf[..len]);
+            corrupted[i] ^= 0b10;
+            match ArchiveHeader::split(&corrupted[..len]) {
+                Err(HeaderError::ChecksumMismatch { .. }) => (),
+                // The magic bytes are checked before the checksum
+                Err(HeaderError::InvalidMagic(_)) if i < 4 => (),
+                result => panic!("corrupted byte {} was not caught: {:?}", i, result),
+            }
+        }
+
+        assert_eq!(
+            ArchiveHeader::split(&header.to_bytes()),
+            Err(HeaderError::MissingChecksum(ArchiveHeader::LEN))
+        );
+    }
 }
@@ -16,6 +16,7 @@ mod tests {
         borrow::Cow,
         boxed::Box,
         collections::{BTreeMap, BTreeSet},
+        format,
         rc::{Rc, Weak},
         string::{String, ToString},
         vec,
@@ -45,6 +46,24 @@ mod tests {
         test_archive_container(&vec![1, 2, 3, 4]);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_pin_box() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            value: Pin<Box<i32>>,
+            name: Pin<Box<str>>,
+        }
+
+        test_archive(&Box::pin(42));
+        test_archive(&Test {
+            value: Box::pin(42),
+            name: Box::into_pin("hello world".to_string().into_boxed_str()),
+        });
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_option() {
@@ -584,7 +603,10 @@ mod tests {
         #[archive_attr(derive(Debug))]
         // The derive macros don't apply the right bounds from Box so we have to manually specify
         // what bounds to apply
-        #[archive(bound(serialize = "__S: Serializer"))]
+        #[archive(bound(
+            serialize = "__S: Serializer",
+            deserialize = "__D: rkyv::de::AllocationBudget"
+        ))]
         enum Node {
             Nil,
             Cons(#[omit_bounds] Box<Node>),
@@ -601,7 +623,10 @@ mod tests {
         #[archive_attr(derive(Debug))]
         // The derive macros don't apply the right bounds from Box so we have to manually specify
         // what bounds to apply
-        #[archive(bound(serialize = "__S: Serializer"))]
+        #[archive(bound(
+            serialize = "__S: Serializer",
+            deserialize = "__D: rkyv::de::AllocationBudget"
+        ))]
         pub enum LinkedList<T: Archive>
         where
             T::Archived: core::fmt::Debug,
@@ -623,6 +648,135 @@ mod tests {
         });
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn recursive_expression_tree() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        #[archive(bound(
+            serialize = "__S: Serializer",
+            deserialize = "__D: rkyv::de::AllocationBudget"
+        ))]
+        enum Expr {
+            Num(i64),
+            Add(#[omit_bounds] Box<Expr>, #[omit_bounds] Box<Expr>),
+            Mul(#[omit_bounds] Box<Expr>, #[omit_bounds] Box<Expr>),
+        }
+
+        // Evaluates the archived tree in place by matching on the archived variants
+        fn eval(expr: &ArchivedExpr) -> i64 {
+            match expr {
+                ArchivedExpr::Num(n) => i64::from(*n),
+                ArchivedExpr::Add(a, b) => eval(a) + eval(b),
+                ArchivedExpr::Mul(a, b) => eval(a) * eval(b),
+            }
+        }
+
+        fn num(n: i64) -> Box<Expr> {
+            Box::new(Expr::Num(n))
+        }
+
+        // (2 + 3) * (4 + (5 * -6))
+        let value = Expr::Mul(
+            Box::new(Expr::Add(num(2), num(3))),
+            Box::new(Expr::Add(num(4), Box::new(Expr::Mul(num(5), num(-6))))),
+        );
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Expr>(buf.as_ref()) };
+
+        assert_eq!(eval(archived), -130);
+        if let ArchivedExpr::Mul(left, _) = archived {
+            assert_eq!(eval(left), 5);
+        } else {
+            panic!("archived expression has the wrong root variant");
+        }
+
+        test_archive(&value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn recursive_config_tree() {
+        use rkyv::with::AsOwned;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(bound(
+            serialize = "__S: rkyv::ser::ScratchSpace + Serializer",
+            deserialize = "__D: rkyv::de::AllocationBudget"
+        ))]
+        enum Value<'a> {
+            Str(#[with(AsOwned)] Cow<'a, str>),
+            List(#[omit_bounds] Vec<Value<'a>>),
+            Map(#[omit_bounds] BTreeMap<String, Value<'a>>),
+        }
+
+        // Looks up a value in the archived tree by following a path of map keys
+        fn get<'a, 'b>(
+            value: &'a ArchivedValue<'b>,
+            path: &[&str],
+        ) -> Option<&'a ArchivedValue<'b>> {
+            match path.split_first() {
+                None => Some(value),
+                Some((key, rest)) => match value {
+                    ArchivedValue::Map(map) => get(map.get(*key)?, rest),
+                    _ => None,
+                },
+            }
+        }
+
+        // Collects every string in the archived tree in order
+        fn strings<'a>(value: &'a ArchivedValue<'_>, out: &mut Vec<&'a str>) {
+            match value {
+                ArchivedValue::Str(s) => out.push(s.as_str()),
+                ArchivedValue::List(list) => list.iter().for_each(|v| strings(v, out)),
+                ArchivedValue::Map(map) => map.values().for_each(|v| strings(v, out)),
+            }
+        }
+
+        let borrowed = "a borrowed string that is long enough to be out of line".to_string();
+        let mut server = BTreeMap::new();
+        server.insert("host".to_string(), Value::Str(Cow::Borrowed("localhost")));
+        server.insert(
+            "ports".to_string(),
+            Value::List(vec![
+                Value::Str(Cow::Borrowed("8080")),
+                Value::Str(Cow::Owned("8443".to_string())),
+            ]),
+        );
+        let mut root = BTreeMap::new();
+        root.insert("server".to_string(), Value::Map(server));
+        root.insert("motd".to_string(), Value::Str(Cow::Borrowed(&borrowed)));
+        root.insert("empty".to_string(), Value::List(Vec::new()));
+        let value = Value::Map(root);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Value>(buf.as_ref()) };
+
+        match get(archived, &["server", "host"]) {
+            Some(ArchivedValue::Str(host)) => assert_eq!(host, "localhost"),
+            _ => panic!("missing server.host"),
+        }
+        match get(archived, &["server", "ports"]) {
+            Some(ArchivedValue::List(ports)) => assert_eq!(ports.len(), 2),
+            _ => panic!("missing server.ports"),
+        }
+        assert!(get(archived, &["server", "host", "name"]).is_none());
+        assert!(get(archived, &["missing"]).is_none());
+
+        let mut all = Vec::new();
+        strings(archived, &mut all);
+        assert_eq!(all, [borrowed.as_str(), "localhost", "8080", "8443"]);
+
+        let deserialized: Value = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn complex_bounds() {
@@ -711,6 +865,192 @@ mod tests {
         test_archive(&value);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_small_std_enums() {
+        use core::{cmp::Ordering, num::FpCategory, ops::Bound, sync::atomic};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            a: Ordering,
+            b: FpCategory,
+            c: atomic::Ordering,
+            d: Bound<String>,
+            e: Bound<i32>,
+            f: Bound<u8>,
+        }
+
+        for (i, ordering) in [Ordering::Less, Ordering::Equal, Ordering::Greater]
+            .iter()
+            .enumerate()
+        {
+            test_archive(&Test {
+                a: *ordering,
+                b: [
+                    FpCategory::Nan,
+                    FpCategory::Infinite,
+                    FpCategory::Zero,
+                    FpCategory::Subnormal,
+                    FpCategory::Normal,
+                ][i],
+                c: [
+                    atomic::Ordering::Relaxed,
+                    atomic::Ordering::Acquire,
+                    atomic::Ordering::SeqCst,
+                ][i],
+                d: Bound::Included("a bound that is stored out-of-line".to_string()),
+                e: Bound::Excluded(-5),
+                f: Bound::Unbounded,
+            });
+        }
+
+        // Archived orderings keep the discriminants of `Ordering`
+        assert_eq!(
+            rkyv::cmp::ArchivedOrdering::Less as i8,
+            Ordering::Less as i8
+        );
+        assert_eq!(
+            rkyv::cmp::ArchivedOrdering::Greater as i8,
+            Ordering::Greater as i8
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_as_bounds() {
+        use core::ops::{Bound, RangeBounds};
+        use rkyv::{ops::ArchivedBound, with::AsBounds};
+
+        // A range that may be open on either side
+        #[derive(Debug, PartialEq)]
+        struct Interval<T> {
+            low: Bound<T>,
+            high: Bound<T>,
+        }
+
+        impl<T> RangeBounds<T> for Interval<T> {
+            fn start_bound(&self) -> Bound<&T> {
+                match &self.low {
+                    Bound::Included(low) => Bound::Included(low),
+                    Bound::Excluded(low) => Bound::Excluded(low),
+                    Bound::Unbounded => Bound::Unbounded,
+                }
+            }
+
+            fn end_bound(&self) -> Bound<&T> {
+                match &self.high {
+                    Bound::Included(high) => Bound::Included(high),
+                    Bound::Excluded(high) => Bound::Excluded(high),
+                    Bound::Unbounded => Bound::Unbounded,
+                }
+            }
+        }
+
+        impl<T> From<(Bound<T>, Bound<T>)> for Interval<T> {
+            fn from((low, high): (Bound<T>, Bound<T>)) -> Self {
+                Self { low, high }
+            }
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(AsBounds<i32>)]
+            numbers: Interval<i32>,
+            #[with(AsBounds<String>)]
+            names: Interval<String>,
+        }
+
+        let value = Test {
+            numbers: Interval {
+                low: Bound::Excluded(-5),
+                high: Bound::Included(10),
+            },
+            names: Interval {
+                low: Bound::Included("a bound that is stored out-of-line".to_string()),
+                high: Bound::Unbounded,
+            },
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        assert_eq!(archived.numbers.start, Bound::Excluded(-5));
+        assert_eq!(archived.numbers.end, Bound::Included(10));
+        assert!(!archived.numbers.contains(&Archived::<i32>::from(-5)));
+        assert!(archived.numbers.contains(&Archived::<i32>::from(10)));
+        match &archived.names.start {
+            ArchivedBound::Included(name) => {
+                assert_eq!(name, "a bound that is stored out-of-line")
+            }
+            _ => panic!("expected an included bound"),
+        }
+        assert_eq!(archived.names.end_bound(), Bound::Unbounded);
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_interned() {
+        use rkyv::{
+            archived_value, ser::serializers::InternSerializer, string::intern::InternTable,
+            with::Intern,
+        };
+
+        #[derive(Archive, Serialize)]
+        struct Test {
+            #[with(Intern)]
+            name: String,
+            #[with(Intern)]
+            tag: &'static str,
+        }
+
+        let value = vec![
+            Test {
+                name: "a name that is repeated many times".to_string(),
+                tag: "first",
+            },
+            Test {
+                name: "a name that is repeated many times".to_string(),
+                tag: "second",
+            },
+            Test {
+                name: "a different name".to_string(),
+                tag: "first",
+            },
+        ];
+
+        let mut serializer = InternSerializer::new(DefaultSerializer::default());
+        let pos = serializer.serialize_value(&value).unwrap();
+        assert_eq!(serializer.table().len(), 4);
+        serializer.serialize_intern_table().unwrap();
+        let (serializer, table) = serializer.into_parts();
+        let buf = serializer.into_serializer().into_inner();
+
+        let archived = unsafe { archived_value::<Vec<Test>>(buf.as_ref(), pos) };
+        let archived_table = unsafe { archived_root::<InternTable>(buf.as_ref()) };
+        assert_eq!(archived_table, &table);
+        assert_eq!(archived_table.len(), 4);
+        assert_eq!(archived_table.get(4), None);
+
+        assert_eq!(archived[0].name, archived[1].name);
+        assert_ne!(archived[0].name, archived[2].name);
+        assert_eq!(archived[0].tag, archived[2].tag);
+        for (archived, value) in archived.iter().zip(value.iter()) {
+            assert_eq!(
+                archived.name.resolve(archived_table),
+                Some(value.name.as_str())
+            );
+            assert_eq!(archived.tag.resolve(archived_table), Some(value.tag));
+            assert_eq!(archived_table.get(archived.tag.index()), Some(value.tag));
+        }
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_atomic() {
@@ -1068,6 +1408,70 @@ mod tests {
             .all(|&b| b == 0));
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn mapped_serializer() {
+        use rkyv::ser::serializers::{AllocScratch, CompositeSerializer, MappedSerializer};
+
+        #[derive(Archive, Serialize)]
+        struct Mesh {
+            positions: Vec<[f32; 3]>,
+            indices: Vec<u16>,
+        }
+
+        #[repr(C, align(256))]
+        struct Staging([u8; 1024]);
+
+        let mut staging = Staging([0; 1024]);
+        let mut flushed = Vec::new();
+        let mut serializer = unsafe {
+            MappedSerializer::new(staging.0.as_mut_ptr(), staging.0.len(), |range| {
+                flushed.push(range)
+            })
+        };
+        assert_eq!(serializer.capacity(), 1024);
+
+        // Flushing with nothing written doesn't call the hook
+        serializer.flush();
+        serializer.write(&[1; 10]).unwrap();
+        serializer.flush();
+        serializer.align(256).unwrap();
+        serializer.write(&[2; 300]).unwrap();
+        serializer.flush();
+
+        // Archiving vecs needs scratch space, which can be added around the mapped serializer
+        let mut serializer =
+            CompositeSerializer::new(serializer, AllocScratch::default(), Infallible);
+        let pos = serializer
+            .serialize_value(&Mesh {
+                positions: vec![[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+                indices: vec![0, 1, 2],
+            })
+            .unwrap();
+        let len = serializer.into_serializer().finish().unwrap();
+
+        // Every flushed range is widened to 256-byte boundaries, so ranges can overlap, and the
+        // archive is padded to a multiple of 256 bytes
+        assert_eq!(len, 768);
+        assert_eq!(flushed, [0..256, 0..768, 512..768]);
+        assert!(staging.0[..10].iter().all(|&b| b == 1));
+        assert!(staging.0[256..556].iter().all(|&b| b == 2));
+        let archived = unsafe { rkyv::archived_value::<Mesh>(&staging.0, pos) };
+        assert_eq!(archived.positions[2][2], 1.0);
+        assert_eq!(archived.indices.as_slice(), [0, 1, 2]);
+
+        // Writing past the end of the mapped memory fails, and finishing pads to the end
+        let mut flushed = Vec::new();
+        let mut serializer = unsafe {
+            MappedSerializer::new(staging.0.as_mut_ptr(), 300, |range| flushed.push(range))
+        };
+        assert!(serializer.write(&[0; 301]).is_err());
+        serializer.write(&[0; 290]).unwrap();
+        assert_eq!(serializer.finish().unwrap(), 300);
+        assert_eq!(flushed.len(), 1);
+        assert_eq!(flushed[0], 0..300);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn const_generics() {
@@ -1084,6 +1488,63 @@ mod tests {
         pub struct Array<T, const N: usize>([T; N]);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn const_generics_ring_buffer() {
+        #[derive(Archive, Deserialize, Serialize, Debug, PartialEq)]
+        pub struct RingBuffer<const N: usize> {
+            data: [u32; N],
+            head: usize,
+        }
+
+        impl<const N: usize> ArchivedRingBuffer<N> {
+            fn capacity(&self) -> usize {
+                N
+            }
+        }
+
+        let mut value = RingBuffer::<16> {
+            data: [0; 16],
+            head: 3,
+        };
+        for (i, x) in value.data.iter_mut().enumerate() {
+            *x = i as u32 * 10;
+        }
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<RingBuffer<16>>(buf.as_ref()) };
+        assert_eq!(archived.capacity(), 16);
+        assert_eq!(archived.data[15], 150);
+        assert_eq!(archived.head, 3);
+        let deserialized: RingBuffer<16> = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        // Const parameters are threaded through alongside type parameters
+        #[derive(Archive, Deserialize, Serialize)]
+        pub struct GenericRingBuffer<T, const N: usize> {
+            data: [T; N],
+            head: usize,
+        }
+
+        let value = GenericRingBuffer::<String, 2> {
+            data: ["a".to_string(), "b".to_string()],
+            head: 1,
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<GenericRingBuffer<String, 2>>(buf.as_ref()) };
+        assert_eq!(archived.data[0], "a");
+        assert_eq!(archived.data[1], "b");
+        assert_eq!(archived.head, 1);
+        let deserialized: GenericRingBuffer<String, 2> = archived
+            .deserialize(&mut DefaultDeserializer::default())
+            .unwrap();
+        assert_eq!(deserialized.data, value.data);
+    }
+
     #[test]
     #[cfg(any(
         not(any(feature = "archive_le", feature = "archive_be")),
@@ -1249,6 +1710,61 @@ mod tests {
         assert_eq!(core::mem::align_of::<ArchivedCAlignRepr>(), 8);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_vec_over_aligned() {
+        use core::mem::{align_of, size_of};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive_attr(repr(C, align(32)))]
+        struct Lanes {
+            values: [f32; 3],
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            tag: u8,
+            lanes: Vec<Lanes>,
+        }
+
+        #[repr(C, align(32))]
+        struct Aligned32<const N: usize>([u8; N]);
+
+        assert_eq!(align_of::<ArchivedLanes>(), 32);
+        assert_eq!(size_of::<ArchivedLanes>(), 32);
+
+        let value = Test {
+            tag: 1,
+            lanes: (0..5)
+                .map(|i| Lanes {
+                    values: [i as f32, i as f32 + 0.5, i as f32 + 0.25],
+                })
+                .collect::<Vec<_>>(),
+        };
+
+        let mut serializer = rkyv::ser::serializers::AllocSerializer::<256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let bytes = serializer.into_serializer().into_inner();
+
+        // Copy the archive into a buffer aligned for the elements
+        let mut buffer = Aligned32([0u8; 512]);
+        buffer.0[..bytes.len()].copy_from_slice(&bytes);
+        let buffer = &buffer.0[..bytes.len()];
+
+        let archived = unsafe { archived_root::<Test>(buffer) };
+        let elements = archived.lanes.as_slice();
+        assert_eq!(elements.len(), 5);
+        let start = elements.as_ptr() as usize;
+        assert_eq!(start % 32, 0);
+        for (i, element) in elements.iter().enumerate() {
+            assert_eq!(element as *const ArchivedLanes as usize, start + i * 32);
+            assert!(element.values[1] == i as f32 + 0.5);
+        }
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_as() {
@@ -1687,16 +2203,16 @@ mod tests {
         #[derive(Archive, Serialize, Deserialize)]
         struct Test {
             #[with(Niche)]
-            inner: Option<Box<String>>,
+            inner: Option<Box<str>>,
         }
 
         #[derive(Archive, Serialize, Deserialize)]
         struct TestNoNiching {
-            inner: Option<Box<String>>,
+            inner: Option<Box<str>>,
         }
 
         let value = Test {
-            inner: Some(Box::new("hello world".to_string())),
+            inner: Some("hello world".into()),
         };
         let mut serializer = DefaultSerializer::default();
         serializer.serialize_value(&value).unwrap();
@@ -1719,6 +2235,94 @@ mod tests {
         assert!(size_of::<Archived<Test>>() < size_of::<Archived<TestNoNiching>>());
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archived_vec_raw_parts() {
+        use core::mem::align_of;
+        use rkyv::with::PreserveCapacity;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(repr(C))]
+        struct Vertex {
+            x: f32,
+            y: f32,
+            color: u32,
+        }
+
+        #[derive(Archive, Serialize)]
+        struct Mesh {
+            vertices: Vec<Vertex>,
+            indices: Box<[u16]>,
+            #[with(PreserveCapacity)]
+            weights: Vec<u64>,
+            empty: Vec<u32>,
+        }
+
+        let mut value = Mesh {
+            vertices: (0..10)
+                .map(|i| Vertex {
+                    x: i as f32,
+                    y: -(i as f32),
+                    color: 0x01020304 * i,
+                })
+                .collect(),
+            indices: (0..30).collect(),
+            weights: Vec::with_capacity(16),
+            empty: Vec::new(),
+        };
+        value.weights.extend(0..5);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Mesh>(buf.as_ref()) };
+
+        let ptr = archived.vertices.as_ptr();
+        let len = archived.vertices.len();
+        assert_eq!(len, 10);
+        assert_eq!(ptr as usize % align_of::<ArchivedVertex>(), 0);
+        assert_eq!(size_of::<ArchivedVertex>(), 12);
+        let raw = unsafe { core::slice::from_raw_parts(ptr, len) };
+        assert_eq!(raw.as_ptr(), archived.vertices.as_slice().as_ptr());
+        for (i, vertex) in raw.iter().enumerate() {
+            assert_eq!(f32::from(vertex.x), i as f32);
+            assert_eq!(u32::from(vertex.color), 0x01020304 * i as u32);
+        }
+
+        // With native endianness, the elements can be read as the native type they were archived
+        // from
+        #[cfg(not(any(
+            all(target_endian = "little", feature = "archive_be"),
+            all(target_endian = "big", feature = "archive_le")
+        )))]
+        {
+            let native = unsafe { core::slice::from_raw_parts(ptr.cast::<[u32; 3]>(), len) };
+            assert_eq!(native[3][2], 0x01020304 * 3);
+            assert_eq!(f32::from_bits(native[3][1]), -3.0);
+        }
+
+        // Archived boxed slices and vecs with capacity give access to the same raw parts
+        assert_eq!(archived.indices.len(), 30);
+        assert_eq!(
+            archived.indices.as_ptr() as usize % align_of::<Archived<u16>>(),
+            0
+        );
+        assert_eq!(archived.weights.len(), 5);
+        assert_eq!(archived.weights.capacity(), 16);
+        assert_eq!(
+            archived.weights.as_ptr(),
+            archived.weights.as_slice().as_ptr()
+        );
+
+        // Empty vecs still have a non-null, aligned pointer
+        assert!(archived.empty.is_empty());
+        assert!(!archived.empty.as_ptr().is_null());
+        assert_eq!(
+            archived.empty.as_ptr() as usize % align_of::<Archived<u32>>(),
+            0
+        );
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn with_copy_optimize() {
@@ -1770,6 +2374,76 @@ mod tests {
         assert_eq!(&*archived.bytes, value.bytes);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn with_swap_optimize() {
+        use rkyv::{
+            with::{DeserializeWith, SwapOptimize, With},
+            ArchivePrimitive,
+        };
+
+        // Batched archives must be identical to the ones produced element by element
+        fn check<T>(value: Vec<T>)
+        where
+            T: ArchivePrimitive + Serialize<DefaultSerializer> + PartialEq,
+            T::Archived: Deserialize<T, Infallible>,
+        {
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(&value).unwrap();
+            let expected = serializer.into_serializer().into_inner();
+
+            let mut serializer = DefaultSerializer::default();
+            serializer
+                .serialize_value(With::<_, SwapOptimize>::cast(&value))
+                .unwrap();
+            let result = serializer.into_serializer().into_inner();
+            assert_eq!(result.as_slice(), expected.as_slice());
+
+            let archived =
+                unsafe { archived_root::<With<Vec<T>, SwapOptimize>>(result.as_slice()) };
+            assert_eq!(archived.len(), value.len());
+            let deserialized: Vec<T> =
+                SwapOptimize::deserialize_with(archived, &mut Infallible).unwrap();
+            assert!(deserialized == value);
+        }
+
+        // Lengths around the size of a batch, kept small enough to fit in 16-bit archives
+        for &len in [0, 1, 2, 255, 256, 257, 1023, 1024, 1025].iter() {
+            check(
+                (0..len as u32)
+                    .map(|i| i.wrapping_mul(0x0102_0304))
+                    .collect(),
+            );
+            check((0..len as i16).collect());
+            check((0..len as u64).map(|i| i << 40 | i).collect());
+            check((0..len).map(|i| i as f64 / 3.0).collect());
+            check((0..len).map(|i| -(i as i128) << 64).collect());
+            check((0..len).collect::<Vec<usize>>());
+            check((0..len).map(|i| i % 3 == 0).collect());
+            // Swapping the bytes of a char makes an invalid char, which rend does not support
+            #[cfg(not(any(
+                all(target_endian = "little", feature = "archive_be"),
+                all(target_endian = "big", feature = "archive_le"),
+            )))]
+            check((0..len as u32).filter_map(char::from_u32).collect());
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            #[with(SwapOptimize)]
+            samples: Vec<f32>,
+            #[with(SwapOptimize)]
+            ids: Vec<u32>,
+        }
+
+        test_archive(&Test {
+            samples: vec![0.5, -1.0, 3.25],
+            ids: (0..2000).collect(),
+        });
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_crate_path() {
@@ -1860,8 +2534,175 @@ mod tests {
     }
 
     #[test]
-    // This test is unfortunately too slow to run through miri
-    #[cfg_attr(miri, ignore)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_btree_map_range() {
+        use core::ops::Bound::{self, Excluded, Included, Unbounded};
+
+        // Enough entries to span several leaf nodes
+        let mut value = BTreeMap::new();
+        for i in 0..2_000 {
+            value.insert(format!("{:04}", i * 2), i);
+        }
+
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_inner();
+        let archived = unsafe { archived_root::<BTreeMap<String, i32>>(result.as_slice()) };
+
+        let check = |start: Bound<&str>, end: Bound<&str>| {
+            let expected = value
+                .range::<str, _>((start, end))
+                .map(|(k, v)| (k.as_str(), *v))
+                .collect::<Vec<_>>();
+            let actual = archived
+                .range::<str, _>((start, end))
+                .map(|(k, v)| (k.as_str(), i32::from(*v)))
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected, "range {:?}..{:?}", start, end);
+        };
+
+        // Keys are all even, so odd keys fall between entries
+        let keys = [
+            "", "0000", "0001", "0998", "1001", "2000", "3998", "3999", "4",
+        ];
+        let bounds = keys
+            .iter()
+            .flat_map(|k| vec![Included(*k), Excluded(*k)])
+            .chain(core::iter::once(Unbounded))
+            .collect::<Vec<_>>();
+        for &start in bounds.iter() {
+            for &end in bounds.iter() {
+                let valid = match (start, end) {
+                    (Excluded(s), Excluded(e)) => s < e,
+                    (Included(s), Included(e))
+                    | (Included(s), Excluded(e))
+                    | (Excluded(s), Included(e)) => s <= e,
+                    _ => true,
+                };
+                if valid {
+                    check(start, end);
+                }
+            }
+        }
+
+        assert_eq!(archived.range::<str, _>(..).count(), 2_000);
+
+        // Standard range syntax works too
+        let bytes = (0..=255u8).map(|b| (b, ())).collect::<BTreeMap<_, _>>();
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        serializer.serialize_value(&bytes).unwrap();
+        let result = serializer.into_inner();
+        let archived_bytes = unsafe { archived_root::<BTreeMap<u8, ()>>(result.as_slice()) };
+        let keys = |range: core::ops::RangeInclusive<u8>| {
+            archived_bytes
+                .range(range)
+                .map(|(k, _)| *k)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(keys(10..=13), [10, 11, 12, 13]);
+        assert_eq!(keys(255..=255), [255]);
+        assert_eq!(archived_bytes.range(..3).count(), 3);
+        assert_eq!(archived_bytes.range(250..).count(), 6);
+
+        let empty: BTreeMap<String, i32> = BTreeMap::new();
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        serializer.serialize_value(&empty).unwrap();
+        let result = serializer.into_inner();
+        let archived = unsafe { archived_root::<BTreeMap<String, i32>>(result.as_slice()) };
+        assert_eq!(archived.range::<str, _>(..).count(), 0);
+        assert_eq!(
+            archived
+                .range::<str, _>((Included("a"), Excluded("b")))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    #[should_panic(expected = "range start is greater than range end")]
+    fn archive_btree_map_range_inverted() {
+        use core::ops::Bound::{Excluded, Included};
+
+        let mut value = BTreeMap::new();
+        value.insert("a".to_string(), 1);
+
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        serializer.serialize_value(&value).unwrap();
+        let result = serializer.into_inner();
+        let archived = unsafe { archived_root::<BTreeMap<String, i32>>(result.as_slice()) };
+        archived.range::<str, _>((Included("b"), Excluded("a")));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn index_archived_containers() {
+        #[derive(Archive, Serialize)]
+        struct Test {
+            values: Vec<u32>,
+            names: BTreeMap<String, u32>,
+        }
+
+        let mut names = BTreeMap::new();
+        names.insert("a".to_string(), 1);
+        names.insert("b".to_string(), 2);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer
+            .serialize_value(&Test {
+                values: vec![10, 20, 30],
+                names,
+            })
+            .unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+        assert_eq!(archived.values[1], 20);
+        assert_eq!(archived.values[1..], [20, 30]);
+        assert_eq!(archived.names["b"], 2);
+
+        // Primitive elements can be updated in place through a mutable reference
+        let values = unsafe {
+            let archived = archived_root_mut::<Test>(Pin::new(buf.as_mut()));
+            &mut archived.get_unchecked_mut().values
+        };
+        values[1] = 25u32.into();
+        for value in &mut values[..2] {
+            *value = (u32::from(*value) + 1).into();
+        }
+
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+        assert_eq!(archived.values.as_slice(), [11, 26, 30]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn index_archived_vec_out_of_bounds() {
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&vec![1u32, 2, 3]).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Vec<u32>>(buf.as_ref()) };
+        let _ = archived[3];
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    #[should_panic(expected = "no entry found for key")]
+    fn index_archived_btree_map_missing_key() {
+        let mut value = BTreeMap::new();
+        value.insert("a".to_string(), 1u32);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<BTreeMap<String, u32>>(buf.as_ref()) };
+        let _ = archived["b"];
+    }
+
+    #[test]
+    // This test is unfortunately too slow to run through miri
+    #[cfg_attr(miri, ignore)]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     // This test creates structures too big to fit in 16-bit offsets
     #[cfg(not(feature = "size_16"))]
@@ -1895,6 +2736,87 @@ mod tests {
         assert_eq!(value, deserialized);
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_btree_map_branching_factor() {
+        use core::ops::Bound::{Excluded, Included};
+        use rkyv::with::{BranchingFactor, With};
+
+        fn test_branching_factor<const B: usize>(len: usize) {
+            // Keys are all even, so odd keys fall between entries
+            let value = (0..len)
+                .map(|i| (format!("{:05}", i * 2), i as i32))
+                .collect::<BTreeMap<_, _>>();
+
+            let mut serializer = AlignedSerializer::new(AlignedVec::new());
+            serializer
+                .serialize_value(With::<_, BranchingFactor<B>>::cast(&value))
+                .unwrap();
+            let result = serializer.into_inner();
+            let archived = unsafe {
+                archived_root::<With<BTreeMap<String, i32>, BranchingFactor<B>>>(result.as_slice())
+            };
+
+            assert_eq!(archived.len(), len);
+            assert_eq!(archived.is_empty(), len == 0);
+
+            for ((k, v), (ak, av)) in value.iter().zip(archived.iter()) {
+                assert_eq!(k, ak);
+                assert_eq!(v, av);
+            }
+            assert_eq!(archived.iter().count(), len);
+
+            for (k, v) in value.iter() {
+                let av = archived
+                    .get(k.as_str())
+                    .expect("failed to find key in archived B-tree map");
+                assert_eq!(v, av);
+            }
+            for i in 0..len {
+                assert!(archived.get(format!("{:05}", i * 2 + 1).as_str()).is_none());
+            }
+            assert!(archived.get("").is_none());
+
+            let start = format!("{:05}", len / 3);
+            let end = format!("{:05}", len);
+            let expected = value
+                .range::<str, _>((Included(start.as_str()), Excluded(end.as_str())))
+                .map(|(k, v)| (k.as_str(), *v))
+                .collect::<Vec<_>>();
+            let actual = archived
+                .range::<str, _>((Included(start.as_str()), Excluded(end.as_str())))
+                .map(|(k, v)| (k.as_str(), i32::from(*v)))
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected);
+
+            let deserialized: BTreeMap<String, i32> =
+                archived.deserialize(&mut Infallible).unwrap();
+            assert_eq!(value, deserialized);
+        }
+
+        for &len in [0, 1, 2, 3, 4, 5, 15, 16, 17, 100, 1_000].iter() {
+            test_branching_factor::<3>(len);
+            test_branching_factor::<4>(len);
+            test_branching_factor::<16>(len);
+            test_branching_factor::<1024>(len);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    #[should_panic(expected = "B-tree branching factor must be between")]
+    fn archive_btree_map_branching_factor_too_small() {
+        use rkyv::with::{BranchingFactor, With};
+
+        let mut value = BTreeMap::new();
+        value.insert(1, 2);
+
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        serializer
+            .serialize_value(With::<_, BranchingFactor<2>>::cast(&value))
+            .unwrap();
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_zst_containers() {
@@ -1942,4 +2864,2230 @@ mod tests {
             b: rc_zst.clone(),
         });
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_with_header() {
+        use rkyv::header::{
+            archived_root_with_header, to_bytes_with_header, ArchiveHeader, HeaderError,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            a: u32,
+            b: String,
+            c: Vec<i32>,
+        }
+
+        let value = Test {
+            a: 42,
+            b: "hello world".to_string(),
+            c: vec![1, 2, 3, 4],
+        };
+
+        let bytes = to_bytes_with_header::<_, 256>(&value).unwrap();
+        let (header, archive) = ArchiveHeader::split(&bytes).unwrap();
+        assert_eq!(header, ArchiveHeader::new(header.root_pos()));
+        assert_eq!(archive.len(), bytes.len() - ArchiveHeader::LEN);
+
+        let archived = unsafe { archived_root_with_header::<Test>(&bytes).unwrap() };
+        assert_eq!(*archived, value);
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        // The archive is not touched if the header is incompatible
+        let mut incompatible = bytes.clone();
+        incompatible[4] = incompatible[4].wrapping_add(1);
+        assert!(matches!(
+            unsafe { archived_root_with_header::<Test>(&incompatible) },
+            Err(HeaderError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archived_root_owns_bytes() {
+        use rkyv::{to_archived_root, ArchivedRoot};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Test {
+            a: u32,
+            b: String,
+            c: Vec<i32>,
+        }
+
+        let value = Test {
+            a: 42,
+            b: "hello world".to_string(),
+            c: vec![1, 2, 3, 4],
+        };
+
+        let root = to_archived_root::<_, 256>(&value).unwrap();
+        assert_eq!(*root, value);
+        assert_eq!(
+            root.pos() + core::mem::size_of::<Archived<Test>>(),
+            root.as_bytes().len()
+        );
+
+        let deserialized: Test = (*root).deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        let pos = root.pos();
+        let bytes = root.into_bytes().into_vec();
+        let root = unsafe { ArchivedRoot::<Test>::from_vec(bytes, pos) };
+        assert_eq!(*root, value);
+
+        let root = unsafe { ArchivedRoot::<Test>::from_root(root.into_bytes()) };
+        assert_eq!(*root, value);
+    }
+
+    #[test]
+    #[cfg(feature = "fst")]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_fst_btree() {
+        use rkyv::with::AsFst;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(AsFst)]
+            set: BTreeSet<String>,
+            #[with(AsFst)]
+            map: BTreeMap<String, Vec<u32>>,
+        }
+
+        let keys = ["mon", "monday", "month", "sun", "sunday", "tue", "tuesday"];
+        let value = Test {
+            set: keys.iter().map(|key| key.to_string()).collect(),
+            map: keys
+                .iter()
+                .enumerate()
+                .map(|(i, key)| (key.to_string(), (0..i as u32).collect()))
+                .collect(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        assert!(archived.set.iter().eq(value.set.iter().cloned()));
+        assert_eq!(archived.map.get("month").unwrap().len(), 2);
+        assert_eq!(archived.map.get("tuesday").unwrap().len(), 6);
+        assert!(archived.map.get("tues").is_none());
+        assert_eq!(
+            archived
+                .map
+                .prefix_iter("sun")
+                .map(|(key, _)| key)
+                .collect::<Vec<_>>(),
+            vec!["sun", "sunday"],
+        );
+        assert_eq!(archived.map.keys(), &archived.set);
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg(feature = "fst")]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn deserialize_with_scratch_pool() {
+        use rkyv::{
+            de::{
+                deserializers::{BudgetDeserializer, SharedDeserializeMap, VecPool},
+                ScratchPool,
+            },
+            with::AsFst,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Dictionary {
+            #[with(AsFst)]
+            words: BTreeSet<String>,
+            #[with(AsFst)]
+            counts: BTreeMap<String, u32>,
+        }
+
+        let archives = (0..10u32)
+            .map(|i| {
+                let value = Dictionary {
+                    words: (0..20 + i).map(|j| format!("word{}", j * 7)).collect(),
+                    counts: (0..i).map(|j| (format!("key{}", j), j)).collect(),
+                };
+                let mut serializer = DefaultSerializer::default();
+                serializer.serialize_value(&value).unwrap();
+                (value, serializer.into_serializer().into_inner())
+            })
+            .collect::<Vec<_>>();
+
+        // The key and stack buffers used to walk each FST are given back to the pool and reused
+        let mut pool = VecPool::new();
+        for (value, buf) in archives.iter() {
+            let archived = unsafe { archived_root::<Dictionary>(buf.as_ref()) };
+            let deserialized: Dictionary = archived.deserialize(&mut pool).unwrap();
+            assert_eq!(&deserialized, value);
+            assert_eq!(pool.len(), 2);
+        }
+
+        // Adapters use the scratch buffers of the deserializer they wrap
+        let mut deserializer = BudgetDeserializer::new(pool, usize::MAX);
+        let archived = unsafe { archived_root::<Dictionary>(archives[0].1.as_ref()) };
+        let deserialized: Dictionary = archived.deserialize(&mut deserializer).unwrap();
+        assert_eq!(deserialized, archives[0].0);
+        let mut pool = deserializer.into_inner();
+        assert_eq!(pool.len(), 2);
+
+        // Buffers are only reused for element types with a compatible layout
+        let mut bytes = pool.take_scratch::<u8>();
+        bytes.extend_from_slice(b"scratch");
+        let words = pool.take_scratch::<u16>();
+        assert_eq!(words.capacity(), 0);
+        pool.return_scratch(bytes);
+        pool.return_scratch(words);
+        assert_eq!(pool.len(), 2);
+        pool.clear();
+        assert!(pool.is_empty());
+
+        // Shared deserialize maps pool scratch buffers and can be reused after clearing them
+        #[derive(Archive, Serialize, Deserialize)]
+        struct Shared {
+            #[with(AsFst)]
+            words: BTreeSet<String>,
+            a: Rc<u32>,
+            b: Rc<u32>,
+        }
+
+        let mut deserializer = SharedDeserializeMap::new();
+        for i in 0..3 {
+            let shared = Rc::new(i);
+            let value = Shared {
+                words: ["a", "b"].iter().map(|word| word.to_string()).collect(),
+                a: shared.clone(),
+                b: shared,
+            };
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(&value).unwrap();
+            let buf = serializer.into_serializer().into_inner();
+            let archived = unsafe { archived_root::<Shared>(buf.as_ref()) };
+
+            deserializer.clear();
+            let deserialized: Shared = archived.deserialize(&mut deserializer).unwrap();
+            assert_eq!(*deserialized.a, i);
+            assert!(Rc::ptr_eq(&deserialized.a, &deserialized.b));
+            assert_eq!(deserialized.words, value.words);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn resumable_archiver() {
+        use rkyv::ser::resumable::{ArchiveProgress, ResumableArchiver};
+
+        #[derive(Archive, Serialize)]
+        struct Entity {
+            name: String,
+            path: Vec<u32>,
+        }
+
+        let entities = (0..200u32)
+            .map(|i| Entity {
+                name: format!("entity number {}", i),
+                path: (0..i % 7).collect(),
+            })
+            .collect::<Vec<_>>();
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&entities).unwrap();
+        let expected = serializer.into_serializer().into_inner();
+
+        let mut archiver = ResumableArchiver::new(&entities, DefaultSerializer::default());
+        let mut flushed = Vec::new();
+        let mut steps = 0;
+        let mut items_archived = 0;
+        let info = loop {
+            let progress = archiver.step(256).unwrap();
+            steps += 1;
+
+            // Flush the bytes written by each step
+            let bytes = archiver.serializer().serializer().inner();
+            flushed.extend_from_slice(&bytes[flushed.len()..]);
+
+            assert!(archiver.items_archived() >= items_archived);
+            items_archived = archiver.items_archived();
+
+            match progress {
+                ArchiveProgress::Pending { written } => {
+                    // Items are small, so steps don't go far over budget
+                    assert!(written < 256 + 64);
+                    assert!(!archiver.is_complete());
+                }
+                ArchiveProgress::Complete(info) => break info,
+            }
+        };
+        assert!(steps > 10);
+        assert!(archiver.is_complete());
+        assert_eq!(archiver.items_archived(), entities.len());
+        assert_eq!(archiver.step(256).unwrap(), ArchiveProgress::Complete(info));
+
+        let bytes = archiver.into_serializer().into_serializer().into_inner();
+        assert_eq!(info.total_len, bytes.len());
+        assert_eq!(flushed, bytes.as_slice());
+        assert_eq!(bytes.as_slice(), expected.as_slice());
+
+        let archived = unsafe { archived_root::<Vec<Entity>>(bytes.as_ref()) };
+        assert_eq!(archived.len(), entities.len());
+        assert_eq!(archived[123].name, "entity number 123");
+        assert_eq!(archived[123].path.len(), 4);
+
+        // A zero budget still makes progress, and empty slices finish
+        let empty: Vec<Entity> = Vec::new();
+        let archiver = ResumableArchiver::new(&empty, DefaultSerializer::default());
+        let (serializer, info) = archiver.finish().unwrap();
+        let bytes = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Vec<Entity>>(bytes.as_ref()) };
+        assert!(archived.is_empty());
+        assert_eq!(info.total_len, bytes.len());
+
+        let mut archiver = ResumableArchiver::new(&entities[..3], DefaultSerializer::default());
+        let mut steps = 1;
+        while !archiver.step(0).unwrap().is_complete() {
+            steps += 1;
+        }
+        // One item per step in each phase, and the root is written with the last item
+        assert_eq!(steps, 6);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_sorted_vec() {
+        use rkyv::with::AsSortedVec;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(AsSortedVec)]
+            table: Vec<(String, u32)>,
+        }
+
+        let value = Test {
+            table: vec![
+                ("delta".to_string(), 4),
+                ("alpha".to_string(), 1),
+                ("charlie".to_string(), 3),
+                ("bravo".to_string(), 2),
+            ],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        assert_eq!(archived.table.len(), 4);
+        assert_eq!(*archived.table.get("alpha").unwrap(), 1);
+        assert_eq!(*archived.table.get("charlie").unwrap(), 3);
+        assert_eq!(archived.table["delta"], 4);
+        assert!(!archived.table.contains_key("echo"));
+
+        let keys = archived
+            .table
+            .keys()
+            .map(|k| k.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["alpha", "bravo", "charlie", "delta"]);
+        assert!(archived.table.values().zip(1u32..).all(|(v, i)| *v == i));
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        let mut sorted = value.table.clone();
+        sorted.sort();
+        assert_eq!(deserialized.table, sorted);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_compressed() {
+        use core::convert::Infallible as Never;
+        use rkyv::{compression::Compression, with::Compress};
+
+        // A run-length encoding of (count, byte) pairs
+        struct Rle;
+
+        impl Compression for Rle {
+            type Error = Never;
+
+            fn compress(bytes: &[u8]) -> Vec<u8> {
+                let mut result = Vec::new();
+                for &byte in bytes {
+                    match result.len() {
+                        len if len >= 2 && result[len - 1] == byte && result[len - 2] < 255 => {
+                            result[len - 2] += 1;
+                        }
+                        _ => result.extend_from_slice(&[1, byte]),
+                    }
+                }
+                result
+            }
+
+            fn decompress(bytes: &[u8]) -> Result<Vec<u8>, Never> {
+                Ok(bytes
+                    .chunks(2)
+                    .flat_map(|run| core::iter::repeat(run[1]).take(run[0] as usize))
+                    .collect())
+            }
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(Compress<Rle, 16>)]
+            small: Vec<u8>,
+            #[with(Compress<Rle, 16>)]
+            large: Vec<u8>,
+            #[with(Compress<Rle, 16>)]
+            noisy: Vec<u8>,
+        }
+
+        let value = Test {
+            small: vec![0; 16],
+            large: vec![7; 1000],
+            noisy: (0..100).collect(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        // Buffers at or below the threshold are stored raw and borrowed on access
+        assert!(!archived.small.is_compressed());
+        assert_eq!(archived.small.stored_bytes(), value.small.as_slice());
+        assert!(matches!(archived.small.bytes(), Ok(Cow::Borrowed(_))));
+
+        assert!(archived.large.is_compressed());
+        assert_eq!(archived.large.stored_bytes().len(), 8);
+        assert_eq!(archived.large.bytes().unwrap(), value.large.as_slice());
+
+        // Buffers that don't shrink when compressed are stored raw
+        assert!(!archived.noisy.is_compressed());
+        assert_eq!(archived.noisy.bytes().unwrap(), value.noisy.as_slice());
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_string_table() {
+        use rkyv::with::{AsStringTable, DeserializeWith};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test<'a> {
+            #[with(AsStringTable)]
+            names: Vec<String>,
+            #[with(AsStringTable)]
+            labels: &'a [&'a str],
+        }
+
+        let value = Test {
+            names: vec![
+                "alpha".to_string(),
+                String::new(),
+                "a string long enough to be out of line".to_string(),
+                "\u{1f980} crab".to_string(),
+            ],
+            labels: &["x", "y"],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        assert_eq!(archived.names.len(), 4);
+        assert_eq!(archived.names, *value.names.as_slice());
+        assert_eq!(archived.names.get(0), Some("alpha"));
+        assert_eq!(&archived.names[1], "");
+        assert_eq!(&archived.names[3], "\u{1f980} crab");
+        assert_eq!(archived.names.get(4), None);
+        assert_eq!(archived.names.pool(), value.names.concat());
+        assert_eq!(archived.names.iter().next_back(), Some("\u{1f980} crab"));
+        assert_eq!(archived.labels, *value.labels);
+
+        let names: Vec<String> =
+            AsStringTable::deserialize_with(&archived.names, &mut Infallible).unwrap();
+        assert_eq!(names, value.names);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_sorted_vec_from_sorted_iter() {
+        use rkyv::{
+            collections::ArchivedSortedVec,
+            with::{AsSortedVec, With},
+        };
+
+        let unsorted = (0..32u32)
+            .rev()
+            .map(|i| (format!("key #{:02}", i), i))
+            .collect::<Vec<_>>();
+        let mut sorted = unsorted.clone();
+        sorted.sort();
+
+        let mut serializer = DefaultSerializer::default();
+        serializer
+            .serialize_value(With::<_, AsSortedVec>::cast(&unsorted))
+            .unwrap();
+        let expected = serializer.into_serializer().into_inner();
+
+        let mut serializer = DefaultSerializer::default();
+        unsafe {
+            let resolver = ArchivedSortedVec::serialize_from_sorted_iter(
+                sorted.iter().map(|(key, value)| (key, value)),
+                &mut serializer,
+            )
+            .unwrap();
+            serializer
+                .resolve_aligned(With::<_, AsSortedVec>::cast(&sorted), resolver)
+                .unwrap();
+        }
+        let buf = serializer.into_serializer().into_inner();
+
+        assert_eq!(buf.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_sorted_index() {
+        use rkyv::with::AsSortedIndex;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Item {
+            name: String,
+            weight: u32,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            items: Vec<Item>,
+            #[with(AsSortedIndex)]
+            by_name: Vec<u32>,
+            #[with(AsSortedIndex)]
+            by_weight: Vec<u32>,
+        }
+
+        let items = vec![
+            Item {
+                name: "delta".to_string(),
+                weight: 10,
+            },
+            Item {
+                name: "alpha".to_string(),
+                weight: 40,
+            },
+            Item {
+                name: "charlie".to_string(),
+                weight: 20,
+            },
+            Item {
+                name: "bravo".to_string(),
+                weight: 30,
+            },
+        ];
+        let mut by_name = (0..items.len() as u32).collect::<Vec<_>>();
+        by_name.sort_by(|&a, &b| items[a as usize].name.cmp(&items[b as usize].name));
+        let mut by_weight = (0..items.len() as u32).collect::<Vec<_>>();
+        by_weight.sort_by_key(|&i| core::cmp::Reverse(items[i as usize].weight));
+
+        let value = Test {
+            items,
+            by_name,
+            by_weight,
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        assert_eq!(archived.by_name.len(), 4);
+        assert_eq!(archived.by_name, *value.by_name.as_slice());
+
+        let names = archived
+            .by_name
+            .iter(&archived.items)
+            .map(|item| item.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["alpha", "bravo", "charlie", "delta"]);
+
+        let charlie = archived
+            .by_name
+            .find_by_key(&archived.items, &"charlie", |item| item.name.as_str())
+            .unwrap();
+        assert_eq!(charlie.weight, 20);
+        assert_eq!(
+            archived
+                .by_name
+                .binary_search_by_key(&archived.items, &"bob", |item| item.name.as_str()),
+            Err(1)
+        );
+
+        let heaviest = archived
+            .by_weight
+            .iter(&archived.items)
+            .map(|item| item.weight)
+            .collect::<Vec<_>>();
+        assert_eq!(heaviest, vec![40, 30, 20, 10]);
+        let found = archived
+            .by_weight
+            .binary_search_by(&archived.items, |item| 30.cmp(&u32::from(item.weight)))
+            .unwrap();
+        assert_eq!(
+            archived.by_weight.get(&archived.items, found).unwrap().name,
+            "bravo"
+        );
+        assert_eq!(archived.by_weight.index_at(found), Some(3));
+        assert!(archived.by_weight.get(&archived.items, 4).is_none());
+
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn serializer_layers() {
+        use rkyv::{
+            impl_serializer_layer,
+            ser::{serializers::InternSerializer, SerializerLayer},
+        };
+
+        struct Hashing<S> {
+            inner: S,
+            hash: u64,
+        }
+
+        impl<S: Fallible> Fallible for Hashing<S> {
+            type Error = S::Error;
+        }
+
+        impl<S: Serializer> SerializerLayer for Hashing<S> {
+            type Inner = S;
+
+            fn inner(&self) -> &S {
+                &self.inner
+            }
+
+            fn inner_mut(&mut self) -> &mut S {
+                &mut self.inner
+            }
+
+            fn map_inner_error(error: S::Error) -> Self::Error {
+                error
+            }
+
+            fn write_layer(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+                for &b in bytes {
+                    self.hash = (self.hash ^ b as u64).wrapping_mul(0x100000001b3);
+                }
+                self.inner.write(bytes)
+            }
+        }
+
+        impl_serializer_layer!(Hashing);
+
+        #[derive(Debug)]
+        enum LimitError<E> {
+            Exceeded,
+            Inner(E),
+        }
+
+        struct Limit<S> {
+            inner: S,
+            limit: usize,
+        }
+
+        impl<S: Fallible> Fallible for Limit<S> {
+            type Error = LimitError<S::Error>;
+        }
+
+        impl<S: Serializer> SerializerLayer for Limit<S> {
+            type Inner = S;
+
+            fn inner(&self) -> &S {
+                &self.inner
+            }
+
+            fn inner_mut(&mut self) -> &mut S {
+                &mut self.inner
+            }
+
+            fn map_inner_error(error: S::Error) -> Self::Error {
+                LimitError::Inner(error)
+            }
+
+            fn write_layer(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+                if self.inner.pos() + bytes.len() > self.limit {
+                    Err(LimitError::Exceeded)
+                } else {
+                    self.inner.write(bytes).map_err(LimitError::Inner)
+                }
+            }
+        }
+
+        impl_serializer_layer!(Limit);
+
+        let value = vec!["hello".to_string(), "a longer string".to_string()];
+
+        let mut serializer = Hashing {
+            inner: Limit {
+                inner: DefaultSerializer::default(),
+                limit: 1024,
+            },
+            hash: 0xcbf29ce484222325,
+        };
+        let pos = serializer.serialize_value(&value).unwrap();
+        let hash = serializer.hash;
+        let buf = serializer.inner.inner.into_serializer().into_inner();
+
+        // The outer layer saw every byte, including padding
+        let mut expected = 0xcbf29ce484222325u64;
+        for &b in buf.iter() {
+            expected = (expected ^ b as u64).wrapping_mul(0x100000001b3);
+        }
+        assert_eq!(hash, expected);
+        assert_eq!(
+            pos + core::mem::size_of::<Archived<Vec<String>>>(),
+            buf.len()
+        );
+
+        let mut serializer = Limit {
+            inner: DefaultSerializer::default(),
+            limit: 8,
+        };
+        assert!(matches!(
+            serializer.serialize_value(&value),
+            Err(LimitError::Exceeded)
+        ));
+
+        // Capabilities of the inner serializers are forwarded through the layers
+        #[derive(Archive, Serialize)]
+        struct Tagged {
+            #[with(rkyv::with::Intern)]
+            tag: String,
+        }
+
+        let value = vec![
+            Tagged {
+                tag: "a".to_string(),
+            },
+            Tagged {
+                tag: "a".to_string(),
+            },
+        ];
+        let mut serializer = Hashing {
+            inner: InternSerializer::new(DefaultSerializer::default()),
+            hash: 0xcbf29ce484222325,
+        };
+        serializer.serialize_value(&value).unwrap();
+        assert_eq!(serializer.inner.table().len(), 1);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_result_log() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct Record {
+            id: u32,
+            tags: Vec<String>,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        #[archive_attr(derive(Debug))]
+        struct AuditError {
+            code: u16,
+            context: BTreeMap<String, Vec<u32>>,
+        }
+
+        // Deriving `Debug` for the archived type would require bounds on `T::Archived` and
+        // `E::Archived` that `derive(Debug)` can't express, so compare directly instead
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        struct Log<T, E> {
+            entries: Vec<Result<T, E>>,
+            nested: Vec<Result<Vec<T>, Option<Box<E>>>>,
+        }
+
+        let mut context = BTreeMap::new();
+        context.insert("retries".to_string(), vec![1, 2, 3]);
+        context.insert("offsets".to_string(), Vec::new());
+
+        let value = Log::<Record, AuditError> {
+            entries: vec![
+                Ok(Record {
+                    id: 1,
+                    tags: vec!["login".to_string(), "admin".to_string()],
+                }),
+                Err(AuditError { code: 403, context }),
+                Ok(Record {
+                    id: 2,
+                    tags: Vec::new(),
+                }),
+            ],
+            nested: vec![
+                Ok(vec![Record {
+                    id: 3,
+                    tags: vec!["logout".to_string()],
+                }]),
+                Err(None),
+                Err(Some(Box::new(AuditError {
+                    code: 500,
+                    context: BTreeMap::new(),
+                }))),
+            ],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Log<Record, AuditError>>(buf.as_ref()) };
+        assert!(*archived == value);
+
+        let deserialized: Log<Record, AuditError> = archived
+            .deserialize(&mut DefaultDeserializer::default())
+            .unwrap();
+        assert_eq!(deserialized, value);
+
+        let ids = archived
+            .entries
+            .iter()
+            .filter_map(|entry| entry.ok())
+            .map(|record| record.id)
+            .collect::<Vec<_>>();
+        assert!(ids == [1u32, 2]);
+
+        for entry in archived.entries.iter() {
+            match entry.as_ref() {
+                Ok(record) => assert_eq!(record.tags.is_empty(), record.id == 2),
+                Err(error) => {
+                    assert_eq!(error.code, 403);
+                    assert_eq!(error.context.get("retries").unwrap().len(), 3);
+                }
+            }
+        }
+
+        let error = archived.nested[2].err().unwrap().as_ref().unwrap();
+        assert_eq!(error.code, 500);
+        assert!(archived.nested[1].err().unwrap().is_none());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn vec_elements_are_contiguous() {
+        #[derive(Archive, Serialize)]
+        struct Item {
+            id: u32,
+            name: String,
+        }
+
+        let value = (0..16)
+            .map(|id| Item {
+                id,
+                name: format!("item name long enough to be out of line #{}", id),
+            })
+            .collect::<Vec<_>>();
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Vec<Item>>(buf.as_ref()) };
+
+        // All of the string data is written before the element array
+        let array_start = archived.as_ptr() as usize;
+        for item in archived.iter() {
+            assert!((item.name.as_ptr() as usize) < array_start);
+        }
+        assert!(archived.iter().zip(0u32..).all(|(item, id)| item.id == id));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn serialize_for_value() {
+        use rkyv::ArchiveFixedSize;
+
+        #[derive(Archive, Serialize)]
+        struct Sample {
+            id: u64,
+            flags: [bool; 3],
+            scale: Option<f32>,
+        }
+
+        // SAFETY: All of the fields of `Sample` implement `ArchiveFixedSize`.
+        unsafe impl ArchiveFixedSize for Sample {}
+
+        let value = Sample {
+            id: 42,
+            flags: [true, false, true],
+            scale: Some(1.5),
+        };
+
+        let mut serializer = AlignedSerializer::for_value::<Sample>();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_inner();
+        assert_eq!(buf.len(), Sample::ARCHIVED_SIZE);
+        assert!(buf.capacity() >= Sample::ARCHIVED_SIZE);
+
+        let archived = unsafe { archived_root::<Sample>(buf.as_ref()) };
+        assert_eq!(archived.id, 42);
+        assert_eq!(archived.flags, [true, false, true]);
+        assert_eq!(archived.scale, Some(1.5));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn ring_serializer() {
+        use rkyv::ser::serializers::{RingSerializer, RingSerializerError};
+
+        #[derive(Archive, Serialize)]
+        struct Event {
+            frame: u32,
+            name: String,
+            inputs: Vec<u8>,
+        }
+
+        let mut ring = RingSerializer::new(500);
+        assert_eq!(ring.capacity(), 512);
+        assert!(ring.is_empty());
+
+        for frame in 0..100u32 {
+            ring.serialize_record(&Event {
+                frame,
+                name: format!("event {}", frame),
+                inputs: (0..frame % 7).map(|i| i as u8).collect(),
+            })
+            .unwrap();
+
+            // The live records are always the most recent ones, in order
+            let frames = ring
+                .records()
+                .map(|bytes| {
+                    let archived = unsafe { archived_root::<Event>(bytes) };
+                    assert_eq!(archived.name, format!("event {}", archived.frame));
+                    assert_eq!(archived.inputs.len() as u32, archived.frame % 7);
+                    archived.frame
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(frames.len(), ring.len());
+            let first = frame + 1 - frames.len() as u32;
+            assert_eq!(frames, (first..=frame).collect::<Vec<_>>());
+        }
+        assert!(ring.len() > 1);
+
+        // Records larger than the ring are rejected without touching the live records
+        let len = ring.len();
+        let result = ring.serialize_record(&Event {
+            frame: 100,
+            name: "x".repeat(1000),
+            inputs: Vec::new(),
+        });
+        assert!(matches!(
+            result,
+            Err(RingSerializerError::RecordTooLarge { capacity: 512, .. })
+        ));
+        assert_eq!(ring.len(), len);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn stride_serializer() {
+        use rkyv::ser::serializers::{
+            StrideArray, StrideHeader, StrideSerializer, StrideSerializerError,
+        };
+
+        #[derive(Archive, Serialize)]
+        struct Row {
+            id: u32,
+            label: String,
+        }
+
+        let mut serializer = StrideSerializer::new(64);
+        assert!(serializer.is_empty());
+        for id in 0..10u32 {
+            serializer
+                .serialize_element(&Row {
+                    id,
+                    label: format!("row label number {}", id),
+                })
+                .unwrap();
+        }
+        assert_eq!(serializer.len(), 10);
+
+        // Elements that don't fit in the stride are rejected without changing the array
+        let result = serializer.serialize_element(&Row {
+            id: 10,
+            label: "x".repeat(100),
+        });
+        assert!(matches!(
+            result,
+            Err(StrideSerializerError::ElementTooLarge { stride: 64, .. })
+        ));
+        assert!(matches!(serializer.serialize_element(&[0u64; 4]), Ok(())));
+        assert_eq!(serializer.len(), 11);
+
+        let bytes = serializer.into_inner();
+        assert_eq!(bytes.len(), StrideHeader::LEN + 11 * 64);
+
+        let array = StrideArray::new(&bytes).unwrap();
+        assert_eq!(array.stride(), 64);
+        assert_eq!(array.len(), 11);
+        for id in 0..10u32 {
+            // Elements are located with pointer arithmetic from the start of the array
+            let start = StrideHeader::LEN + id as usize * 64;
+            let row = unsafe { archived_root::<Row>(&bytes[start..start + 64]) };
+            assert_eq!(row.id, id);
+            assert_eq!(row.label, format!("row label number {}", id));
+
+            let row = unsafe { array.get_archived::<Row>(id as usize) }.unwrap();
+            assert_eq!(row.id, id);
+            assert_eq!(array.get(id as usize).unwrap(), &bytes[start..start + 64]);
+        }
+        assert!(array.get(11).is_none());
+        assert!(StrideArray::new(&bytes[..bytes.len() - 1]).is_none());
+
+        // Strides must be a multiple of the element alignment
+        let mut serializer = StrideSerializer::new(6);
+        assert!(matches!(
+            serializer.serialize_element(&1u32),
+            Err(StrideSerializerError::InvalidAlignment {
+                align: 4,
+                stride: 6
+            })
+        ));
+        serializer.serialize_element(&1u16).unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn delta_serializer() {
+        use rkyv::{
+            archived_value,
+            ser::{serializers::DeltaSerializer, SharedSerializeRegistry},
+        };
+
+        #[derive(Archive, Serialize)]
+        struct Config {
+            name: Rc<String>,
+            version: u32,
+        }
+
+        let name = Rc::new("a name long enough to be out of line".to_string());
+
+        let mut serializer = DefaultSerializer::default();
+        let base_pos = serializer
+            .serialize_value(&Config {
+                name: name.clone(),
+                version: 1,
+            })
+            .unwrap();
+        let name_pos = serializer.get_shared(&*name).unwrap();
+        let base = serializer.into_serializer().into_inner();
+
+        // The delta reuses the name from the base instead of archiving it again
+        let mut serializer = DeltaSerializer::new(DefaultSerializer::default(), base.len());
+        serializer.add_shared(&*name, name_pos).unwrap();
+        let delta_pos = serializer
+            .serialize_value(&Config {
+                name: name.clone(),
+                version: 2,
+            })
+            .unwrap();
+        let delta = serializer.into_inner().into_serializer().into_inner();
+        assert!(delta.len() < name.len());
+
+        let mut combined = AlignedVec::new();
+        combined.extend_from_slice(&base);
+        combined.extend_from_slice(&delta);
+
+        let base_config = unsafe { archived_value::<Config>(&combined, base_pos) };
+        let delta_config = unsafe { archived_value::<Config>(&combined, delta_pos) };
+        assert_eq!(base_config.version, 1);
+        assert_eq!(delta_config.version, 2);
+        assert_eq!(delta_config.name.as_str(), name.as_str());
+        assert_eq!(delta_config.name.as_ptr(), base_config.name.as_ptr());
+
+        // The base can still be read on its own
+        let base_config = unsafe { archived_value::<Config>(&base, base_pos) };
+        assert_eq!(base_config.name.as_str(), name.as_str());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archived_size_hint() {
+        use rkyv::{size_hint::ArchivedSizeHint, with::AsBox};
+
+        #[derive(Archive, Serialize)]
+        #[archive(size_hint)]
+        enum Shape {
+            Point,
+            Circle(u32),
+            Polygon { name: String, points: Vec<[i32; 2]> },
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(
+            size_hint,
+            bound(serialize = "__S: rkyv::ser::ScratchSpace + Serializer")
+        )]
+        struct Layer {
+            name: String,
+            shapes: Vec<Shape>,
+            tags: BTreeMap<u32, String>,
+            parent: Option<Box<u64>>,
+            #[omit_bounds]
+            children: Vec<Layer>,
+            #[with(AsBox)]
+            cache: Vec<u8>,
+        }
+
+        fn check<T: ArchivedSizeHint + Serialize<DefaultSerializer>>(value: &T) {
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(value).unwrap();
+            let len = serializer.into_serializer().into_inner().len();
+            let hint = value.archived_size_hint();
+            assert!(hint >= len, "hint {} is smaller than {}", hint, len);
+            assert!(
+                hint <= 2 * len + 64,
+                "hint {} is much larger than {}",
+                hint,
+                len
+            );
+        }
+
+        check(&Shape::Point);
+        check(&Shape::Circle(3));
+        check(&Shape::Polygon {
+            name: "a polygon with a long name".to_string(),
+            points: vec![[0, 0], [1, 0], [1, 1]],
+        });
+
+        let mut tags = BTreeMap::new();
+        tags.insert(1, "first".to_string());
+        tags.insert(2, "a second tag long enough to be out of line".to_string());
+        let mut layer = Layer {
+            name: "root layer".to_string(),
+            shapes: vec![
+                Shape::Point,
+                Shape::Polygon {
+                    name: "triangle".to_string(),
+                    points: vec![[0, 0], [2, 0], [1, 2]],
+                },
+            ],
+            tags,
+            parent: Some(Box::new(7)),
+            children: vec![Layer {
+                name: "a child layer with a long name".to_string(),
+                shapes: vec![Shape::Circle(10)],
+                tags: BTreeMap::new(),
+                parent: None,
+                children: Vec::new(),
+                cache: Vec::new(),
+            }],
+            cache: vec![0; 4],
+        };
+        check(&layer);
+
+        // Fields archived with a wrapper are not included in the estimate
+        let hint = layer.archived_size_hint();
+        layer.cache = vec![0; 1024];
+        assert_eq!(layer.archived_size_hint(), hint);
+    }
+
+    #[test]
+    #[cfg(any(debug_assertions, feature = "checked_align"))]
+    #[should_panic(expected = "is not aligned to")]
+    fn misaligned_resolve_panics() {
+        let mut serializer = AlignedSerializer::new(AlignedVec::new());
+        serializer.write(&[0]).unwrap();
+        // Skip the alignment step that `serialize_value` would normally perform
+        unsafe {
+            serializer.resolve_aligned(&42u32, ()).unwrap();
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archived_vec_filtered_view() {
+        #[derive(Archive, Serialize)]
+        struct Reading {
+            sensor: u32,
+            value: i32,
+        }
+
+        let readings = (0..20)
+            .map(|i| Reading {
+                sensor: i % 4,
+                value: i as i32 * 10 - 50,
+            })
+            .collect::<Vec<_>>();
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&readings).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Vec<Reading>>(buf.as_ref()) };
+
+        // Filtering borrows the archived elements in place
+        let mut positive = archived.iter().filter(|r| r.value > 0);
+        let first = positive.next().unwrap();
+        assert!(core::ptr::eq(first, &archived[6]));
+        assert_eq!(positive.count(), 13);
+
+        // Standard adapters compose over the archived elements
+        let sensor_two = archived
+            .iter()
+            .filter(|r| r.sensor == 2)
+            .map(|r| i32::from(r.value))
+            .collect::<Vec<_>>();
+        assert_eq!(sensor_two, [-30, 10, 50, 90, 130]);
+        let total: i32 = archived
+            .iter()
+            .rev()
+            .take(4)
+            .map(|r| i32::from(r.value))
+            .sum();
+        assert_eq!(total, 140 + 130 + 120 + 110);
+
+        // References to archived vecs can be iterated directly
+        let mut count = 0;
+        for reading in archived {
+            assert!(u32::from(reading.sensor) < 4);
+            count += 1;
+        }
+        assert_eq!(count, archived.len());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn text_encoded_archive() {
+        use rkyv::util::{decode_base64, decode_hex, encode_base64, encode_hex, TextDecodeError};
+
+        assert_eq!(encode_hex(&[0x00, 0x7f, 0xab, 0xff]), "007fabff");
+        assert_eq!(
+            decode_hex("007FabfF").unwrap().as_slice(),
+            [0x00, 0x7f, 0xab, 0xff]
+        );
+        assert_eq!(encode_base64(b""), "");
+        assert_eq!(encode_base64(b"f"), "Zg==");
+        assert_eq!(encode_base64(b"fo"), "Zm8=");
+        assert_eq!(encode_base64(b"foo"), "Zm9v");
+        assert_eq!(encode_base64(b"foobar"), "Zm9vYmFy");
+        for len in 0..64 {
+            let bytes = (0..len).map(|i| (i * 37) as u8).collect::<Vec<_>>();
+            assert_eq!(decode_hex(&encode_hex(&bytes)).unwrap().as_slice(), bytes);
+            assert_eq!(
+                decode_base64(&encode_base64(&bytes)).unwrap().as_slice(),
+                bytes
+            );
+        }
+
+        assert_eq!(
+            decode_hex("abc").unwrap_err(),
+            TextDecodeError::InvalidLength(3)
+        );
+        assert_eq!(
+            decode_hex("0g").unwrap_err(),
+            TextDecodeError::InvalidCharacter {
+                index: 1,
+                character: 'g'
+            }
+        );
+        assert_eq!(
+            decode_base64("Zm9").unwrap_err(),
+            TextDecodeError::InvalidLength(3)
+        );
+        assert_eq!(
+            decode_base64("Z=9v").unwrap_err(),
+            TextDecodeError::InvalidCharacter {
+                index: 1,
+                character: '='
+            }
+        );
+        assert_eq!(
+            decode_base64("Zm\u{e9}").unwrap_err(),
+            TextDecodeError::InvalidCharacter {
+                index: 2,
+                character: '\u{e9}'
+            }
+        );
+
+        #[derive(Archive, Serialize)]
+        struct Embedded {
+            id: u64,
+            weights: Vec<u128>,
+            name: String,
+        }
+
+        let mut serializer = DefaultSerializer::default();
+        serializer
+            .serialize_value(&Embedded {
+                id: 42,
+                weights: vec![1, 2, 3],
+                name: "an embedded archive".to_string(),
+            })
+            .unwrap();
+        let bytes = serializer.into_serializer().into_inner();
+
+        for decoded in [
+            decode_hex(&encode_hex(&bytes)).unwrap(),
+            decode_base64(&encode_base64(&bytes)).unwrap(),
+        ]
+        .iter()
+        {
+            assert_eq!(decoded.as_ptr() as usize % AlignedVec::ALIGNMENT, 0);
+            let archived = unsafe { archived_root::<Embedded>(decoded) };
+            assert_eq!(archived.id, 42);
+            assert_eq!(archived.weights, [1, 2, 3]);
+            assert_eq!(archived.name, "an embedded archive");
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn access_embedded_archive() {
+        use core::mem::size_of;
+        use rkyv::access_at;
+
+        #[derive(Archive, Serialize)]
+        struct Section {
+            id: u32,
+            name: String,
+            values: Vec<u64>,
+        }
+
+        let sections = [
+            Section {
+                id: 1,
+                name: "the first embedded section".to_string(),
+                values: vec![1, 2, 3],
+            },
+            Section {
+                id: 2,
+                name: "the second embedded section".to_string(),
+                values: vec![4, 5, 6, 7],
+            },
+        ];
+
+        // Lay out a container with a 4KiB header followed by each archive at an aligned offset
+        let mut file = AlignedVec::new();
+        file.extend_from_slice(&[0xcc; 4096]);
+        let mut bases = Vec::new();
+        let mut ends = Vec::new();
+        for section in sections.iter() {
+            while file.len() % 16 != 0 {
+                file.push(0xcc);
+            }
+            bases.push(file.len());
+
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(section).unwrap();
+            file.extend_from_slice(serializer.into_serializer().into_inner().as_ref());
+            ends.push(file.len());
+        }
+
+        for ((section, &base), &end) in sections.iter().zip(bases.iter()).zip(ends.iter()) {
+            // The root is stored at the end of each embedded archive
+            let root_pos = end - base - size_of::<ArchivedSection>();
+            let archived = unsafe { access_at::<Section>(&file, base, root_pos) };
+            assert_eq!(archived.id, section.id);
+            assert_eq!(archived.name, section.name);
+            assert_eq!(archived.values.as_slice(), section.values.as_slice());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_nullable_vec() {
+        use rkyv::with::AsNullable;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive_attr(derive(Debug))]
+        struct Column {
+            #[with(AsNullable)]
+            values: Vec<Option<u64>>,
+        }
+
+        #[derive(Archive, Serialize)]
+        struct NaiveColumn {
+            values: Vec<Option<u64>>,
+        }
+
+        // Every third value is missing, spanning many bitmap blocks and a partial last block
+        let values = (0..1000u64)
+            .map(|i| if i % 3 == 0 { None } else { Some(i * i) })
+            .collect::<Vec<_>>();
+        let value = Column {
+            values: values.clone(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Column>(buf.as_ref()) };
+
+        assert_eq!(archived.values.len(), 1000);
+        assert_eq!(archived.values.null_count(), 334);
+        assert_eq!(archived.values.values().len(), 666);
+        for (i, expected) in values.iter().enumerate() {
+            assert_eq!(archived.values.get(i).map(|v| u64::from(*v)), *expected);
+            assert_eq!(archived.values.is_null(i), expected.is_none());
+        }
+        assert_eq!(archived.values.get(1000), None);
+        assert_eq!(
+            archived
+                .values
+                .iter()
+                .map(|v| v.map(|v| u64::from(*v)))
+                .collect::<Vec<_>>(),
+            values
+        );
+        assert!(archived.values == *values.as_slice());
+
+        let deserialized: Column = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        // Storing only the present values and one bit per element is much more compact than an
+        // archived `Vec<Option<u64>>`, which stores a tag and a full value slot per element
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&NaiveColumn { values }).unwrap();
+        let naive_len = serializer.pos();
+        assert!(buf.len() * 3 < naive_len * 2);
+
+        // Empty and all-null vectors
+        for values in [vec![], vec![None; 40]] {
+            let value = Column { values };
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(&value).unwrap();
+            let buf = serializer.into_serializer().into_inner();
+            let archived = unsafe { archived_root::<Column>(buf.as_ref()) };
+            assert_eq!(archived.values.len(), value.values.len());
+            assert!(archived.values.values().is_empty());
+            assert!(archived.values.iter().all(|v| v.is_none()));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_result_vec() {
+        use rkyv::with::AsResultVec;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive_attr(derive(Debug))]
+        struct AuditLog {
+            #[with(AsResultVec)]
+            entries: Vec<Result<(), u32>>,
+            #[with(AsResultVec)]
+            outputs: Vec<Result<u64, String>>,
+        }
+
+        #[derive(Archive, Serialize)]
+        struct NaiveAuditLog {
+            entries: Vec<Result<(), u32>>,
+        }
+
+        // Mostly successes, spanning many bitmap blocks and a partial last block
+        let entries = (0..1000u32)
+            .map(|i| if i % 50 == 7 { Err(i) } else { Ok(()) })
+            .collect::<Vec<_>>();
+        let outputs = (0..100u64)
+            .map(|i| {
+                if i % 3 == 0 {
+                    Err(format!("failed #{}", i))
+                } else {
+                    Ok(i * i)
+                }
+            })
+            .collect::<Vec<_>>();
+        let value = AuditLog {
+            entries: entries.clone(),
+            outputs: outputs.clone(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<AuditLog>(buf.as_ref()) };
+
+        assert_eq!(archived.entries.len(), 1000);
+        assert_eq!(archived.entries.err_count(), 20);
+        assert_eq!(archived.entries.ok_count(), 980);
+        for (i, expected) in entries.iter().enumerate() {
+            let actual = archived.entries.get(i).unwrap();
+            assert_eq!(actual.map(|_| ()).map_err(|e| u32::from(*e)), *expected);
+            assert_eq!(archived.entries.is_err(i), expected.is_err());
+            assert_eq!(archived.entries.is_ok(i), expected.is_ok());
+        }
+        assert!(archived.entries.get(1000).is_none());
+        assert_eq!(
+            archived
+                .entries
+                .errs()
+                .iter()
+                .map(|e| u32::from(*e))
+                .collect::<Vec<_>>(),
+            (0..20).map(|i| i * 50 + 7).collect::<Vec<_>>()
+        );
+        assert!(archived.entries == *entries.as_slice());
+
+        assert_eq!(archived.outputs.iter().len(), 100);
+        for (actual, expected) in archived.outputs.iter().zip(outputs.iter()) {
+            match (actual, expected) {
+                (Ok(a), Ok(b)) => assert_eq!(a, b),
+                (Err(a), Err(b)) => assert_eq!(a, b),
+                _ => panic!("mismatched result variants"),
+            }
+        }
+        assert!(archived.outputs == *outputs.as_slice());
+
+        let deserialized: AuditLog = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        // Storing one bit per entry and only the errors is much more compact than an archived
+        // `Vec<Result<(), u32>>`, which stores a tag and room for an error in every entry
+        let mut serializer = DefaultSerializer::default();
+        serializer
+            .serialize_value(&NaiveAuditLog { entries })
+            .unwrap();
+        let naive_len = serializer.pos();
+        assert!(naive_len >= 1000 * 8);
+        assert!(buf.len() * 4 < naive_len);
+
+        // Empty, all-ok, and all-err vectors
+        for entries in [vec![], vec![Ok(()); 40], vec![Err(3); 40]] {
+            let value = AuditLog {
+                entries,
+                outputs: Vec::new(),
+            };
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(&value).unwrap();
+            let buf = serializer.into_serializer().into_inner();
+            let archived = unsafe { archived_root::<AuditLog>(buf.as_ref()) };
+            assert!(archived.entries == *value.entries.as_slice());
+            assert_eq!(
+                archived.entries.err_count(),
+                value.entries.iter().filter(|e| e.is_err()).count()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_trailing_tag_enum() {
+        use core::mem::{align_of, size_of};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(tag_position = "trailing", compare(PartialEq))]
+        enum Shape {
+            Empty,
+            Circle { radius: u32 },
+            Polygon(Vec<u32>, u8),
+        }
+
+        for value in [
+            Shape::Empty,
+            Shape::Circle { radius: 42 },
+            Shape::Polygon(vec![1, 2, 3, 4], 7),
+        ] {
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(&value).unwrap();
+            let buf = serializer.into_serializer().into_inner();
+            let archived = unsafe { archived_root::<Shape>(buf.as_ref()) };
+
+            assert!(*archived == value);
+            assert!(value == *archived);
+            match (archived.as_ref(), &value) {
+                (ArchivedShapeRef::Empty, Shape::Empty) => (),
+                (ArchivedShapeRef::Circle { radius }, Shape::Circle { radius: expected }) => {
+                    assert_eq!(*radius, *expected);
+                }
+                (ArchivedShapeRef::Polygon(points, sides), Shape::Polygon(expected, _)) => {
+                    assert_eq!(points.as_slice(), expected.as_slice());
+                    assert_eq!(*sides, 7);
+                }
+                _ => panic!("archived the wrong variant"),
+            }
+
+            let deserialized: Shape = archived.deserialize(&mut Infallible).unwrap();
+            assert_eq!(deserialized, value);
+        }
+
+        // The payload starts at offset 0 and the tag follows the largest variant
+        #[derive(Archive, Serialize)]
+        #[archive(tag_position = "trailing")]
+        enum Sample {
+            A(u64),
+            B(u8, u32),
+        }
+
+        #[derive(Archive, Serialize)]
+        enum LeadingSample {
+            A(u64),
+            B(u8, u32),
+        }
+
+        assert_eq!(size_of::<ArchivedSample>(), 16);
+        assert_eq!(align_of::<ArchivedSample>(), 8);
+        assert_eq!(
+            size_of::<ArchivedSample>(),
+            size_of::<ArchivedLeadingSample>()
+        );
+
+        for (value, leading) in [
+            (Sample::A(u64::MAX), LeadingSample::A(u64::MAX)),
+            (Sample::B(3, 4), LeadingSample::B(3, 4)),
+        ] {
+            let tag = match value {
+                Sample::A(_) => 0,
+                Sample::B(..) => 1,
+            };
+
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(&value).unwrap();
+            let buf = serializer.into_serializer().into_inner();
+            let bytes = &buf[buf.len() - size_of::<ArchivedSample>()..];
+            assert_eq!(bytes[8], tag);
+            if tag == 1 {
+                assert_eq!(bytes[0], 3);
+            }
+
+            // Leading tags come before the payload instead
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(&leading).unwrap();
+            let buf = serializer.into_serializer().into_inner();
+            let bytes = &buf[buf.len() - size_of::<ArchivedLeadingSample>()..];
+            assert_eq!(bytes[0], tag);
+        }
+
+        // Multibyte tags are archived with the archive's endianness
+        #[derive(Archive, Serialize)]
+        #[archive(tag_position = "trailing")]
+        #[archive_attr(repr(u16))]
+        enum Wide {
+            A(u8),
+            B(u8),
+        }
+
+        assert_eq!(size_of::<ArchivedWide>(), 4);
+
+        for (value, tag) in [(Wide::A(5), 0u16), (Wide::B(5), 1u16)] {
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(&value).unwrap();
+            let buf = serializer.into_serializer().into_inner();
+            let bytes = &buf[buf.len() - size_of::<ArchivedWide>()..];
+            let expected = if cfg!(feature = "archive_be") {
+                tag.to_be_bytes()
+            } else if cfg!(feature = "archive_le") {
+                tag.to_le_bytes()
+            } else {
+                tag.to_ne_bytes()
+            };
+            assert_eq!(bytes[0], 5);
+            assert_eq!(bytes[2..], expected);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_delta_vec() {
+        use rkyv::with::AsDeltaVec;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Samples {
+            #[with(AsDeltaVec)]
+            timestamps: Vec<u64>,
+            #[with(AsDeltaVec)]
+            offsets: Vec<i16>,
+        }
+
+        // Increasing timestamps and offsets that wrap around in both directions
+        let value = Samples {
+            timestamps: (0..1000u64)
+                .map(|i| 1_650_000_000_000 + i * 1_000 + i % 7)
+                .collect(),
+            offsets: (0..200i16)
+                .map(|i| {
+                    if i % 2 == 0 {
+                        i16::MAX - i
+                    } else {
+                        i16::MIN + i
+                    }
+                })
+                .collect(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Samples>(buf.as_ref()) };
+
+        assert_eq!(archived.timestamps.len(), 1000);
+        for (i, expected) in value.timestamps.iter().enumerate() {
+            assert_eq!(archived.timestamps.get(i), Some(*expected));
+        }
+        assert_eq!(archived.timestamps.get(1000), None);
+        assert!(archived
+            .timestamps
+            .iter()
+            .eq(value.timestamps.iter().copied()));
+        assert!(archived.offsets == *value.offsets.as_slice());
+
+        // Differences of about a thousand take two bytes each
+        assert_eq!(archived.timestamps.delta_bytes().len(), 2 * (1000 - 16));
+
+        let deserialized: Samples = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        // Empty and single-block vectors
+        for timestamps in [vec![], vec![7], (0..64).collect()] {
+            let value = Samples {
+                timestamps,
+                offsets: vec![],
+            };
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(&value).unwrap();
+            let buf = serializer.into_serializer().into_inner();
+            let archived = unsafe { archived_root::<Samples>(buf.as_ref()) };
+            assert!(archived.timestamps == *value.timestamps.as_slice());
+            assert!(archived.offsets.is_empty());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_rle_vec() {
+        use core::mem::size_of;
+        use rkyv::with::AsRleVec;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Readings {
+            #[with(AsRleVec)]
+            states: Vec<u32>,
+            #[with(AsRleVec)]
+            labels: Vec<String>,
+        }
+
+        #[derive(Archive, Serialize)]
+        struct NaiveReadings {
+            states: Vec<u32>,
+        }
+
+        let value = Readings {
+            states: (0..1000u32)
+                .map(|i| i / 100 + (i % 100 == 50) as u32)
+                .collect(),
+            labels: vec!["idle".to_string(), "idle".to_string(), "busy".to_string()],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Readings>(buf.as_ref()) };
+
+        assert_eq!(archived.states.len(), 1000);
+        assert_eq!(archived.states.run_count(), 30);
+        for (i, expected) in value.states.iter().enumerate() {
+            assert_eq!(
+                archived.states.get(i).map(|v| u32::from(*v)),
+                Some(*expected)
+            );
+        }
+        assert_eq!(archived.states.get(1000), None);
+        assert!(archived.states.iter().eq(value.states.iter()));
+        assert_eq!(
+            archived.states.runs().map(|(_, len)| len).sum::<usize>(),
+            1000
+        );
+        assert_eq!(
+            archived
+                .labels
+                .runs()
+                .map(|(v, len)| (v.as_str(), len))
+                .collect::<Vec<_>>(),
+            [("idle", 2), ("busy", 1)]
+        );
+        assert!(archived.labels == *value.labels.as_slice());
+
+        let deserialized: Readings = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        // Thirty runs take much less space than a thousand values
+        let mut serializer = DefaultSerializer::default();
+        serializer
+            .serialize_value(&NaiveReadings {
+                states: value.states.clone(),
+            })
+            .unwrap();
+        assert_eq!(
+            serializer.pos(),
+            1000 * size_of::<u32>() + size_of::<Archived<Vec<u32>>>()
+        );
+        assert!(buf.len() * 5 < serializer.pos());
+
+        // Empty vectors
+        let value = Readings {
+            states: vec![],
+            labels: vec![],
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Readings>(buf.as_ref()) };
+        assert!(archived.states.is_empty());
+        assert_eq!(archived.states.get(0), None);
+        assert_eq!(archived.states.iter().count(), 0);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn profile_archive_size() {
+        use core::{any::type_name, mem::size_of};
+        use rkyv::{
+            ser::serializers::{ArchiveProfile, ProfilingSerializer},
+            string::repr::INLINE_CAPACITY,
+        };
+
+        #[derive(Archive, Serialize)]
+        struct Item {
+            id: u32,
+            name: String,
+        }
+
+        #[derive(Archive, Serialize)]
+        struct Catalog {
+            items: Vec<Item>,
+            title: String,
+            counts: Vec<u16>,
+        }
+
+        let value = Catalog {
+            items: (0..10)
+                .map(|i| Item {
+                    id: i,
+                    name: format!("an item name that is stored out-of-line #{}", i),
+                })
+                .collect(),
+            title: "short".to_string(),
+            counts: (0..100).collect(),
+        };
+
+        let mut serializer = ProfilingSerializer::new(DefaultSerializer::default());
+        serializer.serialize_value(&value).unwrap();
+        let (serializer, profile) = serializer.into_parts();
+        let buf = serializer.into_serializer().into_inner();
+
+        // Every byte of the archive is attributed to some type
+        assert_eq!(profile.total_bytes(), buf.len());
+        assert_eq!(profile.get(ArchiveProfile::UNTYPED), 0);
+
+        // Out-of-line string bytes are attributed to `str`, and inline strings to their container
+        let out_of_line_len = |s: &String| {
+            if s.len() > INLINE_CAPACITY {
+                s.len()
+            } else {
+                0
+            }
+        };
+        let str_len = value
+            .items
+            .iter()
+            .map(|i| out_of_line_len(&i.name))
+            .sum::<usize>()
+            + out_of_line_len(&value.title);
+        assert_eq!(profile.get("str"), str_len);
+
+        // Each archived item is attributed to the item type
+        assert_eq!(
+            profile.get(type_name::<Item>()),
+            value.items.len() * size_of::<ArchivedItem>()
+        );
+
+        // The root is attributed to the catalog, and the counts are attributed either to the
+        // catalog (when copied as plain bytes) or to each element
+        assert_eq!(
+            profile.get(type_name::<Catalog>()) + profile.get(type_name::<u16>()),
+            size_of::<ArchivedCatalog>() + value.counts.len() * size_of::<u16>()
+        );
+
+        let sorted = profile.sorted_by_size();
+        assert_eq!(sorted.len(), profile.iter().len());
+        assert!(sorted.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert!(format!("{}", profile).contains("str"));
+
+        // The markers are ignored by other serializers
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        assert_eq!(
+            serializer.into_serializer().into_inner().as_slice(),
+            buf.as_slice()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_bytes() {
+        use core::mem::size_of;
+        use rkyv::{
+            bytes::{ArchivedByteArray, ArchivedBytes},
+            vec::ArchivedVec,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive(compare(PartialEq))]
+        struct Packet {
+            #[archive(bytes)]
+            magic: [u8; 4],
+            #[archive(bytes)]
+            payload: Vec<u8>,
+            checksum: u32,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Raw(#[archive(bytes)] Vec<u8>);
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        enum Message {
+            Empty,
+            Data {
+                #[archive(bytes)]
+                bytes: Vec<u8>,
+            },
+        }
+
+        // The archived types use the raw byte layouts
+        fn archived_bytes(value: &ArchivedPacket) -> (&ArchivedByteArray<4>, &ArchivedBytes) {
+            (&value.magic, &value.payload)
+        }
+        assert_eq!(size_of::<ArchivedBytes>(), size_of::<ArchivedVec<u8>>());
+        assert_eq!(size_of::<ArchivedByteArray<4>>(), 4);
+
+        let value = Packet {
+            magic: *b"RKYV",
+            payload: (0..=255).collect(),
+            checksum: 0xdeadbeef,
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Packet>(buf.as_ref()) };
+
+        let (magic, payload) = archived_bytes(archived);
+        assert_eq!(magic.as_slice(), b"RKYV");
+        assert_eq!(*magic, *b"RKYV");
+        assert_eq!(payload.as_slice(), value.payload.as_slice());
+        assert_eq!(payload.len(), 256);
+        assert_eq!(*payload, value.payload);
+        assert!(*archived == value);
+
+        // The payload is stored as a single contiguous region of the archive
+        assert!(buf
+            .as_slice()
+            .windows(256)
+            .any(|window| window == value.payload.as_slice()));
+
+        let deserialized: Packet = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        let value = Raw(vec![1, 2, 3]);
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Raw>(buf.as_ref()) };
+        assert_eq!(archived.0.as_slice(), &[1, 2, 3]);
+        let deserialized: Raw = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        let value = Message::Data {
+            bytes: b"hello world".to_vec(),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Message>(buf.as_ref()) };
+        match archived {
+            ArchivedMessage::Data { bytes } => assert_eq!(bytes.as_slice(), b"hello world"),
+            ArchivedMessage::Empty => panic!("expected data"),
+        }
+        let deserialized: Message = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_preserve_capacity() {
+        use core::mem::size_of;
+        use rkyv::{
+            vec::{ArchivedVec, ArchivedVecWithCapacity},
+            with::PreserveCapacity,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct State {
+            #[archive(preserve_capacity)]
+            names: Vec<String>,
+            ids: Vec<u32>,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Buffer(#[with(PreserveCapacity)] Vec<u16>);
+
+        // Preserving the capacity costs one extra word
+        assert_eq!(
+            size_of::<ArchivedVecWithCapacity<u32>>(),
+            size_of::<ArchivedVec<u32>>() + size_of::<Archived<usize>>()
+        );
+
+        let mut names = Vec::with_capacity(100);
+        names.push("a".to_string());
+        names.push("b".to_string());
+        let mut ids = Vec::with_capacity(100);
+        ids.push(1);
+        let value = State { names, ids };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<State>(buf.as_ref()) };
+        assert_eq!(archived.names.len(), 2);
+        assert_eq!(archived.names.capacity(), 100);
+        assert_eq!(archived.names[1], "b");
+        assert_eq!(
+            archived.names.as_vec().as_slice(),
+            archived.names.as_slice()
+        );
+
+        let deserialized: State = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+        assert!(deserialized.names.capacity() >= 100);
+        // Vecs without the option are deserialized with only the space they need
+        assert_eq!(deserialized.ids.capacity(), 1);
+
+        let value = Buffer(Vec::with_capacity(16));
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Buffer>(buf.as_ref()) };
+        assert!(archived.0.is_empty());
+        assert_eq!(archived.0.capacity(), 16);
+
+        let mut deserialized: Buffer = archived.deserialize(&mut Infallible).unwrap();
+        assert!(deserialized.0.capacity() >= 16);
+        let ptr = deserialized.0.as_ptr();
+        deserialized.0.extend(0..16);
+        // Growing up to the preserved capacity doesn't reallocate
+        assert_eq!(deserialized.0.as_ptr(), ptr);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_inline_vec() {
+        use core::mem::size_of;
+        use rkyv::{
+            collections::ArchivedInlineVec,
+            ser::serializers::CompositeSerializerError,
+            with::{AsInlineVec, AsInlineVecError},
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Node {
+            id: u32,
+            #[archive(inline(capacity = 4))]
+            neighbors: Vec<u32>,
+            #[with(AsInlineVec<2>)]
+            names: Vec<String>,
+        }
+
+        // The elements are stored in the archived value itself
+        assert!(
+            size_of::<ArchivedInlineVec<Archived<u32>, 4>>()
+                >= size_of::<Archived<usize>>() + 4 * size_of::<Archived<u32>>()
+        );
+
+        let value = Node {
+            id: 1,
+            neighbors: vec![2, 3, 4],
+            names: vec!["a".to_string(), "b".to_string()],
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Node>(buf.as_ref()) };
+        assert_eq!(archived.neighbors.len(), 3);
+        assert_eq!(archived.neighbors.capacity(), 4);
+        assert_eq!(archived.neighbors, [2, 3, 4][..]);
+        assert_eq!(archived.names[1], "b");
+
+        let start = archived as *const ArchivedNode as usize;
+        let end = start + size_of::<ArchivedNode>();
+        let first = archived.neighbors.as_ptr() as usize;
+        assert!(first >= start && first < end);
+
+        // The unused capacity is zeroed
+        let unused = first + 3 * size_of::<Archived<u32>>() - buf.as_ptr() as usize;
+        assert_eq!(buf[unused..unused + size_of::<Archived<u32>>()], [0; 4]);
+
+        let deserialized: Node = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        // Vecs that don't fit in the capacity fail to serialize
+        let value = Node {
+            id: 2,
+            neighbors: vec![1, 2, 3, 4, 5],
+            names: Vec::new(),
+        };
+        let mut serializer = DefaultSerializer::default();
+        match serializer.serialize_value(&value) {
+            Err(CompositeSerializerError::InlineVecError(AsInlineVecError::CapacityExceeded {
+                len,
+                capacity,
+            })) => {
+                assert_eq!(len, 5);
+                assert_eq!(capacity, 4);
+            }
+            result => panic!("expected a capacity error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_copy_with_pointers() {
+        #[derive(Archive, Serialize)]
+        #[archive(copy)]
+        struct Named {
+            id: u32,
+            name: String,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive(copy)]
+        enum Tree {
+            Leaf(u32),
+            Node(Vec<u32>),
+        }
+
+        // Only compiles if `T` is neither `Clone` nor `Copy`, otherwise the call is ambiguous
+        trait AmbiguousIfClone<A> {
+            fn check() {}
+        }
+        impl<T: ?Sized> AmbiguousIfClone<()> for T {}
+        impl<T: ?Sized + Clone> AmbiguousIfClone<u8> for T {}
+
+        // Copying an archived value with relative pointers would break its offsets
+        <ArchivedNamed as AmbiguousIfClone<_>>::check();
+        <ArchivedTree as AmbiguousIfClone<_>>::check();
+
+        let value = Named {
+            id: 42,
+            name: "hello world".to_string(),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Named>(buf.as_ref()) };
+        assert_eq!(archived.id, 42);
+        assert_eq!(archived.name, "hello world");
+
+        for value in [Tree::Leaf(1), Tree::Node(vec![1, 2, 3])] {
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(&value).unwrap();
+            let buf = serializer.into_serializer().into_inner();
+            let archived = unsafe { archived_root::<Tree>(buf.as_ref()) };
+            match (archived, &value) {
+                (ArchivedTree::Leaf(archived), Tree::Leaf(expected)) => {
+                    assert_eq!(archived, expected)
+                }
+                (ArchivedTree::Node(archived), Tree::Node(expected)) => {
+                    assert_eq!(archived.len(), expected.len())
+                }
+                _ => panic!("archived the wrong variant"),
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_front_coded_vec() {
+        use rkyv::with::AsFrontCodedVec;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Dictionary {
+            #[with(AsFrontCodedVec)]
+            words: Vec<String>,
+            #[with(AsFrontCodedVec<3>)]
+            paths: Vec<String>,
+        }
+
+        let mut words = (0..100)
+            .map(|i| format!("word{}", i * 7))
+            .chain(["", "wor", "\u{1f980}", "\u{1f980}\u{1f981}", "\u{1f981}"].map(String::from))
+            .collect::<Vec<_>>();
+        words.sort();
+        let value = Dictionary {
+            words,
+            paths: vec![
+                "/usr/bin/cargo".to_string(),
+                "/usr/bin/rustc".to_string(),
+                "/usr/bin/rustdoc".to_string(),
+                "/usr/lib/librustc_driver.so".to_string(),
+            ],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Dictionary>(buf.as_ref()) };
+
+        assert_eq!(archived.words.len(), 105);
+        assert_eq!(archived.words.restart_interval(), 16);
+        for (i, word) in value.words.iter().enumerate() {
+            assert_eq!(archived.words.get(i).as_ref(), Some(word));
+            assert_eq!(archived.words.index_of(word), Some(i));
+        }
+        assert_eq!(archived.words.get(105), None);
+        assert!(archived.words.iter().eq(value.words.iter().cloned()));
+        assert!(archived.words == *value.words.as_slice());
+        for missing in ["word", "word1", "word700", "zzz", "\u{1f980}\u{1f980}"] {
+            assert!(!archived.words.contains(missing));
+        }
+
+        // Each string after the first of its block only stores what's different from the one
+        // before it
+        assert_eq!(archived.paths.restart_interval(), 3);
+        assert_eq!(
+            archived.paths.data_bytes(),
+            b"\x00\x0e/usr/bin/cargo\x09\x05rustc\x0d\x03doc\x00\x1b/usr/lib/librustc_driver.so"
+        );
+        assert_eq!(archived.paths.index_of("/usr/bin/rustdoc"), Some(2));
+        assert_eq!(archived.paths.index_of("/usr/bin/rust"), None);
+
+        let deserialized: Dictionary = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        // Empty vectors and vectors that end on a block boundary
+        for words in [vec![], vec!["a".to_string()], value.words[..48].to_vec()] {
+            let value = Dictionary {
+                words,
+                paths: Vec::new(),
+            };
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(&value).unwrap();
+            let buf = serializer.into_serializer().into_inner();
+            let archived = unsafe { archived_root::<Dictionary>(buf.as_ref()) };
+            assert!(archived.words.iter().eq(value.words.iter().cloned()));
+            assert_eq!(
+                archived.words.index_of("a"),
+                value.words.iter().position(|w| w == "a")
+            );
+        }
+    }
 }
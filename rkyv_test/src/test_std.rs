@@ -2,9 +2,9 @@
 mod tests {
     use crate::util::alloc::*;
     use rkyv::{
-        archived_root,
+        archived_root, archived_value,
         ser::{serializers::WriteSerializer, Serializer},
-        AlignedBytes, Archive, Deserialize, Serialize,
+        AlignedBytes, AlignedVec, Archive, Deserialize, Serialize,
     };
     use std::collections::{HashMap, HashSet};
 
@@ -27,6 +27,78 @@ mod tests {
             .expect_err("serialized to an undersized buffer must fail");
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn mapped_serializer_write() {
+        use rkyv::ser::serializers::MappedSerializer;
+        use std::io::Write;
+
+        #[repr(C, align(256))]
+        struct Staging([u8; 512]);
+
+        let mut staging = Staging([0; 512]);
+        let mut flushed = Vec::new();
+        let mut serializer = unsafe {
+            MappedSerializer::new(staging.0.as_mut_ptr(), 300, |range| flushed.push(range))
+        };
+
+        // Writes are cut short at the end of the mapped memory, and `flush` calls the flush hook
+        serializer.write_all(&[7; 100]).unwrap();
+        Write::flush(&mut serializer).unwrap();
+        assert_eq!(Write::write(&mut serializer, &[8; 250]).unwrap(), 200);
+        assert_eq!(
+            serializer.write_all(&[9]).unwrap_err().kind(),
+            std::io::ErrorKind::WriteZero
+        );
+        assert_eq!(serializer.finish().unwrap(), 300);
+
+        assert_eq!(flushed, [0..256, 0..300]);
+        assert!(staging.0[..100].iter().all(|&b| b == 7));
+        assert!(staging.0[100..300].iter().all(|&b| b == 8));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn write_serializer_append() {
+        use std::io::Cursor;
+
+        #[derive(Archive, Serialize)]
+        struct Record {
+            id: u64,
+            message: String,
+        }
+
+        // A log that already holds an unaligned amount of data
+        let mut log = Cursor::new(vec![0xaa; 5]);
+        log.set_position(0);
+
+        let mut positions = Vec::new();
+        for id in 0..3 {
+            let mut serializer = WriteSerializer::append(&mut log).unwrap();
+            let start = serializer.pos();
+            let pos = serializer
+                .serialize_value(&Record {
+                    id,
+                    message: format!("record number {} in the log", id),
+                })
+                .unwrap();
+            assert!(pos >= start);
+            positions.push(pos);
+        }
+
+        let log = log.into_inner();
+        assert_eq!(&log[..5], &[0xaa; 5]);
+
+        let mut buf = AlignedVec::new();
+        buf.extend_from_slice(&log);
+        for (id, &pos) in positions.iter().enumerate() {
+            assert_eq!(pos % core::mem::align_of::<ArchivedRecord>(), 0);
+            let archived = unsafe { archived_value::<Record>(buf.as_ref(), pos) };
+            assert_eq!(archived.id, id as u64);
+            assert_eq!(archived.message, format!("record number {} in the log", id));
+        }
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn archive_hash_map() {
@@ -66,6 +138,222 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn index_archived_hash_map() {
+        use core::pin::Pin;
+        use rkyv::archived_root_mut;
+
+        let mut value = HashMap::new();
+        value.insert("a".to_string(), 1u32);
+        value.insert("b".to_string(), 2u32);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+
+        let archived = unsafe { archived_root::<HashMap<String, u32>>(buf.as_ref()) };
+        assert_eq!(archived["a"], 1);
+        assert_eq!(archived["b"], 2);
+
+        // Primitive values can be updated in place through a pinned reference
+        let mut archived =
+            unsafe { archived_root_mut::<HashMap<String, u32>>(Pin::new(buf.as_mut())) };
+        *archived.as_mut().get_pin("a").unwrap().get_mut() = 10u32.into();
+
+        // Or through a mutable reference, which is unsafe to get from a pinned reference
+        let archived = unsafe { archived.get_unchecked_mut() };
+        archived["b"] = 20u32.into();
+
+        let archived = unsafe { archived_root::<HashMap<String, u32>>(buf.as_ref()) };
+        assert_eq!(archived["a"], 10);
+        assert_eq!(archived["b"], 20);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    #[should_panic(expected = "no entry found for key")]
+    fn index_archived_hash_map_missing_key() {
+        let mut value = HashMap::new();
+        value.insert("a".to_string(), 1u32);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<HashMap<String, u32>>(buf.as_ref()) };
+        let _ = archived["b"];
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_hash_map_join_archived_keys() {
+        #[derive(Archive, Serialize, Clone, Hash, PartialEq, Eq)]
+        #[archive_attr(derive(Hash, PartialEq, Eq))]
+        struct Sku {
+            vendor: u16,
+            code: u32,
+        }
+
+        #[derive(Archive, Serialize)]
+        struct Order {
+            customer: String,
+            sku: Sku,
+            quantity: u32,
+        }
+
+        #[derive(Archive, Serialize)]
+        struct Catalog {
+            customers: HashMap<String, String>,
+            prices: HashMap<Sku, u32>,
+        }
+
+        fn serialize<T: Serialize<DefaultSerializer>>(value: &T) -> rkyv::AlignedVec {
+            let mut serializer = DefaultSerializer::default();
+            serializer.serialize_value(value).unwrap();
+            serializer.into_serializer().into_inner()
+        }
+
+        let sku = |vendor, code| Sku { vendor, code };
+        let orders = vec![
+            Order {
+                customer: "alice".to_string(),
+                sku: sku(1, 100),
+                quantity: 2,
+            },
+            Order {
+                customer: "a customer with a long name".to_string(),
+                sku: sku(2, 100),
+                quantity: 1,
+            },
+            Order {
+                customer: "mallory".to_string(),
+                sku: sku(1, 200),
+                quantity: 5,
+            },
+        ];
+        let catalog = Catalog {
+            customers: vec![
+                ("alice".to_string(), "north".to_string()),
+                (
+                    "a customer with a long name".to_string(),
+                    "south".to_string(),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            prices: vec![(sku(1, 100), 25), (sku(2, 100), 40), (sku(3, 300), 99)]
+                .into_iter()
+                .collect(),
+        };
+
+        let orders_buf = serialize(&orders);
+        let catalog_buf = serialize(&catalog);
+        let archived_orders = unsafe { archived_root::<Vec<Order>>(orders_buf.as_ref()) };
+        let archived_catalog = unsafe { archived_root::<Catalog>(catalog_buf.as_ref()) };
+
+        // Keys archived in one buffer are used to probe the maps archived in another
+        let joined = archived_orders
+            .iter()
+            .filter_map(|order| {
+                let region = archived_catalog.customers.get(&order.customer)?;
+                let price = archived_catalog.prices.get(&order.sku)?;
+                Some((region.as_str(), price * order.quantity))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(joined, vec![("north", 50), ("south", 40)]);
+
+        assert!(archived_catalog
+            .prices
+            .contains_key(&archived_orders[0].sku));
+        assert!(!archived_catalog
+            .customers
+            .contains_key(&archived_orders[2].customer));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_hash_map_prehashed() {
+        use rkyv::collections::{hash_index::ArchivedHashIndex, ArchivedHashMap};
+
+        let mut hash_map = HashMap::new();
+        for i in 0..64 {
+            hash_map.insert(format!("key #{}", i), i);
+        }
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&hash_map).unwrap();
+        let expected = serializer.into_serializer().into_inner();
+
+        let hashes = hash_map
+            .keys()
+            .map(ArchivedHashIndex::hash_key)
+            .collect::<Vec<_>>();
+        let mut serializer = DefaultSerializer::default();
+        unsafe {
+            let resolver = ArchivedHashMap::serialize_from_prehashed_iter(
+                hashes
+                    .iter()
+                    .copied()
+                    .zip(hash_map.iter())
+                    .map(|(h, (k, v))| (h, k, v)),
+                &mut serializer,
+            )
+            .unwrap();
+            serializer.resolve_aligned(&hash_map, resolver).unwrap();
+        }
+        let buf = serializer.into_serializer().into_inner();
+
+        assert_eq!(buf.as_slice(), expected.as_slice());
+        let archived = unsafe { archived_root::<HashMap<String, i32>>(buf.as_ref()) };
+        for (key, value) in hash_map.iter() {
+            assert_eq!(archived[key.as_str()], *value);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_hash_map_compact() {
+        use core::mem::size_of;
+        use rkyv::{collections::util::Entry, Archived};
+
+        // A map with lots of spare capacity and removed entries
+        let mut hash_map = HashMap::with_capacity(1024);
+        for i in 0..1000u32 {
+            hash_map.insert(i, i * 2);
+        }
+        for i in 0..900u32 {
+            hash_map.remove(&i);
+        }
+        assert!(hash_map.capacity() >= 1024);
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&hash_map).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+
+        // The archive only holds one displacement and one entry per key, plus the root
+        let len = hash_map.len();
+        let expected_len = len * size_of::<Archived<u32>>()
+            + len * size_of::<Entry<Archived<u32>, Archived<u32>>>()
+            + size_of::<Archived<HashMap<u32, u32>>>();
+        assert_eq!(buf.len(), expected_len);
+        assert!(buf.len() < hash_map.capacity() * size_of::<(u32, u32)>());
+
+        // The same entries archive to the same size no matter how the source map was built
+        let compact = hash_map
+            .iter()
+            .map(|(k, v)| (*k, *v))
+            .collect::<HashMap<_, _>>();
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&compact).unwrap();
+        assert_eq!(serializer.pos(), buf.len());
+
+        let archived = unsafe { archived_root::<HashMap<u32, u32>>(buf.as_ref()) };
+        assert_eq!(archived.len(), len);
+        for (key, value) in archived.iter() {
+            assert_eq!(hash_map[&u32::from(*key)], u32::from(*value));
+        }
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     #[allow(deprecated)]
@@ -364,4 +652,415 @@ mod tests {
         value.insert(());
         test_archive(&value);
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn pooled_keys() {
+        use rkyv::with::PooledKeys;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(PooledKeys)]
+            value: HashMap<String, String>,
+        }
+
+        let mut value = HashMap::new();
+        for i in 0..32 {
+            value.insert(
+                format!("a fairly long key #{}", i),
+                format!("a fairly long value #{}", i),
+            );
+        }
+        let value = Test { value };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        assert_eq!(archived.value.len(), value.value.len());
+        for (key, value) in value.value.iter() {
+            assert_eq!(&archived.value[key.as_str()], value);
+        }
+
+        // All of the key bytes come before any of the value bytes
+        let last_key = archived
+            .value
+            .keys()
+            .map(|k| k.as_ptr() as usize)
+            .max()
+            .unwrap();
+        let first_value = archived
+            .value
+            .values()
+            .map(|v| v.as_ptr() as usize)
+            .min()
+            .unwrap();
+        assert!(last_key < first_value);
+
+        let deserialized: Test = archived
+            .deserialize(&mut DefaultDeserializer::default())
+            .unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn boxed_values() {
+        use core::mem::size_of;
+        use rkyv::{boxed::ArchivedBox, string::ArchivedString, with::BoxedValues, Archived};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(BoxedValues)]
+            value: HashMap<String, [u64; 32]>,
+        }
+
+        let mut value = HashMap::new();
+        for i in 0..16 {
+            value.insert(format!("key {}", i), [i; 32]);
+        }
+        let value = Test { value };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        assert_eq!(archived.value.len(), value.value.len());
+        for (key, value) in value.value.iter() {
+            let boxed: &ArchivedBox<[Archived<u64>; 32]> =
+                archived.value.get(key.as_str()).unwrap();
+            assert_eq!(boxed.get(), value);
+        }
+
+        // Values are stored outside of the entries, which only hold keys and pointers
+        let (first_key, last_key) = archived
+            .value
+            .keys()
+            .map(|k| k as *const _ as usize)
+            .fold((usize::MAX, 0), |(min, max), k| (min.min(k), max.max(k)));
+        let entry_size = size_of::<ArchivedString>() + size_of::<ArchivedBox<u64>>();
+        assert!(last_key - first_key < 16 * entry_size);
+        for value in archived.value.values() {
+            let value = value.get() as *const _ as usize;
+            assert!(value + size_of::<[u64; 32]>() <= first_key || value > last_key);
+        }
+
+        let deserialized: Test = archived
+            .deserialize(&mut DefaultDeserializer::default())
+            .unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn open_addressed() {
+        use rkyv::{
+            collections::open_hash_map::{Linear, Quadratic},
+            with::OpenAddressed,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Test {
+            #[with(OpenAddressed)]
+            robin_hood: HashMap<String, u32>,
+            #[with(OpenAddressed<Linear, 100>)]
+            linear: HashMap<String, u32>,
+            #[with(OpenAddressed<Quadratic, 50>)]
+            quadratic: HashMap<String, u32>,
+            #[with(OpenAddressed)]
+            empty: HashMap<String, u32>,
+        }
+
+        // Kept small enough to fit in 16-bit archives
+        let map = (0..250)
+            .map(|i| (format!("key {}", i), i))
+            .collect::<HashMap<_, _>>();
+        let value = Test {
+            robin_hood: map.clone(),
+            linear: map.clone(),
+            quadratic: map.clone(),
+            empty: HashMap::new(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Test>(buf.as_ref()) };
+
+        // The smallest power of two that keeps the load factor at or below the maximum, with at
+        // least one empty bucket
+        assert_eq!(archived.robin_hood.capacity(), 512);
+        assert_eq!(archived.linear.capacity(), 256);
+        assert_eq!(archived.quadratic.capacity(), 512);
+        assert_eq!(archived.empty.capacity(), 0);
+
+        for (key, value) in map.iter() {
+            assert_eq!(archived.robin_hood[key.as_str()], *value);
+            assert_eq!(archived.linear[key.as_str()], *value);
+            assert_eq!(archived.quadratic[key.as_str()], *value);
+        }
+        for i in 250..500 {
+            let key = format!("key {}", i);
+            assert!(!archived.robin_hood.contains_key(key.as_str()));
+            assert!(!archived.linear.contains_key(key.as_str()));
+            assert!(!archived.quadratic.contains_key(key.as_str()));
+        }
+        assert_eq!(archived.empty.get("key 0"), None);
+        assert_eq!(archived.robin_hood.iter().count(), 250);
+
+        let deserialized: Test = archived
+            .deserialize(&mut DefaultDeserializer::default())
+            .unwrap();
+        assert_eq!(deserialized, value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_instant() {
+        use rkyv::{
+            de::deserializers::InstantDeserializer,
+            ser::serializers::{InstantSerializer, InstantSerializerError},
+        };
+        use std::time::{Duration, Instant};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Span {
+            start: Instant,
+            end: Instant,
+            marks: Vec<Instant>,
+        }
+
+        let base = Instant::now();
+        let value = Span {
+            start: base + Duration::from_millis(10),
+            end: base + Duration::new(3, 500),
+            marks: vec![base, base + Duration::from_micros(1500)],
+        };
+
+        let mut serializer = InstantSerializer::new(DefaultSerializer::default(), base);
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_inner().into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Span>(buf.as_ref()) };
+
+        assert_eq!(archived.start.as_millis(), 10);
+        assert_eq!(archived.end.as_secs(), 3);
+        assert_eq!(archived.end.subsec_nanos(), 500);
+        assert_eq!(archived.marks[0].as_nanos(), 0);
+        assert_eq!(archived.marks[1].as_micros(), 1500);
+
+        let new_base = Instant::now() + Duration::from_secs(60);
+        let mut deserializer = InstantDeserializer::new(DefaultDeserializer::default(), new_base);
+        let deserialized: Span = archived.deserialize(&mut deserializer).unwrap();
+        assert_eq!(deserialized.start, new_base + Duration::from_millis(10));
+        assert_eq!(
+            deserialized.end - deserialized.start,
+            value.end - value.start
+        );
+        assert_eq!(deserialized.marks[0], new_base);
+
+        // Instants before the base can't be archived
+        let mut serializer = InstantSerializer::new(DefaultSerializer::default(), value.end);
+        assert!(matches!(
+            serializer.serialize_value(&value),
+            Err(InstantSerializerError::BeforeBase)
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn stacked_serializer_layers() {
+        use rkyv::{
+            ser::serializers::{InstantSerializer, InternSerializer},
+            with::Intern,
+        };
+        use std::time::{Duration, Instant};
+
+        #[derive(Archive, Serialize)]
+        struct Event {
+            at: Instant,
+            #[with(Intern)]
+            kind: String,
+        }
+
+        let base = Instant::now();
+        let value = vec![
+            Event {
+                at: base + Duration::from_millis(5),
+                kind: "click".to_string(),
+            },
+            Event {
+                at: base + Duration::from_millis(8),
+                kind: "click".to_string(),
+            },
+        ];
+
+        // Each layer forwards the capabilities of the layers it wraps, in either order
+        let mut serializer =
+            InternSerializer::new(InstantSerializer::new(DefaultSerializer::default(), base));
+        let pos = serializer.serialize_value(&value).unwrap();
+        assert_eq!(serializer.table().len(), 1);
+        let (serializer, _) = serializer.into_parts();
+        let buf = serializer.into_inner().into_serializer().into_inner();
+        let archived = unsafe { archived_value::<Vec<Event>>(buf.as_ref(), pos) };
+        assert_eq!(archived[1].at.as_millis(), 8);
+        assert_eq!(archived[0].kind, archived[1].kind);
+
+        let mut serializer =
+            InstantSerializer::new(InternSerializer::new(DefaultSerializer::default()), base);
+        serializer.serialize_value(&value).unwrap();
+        assert_eq!(serializer.into_inner().table().len(), 1);
+    }
+
+    // Threads can't be spawned under wasm, and the endian-aware primitives aren't Sync
+    #[test]
+    #[cfg(not(any(feature = "archive_le", feature = "archive_be")))]
+    fn parallel_split_vec() {
+        use rkyv::{vec::ArchivedVec, Archived};
+        use std::thread;
+
+        fn assert_send_sync<T: Send + Sync + ?Sized>() {}
+        assert_send_sync::<ArchivedVec<Archived<u32>>>();
+        assert_send_sync::<[<String as Archive>::Archived]>();
+
+        #[derive(Archive, Serialize)]
+        struct Entry {
+            id: u32,
+            label: String,
+        }
+
+        let value = (0..1000u32)
+            .map(|id| Entry {
+                id,
+                label: id.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Vec<Entry>>(buf.as_ref()) };
+
+        let (left, right) = archived.split_at(400);
+        assert_eq!(left.len(), 400);
+        assert_eq!(right.len(), 600);
+        assert_eq!(right[0].label, "400");
+
+        let scan = |half: &[ArchivedEntry]| {
+            half.iter()
+                .filter(|entry| entry.label.as_str() == entry.id.to_string())
+                .count()
+        };
+        let (left_count, right_count) = thread::scope(|scope| {
+            let left = scope.spawn(|| scan(left));
+            let right = scope.spawn(|| scan(right));
+            (left.join().unwrap(), right.join().unwrap())
+        });
+        assert_eq!(left_count + right_count, 1000);
+
+        let (all, none) = archived.split_at(archived.len());
+        assert_eq!(all.len(), 1000);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "fst")]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn archive_fst() {
+        use rkyv::{with::AsFst, Infallible};
+        use std::collections::BTreeMap;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        struct Dictionary {
+            #[with(AsFst)]
+            words: HashSet<String>,
+            #[with(AsFst)]
+            counts: HashMap<String, u32>,
+        }
+
+        // Lots of shared prefixes and suffixes, a key that's a prefix of others, and the empty key
+        let mut words = HashSet::new();
+        words.insert(String::new());
+        for prefix in ["", "re", "un", "pre", "über"].iter() {
+            for stem in ["test", "tests", "tester", "testing", "do", "done"].iter() {
+                words.insert(format!("{}{}", prefix, stem));
+            }
+        }
+        let counts = words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| (word.clone(), i as u32))
+            .collect::<HashMap<_, _>>();
+        let value = Dictionary { words, counts };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Dictionary>(buf.as_ref()) };
+
+        let sorted = value.counts.iter().collect::<BTreeMap<_, _>>();
+        assert_eq!(archived.words.len(), sorted.len());
+        assert_eq!(archived.counts.len(), sorted.len());
+
+        // Identical suffixes are merged, so there are fewer transitions than key bytes
+        let key_bytes = sorted.keys().map(|key| key.len()).sum::<usize>();
+        assert!(archived.words.transition_count() * 4 < key_bytes);
+
+        for (index, (key, value)) in sorted.iter().enumerate() {
+            assert!(archived.words.contains(key));
+            assert_eq!(archived.words.index_of(key), Some(index));
+            assert!(*archived.counts.get(key).unwrap() == **value);
+        }
+        for missing in ["t", "tes", "redone!", "über", "pretest "].iter() {
+            assert!(!archived.words.contains(missing));
+            assert!(archived.counts.get(missing).is_none());
+        }
+
+        // Iteration is in sorted order
+        assert!(archived
+            .words
+            .iter()
+            .eq(sorted.keys().map(|key| key.to_string())));
+        assert!(archived
+            .counts
+            .iter()
+            .map(|(key, value)| (key, u32::from(*value)))
+            .eq(sorted.iter().map(|(key, value)| (key.to_string(), **value))));
+
+        // Prefix queries
+        assert_eq!(
+            archived.words.prefix_iter("retest").collect::<Vec<_>>(),
+            ["retest", "retester", "retesting", "retests"],
+        );
+        assert_eq!(
+            archived
+                .counts
+                .prefix_iter("über")
+                .map(|(key, _)| key)
+                .collect::<Vec<_>>(),
+            archived.words.prefix_iter("über").collect::<Vec<_>>(),
+        );
+        for (key, count) in archived.counts.prefix_iter("un") {
+            assert!(key.starts_with("un"));
+            assert!(u32::from(*count) == value.counts[&key]);
+        }
+        assert_eq!(archived.words.prefix_iter("x").count(), 0);
+        assert_eq!(archived.words.prefix_iter("").count(), sorted.len());
+
+        let deserialized: Dictionary = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        // Empty sets and maps
+        let empty = Dictionary {
+            words: HashSet::new(),
+            counts: HashMap::new(),
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&empty).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = unsafe { archived_root::<Dictionary>(buf.as_ref()) };
+        assert!(archived.words.is_empty());
+        assert!(!archived.words.contains(""));
+        assert_eq!(archived.counts.iter().count(), 0);
+    }
 }
@@ -186,6 +186,26 @@ mod tests {
         result.unwrap_err();
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn invalid_small_std_enum_tags() {
+        use core::{cmp::Ordering, num::FpCategory, ops::Bound, sync::atomic};
+
+        let buf = AlignedBytes([0xffu8, 0, 1, 2, 5, 0, 0, 0]);
+
+        for i in 0..3 {
+            check_archived_value::<Ordering>(buf.as_ref(), i).unwrap();
+        }
+        check_archived_value::<Ordering>(buf.as_ref(), 3).unwrap_err();
+        check_archived_value::<FpCategory>(buf.as_ref(), 3).unwrap();
+        check_archived_value::<FpCategory>(buf.as_ref(), 4).unwrap_err();
+        check_archived_value::<atomic::Ordering>(buf.as_ref(), 3).unwrap();
+        check_archived_value::<atomic::Ordering>(buf.as_ref(), 4).unwrap_err();
+        check_archived_value::<Bound<u8>>(buf.as_ref(), 1).unwrap();
+        check_archived_value::<Bound<u8>>(buf.as_ref(), 3).unwrap();
+        check_archived_value::<Bound<u8>>(buf.as_ref(), 4).unwrap_err();
+    }
+
     #[cfg(feature = "size_32")]
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
@@ -379,6 +399,78 @@ mod tests {
         serialize_and_check(&Node::Cons(Box::new(Node::Cons(Box::new(Node::Nil)))));
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_recursive_expression_tree() {
+        #[derive(Archive, Serialize)]
+        #[archive(bound(serialize = "__S: Serializer"))]
+        #[archive_attr(derive(CheckBytes))]
+        #[archive_attr(check_bytes(
+            bound = "__C: ::rkyv::validation::ArchiveContext, <__C as ::rkyv::Fallible>::Error: ::bytecheck::Error"
+        ))]
+        enum Expr {
+            Num(i64),
+            Add(
+                #[omit_bounds]
+                #[archive_attr(omit_bounds)]
+                Box<Expr>,
+                #[omit_bounds]
+                #[archive_attr(omit_bounds)]
+                Box<Expr>,
+            ),
+        }
+
+        let value = Expr::Add(
+            Box::new(Expr::Num(1)),
+            Box::new(Expr::Add(Box::new(Expr::Num(2)), Box::new(Expr::Num(3)))),
+        );
+        serialize_and_check(&value);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_recursive_config_tree() {
+        #[cfg(not(feature = "std"))]
+        use alloc::{borrow::Cow, collections::BTreeMap};
+        use rkyv::with::AsOwned;
+        #[cfg(feature = "std")]
+        use std::{borrow::Cow, collections::BTreeMap};
+
+        #[derive(Archive, Serialize)]
+        #[archive(bound(serialize = "__S: rkyv::ser::ScratchSpace + Serializer"))]
+        #[archive_attr(derive(CheckBytes))]
+        #[archive_attr(check_bytes(
+            bound = "__C: ::rkyv::validation::ArchiveContext, <__C as ::rkyv::Fallible>::Error: ::bytecheck::Error"
+        ))]
+        enum Value<'a> {
+            Str(#[with(AsOwned)] Cow<'a, str>),
+            List(
+                #[omit_bounds]
+                #[archive_attr(omit_bounds)]
+                Vec<Value<'a>>,
+            ),
+            Map(
+                #[omit_bounds]
+                #[archive_attr(omit_bounds)]
+                BTreeMap<String, Value<'a>>,
+            ),
+        }
+
+        let mut map = BTreeMap::new();
+        map.insert("name".to_string(), Value::Str(Cow::Borrowed("rkyv")));
+        map.insert(
+            "tags".to_string(),
+            Value::List(vec![
+                Value::Str(Cow::Borrowed("zero-copy")),
+                Value::Str(Cow::Owned(
+                    "a tag that is long enough to be out of line".to_string(),
+                )),
+            ]),
+        );
+        let value = Value::List(vec![Value::Map(map), Value::Map(BTreeMap::new())]);
+        serialize_and_check(&value);
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn check_shared_ptr() {
@@ -423,6 +515,29 @@ mod tests {
         check_archived_root::<BTreeMap<String, i32>>(buf.as_ref()).unwrap();
     }
 
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_b_tree_branching_factor() {
+        #[cfg(not(feature = "std"))]
+        use alloc::collections::BTreeMap;
+        use rkyv::with::{BranchingFactor, With};
+        #[cfg(feature = "std")]
+        use std::collections::BTreeMap;
+
+        let value = (0..500)
+            .map(|i| (i.to_string(), i))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut serializer = DefaultSerializer::default();
+        serializer
+            .serialize_value(With::<_, BranchingFactor<4>>::cast(&value))
+            .unwrap();
+        let buf = serializer.into_serializer().into_inner();
+
+        check_archived_root::<With<BTreeMap<String, i32>, BranchingFactor<4>>>(buf.as_ref())
+            .unwrap();
+    }
+
     #[test]
     #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
     fn check_empty_b_tree() {
@@ -488,4 +603,893 @@ mod tests {
 
         assert_eq!(buf.as_ref(), &ARCHIVED_BYTES);
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn deserialize_with_budget() {
+        use rkyv::{
+            de::deserializers::{BudgetDeserializer, BudgetDeserializerError},
+            Deserialize,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            names: Vec<String>,
+            values: Box<[u64]>,
+        }
+
+        let value = Test {
+            names: vec!["a long enough name".to_string(), "another name".to_string()],
+            values: vec![1, 2, 3, 4].into_boxed_slice(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = check_archived_root::<Test>(buf.as_ref()).unwrap();
+
+        let mut deserializer = BudgetDeserializer::new(DefaultDeserializer::default(), 4096);
+        let deserialized: Test = archived.deserialize(&mut deserializer).unwrap();
+        assert_eq!(deserialized, value);
+        let used = 4096 - deserializer.remaining();
+        assert!(used >= 4 * 8 + "a long enough name".len() + "another name".len());
+
+        let mut deserializer = BudgetDeserializer::new(DefaultDeserializer::default(), used);
+        let deserialized: Test = archived.deserialize(&mut deserializer).unwrap();
+        assert_eq!(deserialized, value);
+        assert_eq!(deserializer.remaining(), 0);
+
+        let mut deserializer = BudgetDeserializer::new(DefaultDeserializer::default(), used - 1);
+        let result: Result<Test, _> = archived.deserialize(&mut deserializer);
+        assert!(matches!(
+            result,
+            Err(BudgetDeserializerError::ExceededBudget { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_sorted_vec() {
+        use rkyv::with::AsSortedVec;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(AsSortedVec)]
+            table: Vec<(String, u32)>,
+        }
+
+        serialize_and_check(&Test {
+            table: vec![("b".to_string(), 2), ("a".to_string(), 1)],
+        });
+        serialize_and_check(&Test { table: Vec::new() });
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_result_log() {
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Record {
+            id: u32,
+            tags: Vec<String>,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Log<T, E> {
+            entries: Vec<Result<T, E>>,
+        }
+
+        serialize_and_check(&Log::<Record, Vec<String>> {
+            entries: vec![
+                Ok(Record {
+                    id: 1,
+                    tags: vec!["a".to_string()],
+                }),
+                Err(vec!["denied".to_string(), "retry".to_string()]),
+            ],
+        });
+    }
+
+    #[cfg(feature = "size_32")]
+    #[cfg(any(
+        all(target_endian = "little", not(feature = "archive_be")),
+        feature = "archive_le"
+    ))]
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_vec_length() {
+        let mut serializer = DefaultSerializer::default();
+        serializer
+            .serialize_value(&vec![1u32, 2, 3, 4])
+            .expect("failed to archive value");
+        let buf = serializer.into_serializer().into_inner();
+        check_archived_root::<Vec<u32>>(buf.as_ref()).unwrap();
+
+        // The root is at the end of the buffer, find its length field
+        let root = buf.len() - 8;
+        let len_pos = (root..buf.len())
+            .step_by(4)
+            .find(|&i| buf[i..i + 4] == 4u32.to_le_bytes())
+            .unwrap();
+
+        // Over-long length fields
+        for &len in &[5u32, 1 << 16, u32::MAX] {
+            let mut corrupted = buf.clone();
+            corrupted[len_pos..len_pos + 4].copy_from_slice(&len.to_le_bytes());
+            check_archived_root::<Vec<u32>>(corrupted.as_ref()).unwrap_err();
+        }
+
+        // Truncated buffer, the elements are no longer in the archive
+        check_archived_root::<Vec<u32>>(&buf[8..]).unwrap_err();
+    }
+
+    #[cfg(feature = "size_32")]
+    #[cfg(any(
+        all(target_endian = "little", not(feature = "archive_be")),
+        feature = "archive_le"
+    ))]
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_string_length() {
+        use rkyv::{string::validation::ArchivedStringError, validation::CheckArchiveError};
+
+        // Out-of-line strings store their length first
+        let mut serializer = DefaultSerializer::default();
+        serializer
+            .serialize_value(&"hello world!".to_string())
+            .expect("failed to archive value");
+        let buf = serializer.into_serializer().into_inner();
+        check_archived_root::<String>(buf.as_ref()).unwrap();
+
+        let root = buf.len() - 8;
+        for &len in &[13u32, 1 << 16, i32::MAX as u32] {
+            let mut corrupted = buf.clone();
+            corrupted[root..root + 4].copy_from_slice(&len.to_le_bytes());
+            check_archived_root::<String>(corrupted.as_ref()).unwrap_err();
+        }
+        check_archived_root::<String>(&buf[4..]).unwrap_err();
+
+        // Inline strings store their length in the last byte
+        let mut serializer = DefaultSerializer::default();
+        serializer
+            .serialize_value(&"hi".to_string())
+            .expect("failed to archive value");
+        let mut buf = serializer.into_serializer().into_inner();
+        check_archived_root::<String>(buf.as_ref()).unwrap();
+
+        let len_pos = buf.len() - 1;
+        buf[len_pos] = 100;
+        let result = check_archived_root::<String>(buf.as_ref());
+        assert!(matches!(
+            result,
+            Err(CheckArchiveError::CheckBytesError(
+                ArchivedStringError::InlineLengthTooLarge { len: 100 }
+            ))
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_relocated_archive() {
+        use rkyv::{
+            ser::serializers::{AllocScratch, BufferSerializer, CompositeSerializer},
+            AlignedVec, Infallible,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Message {
+            id: u32,
+            lines: Vec<String>,
+        }
+
+        let value = Message {
+            id: 7,
+            lines: vec!["a string long enough to be out of line".to_string(); 3],
+        };
+
+        // Serialize into a raw region, as a producer writing to shared memory would
+        let mut region = AlignedVec::with_capacity(512);
+        region.extend_from_slice(&[0u8; 512]);
+        let pos = {
+            let buffer =
+                unsafe { BufferSerializer::from_raw_parts(region.as_mut_ptr(), region.len()) };
+            let mut serializer =
+                CompositeSerializer::new(buffer, AllocScratch::default(), Infallible);
+            serializer.serialize_value(&value).unwrap()
+        };
+
+        // Map the same bytes at a different base address, as a consumer would
+        let mut mapping = AlignedVec::with_capacity(1024);
+        mapping.extend_from_slice(&[0u8; 64]);
+        mapping.extend_from_slice(region.as_slice());
+        let relocated = &mapping[64..];
+        assert_ne!(relocated.as_ptr(), region.as_ptr());
+
+        for bytes in [region.as_slice(), relocated] {
+            let archived = check_archived_value::<Message>(bytes, pos).unwrap();
+            assert_eq!(archived.id, 7);
+            assert_eq!(archived.lines.len(), 3);
+            for line in archived.lines.iter() {
+                assert_eq!(line, "a string long enough to be out of line");
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_on_access() {
+        use core::ops::Range;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Reading {
+            #[archive(check_on_access)]
+            calibrated: bool,
+            #[archive(check_on_access)]
+            window: Range<u32>,
+            label: String,
+        }
+
+        let value = Reading {
+            calibrated: true,
+            window: 10..20,
+            label: "a label long enough to be out of line".to_string(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+
+        // The fields are checked through a pointer, so the rest of the archived value is never
+        // read
+        let root_pos = buf.len() - core::mem::size_of::<ArchivedReading>();
+        let archived = unsafe { buf.as_ptr().add(root_pos).cast::<ArchivedReading>() };
+        assert!(*unsafe { ArchivedReading::try_calibrated(archived) }.unwrap());
+        assert_eq!(
+            unsafe { ArchivedReading::try_window(archived) }
+                .unwrap()
+                .start,
+            10
+        );
+
+        let calibrated_pos = root_pos
+            + unsafe { core::ptr::addr_of!((*archived).calibrated) as usize - archived as usize };
+        buf[calibrated_pos] = 2;
+        assert!(check_archived_root::<Reading>(buf.as_ref()).is_err());
+
+        let archived = unsafe { buf.as_ptr().add(root_pos).cast::<ArchivedReading>() };
+        assert!(unsafe { ArchivedReading::try_calibrated(archived) }.is_err());
+        assert_eq!(
+            unsafe { ArchivedReading::try_window(archived) }
+                .unwrap()
+                .end,
+            20
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_string_table() {
+        use rkyv::with::AsStringTable;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(AsStringTable)]
+            names: Vec<String>,
+        }
+
+        let value = Test {
+            names: vec!["hello".to_string(), "\u{1f980}".to_string()],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+        check_archived_root::<Test>(buf.as_ref()).unwrap();
+
+        let archived = unsafe { rkyv::archived_root::<Test>(buf.as_ref()) };
+        let pool_start = archived.names.pool().as_ptr() as usize - buf.as_ptr() as usize;
+        let pool_end = pool_start + archived.names.pool().len();
+
+        // The spans are written right after the pool
+        let span_size = core::mem::size_of::<rkyv::Archived<usize>>();
+        let spans_start = (pool_end + span_size - 1) / span_size * span_size;
+        let second_start_pos = if cfg!(any(
+            all(target_endian = "little", not(feature = "archive_be")),
+            feature = "archive_le"
+        )) {
+            spans_start + 2 * span_size
+        } else {
+            spans_start + 3 * span_size - 1
+        };
+        assert_eq!(buf[second_start_pos], 5);
+
+        // Move the start of the second string into the middle of the crab
+        let mut corrupt = buf.clone();
+        corrupt[second_start_pos] = 6;
+        assert!(check_archived_root::<Test>(corrupt.as_ref()).is_err());
+
+        // Break the UTF-8 encoding of the pool
+        buf[pool_start + 5] = 0xff;
+        assert!(check_archived_root::<Test>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_compressed() {
+        use rkyv::{compression::Compression, with::Compress};
+
+        struct Reverse;
+
+        impl Compression for Reverse {
+            type Error = ();
+
+            fn compress(bytes: &[u8]) -> Vec<u8> {
+                bytes.iter().rev().copied().skip(1).collect()
+            }
+
+            fn decompress(_: &[u8]) -> Result<Vec<u8>, ()> {
+                Err(())
+            }
+        }
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(Compress<Reverse, 4>)]
+            small: Vec<u8>,
+            #[with(Compress<Reverse, 4>)]
+            large: Vec<u8>,
+        }
+
+        let value = Test {
+            small: vec![1, 2, 3],
+            large: vec![1, 2, 3, 4, 5, 6],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+        let archived = check_archived_root::<Test>(buf.as_ref()).unwrap();
+        assert!(!archived.small.is_compressed());
+        assert!(archived.large.is_compressed());
+        assert_eq!(archived.large.stored_bytes(), &[5, 4, 3, 2, 1]);
+
+        // The flag is the first byte of the archived buffer and may only be 0 or 1
+        let flag_pos = &archived.large as *const _ as usize - buf.as_ptr() as usize;
+        assert_eq!(buf[flag_pos], 1);
+        buf[flag_pos] = 2;
+        assert!(check_archived_root::<Test>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn deserialize_checked() {
+        use rkyv::{validation::CheckDeserializeError, Deserialize, Infallible};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            name: String,
+            values: Vec<u32>,
+        }
+
+        let value = Test {
+            name: "a name that is stored out-of-line".to_string(),
+            values: vec![1, 2, 3, 4],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+
+        // A reference from validated access can be deserialized without checking it again
+        let archived = check_archived_root::<Test>(buf.as_ref()).unwrap();
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized, value);
+
+        let deserialized = rkyv::deserialize_checked::<Test, _>(buf.as_ref(), &mut Infallible);
+        assert_eq!(deserialized.unwrap(), value);
+
+        // Point the name out of bounds so the archive fails validation
+        let name_pos = &archived.name as *const _ as usize - buf.as_ptr() as usize;
+        let len = buf.len();
+        buf[name_pos..len].iter_mut().for_each(|b| *b = 0x7f);
+        let result = rkyv::deserialize_checked::<Test, _>(buf.as_ref(), &mut Infallible);
+        assert!(matches!(result, Err(CheckDeserializeError::CheckError(_))));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_nullable_vec() {
+        use core::mem::size_of;
+        use rkyv::{collections::ArchivedNullableVec, with::AsNullable, Archived, FixedUsize};
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Column {
+            #[with(AsNullable)]
+            values: Vec<Option<u32>>,
+        }
+
+        let value = Column {
+            values: (0..100)
+                .map(|i| if i % 4 == 0 { None } else { Some(i) })
+                .collect(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+        let archived = check_archived_root::<Column>(buf.as_ref()).unwrap();
+        assert_eq!(archived.values.get(5).map(|v| u32::from(*v)), Some(5));
+
+        // A forged huge length must fail validation instead of overflowing
+        let word = size_of::<Archived<usize>>();
+        let len: Archived<usize> = rkyv::to_archived!(100 as FixedUsize);
+        let len = unsafe {
+            core::slice::from_raw_parts((&len as *const Archived<usize>).cast::<u8>(), word)
+        };
+        let start = &archived.values as *const _ as usize - buf.as_ptr() as usize;
+        let len_pos = (start..start + size_of::<ArchivedNullableVec<Archived<u32>>>())
+            .step_by(word)
+            .find(|&pos| buf[pos..pos + word] == *len)
+            .unwrap();
+        let mut huge = buf.clone();
+        huge[len_pos..len_pos + word]
+            .iter_mut()
+            .for_each(|b| *b = 0xff);
+        assert!(check_archived_root::<Column>(huge.as_ref()).is_err());
+
+        // Setting a validity bit without adding a value must fail validation. The bitmap blocks
+        // are written right before the values, and the last block has the validity bits of
+        // elements 96 through 99 after its rank.
+        let values_pos = archived.values.values().as_ptr() as usize - buf.as_ptr() as usize;
+        assert!(archived.values.is_null(96));
+        let little_endian = cfg!(any(
+            all(target_endian = "little", not(feature = "archive_be")),
+            feature = "archive_le"
+        ));
+        if little_endian {
+            buf[values_pos - 4] |= 1;
+        } else {
+            buf[values_pos - 1] |= 1;
+        }
+        assert!(check_archived_root::<Column>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_result_vec() {
+        use rkyv::with::AsResultVec;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct AuditLog {
+            #[with(AsResultVec)]
+            entries: Vec<Result<(), u32>>,
+        }
+
+        let value = AuditLog {
+            entries: (0..100)
+                .map(|i| if i % 4 == 0 { Err(i) } else { Ok(()) })
+                .collect(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+        let archived = check_archived_root::<AuditLog>(buf.as_ref()).unwrap();
+        assert_eq!(
+            archived.entries.get(8).unwrap().map_err(|e| u32::from(*e)),
+            Err(8)
+        );
+
+        // Marking an error as a success without removing its error value must fail validation.
+        // The bitmap blocks are written right before the errors, and the last block has the bits
+        // of elements 96 through 99 after its rank.
+        let errs_pos = archived.entries.errs().as_ptr() as usize - buf.as_ptr() as usize;
+        assert!(archived.entries.is_err(96));
+        let little_endian = cfg!(any(
+            all(target_endian = "little", not(feature = "archive_be")),
+            feature = "archive_le"
+        ));
+        if little_endian {
+            buf[errs_pos - 4] |= 1;
+        } else {
+            buf[errs_pos - 1] |= 1;
+        }
+        assert!(check_archived_root::<AuditLog>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_preserve_capacity() {
+        use rkyv::{with::PreserveCapacity, Archived, Deserialize, FixedUsize, Infallible};
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[archive(preserve_capacity)]
+            names: Vec<String>,
+        }
+
+        let mut names = Vec::with_capacity(8);
+        names.push("hello world".to_string());
+        let value = Test { names };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+        let archived = check_archived_root::<Test>(buf.as_ref()).unwrap();
+        assert_eq!(archived.names.capacity(), 8);
+        assert_eq!(archived.names[0], "hello world");
+
+        let names_pos = &archived.names as *const _ as usize - buf.as_ptr() as usize;
+
+        // The capacity isn't backed by any data, so a huge capacity is valid but is clamped when
+        // deserializing
+        let word = core::mem::size_of::<Archived<usize>>();
+        let capacity_pos = names_pos + 2 * word;
+        let mut huge = buf.clone();
+        huge[capacity_pos..capacity_pos + word]
+            .iter_mut()
+            .for_each(|b| *b = 0xff);
+        let archived = check_archived_root::<Test>(huge.as_ref()).unwrap();
+        assert_eq!(archived.names.capacity(), FixedUsize::MAX as usize);
+        let deserialized: Test = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(deserialized.names, ["hello world"]);
+        assert!(deserialized.names.capacity() <= 1 + PreserveCapacity::MAX_SPARE_CAPACITY);
+
+        // Point the elements out of bounds so the archive fails validation
+        buf[names_pos..names_pos + 4]
+            .iter_mut()
+            .for_each(|b| *b = 0x7f);
+        assert!(check_archived_root::<Test>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_inline_vec() {
+        use core::mem::size_of;
+        use rkyv::{string::ArchivedString, Archived};
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[archive(inline(capacity = 3))]
+            names: Vec<String>,
+        }
+
+        let value = Test {
+            names: vec!["a".to_string(), "hello world".to_string()],
+        };
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+        let archived = check_archived_root::<Test>(buf.as_ref()).unwrap();
+        assert_eq!(archived.names.len(), 2);
+        assert_eq!(archived.names[1], "hello world");
+
+        // A length greater than the capacity must fail validation. The length is stored either
+        // before or after the elements.
+        let start = &archived.names as *const _ as usize - buf.as_ptr() as usize;
+        let first = archived.names.as_ptr() as usize - buf.as_ptr() as usize;
+        let len_pos = if start < first {
+            start
+        } else {
+            first + 3 * size_of::<ArchivedString>()
+        };
+        buf[len_pos..len_pos + size_of::<Archived<usize>>()]
+            .iter_mut()
+            .for_each(|b| *b = 0x7f);
+        assert!(check_archived_root::<Test>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_archive_bytes() {
+        use core::mem::size_of;
+        use rkyv::bytes::ArchivedBytes;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[archive(bytes)]
+            magic: [u8; 4],
+            #[archive(bytes)]
+            payload: Vec<u8>,
+        }
+
+        serialize_and_check(&Test {
+            magic: *b"RKYV",
+            payload: vec![1, 2, 3, 4, 5],
+        });
+        serialize_and_check(&Test {
+            magic: [0; 4],
+            payload: Vec::new(),
+        });
+
+        // A payload that extends past the end of the archive is rejected
+        let mut serializer = DefaultSerializer::default();
+        serializer
+            .serialize_value(&Test {
+                magic: *b"RKYV",
+                payload: vec![1, 2, 3, 4, 5],
+            })
+            .unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+        let payload = {
+            let archived = unsafe { rkyv::archived_root::<Test>(buf.as_ref()) };
+            (&archived.payload as *const ArchivedBytes as usize) - buf.as_ptr() as usize
+        };
+        for byte in &mut buf[payload..payload + size_of::<ArchivedBytes>()] {
+            *byte = 0x7f;
+        }
+        assert!(check_archived_root::<Test>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_niche() {
+        use rkyv::with::Niche;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(Niche)]
+            some: Option<Box<str>>,
+            #[with(Niche)]
+            none: Option<Box<[u32]>>,
+        }
+
+        serialize_and_check(&Test {
+            some: Some("hello world".into()),
+            none: None,
+        });
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_interned() {
+        use rkyv::{ser::serializers::InternSerializer, string::intern::InternTable, with::Intern};
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(Intern)]
+            name: String,
+        }
+
+        let mut serializer = InternSerializer::new(DefaultSerializer::default());
+        let pos = serializer
+            .serialize_value(&vec![
+                Test {
+                    name: "hello world".to_string(),
+                },
+                Test {
+                    name: "hello world".to_string(),
+                },
+            ])
+            .unwrap();
+        serializer.serialize_intern_table().unwrap();
+        let buf = serializer.into_inner().into_serializer().into_inner();
+
+        let archived = check_archived_value::<Vec<Test>>(buf.as_ref(), pos).unwrap();
+        let table = check_archived_root::<InternTable>(buf.as_ref()).unwrap();
+        assert_eq!(archived[1].name.resolve(table), Some("hello world"));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_sentinel() {
+        use rkyv::with::Sentinel;
+
+        #[derive(Archive, Serialize)]
+        #[archive(niche = "u64::MAX")]
+        #[archive_attr(derive(CheckBytes))]
+        struct Id(u64);
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(Sentinel)]
+            some: Option<Id>,
+            #[with(Sentinel)]
+            none: Option<Id>,
+        }
+
+        serialize_and_check(&Test {
+            some: Some(Id(42)),
+            none: None,
+        });
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_trailing_tag_enum() {
+        use core::mem::{align_of, size_of};
+        use rkyv::Archived;
+
+        #[derive(Archive, Serialize)]
+        #[archive(tag_position = "trailing")]
+        #[archive_attr(derive(CheckBytes))]
+        enum Shape {
+            Empty,
+            Circle { radius: u32 },
+            Polygon(Vec<u32>, bool),
+        }
+
+        serialize_and_check(&Shape::Empty);
+        serialize_and_check(&Shape::Circle { radius: 42 });
+        serialize_and_check(&Shape::Polygon(vec![1, 2, 3], true));
+
+        // The tag follows the payload and is padded to the alignment of the archived enum. Only tags
+        // 0 through 2 are valid.
+        let mut serializer = DefaultSerializer::default();
+        serializer
+            .serialize_value(&Shape::Polygon(vec![1, 2, 3], true))
+            .unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+        let tag_pos = buf.len() - align_of::<ArchivedShape>();
+        assert_eq!(buf[tag_pos], 2);
+        buf[tag_pos] = 3;
+        assert!(check_archived_root::<Shape>(buf.as_ref()).is_err());
+
+        // Invalid fields of the active variant fail validation
+        buf[tag_pos] = 2;
+        let last = buf.len() - size_of::<ArchivedShape>() + size_of::<Archived<Vec<u32>>>();
+        buf[last] = 2;
+        assert!(check_archived_root::<Shape>(buf.as_ref()).is_err());
+        buf[last] = 1;
+        check_archived_root::<Shape>(buf.as_ref()).unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_delta_vec() {
+        use rkyv::with::AsDeltaVec;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Samples {
+            #[with(AsDeltaVec)]
+            timestamps: Vec<u64>,
+        }
+
+        let value = Samples {
+            timestamps: (0..100u64).map(|i| 1_000_000 + i * 1_000).collect(),
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+        let archived = check_archived_root::<Samples>(buf.as_ref()).unwrap();
+        assert_eq!(archived.timestamps.get(99), Some(1_099_000));
+
+        // Truncating the varint at the end of the last block must fail validation
+        let deltas_end = archived.timestamps.delta_bytes().as_ptr() as usize
+            + archived.timestamps.delta_bytes().len()
+            - buf.as_ptr() as usize;
+        assert_eq!(buf[deltas_end - 1] & 0x80, 0);
+        buf[deltas_end - 1] |= 0x80;
+        assert!(check_archived_root::<Samples>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_rle_vec() {
+        use rkyv::with::AsRleVec;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Readings {
+            #[with(AsRleVec)]
+            states: Vec<u8>,
+        }
+
+        let value = Readings {
+            states: vec![1, 1, 1, 2, 2, 3],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+        let archived = check_archived_root::<Readings>(buf.as_ref()).unwrap();
+        assert_eq!(archived.states.get(4), Some(&2));
+
+        // The run ends are written first, so making the second run end where the first one does
+        // must fail validation
+        let size = core::mem::size_of::<rkyv::Archived<usize>>();
+        buf.copy_within(0..size, size);
+        assert!(check_archived_root::<Readings>(buf.as_ref()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_archive_with_checksum() {
+        use rkyv::header::{
+            check_archived_root_with_header, to_bytes_with_checksum, ArchiveHeader,
+            CheckHeaderError, HeaderError,
+        };
+
+        let value = "a string that is long enough to be stored out of line".to_string();
+        let mut bytes = to_bytes_with_checksum::<_, 256>(&value).unwrap();
+        let archived = check_archived_root_with_header::<String>(bytes.as_ref()).unwrap();
+        assert_eq!(archived, &value);
+
+        // Bit rot is reported as a checksum mismatch instead of a validation error
+        let len = bytes.len();
+        bytes[len - ArchiveHeader::CHECKSUM_LEN - 1] ^= 0xff;
+        assert!(matches!(
+            check_archived_root_with_header::<String>(bytes.as_ref()),
+            Err(CheckHeaderError::HeaderError(
+                HeaderError::ChecksumMismatch { .. }
+            ))
+        ));
+
+        // Archives with a matching checksum are still validated, so an archive that was written
+        // with invalid UTF-8 in the first place is rejected after its checksum is verified
+        bytes[len - ArchiveHeader::CHECKSUM_LEN - 1] ^= 0xff;
+        bytes[ArchiveHeader::LEN] = 0xff;
+        let archive_end = len - ArchiveHeader::CHECKSUM_LEN;
+        let header = ArchiveHeader::from_bytes(bytes.as_ref()).unwrap();
+        let checksum = header.checksum(&bytes[ArchiveHeader::LEN..archive_end]);
+        bytes[archive_end..].copy_from_slice(&checksum.to_le_bytes());
+        assert!(matches!(
+            check_archived_root_with_header::<String>(bytes.as_ref()),
+            Err(CheckHeaderError::CheckError(_))
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_front_coded_vec() {
+        use rkyv::with::AsFrontCodedVec;
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Dictionary {
+            #[with(AsFrontCodedVec<2>)]
+            words: Vec<String>,
+        }
+
+        let value = Dictionary {
+            words: vec![
+                "\u{1f980}".to_string(),
+                "\u{1f980}\u{1f981}".to_string(),
+                "\u{1f981}".to_string(),
+            ],
+        };
+
+        let mut serializer = DefaultSerializer::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = check_archived_root::<Dictionary>(buf.as_ref()).unwrap();
+        assert_eq!(archived.words.get(1).as_deref(), Some("\u{1f980}\u{1f981}"));
+
+        let data_start = archived.words.data_bytes().as_ptr() as usize - buf.as_ptr() as usize;
+        // The second string shares the four bytes of the first crab
+        assert_eq!(buf[data_start + 6], 4);
+
+        // Sharing a prefix that ends in the middle of the crab must fail validation
+        let mut corrupt = buf.clone();
+        corrupt[data_start + 6] = 2;
+        assert!(check_archived_root::<Dictionary>(corrupt.as_ref()).is_err());
+
+        // The first string of a block can't share a prefix
+        let mut corrupt = buf.clone();
+        corrupt[data_start + 12] = 4;
+        assert!(check_archived_root::<Dictionary>(corrupt.as_ref()).is_err());
+
+        // Break the UTF-8 encoding of a suffix
+        let mut corrupt = buf;
+        corrupt[data_start + 2] = 0xff;
+        assert!(check_archived_root::<Dictionary>(corrupt.as_ref()).is_err());
+    }
 }
@@ -25,4 +25,219 @@ mod tests {
         set.insert("baz".to_string());
         serialize_and_check(&set);
     }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_boxed_values() {
+        use bytecheck::CheckBytes;
+        use rkyv::{
+            check_archived_root,
+            ser::{serializers::AllocSerializer, Serializer},
+            with::BoxedValues,
+            Archive, Serialize,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(BoxedValues)]
+            value: HashMap<String, Vec<u32>>,
+        }
+
+        let mut value = HashMap::new();
+        value.insert("a".to_string(), vec![1, 2, 3]);
+        value.insert("b".to_string(), vec![4, 5, 6]);
+        let value = Test { value };
+
+        let mut serializer = AllocSerializer::<256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = check_archived_root::<Test>(buf.as_ref()).unwrap();
+        assert_eq!(archived.value.get("b").unwrap().get(), &[4, 5, 6][..]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_open_addressed() {
+        use bytecheck::CheckBytes;
+        use rkyv::{
+            check_archived_root,
+            collections::open_hash_map::{Linear, Quadratic},
+            ser::{serializers::AllocSerializer, Serializer},
+            with::OpenAddressed,
+            Archive, Serialize,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Test {
+            #[with(OpenAddressed)]
+            robin_hood: HashMap<String, Vec<u32>>,
+            #[with(OpenAddressed<Linear, 100>)]
+            linear: HashMap<String, Vec<u32>>,
+            #[with(OpenAddressed<Quadratic>)]
+            quadratic: HashMap<String, Vec<u32>>,
+        }
+
+        let map = (0..64)
+            .map(|i| (format!("key {}", i), vec![i; 3]))
+            .collect::<HashMap<_, _>>();
+        let value = Test {
+            robin_hood: map.clone(),
+            linear: map.clone(),
+            quadratic: map,
+        };
+
+        let mut serializer = AllocSerializer::<256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = check_archived_root::<Test>(buf.as_ref()).unwrap();
+        assert_eq!(archived.robin_hood["key 3"], [3, 3, 3][..]);
+        assert_eq!(archived.linear["key 63"], [63, 63, 63][..]);
+        assert_eq!(archived.quadratic.get("key 64"), None);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn lazy_archive() {
+        use rkyv::{
+            ser::{serializers::AllocSerializer, Serializer},
+            validation::lazy::LazyArchive,
+            Archive, Serialize,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(bytecheck::CheckBytes))]
+        struct Chunk {
+            id: u32,
+            name: String,
+        }
+
+        let mut serializer = AllocSerializer::<256>::default();
+        let positions = (0..4)
+            .map(|i| {
+                serializer
+                    .serialize_value(&Chunk {
+                        id: i,
+                        name: format!("chunk number {} with an out-of-line name", i),
+                    })
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let mut bytes = serializer.into_serializer().into_inner();
+
+        // Make the name of the last chunk invalid UTF-8
+        let name = bytes
+            .windows(14)
+            .position(|w| w == b"chunk number 3")
+            .unwrap();
+        bytes[name] = 0xff;
+        let bytes = &bytes[..];
+
+        let archive = LazyArchive::new(bytes);
+        assert_eq!(archive.checked_count(), 0);
+        assert!(archive.checked_ranges().is_empty());
+
+        // Accessing the first two chunks validates only those chunks
+        let size = core::mem::size_of::<ArchivedChunk>();
+        assert!(archive.access::<Chunk>(positions[0]).unwrap().id == 0);
+        assert!(archive.is_checked::<Chunk>(positions[0]));
+        assert!(!archive.is_checked::<Chunk>(positions[1]));
+        assert!(archive.access::<Chunk>(positions[1]).unwrap().id == 1);
+        assert_eq!(archive.checked_count(), 2);
+        assert_eq!(
+            archive.checked_ranges(),
+            vec![
+                positions[0]..positions[0] + size,
+                positions[1]..positions[1] + size,
+            ]
+        );
+
+        // Accessing a checked chunk again doesn't record it again
+        assert_eq!(
+            archive.access::<Chunk>(positions[0]).unwrap().name.as_str(),
+            "chunk number 0 with an out-of-line name"
+        );
+        assert_eq!(archive.checked_count(), 2);
+
+        // The corrupted chunk is only rejected once it's accessed
+        assert!(archive.access::<Chunk>(positions[3]).is_err());
+        assert!(!archive.is_checked::<Chunk>(positions[3]));
+        assert_eq!(archive.checked_count(), 2);
+
+        // The archive can be shared between threads
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    assert!(archive.access::<Chunk>(positions[2]).unwrap().id == 2);
+                });
+            }
+        });
+        assert!(archive.is_checked::<Chunk>(positions[2]));
+        assert_eq!(archive.checked_count(), 3);
+
+        archive.clear();
+        assert_eq!(archive.checked_count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "fst")]
+    #[cfg_attr(feature = "wasm", wasm_bindgen_test)]
+    fn check_fst() {
+        use bytecheck::CheckBytes;
+        use rkyv::{
+            check_archived_root,
+            ser::{serializers::AllocSerializer, Serializer},
+            with::AsFst,
+            Archive, Serialize,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Dictionary {
+            #[with(AsFst)]
+            words: HashSet<String>,
+            #[with(AsFst)]
+            definitions: HashMap<String, String>,
+        }
+
+        let words = ["", "tea", "team", "tear", "ten", "to", "toe", "ton", "tone"];
+        let value = Dictionary {
+            words: words.iter().map(|w| w.to_string()).collect(),
+            definitions: words
+                .iter()
+                .map(|w| (w.to_string(), format!("the definition of {:?}", w)))
+                .collect(),
+        };
+        let mut serializer = AllocSerializer::<256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let buf = serializer.into_serializer().into_inner();
+        let archived = check_archived_root::<Dictionary>(buf.as_ref()).unwrap();
+        assert!(archived.words.contains("tone"));
+        assert_eq!(
+            archived.definitions.get("toe").unwrap(),
+            "the definition of \"toe\""
+        );
+
+        #[derive(Archive, Serialize)]
+        #[archive_attr(derive(CheckBytes))]
+        struct Letters {
+            #[with(AsFst)]
+            letters: HashSet<String>,
+        }
+
+        let value = Letters {
+            letters: ["a", "b"].iter().map(|w| w.to_string()).collect(),
+        };
+        let mut serializer = AllocSerializer::<256>::default();
+        serializer.serialize_value(&value).unwrap();
+        let mut buf = serializer.into_serializer().into_inner();
+        check_archived_root::<Letters>(buf.as_ref()).unwrap();
+
+        // The root node has transitions on `a` and `b`, swapping them makes them unsorted
+        let bytes = buf.windows(2).position(|w| w == b"ab").unwrap();
+        buf[bytes] = b'b';
+        buf[bytes + 1] = b'a';
+        assert!(check_archived_root::<Letters>(buf.as_ref()).is_err());
+    }
 }